@@ -11,7 +11,7 @@
 
 use crate::events::spaces::*;
 use clap_sys::events::clap_event_header;
-use std::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Formatter};
 
 pub mod event_types;
 pub mod io;
@@ -299,7 +299,7 @@ where
 }
 
 impl Debug for UnknownEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self.as_core_event() {
             Some(e) => Debug::fmt(&e, f),
             None => f
@@ -6,7 +6,8 @@ use clap_sys::events::{
     clap_event_midi, clap_event_midi2, clap_event_midi_sysex, CLAP_EVENT_MIDI, CLAP_EVENT_MIDI2,
     CLAP_EVENT_MIDI_SYSEX,
 };
-use std::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
 
 #[derive(Copy, Clone)]
 pub struct MidiEvent {
@@ -83,7 +84,7 @@ impl PartialEq for MidiEvent {
 impl Eq for MidiEvent {}
 
 impl Debug for MidiEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("MidiEvent")
             .field("header", &self.header())
             .field("port_index", &self.inner.port_index)
@@ -179,7 +180,7 @@ impl PartialEq for MidiSysExEvent {
 impl Eq for MidiSysExEvent {}
 
 impl Debug for MidiSysExEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("MidiSysexEvent")
             .field("header", &self.header())
             .field("port_index", &self.inner.port_index)
@@ -188,6 +189,110 @@ impl Debug for MidiSysExEvent {
     }
 }
 
+/// A [`MidiSysExEvent`] that borrows its SysEx payload for the lifetime `'a`.
+///
+/// [`MidiSysExEvent`] only holds a raw pointer to its payload, which makes reading it `unsafe`.
+/// This type instead ties the payload's lifetime to the event, so that it can be safely read
+/// using [`data`](Self::data).
+///
+/// Note that pushing this event into an [`EventBuffer`] using [`push`](crate::events::io::EventBuffer::push) only
+/// copies the pointer to the payload, not the payload itself. Use
+/// [`push_sysex`](crate::events::io::EventBuffer::push_sysex) to have the buffer store a copy of the payload instead.
+///
+/// # Example
+///
+/// ```
+/// use clack_common::events::event_types::BorrowedMidiSysExEvent;
+///
+/// let payload = [0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7];
+/// let event = BorrowedMidiSysExEvent::new(0, 0, &payload);
+///
+/// assert_eq!(event.data(), &payload);
+/// assert_eq!(event.as_event().buffer_size(), 6);
+/// ```
+///
+/// [`EventBuffer`]: crate::events::io::EventBuffer
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BorrowedMidiSysExEvent<'a> {
+    event: MidiSysExEvent,
+    _data: PhantomData<&'a [u8]>,
+}
+
+impl<'a> BorrowedMidiSysExEvent<'a> {
+    /// Creates a new SysEx event, borrowing the given `data` as its payload.
+    #[inline]
+    pub fn new(time: u32, port_index: u16, data: &'a [u8]) -> Self {
+        Self {
+            event: MidiSysExEvent::new(time, port_index, data),
+            _data: PhantomData,
+        }
+    }
+
+    /// Returns the SysEx payload of this event.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        // SAFETY: the payload was borrowed for 'a when this event was created.
+        unsafe { self.event.data() }
+    }
+
+    /// Returns the port index of this event.
+    #[inline]
+    pub fn port_index(&self) -> u16 {
+        self.event.port_index()
+    }
+
+    /// Returns the underlying [`MidiSysExEvent`].
+    #[inline]
+    pub fn as_event(&self) -> &MidiSysExEvent {
+        &self.event
+    }
+}
+
+impl AsRef<UnknownEvent> for BorrowedMidiSysExEvent<'_> {
+    #[inline]
+    fn as_ref(&self) -> &UnknownEvent {
+        self.event.as_unknown()
+    }
+}
+
+impl AsRef<MidiSysExEvent> for BorrowedMidiSysExEvent<'_> {
+    #[inline]
+    fn as_ref(&self) -> &MidiSysExEvent {
+        &self.event
+    }
+}
+
+/// A MIDI 2.0 event, containing a single 128-bit Universal MIDI Packet (UMP).
+///
+/// The packet is stored as four 32-bit words. Its first word always starts with the following
+/// fields, from the most significant bits to the least significant ones:
+///
+/// | Bits    | Field                                  | Accessor                                 |
+/// |---------|----------------------------------------|------------------------------------------|
+/// | 31 - 28 | Message Type (MT)                      | [`message_type`](Self::message_type)     |
+/// | 27 - 24 | Group                                  | [`group`](Self::group)                   |
+/// | 23 - 16 | Status (for channel voice messages)    | [`status`](Self::status)                 |
+///
+/// For MIDI 2.0 channel voice messages (message type `0x4`), the status byte is itself split
+/// into an opcode (high nibble, e.g. `0x9` for Note On), and a channel (low nibble, see
+/// [`channel`](Self::channel)).
+///
+/// The meaning of the remaining bits and words depends on the message type. See the
+/// Universal MIDI Packet specification for more information.
+///
+/// # Example
+///
+/// ```
+/// use clack_common::events::event_types::Midi2Event;
+///
+/// // A MIDI 2.0 Note On, on group 0, channel 2, for note 60, with maximum velocity.
+/// let event = Midi2Event::new(0, 0, [0x4092_3C00, 0xFFFF_0000, 0, 0]);
+///
+/// assert_eq!(event.message_type(), 0x4);
+/// assert_eq!(event.group(), 0);
+/// assert_eq!(event.status(), 0x92);
+/// assert_eq!(event.channel(), 2);
+/// ```
 #[derive(Copy, Clone)]
 pub struct Midi2Event {
     inner: clap_event_midi2,
@@ -207,6 +312,7 @@ impl AsRef<UnknownEvent> for Midi2Event {
 }
 
 impl Midi2Event {
+    /// Creates a new MIDI 2.0 event from the four 32-bit words of a Universal MIDI Packet.
     #[inline]
     pub fn new(time: u32, port_index: u16, data: [u32; 4]) -> Self {
         Self {
@@ -218,6 +324,7 @@ impl Midi2Event {
         }
     }
 
+    /// Returns the four 32-bit words of the Universal MIDI Packet.
     #[inline]
     pub fn data(&self) -> [u32; 4] {
         self.inner.data
@@ -234,6 +341,39 @@ impl Midi2Event {
         self
     }
 
+    /// Returns the Message Type of the packet, i.e. the bits 31 to 28 of its first word.
+    ///
+    /// For instance, `0x4` is a MIDI 2.0 channel voice message, and `0x2` is a MIDI 1.0 channel
+    /// voice message.
+    #[inline]
+    pub fn message_type(&self) -> u8 {
+        (self.inner.data[0] >> 28) as u8
+    }
+
+    /// Returns the Group of the packet, i.e. the bits 27 to 24 of its first word.
+    #[inline]
+    pub fn group(&self) -> u8 {
+        ((self.inner.data[0] >> 24) & 0xF) as u8
+    }
+
+    /// Returns the status byte of the packet, i.e. the bits 23 to 16 of its first word.
+    ///
+    /// This is only meaningful for message types that carry a status byte, such as channel voice
+    /// messages.
+    #[inline]
+    pub fn status(&self) -> u8 {
+        ((self.inner.data[0] >> 16) & 0xFF) as u8
+    }
+
+    /// Returns the channel of the packet, i.e. the bits 19 to 16 of its first word, which are the
+    /// low nibble of the [status](Self::status) byte.
+    ///
+    /// This is only meaningful for channel voice messages.
+    #[inline]
+    pub fn channel(&self) -> u8 {
+        self.status() & 0xF
+    }
+
     #[inline]
     pub fn port_index(&self) -> u16 {
         self.inner.port_index
@@ -263,7 +403,7 @@ impl PartialEq for Midi2Event {
 impl Eq for Midi2Event {}
 
 impl Debug for Midi2Event {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Midi2Event")
             .field("header", &self.header())
             .field("port_index", &self.inner.port_index)
@@ -2,8 +2,8 @@
 
 use crate::events::{Event, EventFlags, EventHeader, Pckn};
 use clap_sys::events::clap_event_note;
-use std::fmt::Formatter;
-use std::marker::PhantomData;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
 
 #[derive(Copy, Clone)]
 #[repr(C)]
@@ -290,9 +290,9 @@ macro_rules! impl_note_traits {
                 }
             }
 
-            impl std::fmt::Debug for $type {
+            impl core::fmt::Debug for $type {
                 #[inline]
-                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     self.inner.fmt(f, stringify!($type))
                 }
             }
@@ -2,22 +2,42 @@ use crate::events::helpers::impl_event_helpers;
 use crate::events::spaces::CoreEventSpace;
 use crate::events::{impl_event_pckn, Event, EventFlags, EventHeader, Match, Pckn, UnknownEvent};
 use clap_sys::events::*;
-use std::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Formatter};
 
+/// The kind of per-note expression a [`NoteExpressionEvent`] carries.
+///
+/// The range and unit of the event's [`value`](NoteExpressionEvent::value) depend on this type.
 #[non_exhaustive]
 #[repr(i32)]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum NoteExpressionType {
+    /// The note's volume, as a linear gain.
+    ///
+    /// The value ranges from `0.0` (silence) to `4.0` (+12dB), with `1.0` being the note's
+    /// nominal volume.
     Volume = CLAP_NOTE_EXPRESSION_VOLUME,
+    /// The note's stereo panning.
+    ///
+    /// The value ranges from `0.0` (left) to `1.0` (right), with `0.5` being the center.
     Pan = CLAP_NOTE_EXPRESSION_PAN,
+    /// The note's tuning, relative to its key.
+    ///
+    /// The value is in semitones, from `-120.0` to `120.0`.
     Tuning = CLAP_NOTE_EXPRESSION_TUNING,
+    /// The note's vibrato amount, from `0.0` to `1.0`.
     Vibrato = CLAP_NOTE_EXPRESSION_VIBRATO,
+    /// The note's expression amount, from `0.0` to `1.0`.
     Expression = CLAP_NOTE_EXPRESSION_EXPRESSION,
+    /// The note's brightness (i.e. its timbre), from `0.0` to `1.0`.
     Brightness = CLAP_NOTE_EXPRESSION_BRIGHTNESS,
+    /// The note's pressure (i.e. its aftertouch), from `0.0` to `1.0`.
     Pressure = CLAP_NOTE_EXPRESSION_PRESSURE,
 }
 
 impl NoteExpressionType {
+    /// Gets a [`NoteExpressionType`] from its raw, C-FFI compatible identifier.
+    ///
+    /// Returns `None` if the given identifier is unknown.
     #[inline]
     pub const fn from_raw(raw: clap_note_expression) -> Option<Self> {
         use NoteExpressionType::*;
@@ -26,18 +46,24 @@ impl NoteExpressionType {
             CLAP_NOTE_EXPRESSION_PAN => Some(Pan),
             CLAP_NOTE_EXPRESSION_TUNING => Some(Tuning),
             CLAP_NOTE_EXPRESSION_VIBRATO => Some(Vibrato),
+            CLAP_NOTE_EXPRESSION_EXPRESSION => Some(Expression),
             CLAP_NOTE_EXPRESSION_BRIGHTNESS => Some(Brightness),
             CLAP_NOTE_EXPRESSION_PRESSURE => Some(Pressure),
             _ => None,
         }
     }
 
+    /// Returns the raw, C-FFI compatible identifier of this expression type.
     #[inline]
     pub const fn into_raw(self) -> clap_note_expression {
         self as clap_note_expression
     }
 }
 
+/// An event that applies a per-note expression (e.g. tuning, pressure or brightness) to the notes
+/// matching its [`Pckn`].
+///
+/// This is typically used to implement MPE-like, per-note modulation.
 #[derive(Copy, Clone)]
 pub struct NoteExpressionEvent {
     inner: clap_event_note_expression,
@@ -57,6 +83,8 @@ impl AsRef<UnknownEvent> for NoteExpressionEvent {
 }
 
 impl NoteExpressionEvent {
+    /// Creates a new note expression event of the given type and value, targeting the notes
+    /// matching the given [`Pckn`].
     #[inline]
     pub const fn new(
         time: u32,
@@ -77,32 +105,63 @@ impl NoteExpressionEvent {
         }
     }
 
+    /// Creates a new [`Tuning`](NoteExpressionType::Tuning) expression event, which tunes the
+    /// matching notes by the given amount of semitones.
+    #[inline]
+    pub const fn tuning(time: u32, pckn: Pckn, semitones: f64) -> Self {
+        Self::new(time, pckn, NoteExpressionType::Tuning, semitones)
+    }
+
+    /// Creates a new [`Pressure`](NoteExpressionType::Pressure) expression event, with a pressure
+    /// value between `0.0` and `1.0`.
+    #[inline]
+    pub const fn pressure(time: u32, pckn: Pckn, pressure: f64) -> Self {
+        Self::new(time, pckn, NoteExpressionType::Pressure, pressure)
+    }
+
+    /// Creates a new [`Brightness`](NoteExpressionType::Brightness) expression event, with a
+    /// brightness value between `0.0` and `1.0`.
+    #[inline]
+    pub const fn brightness(time: u32, pckn: Pckn, brightness: f64) -> Self {
+        Self::new(time, pckn, NoteExpressionType::Brightness, brightness)
+    }
+
+    /// Returns the type of this expression.
+    ///
+    /// Returns `None` if the expression type is unknown.
     #[inline]
     pub const fn expression_type(&self) -> Option<NoteExpressionType> {
         NoteExpressionType::from_raw(self.inner.expression_id)
     }
 
+    /// Sets the type of this expression.
     #[inline]
     pub fn set_expression_type(&mut self, expression_type: NoteExpressionType) {
         self.inner.expression_id = expression_type.into_raw()
     }
 
+    /// Sets the type of this expression, and returns the event.
     #[inline]
     pub const fn with_expression_type(mut self, expression_type: NoteExpressionType) -> Self {
         self.inner.expression_id = expression_type.into_raw();
         self
     }
 
+    /// Returns the value of this expression.
+    ///
+    /// Its range and unit depend on the [expression type](NoteExpressionType).
     #[inline]
     pub const fn value(&self) -> f64 {
         self.inner.value
     }
 
+    /// Sets the value of this expression.
     #[inline]
     pub fn set_value(&mut self, value: f64) {
         self.inner.value = value
     }
 
+    /// Sets the value of this expression, and returns the event.
     #[inline]
     pub const fn with_value(mut self, value: f64) -> Self {
         self.inner.value = value;
@@ -126,7 +185,7 @@ impl PartialEq for NoteExpressionEvent {
 }
 
 impl Debug for NoteExpressionEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("NoteExpressionEvent")
             .field("port_index", &self.inner.port_index)
             .field("channel", &self.inner.channel)
@@ -137,3 +196,24 @@ impl Debug for NoteExpressionEvent {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expression_types_round_trip() {
+        use NoteExpressionType::*;
+
+        for expression_type in [
+            Volume, Pan, Tuning, Vibrato, Expression, Brightness, Pressure,
+        ] {
+            assert_eq!(
+                NoteExpressionType::from_raw(expression_type.into_raw()),
+                Some(expression_type)
+            );
+        }
+
+        assert_eq!(NoteExpressionType::from_raw(-1), None);
+    }
+}
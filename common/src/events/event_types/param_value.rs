@@ -7,7 +7,7 @@ use clap_sys::events::{
     CLAP_EVENT_PARAM_GESTURE_BEGIN, CLAP_EVENT_PARAM_GESTURE_END, CLAP_EVENT_PARAM_MOD,
     CLAP_EVENT_PARAM_VALUE,
 };
-use std::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Formatter};
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -111,7 +111,7 @@ impl PartialEq for ParamValueEvent {
 }
 
 impl Debug for ParamValueEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("ParamValueEvent")
             .field("header", &self.header())
             .field("port_index", &self.inner.port_index)
@@ -226,7 +226,7 @@ impl PartialEq for ParamModEvent {
 }
 
 impl Debug for ParamModEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("ParamModEvent")
             .field("header", &self.header())
             .field("port_index", &self.inner.port_index)
@@ -287,7 +287,7 @@ impl ParamGestureBeginEvent {
 }
 
 impl Debug for ParamGestureBeginEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("ParamGestureBeginEvent")
             .field("header", &self.header())
             .field("header", &self.header())
@@ -352,7 +352,7 @@ impl ParamGestureEndEvent {
 }
 
 impl Debug for ParamGestureEndEvent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("ParamGestureEndEvent")
             .field("header", &self.header())
             .field("header", self.header())
@@ -99,4 +99,164 @@ impl TransportEvent {
         // SAFETY: This type is #[repr(C)]-compatible with clap_event_transport
         unsafe { &mut *(raw as *mut clap_event_transport as *mut Self) }
     }
+
+    /// Returns this transport's tempo, in beats per minute.
+    ///
+    /// This returns [`None`] if this transport does not provide a valid tempo, i.e. if the
+    /// [`HAS_TEMPO`](TransportFlags::HAS_TEMPO) flag is not set, or if the tempo isn't a finite,
+    /// strictly positive value.
+    #[inline]
+    pub fn tempo(&self) -> Option<f64> {
+        self.valid_tempo()
+    }
+
+    /// Returns `true` if the transport is currently playing, i.e. if the
+    /// [`IS_PLAYING`](TransportFlags::IS_PLAYING) flag is set.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.flags.contains(TransportFlags::IS_PLAYING)
+    }
+
+    /// Returns the current song position, in beats.
+    ///
+    /// This returns [`None`] if this transport does not provide a beats timeline, i.e. if the
+    /// [`HAS_BEATS_TIMELINE`](TransportFlags::HAS_BEATS_TIMELINE) flag is not set.
+    #[inline]
+    pub fn song_pos_beats(&self) -> Option<f64> {
+        if !self.flags.contains(TransportFlags::HAS_BEATS_TIMELINE) {
+            return None;
+        }
+
+        Some(self.song_pos_beats.to_float())
+    }
+
+    /// Returns the current time signature, as a `(numerator, denominator)` pair.
+    ///
+    /// This returns [`None`] if this transport does not provide a time signature, i.e. if the
+    /// [`HAS_TIME_SIGNATURE`](TransportFlags::HAS_TIME_SIGNATURE) flag is not set, or if either
+    /// the numerator or the denominator isn't strictly positive.
+    #[inline]
+    pub fn time_signature(&self) -> Option<(u16, u16)> {
+        if !self.flags.contains(TransportFlags::HAS_TIME_SIGNATURE)
+            || self.time_signature_numerator <= 0
+            || self.time_signature_denominator <= 0
+        {
+            return None;
+        }
+
+        Some((
+            self.time_signature_numerator as u16,
+            self.time_signature_denominator as u16,
+        ))
+    }
+
+    /// Converts a duration in beats to a duration in samples, using this transport's tempo and
+    /// the given sample rate.
+    ///
+    /// This returns [`None`] if this transport does not provide a valid tempo, i.e. if the
+    /// [`HAS_TEMPO`](TransportFlags::HAS_TEMPO) flag is not set, or if the tempo isn't a finite,
+    /// strictly positive value.
+    #[inline]
+    pub fn beats_to_samples(&self, beats: f64, sample_rate: f64) -> Option<f64> {
+        let tempo = self.valid_tempo()?;
+        Some(beats * 60.0 / tempo * sample_rate)
+    }
+
+    /// Converts a duration in samples to a duration in beats, using this transport's tempo and
+    /// the given sample rate.
+    ///
+    /// This returns [`None`] if this transport does not provide a valid tempo, i.e. if the
+    /// [`HAS_TEMPO`](TransportFlags::HAS_TEMPO) flag is not set, or if the tempo isn't a finite,
+    /// strictly positive value.
+    #[inline]
+    pub fn samples_to_beats(&self, samples: f64, sample_rate: f64) -> Option<f64> {
+        let tempo = self.valid_tempo()?;
+        Some(samples * tempo / (60.0 * sample_rate))
+    }
+
+    #[inline]
+    fn valid_tempo(&self) -> Option<f64> {
+        if !self.flags.contains(TransportFlags::HAS_TEMPO)
+            || !self.tempo.is_finite()
+            || self.tempo <= 0.0
+        {
+            return None;
+        }
+
+        Some(self.tempo)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::EventFlags;
+
+    fn transport(flags: TransportFlags, tempo: f64) -> TransportEvent {
+        TransportEvent {
+            header: EventHeader::new_core(0, EventFlags::empty()),
+            flags,
+            song_pos_beats: BeatTime::from_int(0),
+            song_pos_seconds: SecondsTime::from_int(0),
+            tempo,
+            tempo_inc: 0.0,
+            loop_start_beats: BeatTime::from_int(0),
+            loop_end_beats: BeatTime::from_int(0),
+            loop_start_seconds: SecondsTime::from_int(0),
+            loop_end_seconds: SecondsTime::from_int(0),
+            bar_start: BeatTime::from_int(0),
+            bar_number: 0,
+            time_signature_numerator: 4,
+            time_signature_denominator: 4,
+        }
+    }
+
+    #[test]
+    fn converts_between_beats_and_samples() {
+        let transport = transport(TransportFlags::HAS_TEMPO, 120.0);
+
+        assert_eq!(transport.beats_to_samples(1.0, 48_000.0), Some(24_000.0));
+        assert_eq!(transport.beats_to_samples(0.25, 44_100.0), Some(5_512.5));
+        assert_eq!(transport.samples_to_beats(24_000.0, 48_000.0), Some(1.0));
+        assert_eq!(transport.samples_to_beats(96_000.0, 48_000.0), Some(4.0));
+    }
+
+    #[test]
+    fn accessors_check_flags() {
+        let mut transport = transport(TransportFlags::empty(), 120.0);
+        transport.song_pos_beats = BeatTime::from_float(2.5);
+        transport.time_signature_numerator = 7;
+        transport.time_signature_denominator = 8;
+
+        assert_eq!(transport.tempo(), None);
+        assert!(!transport.is_playing());
+        assert_eq!(transport.song_pos_beats(), None);
+        assert_eq!(transport.time_signature(), None);
+
+        transport.flags = TransportFlags::HAS_TEMPO
+            | TransportFlags::IS_PLAYING
+            | TransportFlags::HAS_BEATS_TIMELINE
+            | TransportFlags::HAS_TIME_SIGNATURE;
+
+        assert_eq!(transport.tempo(), Some(120.0));
+        assert!(transport.is_playing());
+        assert_eq!(transport.song_pos_beats(), Some(2.5));
+        assert_eq!(transport.time_signature(), Some((7, 8)));
+
+        transport.time_signature_denominator = 0;
+        assert_eq!(transport.time_signature(), None);
+    }
+
+    #[test]
+    fn conversions_require_a_valid_tempo() {
+        let no_tempo = transport(TransportFlags::empty(), 120.0);
+        assert_eq!(no_tempo.beats_to_samples(1.0, 48_000.0), None);
+        assert_eq!(no_tempo.samples_to_beats(1.0, 48_000.0), None);
+
+        let zero_tempo = transport(TransportFlags::HAS_TEMPO, 0.0);
+        assert_eq!(zero_tempo.beats_to_samples(1.0, 48_000.0), None);
+
+        let nan_tempo = transport(TransportFlags::HAS_TEMPO, f64::NAN);
+        assert_eq!(nan_tempo.samples_to_beats(1.0, 48_000.0), None);
+    }
 }
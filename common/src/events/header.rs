@@ -5,9 +5,9 @@ use crate::events::Event;
 use bitflags::bitflags;
 use clap_sys::events::clap_event_header;
 use clap_sys::events::{CLAP_EVENT_DONT_RECORD, CLAP_EVENT_IS_LIVE};
-use std::cmp::Ordering;
-use std::fmt;
-use std::marker::PhantomData;
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
 
 /// The common metadata header of all CLAP events.
 ///
@@ -4,15 +4,19 @@
 #![deny(missing_docs)]
 
 mod batcher;
+#[cfg(feature = "alloc")]
 mod buffer;
 mod implementation;
 mod input;
 mod merger;
 mod output;
+mod overflow;
 
 pub use batcher::*;
+#[cfg(feature = "alloc")]
 pub use buffer::*;
 pub use implementation::*;
 pub use input::*;
 pub use merger::*;
 pub use output::*;
+pub use overflow::*;
@@ -1,5 +1,5 @@
 use crate::events::io::{InputEvents, InputEventsIter};
-use std::ops::Bound;
+use core::ops::{Bound, Range};
 
 #[derive(Copy, Clone, Debug)]
 enum State {
@@ -13,6 +13,13 @@ enum State {
     Ended,
 }
 
+/// The maximum size of the batches, alongside the total number of frames of the processed block.
+#[derive(Copy, Clone, Debug)]
+struct BatchCap {
+    max_frames: usize,
+    frames_count: usize,
+}
+
 /// An iterator which batches input events by grouping them together.
 ///
 /// See the [`InputEvents::batch`] method documentation for more details and usage examples.
@@ -22,6 +29,9 @@ pub struct EventBatcher<'a> {
     events: &'a InputEvents<'a>,
     events_len: u32,
     state: State,
+    cap: Option<BatchCap>,
+    /// The sample range of a batch that still needs to be split, because it exceeded the cap.
+    split_remainder: Option<Range<usize>>,
 }
 
 impl<'a> EventBatcher<'a> {
@@ -38,9 +48,47 @@ impl<'a> EventBatcher<'a> {
                     events.get(0).map(|e| e.header().time())
                 },
             },
+            cap: None,
+            split_remainder: None,
+        }
+    }
+
+    pub(crate) fn with_max(
+        events: &'a InputEvents<'a>,
+        frames_count: u32,
+        max_frames: u32,
+    ) -> Self {
+        assert!(
+            max_frames > 0,
+            "Maximum batch size must be greater than zero"
+        );
+
+        Self {
+            cap: Some(BatchCap {
+                max_frames: max_frames as usize,
+                frames_count: frames_count as usize,
+            }),
+            ..Self::new(events)
         }
     }
 
+    /// Splits the given batch if it exceeds the cap, storing the rest in `split_remainder`.
+    fn split(&mut self, mut batch: EventBatch<'a>) -> EventBatch<'a> {
+        let Some(cap) = self.cap else {
+            return batch;
+        };
+
+        let end = batch
+            .next_batch_first_sample
+            .unwrap_or(cap.frames_count)
+            .max(batch.first_sample);
+        let capped_end = end.min(batch.first_sample + cap.max_frames);
+
+        self.split_remainder = (capped_end < end).then_some(capped_end..end);
+        batch.next_batch_first_sample = Some(capped_end);
+        batch
+    }
+
     fn next_non_matching(
         &self,
         current_event_index: u32,
@@ -65,6 +113,22 @@ impl<'a> Iterator for EventBatcher<'a> {
     type Item = EventBatch<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let batch = match self.split_remainder.take() {
+            // The remainder of a split batch never contains any events.
+            Some(remainder) => EventBatch {
+                events: InputEventsIter::new(self.events, self.events_len..self.events_len),
+                first_sample: remainder.start,
+                next_batch_first_sample: Some(remainder.end),
+            },
+            None => self.next_uncapped()?,
+        };
+
+        Some(self.split(batch))
+    }
+}
+
+impl<'a> EventBatcher<'a> {
+    fn next_uncapped(&mut self) -> Option<EventBatch<'a>> {
         use crate::events::io::batcher::State::*;
 
         let (current_event_index, current_sample, next_non_matching_event) = match self.state {
@@ -153,6 +217,31 @@ impl<'a> EventBatch<'a> {
         self.next_batch_first_sample
     }
 
+    /// Returns the batch's bounds as a range of sample indexes, using the given `frames_count` as
+    /// the end of the last batch.
+    ///
+    /// This is equivalent to `first_sample..next_batch_first_sample`, or
+    /// `first_sample..frames_count` if this is the last batch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::event_types::MidiEvent;
+    /// use clack_common::events::io::EventBuffer;
+    ///
+    /// let mut buffer = EventBuffer::new();
+    /// buffer.push(&MidiEvent::new(10, 0, [0x90, 60, 127]));
+    ///
+    /// let input_events = buffer.as_input();
+    /// let ranges: Vec<_> = input_events.batch().map(|b| b.sample_range(32)).collect();
+    ///
+    /// assert_eq!(ranges, [0..10, 10..32]);
+    /// ```
+    #[inline]
+    pub fn sample_range(&self, frames_count: usize) -> Range<usize> {
+        self.first_sample..self.next_batch_first_sample.unwrap_or(frames_count)
+    }
+
     /// Returns the batch's bounds as a pair of sample index bounds, which can be directly used
     /// for indexing into a slice.
     ///
@@ -388,4 +477,99 @@ mod tests {
 
         assert!(events.next().is_none())
     }
+
+    #[test]
+    pub fn batches_midi2_events_with_core_events() {
+        use crate::events::event_types::{Midi2Event, NoteOnEvent};
+        use crate::events::spaces::CoreEventSpace;
+        use crate::events::Pckn;
+
+        let mut buf = crate::events::io::EventBuffer::new();
+        buf.push(&NoteOnEvent::new(
+            0,
+            Pckn::new(0u16, 0u16, 60u16, 0u32),
+            1.0,
+        ));
+        buf.push(&Midi2Event::new(4, 0, [0x4092_3C00, 0xFFFF_0000, 0, 0]));
+
+        let events = buf.as_input();
+        let mut events = events.batch();
+
+        let batch = events.next().unwrap();
+        assert_eq!(batch.next_batch_first_sample(), Some(4));
+        assert!(matches!(
+            batch.events().next().unwrap().as_core_event(),
+            Some(CoreEventSpace::NoteOn(_))
+        ));
+
+        let batch = events.next().unwrap();
+        assert_eq!(batch.first_sample(), 4);
+        match batch.events().next().unwrap().as_core_event() {
+            Some(CoreEventSpace::Midi2(event)) => {
+                assert_eq!(event.message_type(), 0x4);
+                assert_eq!(event.status(), 0x92);
+                assert_eq!(event.channel(), 2);
+            }
+            e => panic!("Expected MIDI 2 event, got {e:?}"),
+        }
+
+        assert!(events.next().is_none())
+    }
+
+    /// Collects the sample ranges and event counts of each batch.
+    fn capped_batches(
+        events: &InputEvents,
+        frames_count: u32,
+        max: u32,
+    ) -> Vec<(Range<usize>, usize)> {
+        events
+            .batch_with_max(frames_count, max)
+            .map(|b| (b.sample_range(frames_count as usize), b.events().len()))
+            .collect()
+    }
+
+    #[test]
+    pub fn capped_batches_with_empty_events() {
+        let events = InputEvents::empty();
+
+        assert_eq!(capped_batches(&events, 64, 64), [(0..64, 0)]);
+        assert_eq!(capped_batches(&events, 64, 128), [(0..64, 0)]);
+        assert_eq!(
+            capped_batches(&events, 100, 32),
+            [(0..32, 0), (32..64, 0), (64..96, 0), (96..100, 0)]
+        );
+    }
+
+    #[test]
+    pub fn capped_batches_split_around_events() {
+        let buf = [
+            ParamGestureBeginEvent::new(0, PARAM),
+            ParamGestureBeginEvent::new(5, PARAM),
+            ParamGestureBeginEvent::new(5, PARAM),
+            ParamGestureBeginEvent::new(40, PARAM),
+        ];
+        let events = InputEvents::from_buffer(&buf);
+
+        assert_eq!(
+            capped_batches(&events, 70, 16),
+            [
+                (0..5, 1),
+                (5..21, 2),
+                (21..37, 0),
+                (37..40, 0),
+                (40..56, 1),
+                (56..70, 0)
+            ]
+        );
+    }
+
+    #[test]
+    pub fn capped_batches_have_an_end() {
+        let buf = [ParamGestureBeginEvent::new(10, PARAM)];
+        let events = InputEvents::from_buffer(&buf);
+
+        let last = events.batch_with_max(20, 64).last().unwrap();
+        assert_eq!(last.first_sample(), 10);
+        assert_eq!(last.next_batch_first_sample(), Some(20));
+    }
 }
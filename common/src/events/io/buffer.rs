@@ -1,11 +1,16 @@
-use crate::events::event_types::TransportEvent;
+use crate::events::event_types::{
+    MidiSysExEvent, NoteExpressionEvent, NoteExpressionType, NoteOffEvent, NoteOnEvent,
+    ParamModEvent, ParamValueEvent, TransportEvent,
+};
 use crate::events::io::implementation::{InputEventBuffer, OutputEventBuffer};
 use crate::events::io::{InputEvents, OutputEvents, TryPushError};
-use crate::events::UnknownEvent;
-use clap_sys::events::clap_event_header;
-use core::mem::{size_of_val, MaybeUninit};
-use std::fmt::{Debug, Formatter};
-use std::ops::{Index, Range};
+use crate::events::{Pckn, UnknownEvent};
+use crate::utils::{ClapId, Cookie};
+use alloc::vec::Vec;
+use clap_sys::events::{clap_event_header, clap_event_midi_sysex};
+use core::fmt::{Debug, Formatter};
+use core::mem::{size_of, size_of_val, MaybeUninit};
+use core::ops::{Index, Range};
 
 #[repr(C, align(8))]
 #[derive(Copy, Clone)]
@@ -46,10 +51,40 @@ struct AlignedEventHeader(clap_event_header);
 /// To avoid allocations, hosts should use the [`with_capacity`](EventBuffer::with_capacity)
 /// to pre-allocate a reasonable amount of space for plugins to send their events.
 ///
-/// However, this is always a best-effort, and not a guarantee.
+/// However, this is always a best-effort, and not a guarantee: by default, when a plugin pushes
+/// more events than the buffer can hold through [`OutputEvents::try_push`], the buffer grows,
+/// which allocates on the audio thread. Hosts that require deterministic behavior can instead use
+/// the [`FailOnFull`](EventBufferGrowthPolicy::FailOnFull) growth policy (see
+/// [`set_growth_policy`](EventBuffer::set_growth_policy)), in which case overflowing events are
+/// rejected instead.
 pub struct EventBuffer {
     headers: Vec<MaybeUninit<AlignedEventHeader>>, // force 64-bit alignment
     indexes: Vec<u32>,
+    /// The header indexes of the SysEx events pushed with `push_sysex`, whose payload is stored
+    /// right after them in `headers`.
+    inline_sysex: Vec<u32>,
+    growth_policy: EventBufferGrowthPolicy,
+}
+
+/// How an [`EventBuffer`] behaves when an event is pushed to it through
+/// [`try_push`](EventBuffer::try_push) (e.g. by a plugin, through [`OutputEvents`]), but it does
+/// not have enough capacity left to hold it.
+///
+/// This policy does not apply to the other push methods of [`EventBuffer`] (such as
+/// [`push`](EventBuffer::push)), which always grow the buffer as needed.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum EventBufferGrowthPolicy {
+    /// The buffer grows to fit the event, which may allocate.
+    ///
+    /// This is the default.
+    #[default]
+    Grow,
+    /// The event is rejected, and [`try_push`](EventBuffer::try_push) returns a
+    /// [`TryPushError`]. The buffer is guaranteed never to allocate.
+    ///
+    /// Hosts can wrap the buffer in an [`OverflowCounter`](crate::events::io::OverflowCounter) to
+    /// keep track of how many events were rejected.
+    FailOnFull,
 }
 
 #[inline]
@@ -69,6 +104,8 @@ impl EventBuffer {
         Self {
             headers: Vec::new(),
             indexes: Vec::new(),
+            inline_sysex: Vec::new(),
+            growth_policy: EventBufferGrowthPolicy::Grow,
         }
     }
 
@@ -87,15 +124,36 @@ impl EventBuffer {
             // TransportEvent is the largest standard CLAP event.
             headers: Vec::with_capacity(events * core::mem::size_of::<TransportEvent>()),
             indexes: Vec::with_capacity(events),
+            inline_sysex: Vec::new(),
+            growth_policy: EventBufferGrowthPolicy::Grow,
         }
     }
 
+    /// Sets how this buffer behaves when an event is pushed through
+    /// [`try_push`](Self::try_push) (and therefore through [`OutputEvents`]) but the buffer is
+    /// full.
+    ///
+    /// See [`EventBufferGrowthPolicy`] for more information.
+    #[inline]
+    pub fn set_growth_policy(&mut self, policy: EventBufferGrowthPolicy) {
+        self.growth_policy = policy;
+    }
+
+    /// Returns the current growth policy of this buffer.
+    ///
+    /// See [`EventBufferGrowthPolicy`] for more information.
+    #[inline]
+    pub fn growth_policy(&self) -> EventBufferGrowthPolicy {
+        self.growth_policy
+    }
+
     /// Clears the buffer, removing all events.
     ///
     /// Note that this has no effect on the allocated capacity of the buffer.
     pub fn clear(&mut self) {
         self.indexes.clear();
         self.headers.clear();
+        self.inline_sysex.clear();
     }
 
     /// Returns the number of events in this buffer.
@@ -141,6 +199,116 @@ impl EventBuffer {
         }
     }
 
+    /// Returns an iterator of all the events contained in this buffer which have a time in the
+    /// `start..end` range.
+    ///
+    /// This is useful for hosts splitting a large processing block into smaller sub-blocks, as
+    /// each sub-block must only be given the events that fall within its own time range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::event_types::MidiEvent;
+    /// use clack_common::events::io::EventBuffer;
+    /// use clack_common::events::Event;
+    ///
+    /// let mut buffer = EventBuffer::new();
+    /// buffer.push(&MidiEvent::new(10, 0, [0x90, 60, 127]));
+    /// buffer.push(&MidiEvent::new(64, 0, [0x80, 60, 0]));
+    ///
+    /// let first_half: Vec<_> = buffer.events_in_range(0, 64).collect();
+    /// assert_eq!(first_half.len(), 1);
+    /// assert_eq!(first_half[0].header().time(), 10);
+    /// ```
+    pub fn events_in_range(&self, start: u32, end: u32) -> impl Iterator<Item = &UnknownEvent> {
+        self.iter()
+            .filter(move |e| (start..end).contains(&e.header().time()))
+    }
+
+    /// Pushes a copy of all the events of the `source` buffer which have a time in the
+    /// `start..end` range, re-basing their times so that they are relative to `start`.
+    ///
+    /// For instance, an event with a time of `70` in the `source` buffer is pushed with a time of
+    /// `6` if `start` is `64`.
+    ///
+    /// This allows hosts doing sub-block processing to easily produce the [`InputEvents`] of a
+    /// sub-block starting at `start`, by calling [`as_input`](EventBuffer::as_input) on this
+    /// buffer afterward.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::event_types::MidiEvent;
+    /// use clack_common::events::io::EventBuffer;
+    /// use clack_common::events::Event;
+    ///
+    /// let mut buffer = EventBuffer::new();
+    /// buffer.push(&MidiEvent::new(10, 0, [0x90, 60, 127]));
+    /// buffer.push(&MidiEvent::new(70, 0, [0x80, 60, 0]));
+    ///
+    /// let mut sub_block = EventBuffer::new();
+    /// sub_block.push_range_rebased(&buffer, 64, 128);
+    ///
+    /// let input_events = sub_block.as_input();
+    /// assert_eq!(input_events.len(), 1);
+    /// assert_eq!(input_events.get(0).unwrap().header().time(), 6);
+    /// ```
+    ///
+    /// # Realtime Safety
+    ///
+    /// This method may allocate, just like [`push`](EventBuffer::push).
+    pub fn push_range_rebased(&mut self, source: &EventBuffer, start: u32, end: u32) {
+        for event in source.events_in_range(start, end) {
+            let index = self.append_header_data(event);
+
+            // SAFETY: the event header was just written by append_header_data
+            let header = unsafe { self.headers[index].assume_init_mut() };
+            header.0.time -= start;
+
+            self.indexes.push(index as u32);
+        }
+    }
+
+    /// Pushes a copy of all the events of the `source` buffer, adding the given `offset` to their
+    /// times.
+    ///
+    /// This is the reverse operation of [`push_range_rebased`](EventBuffer::push_range_rebased):
+    /// it allows hosts doing sub-block processing to gather the output events a plugin produced
+    /// during a sub-block starting at `offset`, with times relative to the whole block.
+    ///
+    /// Times that would overflow are saturated to [`u32::MAX`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::event_types::MidiEvent;
+    /// use clack_common::events::io::EventBuffer;
+    /// use clack_common::events::Event;
+    ///
+    /// let mut sub_block = EventBuffer::new();
+    /// sub_block.push(&MidiEvent::new(6, 0, [0x80, 60, 0]));
+    ///
+    /// let mut block = EventBuffer::new();
+    /// block.push_all_offset(&sub_block, 64);
+    ///
+    /// assert_eq!(block[0].header().time(), 70);
+    /// ```
+    ///
+    /// # Realtime Safety
+    ///
+    /// This method may allocate, just like [`push`](EventBuffer::push).
+    pub fn push_all_offset(&mut self, source: &EventBuffer, offset: u32) {
+        for event in source {
+            let index = self.append_header_data(event);
+
+            // SAFETY: the event header was just written by append_header_data
+            let header = unsafe { self.headers[index].assume_init_mut() };
+            header.0.time = header.0.time.saturating_add(offset);
+
+            self.indexes.push(index as u32);
+        }
+    }
+
     /// Sorts the events contained in this buffer, based on their time.
     ///
     /// It is necessary to sort the events before passing them to a plugin.
@@ -181,6 +349,143 @@ impl EventBuffer {
         self.indexes.push(index as u32);
     }
 
+    /// Tries to push the given event into the buffer, following the buffer's
+    /// [growth policy](Self::set_growth_policy).
+    ///
+    /// The event is always added at the end of the buffer. This is the method used when a plugin
+    /// pushes events to this buffer through [`OutputEvents`].
+    ///
+    /// # Errors
+    ///
+    /// If the growth policy is [`FailOnFull`](EventBufferGrowthPolicy::FailOnFull) and the buffer
+    /// does not have enough spare capacity to hold the event, the event is not pushed and a
+    /// [`TryPushError`] is returned. This never fails with the default
+    /// [`Grow`](EventBufferGrowthPolicy::Grow) policy.
+    ///
+    /// # Realtime Safety
+    ///
+    /// This method may allocate, unless the growth policy is
+    /// [`FailOnFull`](EventBufferGrowthPolicy::FailOnFull).
+    pub fn try_push<E: AsRef<UnknownEvent> + ?Sized>(
+        &mut self,
+        event: &E,
+    ) -> Result<(), TryPushError> {
+        let event = event.as_ref();
+
+        if self.growth_policy == EventBufferGrowthPolicy::FailOnFull && !self.can_fit(event) {
+            return Err(TryPushError::new());
+        }
+
+        self.push(event);
+        Ok(())
+    }
+
+    /// Pushes a new [`NoteOnEvent`] with the given parameters at the end of the buffer.
+    ///
+    /// This is a shorthand for pushing a [`NoteOnEvent::new`].
+    #[inline]
+    pub fn push_note_on(&mut self, time: u32, pckn: Pckn, velocity: f64) {
+        self.push(&NoteOnEvent::new(time, pckn, velocity))
+    }
+
+    /// Pushes a new [`NoteOffEvent`] with the given parameters at the end of the buffer.
+    ///
+    /// This is a shorthand for pushing a [`NoteOffEvent::new`].
+    #[inline]
+    pub fn push_note_off(&mut self, time: u32, pckn: Pckn, velocity: f64) {
+        self.push(&NoteOffEvent::new(time, pckn, velocity))
+    }
+
+    /// Pushes a new [`NoteExpressionEvent`] with the given parameters at the end of the buffer.
+    ///
+    /// This is a shorthand for pushing a [`NoteExpressionEvent::new`].
+    #[inline]
+    pub fn push_note_expression(
+        &mut self,
+        time: u32,
+        pckn: Pckn,
+        expression_type: NoteExpressionType,
+        value: f64,
+    ) {
+        self.push(&NoteExpressionEvent::new(
+            time,
+            pckn,
+            expression_type,
+            value,
+        ))
+    }
+
+    /// Pushes a new [`ParamValueEvent`] with the given parameters at the end of the buffer.
+    ///
+    /// The event applies to all notes (i.e. it uses [`Pckn::match_all`]), and has no cookie.
+    /// Use [`push`](Self::push) with a [`ParamValueEvent::new`] to set those as well.
+    #[inline]
+    pub fn push_param_value(&mut self, time: u32, param_id: ClapId, value: f64) {
+        self.push(&ParamValueEvent::new(
+            time,
+            param_id,
+            Pckn::match_all(),
+            value,
+            Cookie::empty(),
+        ))
+    }
+
+    /// Pushes a new [`ParamModEvent`] with the given parameters at the end of the buffer.
+    ///
+    /// The event applies to all notes (i.e. it uses [`Pckn::match_all`]), and has no cookie.
+    /// Use [`push`](Self::push) with a [`ParamModEvent::new`] to set those as well.
+    #[inline]
+    pub fn push_param_mod(&mut self, time: u32, param_id: ClapId, amount: f64) {
+        self.push(&ParamModEvent::new(
+            time,
+            param_id,
+            Pckn::match_all(),
+            amount,
+            Cookie::empty(),
+        ))
+    }
+
+    /// Pushes a new [`MidiSysExEvent`] with the given parameters at the end of the buffer,
+    /// storing a copy of the given SysEx payload within the buffer itself.
+    ///
+    /// Unlike pushing a [`MidiSysExEvent`] or a
+    /// [`BorrowedMidiSysExEvent`](crate::events::event_types::BorrowedMidiSysExEvent), which only
+    /// copies the pointer to their payload, the `data` does not need to outlive this buffer.
+    /// The pushed event's payload pointer remains valid for as long as the event is in the buffer,
+    /// even if the buffer has to grow afterward.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::io::EventBuffer;
+    /// use clack_common::events::spaces::CoreEventSpace;
+    ///
+    /// let mut buffer = EventBuffer::new();
+    /// buffer.push_sysex(0, 0, &[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]);
+    ///
+    /// let Some(CoreEventSpace::MidiSysEx(event)) = buffer[0].as_core_event() else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// // SAFETY: the payload is stored in the buffer, which outlives this borrow.
+    /// assert_eq!(unsafe { event.data() }, &[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]);
+    /// ```
+    ///
+    /// # Realtime Safety
+    ///
+    /// This method may allocate, just like [`push`](EventBuffer::push).
+    pub fn push_sysex(&mut self, time: u32, port_index: u16, data: &[u8]) {
+        // The pointer to the given data is only temporary, and is replaced right below.
+        let index = self.append_header_data(MidiSysExEvent::new(time, port_index, data).as_ref());
+
+        let payload = self.allocate_mut(data.len());
+        payload.copy_from_slice(data);
+
+        self.inline_sysex.push(index as u32);
+        self.update_sysex_pointer(index);
+        self.indexes.push(index as u32);
+    }
+
     /// Produces an [`InputEvents`] that wraps this buffer as an [`InputEventBuffer`] implementation.
     ///
     /// This helper method is strictly equivalent to using [`InputEvents::from_buffer`].
@@ -197,6 +502,14 @@ impl EventBuffer {
         OutputEvents::from_buffer(self)
     }
 
+    /// Returns `true` if the given event can be pushed without growing the buffer.
+    fn can_fit(&self, event: &UnknownEvent) -> bool {
+        let headers_size = byte_index_to_value_index::<AlignedEventHeader>(event.as_bytes().len());
+
+        self.headers.capacity() - self.headers.len() >= headers_size
+            && self.indexes.len() < self.indexes.capacity()
+    }
+
     fn append_header_data(&mut self, event: &UnknownEvent) -> usize {
         let index = self.headers.len();
         let event_bytes = event.as_bytes();
@@ -207,12 +520,36 @@ impl EventBuffer {
         index
     }
 
+    /// Points the SysEx event at the given header index to its payload, stored right after it.
+    fn update_sysex_pointer(&mut self, index: usize) {
+        let payload_index =
+            index + byte_index_to_value_index::<AlignedEventHeader>(size_of::<MidiSysExEvent>());
+
+        // SAFETY: the index points to a SysEx event written by push_sysex, which fits in the
+        // buffer and is suitably aligned, since headers are 64-bit aligned.
+        // The payload starts right after the event (it may be empty, i.e. at the end of the buffer).
+        unsafe {
+            let headers = self.headers.as_mut_ptr();
+            let event = headers.add(index) as *mut clap_event_midi_sysex;
+
+            (*event).buffer = headers.add(payload_index) as *const u8;
+        }
+    }
+
     fn allocate_mut(&mut self, byte_size: usize) -> &mut [u8] {
         let previous_len = self.headers.len();
+        let previous_ptr = self.headers.as_ptr();
         let headers_size = byte_index_to_value_index::<AlignedEventHeader>(byte_size);
         self.headers
             .resize(previous_len + headers_size, MaybeUninit::zeroed());
 
+        // The storage was moved: all the inline SysEx payload pointers have to be updated.
+        if self.headers.as_ptr() != previous_ptr {
+            for i in 0..self.inline_sysex.len() {
+                self.update_sysex_pointer(self.inline_sysex[i] as usize);
+            }
+        }
+
         // PANIC: we just resized, this should not panic unless there is a bug in the implementation
         let new_elements = &mut self.headers[previous_len..];
 
@@ -239,6 +576,53 @@ impl<'a> IntoIterator for &'a EventBuffer {
     }
 }
 
+/// Collects all events produced by an iterator into a new [`EventBuffer`].
+///
+/// The events are stored in the order the iterator produces them: they are *not* sorted by time.
+/// If the iterator doesn't yield them in chronological order, the caller is responsible for
+/// calling [`sort`](EventBuffer::sort) before passing the buffer to a plugin.
+///
+/// # Example
+///
+/// ```
+/// use clack_common::events::event_types::{NoteOnEvent, ParamValueEvent};
+/// use clack_common::events::io::EventBuffer;
+/// use clack_common::events::{Event, Pckn, UnknownEvent};
+/// use clack_common::utils::{ClapId, Cookie};
+///
+/// let note_on = NoteOnEvent::new(0, Pckn::new(0u16, 0u16, 60u16, 0u32), 1.0);
+/// let param = ParamValueEvent::new(4, ClapId::new(1), Pckn::match_all(), 0.5, Cookie::empty());
+///
+/// let events: [&UnknownEvent; 2] = [note_on.as_unknown(), param.as_unknown()];
+/// let buffer: EventBuffer = events.into_iter().collect();
+///
+/// assert_eq!(buffer.len(), 2);
+/// assert_eq!(buffer[0].as_event(), Some(&note_on));
+/// ```
+impl<E: AsRef<UnknownEvent>> FromIterator<E> for EventBuffer {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut buffer = EventBuffer::new();
+        buffer.extend(iter);
+        buffer
+    }
+}
+
+/// Pushes all events produced by an iterator at the end of the buffer.
+///
+/// Like [`push`](EventBuffer::push), this does not keep the buffer sorted: the caller is
+/// responsible for calling [`sort`](EventBuffer::sort) if the new events aren't all later than
+/// the ones already in the buffer.
+impl<E: AsRef<UnknownEvent>> Extend<E> for EventBuffer {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.indexes.reserve(iter.size_hint().0);
+
+        for event in iter {
+            self.push(event.as_ref());
+        }
+    }
+}
+
 impl InputEventBuffer for EventBuffer {
     #[inline]
     fn len(&self) -> u32 {
@@ -252,10 +636,9 @@ impl InputEventBuffer for EventBuffer {
 }
 
 impl OutputEventBuffer for EventBuffer {
+    #[inline]
     fn try_push(&mut self, event: &UnknownEvent) -> Result<(), TryPushError> {
-        self.push(event);
-
-        Ok(())
+        EventBuffer::try_push(self, event)
     }
 }
 
@@ -267,7 +650,7 @@ impl Default for EventBuffer {
 }
 
 impl Debug for EventBuffer {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let mut list = f.debug_list();
         for event in self {
             if let Some(event) = event.as_core_event() {
@@ -309,9 +692,11 @@ impl<'a> Iterator for EventBufferIter<'a> {
 
 #[cfg(test)]
 mod test {
-    use crate::events::event_types::MidiEvent;
-    use crate::events::io::EventBuffer;
-    use crate::events::Event;
+    use crate::events::event_types::*;
+    use crate::events::io::{EventBuffer, EventBufferGrowthPolicy, OverflowCounter};
+    use crate::events::spaces::{CoreEventSpace, EventSpaceId};
+    use crate::events::{Event, Pckn};
+    use crate::utils::{ClapId, Cookie};
 
     #[test]
     fn it_works() {
@@ -338,4 +723,218 @@ mod test {
         assert_eq!(Some(&event_2), buffer.get(2).unwrap().as_event());
         assert_eq!(Some(&event_3), buffer.get(3).unwrap().as_event());
     }
+
+    #[test]
+    fn typed_push_helpers_round_trip() {
+        let pckn = Pckn::new(0u16, 1u16, 60u16, 42u32);
+        let param_id = ClapId::new(5);
+
+        let mut buffer = EventBuffer::new();
+        buffer.push_note_on(0, pckn, 0.5);
+        buffer.push_param_value(1, param_id, 0.25);
+        buffer.push_param_mod(2, param_id, -0.1);
+        buffer.push_note_off(3, pckn, 0.75);
+        buffer.push_note_expression(4, pckn, NoteExpressionType::Tuning, 0.5);
+
+        assert_eq!(buffer.len(), 5);
+
+        assert_eq!(
+            Some(&NoteOnEvent::new(0, pckn, 0.5)),
+            buffer.get(0).unwrap().as_event()
+        );
+        assert_eq!(
+            Some(&ParamValueEvent::new(
+                1,
+                param_id,
+                Pckn::match_all(),
+                0.25,
+                Cookie::empty()
+            )),
+            buffer.get(1).unwrap().as_event()
+        );
+        assert_eq!(
+            Some(&ParamModEvent::new(
+                2,
+                param_id,
+                Pckn::match_all(),
+                -0.1,
+                Cookie::empty()
+            )),
+            buffer.get(2).unwrap().as_event()
+        );
+        assert_eq!(
+            Some(&NoteOffEvent::new(3, pckn, 0.75)),
+            buffer.get(3).unwrap().as_event()
+        );
+
+        let Some(CoreEventSpace::NoteExpression(expression)) = buffer[4].as_core_event() else {
+            panic!("Expected a note expression event");
+        };
+        assert_eq!(expression, &NoteExpressionEvent::tuning(4, pckn, 0.5));
+        assert_eq!(
+            expression.expression_type(),
+            Some(NoteExpressionType::Tuning)
+        );
+
+        for event in &buffer {
+            assert_eq!(
+                event.header().space_id().map(|id| id.id()),
+                Some(EventSpaceId::core().id())
+            );
+        }
+    }
+
+    #[test]
+    fn can_slice_time_ranges() {
+        let events = [
+            MidiEvent::new(0, 0, [0; 3]),
+            MidiEvent::new(63, 0, [1; 3]),
+            MidiEvent::new(64, 0, [2; 3]),
+            MidiEvent::new(100, 0, [3; 3]),
+            MidiEvent::new(128, 0, [4; 3]),
+        ];
+
+        let mut buffer = EventBuffer::new();
+        buffer.push_all(events.iter().map(|e| e.as_unknown()));
+
+        let first_block: Vec<_> = buffer.events_in_range(0, 64).collect();
+        assert_eq!(first_block.len(), 2);
+        assert_eq!(Some(&events[0]), first_block[0].as_event());
+        assert_eq!(Some(&events[1]), first_block[1].as_event());
+
+        let mut sub_block = EventBuffer::new();
+        sub_block.push_range_rebased(&buffer, 64, 128);
+
+        assert_eq!(sub_block.len(), 2);
+        assert_eq!(
+            Some(&MidiEvent::new(0, 0, [2; 3])),
+            sub_block.get(0).unwrap().as_event()
+        );
+        assert_eq!(
+            Some(&MidiEvent::new(36, 0, [3; 3])),
+            sub_block.get(1).unwrap().as_event()
+        );
+
+        assert_eq!(buffer.events_in_range(129, 256).count(), 0);
+
+        let mut block = EventBuffer::new();
+        block.push_all_offset(&sub_block, 64);
+        assert_eq!(block.get(0).unwrap().as_event(), Some(&events[2]));
+        assert_eq!(block.get(1).unwrap().as_event(), Some(&events[3]));
+    }
+
+    #[test]
+    fn sysex_payloads_round_trip() {
+        fn payload(i: u8) -> Vec<u8> {
+            (0..i).map(|b| b.wrapping_mul(i)).collect()
+        }
+
+        // No pre-allocation, so that the storage gets moved many times while pushing.
+        let mut buffer = EventBuffer::new();
+
+        for i in 0..64u8 {
+            // The source payload is dropped right after: the buffer must not point to it.
+            buffer.push_sysex(i as u32, 1, &payload(i));
+            buffer.push(&MidiEvent::new(i as u32, 0, [i; 3]));
+        }
+
+        assert_eq!(buffer.len(), 128);
+
+        let input = buffer.as_input();
+        for i in 0..64u8 {
+            let event = input.get(i as u32 * 2).unwrap();
+            let event: &MidiSysExEvent = event.as_event().unwrap();
+
+            assert_eq!(event.header().time(), i as u32);
+            assert_eq!(event.port_index(), 1);
+            // SAFETY: the payload is stored in the buffer, which outlives this borrow.
+            assert_eq!(unsafe { event.data() }, payload(i).as_slice());
+
+            let midi_event: &MidiEvent = input.get(i as u32 * 2 + 1).unwrap().as_event().unwrap();
+            assert_eq!(midi_event.data(), [i; 3]);
+        }
+    }
+
+    #[test]
+    fn sysex_payloads_survive_sorting_and_inserting() {
+        let mut buffer = EventBuffer::new();
+        buffer.push_sysex(10, 0, &[0xF0, 1, 0xF7]);
+        buffer.push_sysex(5, 0, &[0xF0, 2, 2, 0xF7]);
+        buffer.insert(&BorrowedMidiSysExEvent::new(0, 0, &[]), 0);
+        buffer.sort();
+
+        let payloads: Vec<&[u8]> = buffer
+            .iter()
+            // SAFETY: the first payload is 'static, the others are stored in the buffer.
+            .map(|e| unsafe { e.as_event::<MidiSysExEvent>().unwrap().data() })
+            .collect();
+
+        assert_eq!(payloads, [&[][..], &[0xF0, 2, 2, 0xF7], &[0xF0, 1, 0xF7]]);
+
+        buffer.clear();
+        buffer.push_sysex(0, 0, &[0xF0, 3, 0xF7]);
+
+        // SAFETY: the payload is stored in the buffer, which outlives this borrow.
+        let payload = unsafe { buffer[0].as_event::<MidiSysExEvent>().unwrap().data() };
+        assert_eq!(payload, &[0xF0, 3, 0xF7]);
+    }
+
+    #[test]
+    fn can_collect_and_extend_mixed_events() {
+        let note_on = NoteOnEvent::new(10, Pckn::new(0u16, 0u16, 60u16, 1u32), 1.0);
+        let param =
+            ParamValueEvent::new(0, ClapId::new(3), Pckn::match_all(), 0.5, Cookie::empty());
+        let sysex = BorrowedMidiSysExEvent::new(5, 0, &[0xF0, 1, 0xF7]);
+
+        let mut buffer: EventBuffer = [note_on.as_unknown(), param.as_unknown()]
+            .into_iter()
+            .collect();
+
+        buffer.extend([&sysex]);
+        buffer.extend([MidiEvent::new(2, 0, [0x90, 60, 127])]);
+
+        // Events are kept in insertion order, not sorted.
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer[0].as_event(), Some(&note_on));
+        assert_eq!(buffer[1].as_event(), Some(&param));
+        assert_eq!(buffer[2].header().time(), 5);
+        assert_eq!(
+            buffer[3].as_event(),
+            Some(&MidiEvent::new(2, 0, [0x90, 60, 127]))
+        );
+
+        buffer.sort();
+        let times: Vec<u32> = buffer.iter().map(|e| e.header().time()).collect();
+        assert_eq!(times, [0, 2, 5, 10]);
+    }
+
+    #[test]
+    fn fail_on_full_buffer_rejects_overflowing_events() {
+        let mut buffer = EventBuffer::with_capacity(2);
+        assert_eq!(buffer.growth_policy(), EventBufferGrowthPolicy::Grow);
+        buffer.set_growth_policy(EventBufferGrowthPolicy::FailOnFull);
+
+        let events: Vec<_> = (0..3).map(|i| MidiEvent::new(i, 0, [i as u8; 3])).collect();
+        let mut counter = OverflowCounter::new(buffer);
+
+        let mut output = counter.as_output();
+        assert!(output.try_push(events[0].as_unknown()).is_ok());
+        assert!(output.try_push(events[1].as_unknown()).is_ok());
+        assert!(output.try_push(events[2].as_unknown()).is_err());
+
+        assert_eq!(counter.overflow_count(), 1);
+
+        let mut buffer = counter.into_inner();
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer[1].as_event(), Some(&events[1]));
+
+        // Clearing makes room again, without growing.
+        buffer.clear();
+        assert!(buffer.try_push(&events[2]).is_ok());
+
+        buffer.set_growth_policy(EventBufferGrowthPolicy::Grow);
+        assert!(buffer.try_push(&events[0]).is_ok());
+        assert!(buffer.try_push(&events[1]).is_ok());
+        assert_eq!(buffer.len(), 3);
+    }
 }
@@ -4,6 +4,9 @@ use crate::events::{Event, UnknownEvent};
 use crate::utils::handle_panic;
 use clap_sys::events::{clap_event_header, clap_input_events, clap_output_events};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// A trait for all types which can act as an ordered, indexed list of [`UnknownEvent`]s.
 ///
 /// This is the backing implementation of an [`InputEvents`](crate::events::io::InputEvents), which
@@ -159,6 +162,7 @@ impl<T: Event> InputEventBuffer for &[T] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Event> InputEventBuffer for Vec<T> {
     #[inline]
     fn len(&self) -> u32 {
@@ -212,6 +216,7 @@ impl InputEventBuffer for &[&UnknownEvent] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl InputEventBuffer for Vec<&UnknownEvent> {
     #[inline]
     fn len(&self) -> u32 {
@@ -258,6 +263,7 @@ impl<'a, T: Event<EventSpace<'a> = CoreEventSpace<'a>> + Clone> OutputEventBuffe
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Event<EventSpace<'a> = CoreEventSpace<'a>> + Clone> OutputEventBuffer for Vec<T> {
     fn try_push(&mut self, event: &UnknownEvent) -> Result<(), TryPushError> {
         if let Some(event) = event.as_event::<T>() {
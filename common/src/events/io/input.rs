@@ -2,9 +2,9 @@ use crate::events::io::implementation::{raw_input_events, InputEventBuffer};
 use crate::events::io::EventBatcher;
 use crate::events::UnknownEvent;
 use clap_sys::events::clap_input_events;
-use std::fmt::{Debug, Formatter};
-use std::marker::PhantomData;
-use std::ops::{Index, Range};
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::{Index, Range};
 
 /// An input list of timestamped events.
 ///
@@ -243,6 +243,49 @@ impl<'a> InputEvents<'a> {
     pub fn batch(&self) -> EventBatcher {
         EventBatcher::new(self)
     }
+
+    /// Returns an iterator which batches the events like [`batch`](Self::batch) does, but also
+    /// splits batches so that none of them are longer than `max_frames` samples.
+    ///
+    /// This is useful for plugins that want to process audio in blocks of bounded size (e.g. for
+    /// consistent SIMD block sizes, or cache locality), regardless of how many events there are.
+    ///
+    /// Because the last batch has to be split as well, the total number of frames of the
+    /// processed block (`frames_count`) must be provided. Unlike [`batch`](Self::batch), all the
+    /// produced batches therefore have a [`next_batch_first_sample`], including the last one,
+    /// for which it is `frames_count`.
+    ///
+    /// Events are always part of the first batch of a split, so the batches produced by the split
+    /// of a longer batch may contain no events at all. If there are no events, this produces
+    /// `frames_count / max_frames` batches, plus one for any remaining samples.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `max_frames` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::event_types::MidiEvent;
+    /// use clack_common::events::io::EventBuffer;
+    ///
+    /// let mut buffer = EventBuffer::new();
+    /// buffer.push(&MidiEvent::new(10, 0, [0x90, 60, 127]));
+    ///
+    /// let input_events = buffer.as_input();
+    /// let batches: Vec<_> = input_events
+    ///     .batch_with_max(100, 32)
+    ///     .map(|b| (b.sample_range(100), b.events().len()))
+    ///     .collect();
+    ///
+    /// assert_eq!(batches, [(0..10, 0), (10..42, 1), (42..74, 0), (74..100, 0)]);
+    /// ```
+    ///
+    /// [`next_batch_first_sample`]: crate::events::io::EventBatch::next_batch_first_sample
+    #[inline]
+    pub fn batch_with_max(&self, frames_count: u32, max_frames: u32) -> EventBatcher<'_> {
+        EventBatcher::with_max(self, frames_count, max_frames)
+    }
 }
 
 impl<'a> IntoIterator for &'a InputEvents<'a> {
@@ -286,7 +329,7 @@ impl Index<usize> for InputEvents<'_> {
 }
 
 impl Debug for InputEvents<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let mut list = f.debug_list();
         for event in self {
             if let Some(event) = event.as_core_event() {
@@ -2,9 +2,8 @@ use crate::events::io::implementation::{raw_output_events, OutputEventBuffer};
 use crate::events::io::void_output_events;
 use crate::events::UnknownEvent;
 use clap_sys::events::clap_output_events;
-use std::error::Error;
-use std::fmt::{Display, Formatter};
-use std::marker::PhantomData;
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
 
 /// An ordered list of timestamped events.
 ///
@@ -117,6 +116,8 @@ impl<'a> OutputEvents<'a> {
     ///
     /// The exact reason is left at the implementer's discretion, but this is usually a sign that
     /// the implementer ran out of buffer space, and either cannot or refuses to allocate more.
+    /// Hosts can use an [`OverflowCounter`](crate::events::io::OverflowCounter) to keep track of
+    /// how many events their buffer rejected.
     ///
     /// # Realtime Safety
     ///
@@ -124,6 +125,12 @@ impl<'a> OutputEvents<'a> {
     /// realtime safety cannot be guaranteed. However, it is expected for hosts to pre-allocate a
     /// reasonable amount of space before forwarding the list to the plugin, in order to make
     /// allocations as unlikely as possible.
+    ///
+    /// Hosts that need this operation to be realtime-safe can use an
+    /// [`EventBuffer`](crate::events::io::EventBuffer) with the
+    /// [`FailOnFull`](crate::events::io::EventBufferGrowthPolicy::FailOnFull) growth policy, which
+    /// never allocates and rejects events once full instead. [`Extend`]ing an [`OutputEvents`]
+    /// goes through this method as well, and silently drops the rejected events.
     #[inline]
     pub fn try_push<E: AsRef<UnknownEvent>>(&mut self, event: E) -> Result<(), TryPushError> {
         let try_push = self.inner.try_push.ok_or(TryPushError)?;
@@ -153,12 +160,13 @@ impl TryPushError {
 }
 
 impl Display for TryPushError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.write_str("Failed to push event into output event buffer")
     }
 }
 
-impl Error for TryPushError {}
+#[cfg(feature = "std")]
+impl std::error::Error for TryPushError {}
 
 impl<'a, I: OutputEventBuffer> From<&'a mut I> for OutputEvents<'a> {
     #[inline]
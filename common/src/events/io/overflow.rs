@@ -0,0 +1,148 @@
+use crate::events::io::{OutputEventBuffer, OutputEvents, TryPushError};
+use crate::events::UnknownEvent;
+
+/// An [`OutputEventBuffer`] wrapper that counts how many events the wrapped buffer rejected.
+///
+/// Hosts that use pre-sized, non-growing event buffers on the audio thread may use this to detect
+/// (and e.g. log) when a plugin produced more output events than the buffer could hold.
+///
+/// Every rejected event is still reported to the plugin as a failed push, as per the CLAP
+/// specification. This wrapper only records it for the host to inspect after processing.
+///
+/// Note that [`EventBuffer`](crate::events::io::EventBuffer) grows to fit all the events pushed
+/// to it by default, and therefore never rejects any, unless its growth policy is set to
+/// [`FailOnFull`](crate::events::io::EventBufferGrowthPolicy::FailOnFull).
+///
+/// # Example
+///
+/// ```
+/// use clack_common::events::{Event, Pckn};
+/// use clack_common::events::event_types::NoteOnEvent;
+/// use clack_common::events::io::OverflowCounter;
+///
+/// // This buffer can only hold a single event.
+/// let mut buffer = OverflowCounter::new(None::<NoteOnEvent>);
+///
+/// let mut output_events = buffer.as_output();
+/// let event = NoteOnEvent::new(0, Pckn::new(0u16, 0u16, 12u16, 60u32), 4.2);
+/// output_events.try_push(event.as_unknown()).unwrap();
+/// assert!(output_events.try_push(event.as_unknown()).is_err());
+///
+/// assert_eq!(buffer.overflow_count(), 1);
+/// assert_eq!(buffer.into_inner(), Some(event));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct OverflowCounter<B> {
+    buffer: B,
+    overflow_count: u32,
+}
+
+impl<B: OutputEventBuffer> OverflowCounter<B> {
+    /// Wraps the given buffer, with an overflow count of zero.
+    #[inline]
+    pub const fn new(buffer: B) -> Self {
+        Self {
+            buffer,
+            overflow_count: 0,
+        }
+    }
+
+    /// Returns how many events the wrapped buffer rejected since this counter was created or
+    /// last [reset](Self::reset_overflow_count).
+    #[inline]
+    pub const fn overflow_count(&self) -> u32 {
+        self.overflow_count
+    }
+
+    /// Returns `true` if the wrapped buffer rejected at least one event since this counter was
+    /// created or last [reset](Self::reset_overflow_count).
+    #[inline]
+    pub const fn has_overflowed(&self) -> bool {
+        self.overflow_count > 0
+    }
+
+    /// Resets the overflow count back to zero.
+    #[inline]
+    pub fn reset_overflow_count(&mut self) {
+        self.overflow_count = 0;
+    }
+
+    /// Returns a shared reference to the wrapped buffer.
+    #[inline]
+    pub const fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Returns a mutable reference to the wrapped buffer.
+    #[inline]
+    pub fn buffer_mut(&mut self) -> &mut B {
+        &mut self.buffer
+    }
+
+    /// Consumes this counter, returning the wrapped buffer.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Produces an [`OutputEvents`] that wraps this counter as an [`OutputEventBuffer`]
+    /// implementation.
+    ///
+    /// This helper method is strictly equivalent to using [`OutputEvents::from_buffer`].
+    #[inline]
+    pub fn as_output(&mut self) -> OutputEvents<'_> {
+        OutputEvents::from_buffer(self)
+    }
+}
+
+impl<B: OutputEventBuffer> OutputEventBuffer for OverflowCounter<B> {
+    #[inline]
+    fn try_push(&mut self, event: &UnknownEvent) -> Result<(), TryPushError> {
+        let result = self.buffer.try_push(event);
+
+        if result.is_err() {
+            self.overflow_count = self.overflow_count.saturating_add(1);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::events::event_types::MidiEvent;
+    use crate::events::io::{OutputEvents, OverflowCounter};
+    use crate::events::Event;
+
+    #[test]
+    fn counts_rejected_events() {
+        let mut counter = OverflowCounter::new(None::<MidiEvent>);
+        assert!(!counter.has_overflowed());
+
+        let event = MidiEvent::new(0, 0, [0; 3]);
+        {
+            let mut output = OutputEvents::from_buffer(&mut counter);
+            assert!(output.try_push(event.as_unknown()).is_ok());
+            assert!(output.try_push(event.as_unknown()).is_err());
+            assert!(output.try_push(event.as_unknown()).is_err());
+        }
+
+        assert_eq!(counter.overflow_count(), 2);
+        assert!(counter.has_overflowed());
+        assert_eq!(counter.buffer(), &Some(event));
+
+        counter.reset_overflow_count();
+        assert_eq!(counter.overflow_count(), 0);
+    }
+
+    #[test]
+    fn accepted_events_are_not_counted() {
+        let mut counter = OverflowCounter::new(Vec::<MidiEvent>::new());
+
+        let event = MidiEvent::new(0, 0, [0; 3]);
+        counter.as_output().extend([event.as_unknown(); 4]);
+
+        assert_eq!(counter.overflow_count(), 0);
+        assert_eq!(counter.buffer().len(), 4);
+    }
+}
@@ -14,6 +14,13 @@ use Match::*;
 ///
 /// See the [`matches`](Pckn::matches) for an implementation of the PCKN matching logic that you
 /// can use to match incoming events against active voices.
+///
+/// # Wildcard sentinels
+///
+/// In the raw CLAP events, wildcards are represented by a `-1` sentinel value in each field:
+/// `port_index`, `channel` and `key` are `i16` values, and `note_id` is an `i32` value, all of
+/// which use `-1` to mean "any". The [`from_raw`](Pckn::from_raw) and `raw_*` accessors convert
+/// between this representation and [`Match::All`].
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Pckn {
     /// The Note Port the plugin received this event on. See the Note Ports extension.
@@ -56,6 +63,52 @@ impl Pckn {
         }
     }
 
+    /// Returns a [`Pckn`] tuple where every component is a wildcard.
+    ///
+    /// This is the same as [`match_all`](Pckn::match_all), and is mostly useful as a starting
+    /// point for the `with_*` builder methods.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::{Match, Pckn};
+    ///
+    /// let pckn = Pckn::wildcard().with_channel(3u16).with_key(60u16);
+    /// assert_eq!(pckn, Pckn::new(Match::All, 3u16, 60u16, Match::All));
+    /// ```
+    #[inline]
+    pub const fn wildcard() -> Self {
+        Self::match_all()
+    }
+
+    /// Returns this PCKN tuple with its Port component replaced with the given one.
+    #[inline]
+    pub fn with_port_index(mut self, port_index: impl Into<Match<u16>>) -> Self {
+        self.port_index = port_index.into();
+        self
+    }
+
+    /// Returns this PCKN tuple with its Channel component replaced with the given one.
+    #[inline]
+    pub fn with_channel(mut self, channel: impl Into<Match<u16>>) -> Self {
+        self.channel = channel.into();
+        self
+    }
+
+    /// Returns this PCKN tuple with its Key component replaced with the given one.
+    #[inline]
+    pub fn with_key(mut self, key: impl Into<Match<u16>>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Returns this PCKN tuple with its Note ID component replaced with the given one.
+    #[inline]
+    pub fn with_note_id(mut self, note_id: impl Into<Match<u32>>) -> Self {
+        self.note_id = note_id.into();
+        self
+    }
+
     /// Returns whether this [`Pckn`] tuple matches all possible notes.
     ///
     /// This is true if all four matchers are set to [`Match::All`].
@@ -69,6 +122,10 @@ impl Pckn {
     /// Returns `true` if this PCKN tuple matches the given one, considering both specific values
     /// and wildcard [`Match::All`] values.
     ///
+    /// Each component is compared separately, and the tuples match only if all four components
+    /// match. A wildcard component matches any value in the other tuple, whichever side it is on,
+    /// so this operation is symmetric. Two specific components match only if they are equal.
+    ///
     /// # Examples
     ///
     /// ```
@@ -427,3 +484,66 @@ macro_rules! impl_event_pckn {
 
 use crate::utils::ClapId;
 pub(crate) use impl_event_pckn;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_truth_table() {
+        // Each bit of the mask selects whether the matching component is a wildcard.
+        fn pckn_from_mask(mask: u8, port: u16, channel: u16, key: u16, note_id: u32) -> Pckn {
+            fn pick<T>(wildcard: bool, value: T) -> Match<T> {
+                if wildcard {
+                    All
+                } else {
+                    Specific(value)
+                }
+            }
+
+            Pckn {
+                port_index: pick(mask & 0b0001 != 0, port),
+                channel: pick(mask & 0b0010 != 0, channel),
+                key: pick(mask & 0b0100 != 0, key),
+                note_id: pick(mask & 0b1000 != 0, note_id),
+            }
+        }
+
+        for self_mask in 0..16u8 {
+            for other_mask in 0..16u8 {
+                let a = pckn_from_mask(self_mask, 0, 1, 60, 42);
+
+                // Identical specific values always match.
+                let same = pckn_from_mask(other_mask, 0, 1, 60, 42);
+                assert!(a.matches(&same));
+                assert!(same.matches(&a));
+
+                // Differing specific values only match where either side is a wildcard.
+                let different = pckn_from_mask(other_mask, 1, 2, 61, 43);
+                let expected = (self_mask | other_mask) == 0b1111;
+                assert_eq!(a.matches(&different), expected);
+                assert_eq!(different.matches(&a), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn wildcard_sentinels() {
+        let pckn = Pckn::from_raw(-1, -1, -1, -1);
+        assert_eq!(pckn, Pckn::wildcard());
+        assert!(pckn.matches_all());
+
+        assert_eq!(pckn.raw_port_index(), -1);
+        assert_eq!(pckn.raw_channel(), -1);
+        assert_eq!(pckn.raw_key(), -1);
+        assert_eq!(pckn.raw_note_id(), -1);
+
+        let pckn = Pckn::wildcard()
+            .with_port_index(0u16)
+            .with_channel(1u16)
+            .with_key(60u16)
+            .with_note_id(42u32);
+        assert_eq!(pckn, Pckn::from_raw(0, 1, 60, 42));
+        assert!(!pckn.matches_all());
+    }
+}
@@ -1,11 +1,13 @@
 mod core;
+mod custom;
 mod id;
 
 pub use self::core::*;
+pub use custom::CustomEventSpace;
 pub use id::*;
 
 use crate::events::UnknownEvent;
-use std::ffi::CStr;
+use ::core::ffi::CStr;
 
 /// Holds all the possible event types included in a given event space.  
 ///
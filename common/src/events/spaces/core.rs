@@ -1,7 +1,7 @@
 use crate::events::event_types::*;
 use crate::events::{Event, EventSpace, UnknownEvent};
-use std::ffi::CStr;
-use std::fmt::{Debug, Formatter};
+use core::ffi::CStr;
+use core::fmt::{Debug, Formatter};
 
 #[derive(Copy, Clone, PartialEq)]
 pub enum CoreEventSpace<'a> {
@@ -67,7 +67,7 @@ unsafe impl<'a> EventSpace<'a> for CoreEventSpace<'a> {
 
 impl Debug for CoreEventSpace<'_> {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             CoreEventSpace::NoteOn(e) => Debug::fmt(e, f),
             CoreEventSpace::NoteOff(e) => Debug::fmt(e, f),
@@ -0,0 +1,119 @@
+use crate::events::spaces::{EventSpace, EventSpaceId};
+use crate::events::{Event, EventFlags, EventHeader, UnknownEvent};
+use core::ffi::CStr;
+
+/// Ties a custom event type to its own, custom event space.
+///
+/// This is the simplest way to define vendor-specific events: instead of implementing
+/// [`EventSpace`] manually, the event type declares the name of its event space through
+/// [`SPACE_NAME`](CustomEventSpace::SPACE_NAME), and uses `&'a Self` as its event space.
+///
+/// Because the numerical ID of custom event spaces is only known at runtime, plugins must first
+/// query it from the host's event registry (usually during initialization), and then use it to
+/// create and read events of this type.
+///
+/// # Safety
+///
+/// Implementers of this trait *must* ensure the [`SPACE_NAME`](CustomEventSpace::SPACE_NAME) is unique
+/// to this event type, and that this event type is ABI-compatible with the events of that space.
+///
+/// # Example
+///
+/// ```
+/// use clack_common::events::io::EventBuffer;
+/// use clack_common::events::spaces::{CustomEventSpace, EventSpaceId};
+/// use clack_common::events::{Event, EventFlags, EventHeader, UnknownEvent};
+/// use core::ffi::CStr;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// pub struct VendorEvent {
+///     header: EventHeader<Self>,
+///     pub value: f64,
+/// }
+///
+/// impl AsRef<UnknownEvent> for VendorEvent {
+///     fn as_ref(&self) -> &UnknownEvent {
+///         self.as_unknown()
+///     }
+/// }
+///
+/// // SAFETY: VendorEvent starts with an event header, and its event space is its own.
+/// unsafe impl Event for VendorEvent {
+///     const TYPE_ID: u16 = 0;
+///     type EventSpace<'a> = &'a VendorEvent;
+/// }
+///
+/// // SAFETY: This space name is unique to this event type.
+/// unsafe impl CustomEventSpace for VendorEvent {
+///     const SPACE_NAME: &'static CStr =
+///         match CStr::from_bytes_with_nul(b"com.example.vendor-event\0") {
+///             Ok(name) => name,
+///             Err(_) => panic!(),
+///         };
+/// }
+///
+/// // This ID would be queried from the host's event registry during initialization.
+/// # // SAFETY: The ID is only used for VendorEvent.
+/// let space_id: EventSpaceId<&VendorEvent> =
+///     unsafe { EventSpaceId::new(4242).unwrap().into_unchecked() };
+///
+/// let event = VendorEvent {
+///     header: VendorEvent::new_header(space_id, 10, EventFlags::empty()),
+///     value: 0.5,
+/// };
+///
+/// let mut buffer = EventBuffer::new();
+/// buffer.push(&event);
+///
+/// let received = buffer.iter().next().unwrap();
+/// assert_eq!(VendorEvent::from_unknown(received, space_id), Some(&event));
+/// ```
+pub unsafe trait CustomEventSpace: Event {
+    /// The name of the custom event space of this event type.
+    const SPACE_NAME: &'static CStr;
+
+    /// Creates a new header for this event type, using the given event space ID, `time` and
+    /// `flags`.
+    #[inline]
+    fn new_header(
+        space_id: EventSpaceId<Self::EventSpace<'_>>,
+        time: u32,
+        flags: EventFlags,
+    ) -> EventHeader<Self> {
+        EventHeader::new_for_space(space_id, time, flags)
+    }
+
+    /// Attempts to decode the given unknown event as an event of this type, using the given
+    /// event space ID.
+    ///
+    /// This returns `None` if the given event does not belong to this type's event space.
+    #[inline]
+    fn from_unknown<'a>(
+        event: &'a UnknownEvent,
+        space_id: EventSpaceId<Self::EventSpace<'a>>,
+    ) -> Option<&'a Self> {
+        event.as_event_for_space(space_id)
+    }
+}
+
+// SAFETY: The CustomEventSpace trait guarantees SPACE_NAME is the name of this event's space.
+unsafe impl<'a, E: CustomEventSpace> EventSpace<'a> for &'a E {
+    const NAME: &'static CStr = E::SPACE_NAME;
+
+    #[inline]
+    unsafe fn from_unknown(event: &'a UnknownEvent) -> Option<Self> {
+        let header = event.header();
+
+        if header.type_id() != E::TYPE_ID || header.size() != core::mem::size_of::<E>() as u32 {
+            return None;
+        }
+
+        Some(event.as_event_unchecked())
+    }
+
+    #[inline]
+    fn as_unknown(&self) -> &'a UnknownEvent {
+        Event::as_unknown(*self)
+    }
+}
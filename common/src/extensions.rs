@@ -3,7 +3,7 @@
 //! See the documentation of the `extensions` module in the `clack-plugin` and `clack-host` crates
 //! for implementation examples.
 
-use std::ffi::CStr;
+use core::ffi::CStr;
 
 mod raw;
 pub use raw::{RawExtension, RawExtensionImplementation};
@@ -74,3 +74,128 @@ pub unsafe trait ExtensionImplementation<I>: Extension {
     /// The implementation of the extension.
     const IMPLEMENTATION: RawExtensionImplementation;
 }
+
+/// Declares a new extension type, and optionally provides implementations for it.
+///
+/// Every extension type in Clack follows the same pattern: a `Copy` wrapper around a
+/// [`RawExtension`] to the extension's raw, C-FFI compatible struct, which implements the
+/// [`Extension`] trait to tie it to its identifier. This macro generates all of that boilerplate,
+/// which is especially useful for implementing custom or vendor-specific extensions.
+///
+/// The wrapped [`RawExtension`] is accessible through the `0` field of the generated type, to be
+/// used with e.g. the `use_extension` method of the plugin or host handles.
+///
+/// The generated type also implements [`ExtensionImplementation`] if an implementation is given.
+/// Its value must be the raw struct of the extension: this is checked at compile time.
+///
+/// # Safety
+///
+/// This macro relies on the given identifier matching the given raw struct type. If it doesn't,
+/// the extension data will be misinterpreted, leading to Undefined Behavior. Just like the
+/// [`Extension`] and [`ExtensionImplementation`] traits it implements, both forms of this macro
+/// therefore require an explicit `unsafe` keyword, to make this invariant visible at the call
+/// site.
+///
+/// # Example
+///
+/// ```
+/// use clack_common::clack_extension;
+/// use clack_common::extensions::{ExtensionImplementation, PluginExtensionSide};
+/// use core::ffi::CStr;
+///
+/// const CLAP_EXT_GREETER: &CStr = match CStr::from_bytes_with_nul(b"com.example.greeter\0") {
+///     Ok(id) => id,
+///     Err(_) => unreachable!(),
+/// };
+///
+/// #[repr(C)]
+/// pub struct clap_plugin_greeter {
+///     pub greeting: Option<unsafe extern "C" fn() -> u32>,
+/// }
+///
+/// clack_extension! {
+///     /// The plugin side of the Greeter extension.
+///     // SAFETY: CLAP_EXT_GREETER is the identifier of the clap_plugin_greeter extension.
+///     pub unsafe struct PluginGreeter(clap_plugin_greeter): PluginExtensionSide = CLAP_EXT_GREETER;
+/// }
+///
+/// pub struct MyPlugin;
+///
+/// unsafe extern "C" fn greeting() -> u32 {
+///     42
+/// }
+///
+/// clack_extension! {
+///     // SAFETY: the implementation is a clap_plugin_greeter, matching the extension type.
+///     unsafe impl ExtensionImplementation<MyPlugin> for PluginGreeter = clap_plugin_greeter {
+///         greeting: Some(greeting),
+///     };
+/// }
+///
+/// # let _ = <PluginGreeter as ExtensionImplementation<MyPlugin>>::IMPLEMENTATION;
+/// ```
+///
+/// Implementations can also be generic, with `where` clauses using a restricted syntax: each
+/// generic parameter and each predicate can only have a single bound, and each predicate must be
+/// introduced by a `for<'a>` binder.
+///
+/// ```ignore
+/// clack_extension! {
+///     unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginGreeter
+///     where
+///         for<'a> P::MainThread<'a>: PluginGreeterImpl,
+///     = clap_plugin_greeter {
+///         greeting: Some(greeting::<P>),
+///     };
+/// }
+/// ```
+#[macro_export]
+macro_rules! clack_extension {
+    (
+        $(#[$meta:meta])*
+        $vis:vis unsafe struct $name:ident($raw:ty): $side:ty = $identifier:expr $(;)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone)]
+        $vis struct $name($crate::extensions::RawExtension<$side, $raw>);
+
+        // SAFETY: The user of this macro guarantees the identifier matches the raw extension type.
+        unsafe impl $crate::extensions::Extension for $name {
+            const IDENTIFIER: &'static ::core::ffi::CStr = $identifier;
+            type ExtensionSide = $side;
+
+            #[inline]
+            unsafe fn from_raw(raw: $crate::extensions::RawExtension<Self::ExtensionSide>) -> Self {
+                Self(raw.cast())
+            }
+        }
+
+        impl $name {
+            #[doc(hidden)]
+            #[inline]
+            pub const fn __raw_implementation(
+                implementation: &'static $raw,
+            ) -> $crate::extensions::RawExtensionImplementation {
+                $crate::extensions::RawExtensionImplementation::new(implementation)
+            }
+        }
+    };
+    (
+        unsafe impl $(<$($generic:ident: $generic_bound:path),+ $(,)?>)?
+        ExtensionImplementation<$implementor:ty> for $name:ty
+        $(where $(for<$($lifetime:lifetime),+> $bounded:ty: $bound:path),+ $(,)?)?
+        = $implementation:expr $(;)?
+    ) => {
+        // SAFETY: The implementation's type is checked to be the raw extension type by
+        // __raw_implementation.
+        unsafe impl $(<$($generic: $generic_bound),+>)?
+            $crate::extensions::ExtensionImplementation<$implementor> for $name
+        $(where $(for<$($lifetime),+> $bounded: $bound),+)?
+        {
+            const IMPLEMENTATION: $crate::extensions::RawExtensionImplementation =
+                <$name>::__raw_implementation(&$implementation);
+        }
+    };
+}
+
+pub use crate::clack_extension;
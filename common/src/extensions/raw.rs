@@ -2,10 +2,10 @@
 use crate::extensions::{ExtensionSide, HostExtensionSide, PluginExtensionSide};
 use clap_sys::host::clap_host;
 use clap_sys::plugin::clap_plugin;
-use std::ffi::c_void;
-use std::fmt::{Debug, Formatter};
-use std::marker::PhantomData;
-use std::ptr::NonNull;
+use core::ffi::c_void;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::ptr::NonNull;
 
 /// A raw extension pointer.
 ///
@@ -41,13 +41,23 @@ use std::ptr::NonNull;
 ///
 /// This pointer type is only useful for *consuming* extension pointers. For producing extension
 /// pointers from an extension implementation, use the [`RawExtensionImplementation`] type instead.  
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct RawExtension<S: ExtensionSide, T = ()> {
     extension_ptr: NonNull<T>,
     host_or_plugin_ptr: NonNull<c_void>, // Can be either clap_host or clap_plugin
     _side: PhantomData<fn() -> S>,
 }
 
+// Implemented manually, as deriving would require both S and T to be Copy as well.
+impl<S: ExtensionSide, T> Clone for RawExtension<S, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: ExtensionSide, T> Copy for RawExtension<S, T> {}
+
 // SAFETY: this is just a couple of pointers, and the type doesn't care being used on any thread.
 // Thread-safety is enforced by the plugin handle type that is passed to the methods.
 unsafe impl<S: ExtensionSide, T> Send for RawExtension<S, T> {}
@@ -185,7 +195,7 @@ impl RawExtensionImplementation {
 
 impl Debug for RawExtensionImplementation {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "RawExtensionImplementation({:p})", self.inner)
     }
 }
@@ -1,16 +1,35 @@
 #![doc(html_logo_url = "https://raw.githubusercontent.com/prokopyl/clack/main/logo.svg")]
 #![deny(clippy::undocumented_unsafe_blocks)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! A small crate containing various CLAP utilities and definitions that are common to both
 //! plugins and hosts.
 //!
 //! All modules of this crate are re-exported in the `clack-host` and `clack-plugin` crates. Most users
 //! should not have to use `clack-common` directly.
+//!
+//! # Features
+//!
+//! This crate can be used without the standard library, for targets where it is unavailable.
+//! Only the `std` feature is enabled by default:
+//!
+//! * `std`: enables the [`stream`] module, implementations of [`std::error::Error`], and
+//!   panic-catching in the FFI callbacks of event lists. This implies `alloc`.
+//! * `alloc`: enables the heap-allocated [`EventBuffer`](events::io::EventBuffer) type, as well as
+//!   the event list implementations for [`Vec`].
+//!
+//! Without `std`, panics occurring in event list callbacks (e.g. in a custom
+//! [`InputEventBuffer`](events::io::InputEventBuffer) implementation) cannot be caught, and will
+//! unwind or abort according to the target's panic strategy.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod entry;
 pub mod events;
 pub mod extensions;
 pub mod plugin;
 pub mod process;
+#[cfg(feature = "std")]
 pub mod stream;
 pub mod utils;
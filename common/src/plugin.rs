@@ -5,7 +5,7 @@
 /// Non-standard features should be formatted as: "$namespace:$feature"
 pub mod features {
     use clap_sys::plugin_features::*;
-    use std::ffi::CStr;
+    use core::ffi::CStr;
 
     /// `"instrument"`: The plugin can process note events and then produce audio
     pub const INSTRUMENT: &CStr = CLAP_PLUGIN_FEATURE_INSTRUMENT;
@@ -13,6 +13,8 @@ pub mod features {
     pub const AUDIO_EFFECT: &CStr = CLAP_PLUGIN_FEATURE_AUDIO_EFFECT;
     /// `"note-effect"`: The plugin is a note effect or a note generator/sequencer
     pub const NOTE_EFFECT: &CStr = CLAP_PLUGIN_FEATURE_NOTE_EFFECT;
+    /// `"note-detector"`: The plugin analyzes audio and produces note events
+    pub const NOTE_DETECTOR: &CStr = CLAP_PLUGIN_FEATURE_NOTE_DETECTOR;
     /// `"analyzer"`: The plugin is an analyzer
     pub const ANALYZER: &CStr = CLAP_PLUGIN_FEATURE_ANALYZER;
 
@@ -89,3 +91,53 @@ pub mod features {
     /// `"ambisonic"`
     pub const AMBISONIC: &CStr = CLAP_PLUGIN_FEATURE_AMBISONIC;
 }
+
+/// The main category of a plugin, as described by the standard plugin feature taxonomy.
+///
+/// Each category matches one of the main features of the [`features`] module.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PluginCategory {
+    /// The plugin can process note events and then produce audio. See [`features::INSTRUMENT`].
+    Instrument,
+    /// The plugin is an audio effect. See [`features::AUDIO_EFFECT`].
+    AudioEffect,
+    /// The plugin is a note effect or a note generator/sequencer. See [`features::NOTE_EFFECT`].
+    NoteEffect,
+    /// The plugin analyzes audio and produces note events. See [`features::NOTE_DETECTOR`].
+    NoteDetector,
+    /// The plugin is an analyzer. See [`features::ANALYZER`].
+    Analyzer,
+}
+
+impl PluginCategory {
+    /// Returns the category matching the given feature, or [`None`] if the feature is not one of
+    /// the main category features.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::plugin::{features, PluginCategory};
+    ///
+    /// assert_eq!(PluginCategory::from_feature(features::INSTRUMENT), Some(PluginCategory::Instrument));
+    /// assert_eq!(PluginCategory::from_feature(features::STEREO), None);
+    /// ```
+    pub fn from_feature(feature: &core::ffi::CStr) -> Option<Self> {
+        use PluginCategory::*;
+
+        [Instrument, AudioEffect, NoteEffect, NoteDetector, Analyzer]
+            .into_iter()
+            .find(|category| category.feature() == feature)
+    }
+
+    /// Returns the feature matching this category.
+    #[inline]
+    pub const fn feature(&self) -> &'static core::ffi::CStr {
+        match self {
+            PluginCategory::Instrument => features::INSTRUMENT,
+            PluginCategory::AudioEffect => features::AUDIO_EFFECT,
+            PluginCategory::NoteEffect => features::NOTE_EFFECT,
+            PluginCategory::NoteDetector => features::NOTE_DETECTOR,
+            PluginCategory::Analyzer => features::ANALYZER,
+        }
+    }
+}
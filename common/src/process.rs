@@ -1,5 +1,5 @@
 use clap_sys::process::*;
-use std::fmt::Debug;
+use core::fmt::Debug;
 
 mod constant_mask;
 pub use constant_mask::*;
@@ -10,6 +10,15 @@ pub use constant_mask::*;
 ///
 /// Note that Clack uses a [`Result`] enum for relaying a failed processing to the host,
 /// unlike the C CLAP API which uses an extra state in enum (`CLAP_PROCESS_ERROR`) to indicate failure.
+///
+/// This means there is intentionally no `Error` variant in this type: on the plugin side, returning
+/// any `Err` from `process` is what sends `CLAP_PROCESS_ERROR` to the host (the error's message
+/// being logged to the host first). On the host side, a `CLAP_PROCESS_ERROR` status is returned
+/// as an `Err` as well.
+///
+/// The raw status can still be converted to and from a `Result` using
+/// [`from_raw`](Self::from_raw) and [`raw_from_result`](Self::raw_from_result), which round-trip
+/// all statuses, including `CLAP_PROCESS_ERROR`.
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum ProcessStatus {
@@ -47,7 +56,67 @@ impl ProcessStatus {
         }
     }
 
-    pub fn combined_with(self, other: ProcessStatus) -> ProcessStatus {
+    /// Returns the raw, C-FFI compatible value of this status.
+    #[inline]
+    pub const fn as_raw(self) -> clap_process_status {
+        self as clap_process_status
+    }
+
+    /// Gets the raw, C-FFI compatible value from either a [`ProcessStatus`], or `Err(())`, which
+    /// becomes `CLAP_PROCESS_ERROR`.
+    ///
+    /// This is the inverse of [`from_raw`](Self::from_raw).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::process::ProcessStatus;
+    /// use clap_sys::process::CLAP_PROCESS_ERROR;
+    ///
+    /// let raw = ProcessStatus::raw_from_result(Err(()));
+    /// assert_eq!(raw, CLAP_PROCESS_ERROR);
+    /// assert_eq!(ProcessStatus::from_raw(raw), Some(Err(())));
+    /// ```
+    #[inline]
+    pub const fn raw_from_result(result: Result<Self, ()>) -> clap_process_status {
+        match result {
+            Ok(status) => status.as_raw(),
+            Err(()) => CLAP_PROCESS_ERROR,
+        }
+    }
+
+    /// Combines this status with the status of another plugin, returning the resulting status for
+    /// both plugins taken as a whole, e.g. in a chain of plugins.
+    ///
+    /// The statuses are combined with the following precedence:
+    /// [`Continue`](Self::Continue) > [`ContinueIfNotQuiet`](Self::ContinueIfNotQuiet) >
+    /// [`Tail`](Self::Tail) > [`Sleep`](Self::Sleep).
+    ///
+    /// In other words, if any plugin needs to continue processing, the whole chain continues, and
+    /// the chain may only sleep if all the plugins in it are sleeping. This operation is
+    /// commutative and associative, and [`Sleep`](Self::Sleep) is its identity value, which makes
+    /// it suitable to fold over the statuses of a chain of plugins.
+    ///
+    /// Processing errors are not represented by this type, but by the [`Result`] returned by the
+    /// plugin's `process` method. As an error in any plugin is an error for the whole chain, it
+    /// should be propagated instead, e.g. using [`Iterator::try_fold`] as shown below.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::process::ProcessStatus;
+    /// use ProcessStatus::*;
+    ///
+    /// let statuses: [Result<ProcessStatus, ()>; 3] = [Ok(Sleep), Ok(Tail), Ok(ContinueIfNotQuiet)];
+    /// let combined: Result<_, ()> = statuses.into_iter().try_fold(Sleep, |acc, s| Ok(acc.combine(s?)));
+    /// assert_eq!(combined, Ok(ContinueIfNotQuiet));
+    ///
+    /// let statuses: [Result<ProcessStatus, ()>; 2] = [Ok(Continue), Err(())];
+    /// let combined: Result<_, ()> = statuses.into_iter().try_fold(Sleep, |acc, s| Ok(acc.combine(s?)));
+    /// assert_eq!(combined, Err(()));
+    /// ```
+    #[inline]
+    pub const fn combine(self, other: ProcessStatus) -> ProcessStatus {
         use ProcessStatus::*;
 
         match (self, other) {
@@ -57,13 +126,33 @@ impl ProcessStatus {
             (Sleep, Sleep) => Sleep,
         }
     }
+
+    /// Combines this status with another one.
+    ///
+    /// This is the same as [`combine`](Self::combine).
+    #[inline]
+    pub const fn combined_with(self, other: ProcessStatus) -> ProcessStatus {
+        self.combine(other)
+    }
 }
 
 /// The audio configuration passed to a plugin's audio processor upon activation.
 ///
 /// Those settings are constant throughout the audio processor's lifetime,
 /// i.e. from a plugin's activation until its deactivation.
+///
+/// The range of frame counts the plugin may be asked to process at once is represented by the
+/// inclusive bounds [`min_frames_count`](Self::min_frames_count) and
+/// [`max_frames_count`](Self::max_frames_count), matching the arguments of the CLAP `activate`
+/// function.
+///
+/// This type can be built directly using a struct literal, or using the
+/// [`new`](Self::new) constructor, which also validates the configuration.
+///
+/// If the `serde` feature is enabled, this type also implements `serde`'s `Serialize` and
+/// `Deserialize` traits, e.g. to allow hosts to persist audio setups.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PluginAudioConfiguration {
     /// The audio's sample rate.
     pub sample_rate: f64,
@@ -73,6 +162,95 @@ pub struct PluginAudioConfiguration {
     pub max_frames_count: u32,
 }
 
+impl PluginAudioConfiguration {
+    /// Creates a new audio configuration from the given sample rate and frame count bounds.
+    ///
+    /// # Errors
+    ///
+    /// This returns a [`PluginAudioConfigurationError`] if the sample rate is not a finite,
+    /// strictly positive number, if `max_frames_count` is zero, or if `min_frames_count` is
+    /// greater than `max_frames_count`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::process::{PluginAudioConfiguration, PluginAudioConfigurationError};
+    ///
+    /// let config = PluginAudioConfiguration::new(48_000.0, 32, 512).unwrap();
+    /// assert_eq!(config.max_frames_count, 512);
+    ///
+    /// assert_eq!(
+    ///     PluginAudioConfiguration::new(48_000.0, 512, 32),
+    ///     Err(PluginAudioConfigurationError::MinGreaterThanMax)
+    /// );
+    /// ```
+    pub fn new(
+        sample_rate: f64,
+        min_frames_count: u32,
+        max_frames_count: u32,
+    ) -> Result<Self, PluginAudioConfigurationError> {
+        let configuration = Self {
+            sample_rate,
+            min_frames_count,
+            max_frames_count,
+        };
+
+        configuration.validate()?;
+        Ok(configuration)
+    }
+
+    /// Checks whether this audio configuration is valid.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`new`](Self::new).
+    pub fn validate(&self) -> Result<(), PluginAudioConfigurationError> {
+        if !self.sample_rate.is_finite() || self.sample_rate <= 0.0 {
+            return Err(PluginAudioConfigurationError::InvalidSampleRate);
+        }
+
+        if self.max_frames_count == 0 {
+            return Err(PluginAudioConfigurationError::ZeroMaxFramesCount);
+        }
+
+        if self.min_frames_count > self.max_frames_count {
+            return Err(PluginAudioConfigurationError::MinGreaterThanMax);
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors that can occur when validating a [`PluginAudioConfiguration`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PluginAudioConfigurationError {
+    /// The sample rate is zero, negative, or not a finite number.
+    InvalidSampleRate,
+    /// The maximum frame count is zero.
+    ZeroMaxFramesCount,
+    /// The minimum frame count is greater than the maximum frame count.
+    MinGreaterThanMax,
+}
+
+impl core::fmt::Display for PluginAudioConfigurationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PluginAudioConfigurationError::InvalidSampleRate => {
+                f.write_str("Sample rate must be a finite, strictly positive number")
+            }
+            PluginAudioConfigurationError::ZeroMaxFramesCount => {
+                f.write_str("Maximum frame count must not be zero")
+            }
+            PluginAudioConfigurationError::MinGreaterThanMax => {
+                f.write_str("Minimum frame count must not be greater than maximum frame count")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PluginAudioConfigurationError {}
+
 use clap_sys::audio_buffer::clap_audio_buffer;
 
 /// Processing-related information about an audio port.
@@ -115,3 +293,102 @@ impl AudioPortProcessingInfo {
         self.constant_mask
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ProcessStatus::{self, *};
+    use super::{PluginAudioConfiguration, PluginAudioConfigurationError};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn audio_configuration_is_serializable() {
+        fn assert_serde<T: serde::Serialize + for<'de> serde::Deserialize<'de>>() {}
+        assert_serde::<PluginAudioConfiguration>();
+    }
+
+    #[test]
+    fn audio_configuration_validation() {
+        use PluginAudioConfigurationError::*;
+
+        assert!(PluginAudioConfiguration::new(44_100.0, 1, 1).is_ok());
+        assert!(PluginAudioConfiguration::new(44_100.0, 0, 256).is_ok());
+
+        assert_eq!(
+            PluginAudioConfiguration::new(0.0, 1, 256),
+            Err(InvalidSampleRate)
+        );
+        assert_eq!(
+            PluginAudioConfiguration::new(-44_100.0, 1, 256),
+            Err(InvalidSampleRate)
+        );
+        assert_eq!(
+            PluginAudioConfiguration::new(f64::NAN, 1, 256),
+            Err(InvalidSampleRate)
+        );
+        assert_eq!(
+            PluginAudioConfiguration::new(44_100.0, 0, 0),
+            Err(ZeroMaxFramesCount)
+        );
+        assert_eq!(
+            PluginAudioConfiguration::new(44_100.0, 257, 256),
+            Err(MinGreaterThanMax)
+        );
+    }
+
+    #[test]
+    fn raw_statuses_round_trip() {
+        for result in [
+            Ok(Continue),
+            Ok(ContinueIfNotQuiet),
+            Ok(Tail),
+            Ok(Sleep),
+            Err(()),
+        ] {
+            let raw = ProcessStatus::raw_from_result(result);
+            assert_eq!(ProcessStatus::from_raw(raw), Some(result));
+        }
+
+        assert_eq!(ProcessStatus::from_raw(i32::MAX), None);
+    }
+
+    #[test]
+    fn combine_table() {
+        let table: [(ProcessStatus, ProcessStatus, ProcessStatus); 16] = [
+            (Continue, Continue, Continue),
+            (Continue, ContinueIfNotQuiet, Continue),
+            (Continue, Tail, Continue),
+            (Continue, Sleep, Continue),
+            (ContinueIfNotQuiet, Continue, Continue),
+            (ContinueIfNotQuiet, ContinueIfNotQuiet, ContinueIfNotQuiet),
+            (ContinueIfNotQuiet, Tail, ContinueIfNotQuiet),
+            (ContinueIfNotQuiet, Sleep, ContinueIfNotQuiet),
+            (Tail, Continue, Continue),
+            (Tail, ContinueIfNotQuiet, ContinueIfNotQuiet),
+            (Tail, Tail, Tail),
+            (Tail, Sleep, Tail),
+            (Sleep, Continue, Continue),
+            (Sleep, ContinueIfNotQuiet, ContinueIfNotQuiet),
+            (Sleep, Tail, Tail),
+            (Sleep, Sleep, Sleep),
+        ];
+
+        for (a, b, expected) in table {
+            assert_eq!(a.combine(b), expected, "{a:?} combined with {b:?}");
+            assert_eq!(a.combined_with(b), expected);
+        }
+    }
+
+    #[test]
+    fn errors_propagate_when_folding() {
+        let fold = |statuses: &[Result<ProcessStatus, ()>]| {
+            statuses
+                .iter()
+                .try_fold(Sleep, |acc, s| Ok::<_, ()>(acc.combine((*s)?)))
+        };
+
+        assert_eq!(fold(&[]), Ok(Sleep));
+        assert_eq!(fold(&[Ok(Sleep), Ok(Sleep)]), Ok(Sleep));
+        assert_eq!(fold(&[Ok(Sleep), Ok(Tail), Ok(Sleep)]), Ok(Tail));
+        assert_eq!(fold(&[Ok(Continue), Err(()), Ok(Sleep)]), Err(()));
+    }
+}
@@ -1,5 +1,5 @@
-use std::fmt::{Debug, Formatter};
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use core::fmt::{Debug, Formatter};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
 
 /// A hint that indicates which channels of an audio port are constant.
 ///
@@ -100,7 +100,7 @@ impl ConstantMask {
 
 impl Debug for ConstantMask {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         core::fmt::Binary::fmt(&self.0, f)
     }
 }
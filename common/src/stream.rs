@@ -2,11 +2,11 @@
 
 use crate::utils::{slice_from_external_parts, slice_from_external_parts_mut};
 use clap_sys::stream::{clap_istream, clap_ostream};
+use core::ffi::c_void;
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
 use std::error::Error;
-use std::ffi::c_void;
-use std::fmt::{Display, Formatter};
 use std::io::{ErrorKind, Read, Write};
-use std::marker::PhantomData;
 
 /// An error code that can be raised by CLAP stream methods.
 #[derive(Copy, Clone, Debug)]
@@ -23,7 +23,7 @@ impl StreamError {
 
 impl Display for StreamError {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "CLAP stream error (code: {})", self.code)
     }
 }
@@ -38,6 +38,14 @@ pub struct InputStream<'a>(clap_istream, PhantomData<(&'a mut clap_istream, *con
 
 impl<'a> InputStream<'a> {
     /// Creates a new input stream for an existing [reader](Read) implementation.
+    ///
+    /// This allows hosts to hand any reader (e.g. a file, or a byte slice) over to a plugin, e.g.
+    /// when loading its state.
+    ///
+    /// Reads are forwarded to the given reader, following CLAP's return-value convention: the
+    /// number of bytes read is returned on success (`0` meaning the end of the stream was reached),
+    /// and `-1` is returned if the reader returned an error. Reads that were
+    /// [interrupted](ErrorKind::Interrupted) are retried a few times before giving up.
     pub fn from_reader<R: Read + Sized + 'a>(reader: &'a mut R) -> Self {
         Self(
             clap_istream {
@@ -87,7 +95,15 @@ impl Read for InputStream<'_> {
 pub struct OutputStream<'a>(clap_ostream, PhantomData<(&'a mut clap_ostream, *const ())>);
 
 impl<'a> OutputStream<'a> {
-    /// Creates a new output stream for an existing [write](Write) implementation.
+    /// Creates a new output stream for an existing [writer](Write) implementation.
+    ///
+    /// This allows hosts to hand any writer (e.g. a file, or a `Vec<u8>`) over to a plugin, e.g.
+    /// when saving its state.
+    ///
+    /// Writes are forwarded to the given writer, following CLAP's return-value convention: the
+    /// number of bytes written is returned on success, and `-1` is returned if the writer returned
+    /// an error. Writes that were [interrupted](ErrorKind::Interrupted) are retried a few times
+    /// before giving up.
     pub fn from_writer<W: Write + Sized + 'a>(writer: &'a mut W) -> Self {
         Self(
             clap_ostream {
@@ -1,25 +1,27 @@
 //! Various CLAP-related utilities.
 
-#[cfg(not(test))]
+#[cfg(all(feature = "std", not(test)))]
 #[allow(unused)]
 pub(crate) use std::panic::catch_unwind as handle_panic;
 
-#[cfg(test)]
+#[cfg(any(not(feature = "std"), test))]
 #[inline]
 #[allow(unused)]
-pub(crate) fn handle_panic<F: FnOnce() -> R, R>(f: F) -> std::thread::Result<R> {
+pub(crate) fn handle_panic<F: FnOnce() -> R, R>(f: F) -> Result<R, core::convert::Infallible> {
     Ok(f())
 }
 
+mod color;
 mod fixed_point;
 mod id;
 mod version;
 
+pub use color::Color;
 pub use fixed_point::*;
-pub use id::ClapId;
+pub use id::{ClapId, InvalidClapIdError};
 pub use version::ClapVersion;
 
-use std::ffi::c_void;
+use core::ffi::c_void;
 
 /// An opaque pointer for use in e.g. parameter definitions and parameter-related events.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -82,6 +84,7 @@ pub(crate) unsafe fn slice_from_external_parts<'a, T>(data: *const T, len: usize
 /// Same as [`core::slice::from_raw_parts_mut`], except the provided pointer *can* be null or
 /// dangling for zero-length slices.
 #[inline]
+#[cfg(feature = "std")]
 pub(crate) unsafe fn slice_from_external_parts_mut<'a, T>(data: *mut T, len: usize) -> &'a mut [T] {
     if len == 0 {
         return &mut [];
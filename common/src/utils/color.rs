@@ -0,0 +1,45 @@
+use clap_sys::color::clap_color;
+
+/// An 8-bit-per-channel ARGB color.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Color {
+    pub alpha: u8,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    /// Creates a new, fully opaque color from its red, green and blue components.
+    #[inline]
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self {
+            alpha: 0xff,
+            red,
+            green,
+            blue,
+        }
+    }
+
+    /// Creates a color from its raw, C-FFI compatible representation.
+    #[inline]
+    pub const fn from_raw(raw: clap_color) -> Self {
+        Self {
+            alpha: raw.alpha,
+            red: raw.red,
+            green: raw.green,
+            blue: raw.blue,
+        }
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this color.
+    #[inline]
+    pub const fn to_raw(self) -> clap_color {
+        clap_color {
+            alpha: self.alpha,
+            red: self.red,
+            green: self.green,
+            blue: self.blue,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::Add;
+use core::ops::Add;
 
 pub type BeatTime = FixedPoint;
 pub type SecondsTime = FixedPoint;
@@ -1,6 +1,6 @@
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
 use core::num::NonZeroU32;
-use std::cmp::Ordering;
-use std::fmt::{Debug, Display, Formatter};
 
 /// A standardized CLAP identifier.
 ///
@@ -8,8 +8,14 @@ use std::fmt::{Debug, Display, Formatter};
 ///
 /// This invalid value is generally used in the CLAP ABI to indicate failure.
 ///
-/// This type ensures that the invalid value (i.e. [`u32::MAX`]) is never used in place of an actual
-/// CLAP ID.
+/// This type ensures that the invalid value (i.e. [`ClapId::INVALID_RAW`]) is never used in place of
+/// an actual CLAP ID. Because a [`ClapId`] can never be invalid, there is no invalid [`ClapId`]
+/// value: raw CLAP IDs that may be invalid are best represented as an `Option<ClapId>`, where
+/// `None` corresponds to the invalid value. See [`ClapId::from_raw`] and
+/// [`ClapId::optional_to_raw`] to convert between the two.
+///
+/// Converting a raw `u32` into a [`ClapId`] is done using [`ClapId::try_from`], which fails on the
+/// invalid value, or [`ClapId::new`], which panics on it.
 ///
 /// # Example
 ///
@@ -18,18 +24,31 @@ use std::fmt::{Debug, Display, Formatter};
 ///
 /// let id: ClapId = ClapId::new(42);
 /// assert_eq!(42, id.get());
+///
+/// assert_eq!(ClapId::try_from(42), Ok(id));
+/// assert!(ClapId::try_from(ClapId::INVALID_RAW).is_err());
+/// assert!(!ClapId::is_valid_raw(ClapId::INVALID_RAW));
+///
+/// assert_eq!(ClapId::from_raw(ClapId::INVALID_RAW), None);
+/// assert_eq!(ClapId::optional_to_raw(None), ClapId::INVALID_RAW);
 /// ```
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct ClapId(NonZeroU32);
 
 impl ClapId {
+    /// The raw value CLAP reserves to represent an invalid identifier, i.e. [`u32::MAX`].
+    ///
+    /// No [`ClapId`] can ever hold this value. Use `Option<ClapId>` to represent a possibly invalid
+    /// identifier instead.
+    pub const INVALID_RAW: u32 = u32::MAX;
+
     /// Creates a new CLAP identifier from its numeric value.
     ///
     /// # Panics
     ///
-    /// This function will panic if `id` is [`u32::MAX`].
+    /// This function will panic if `id` is [`ClapId::INVALID_RAW`].
     ///
-    /// For a non-panicking version, see [`ClapId::from_raw`].
+    /// For non-panicking versions, see [`ClapId::from_raw`] or [`ClapId::try_from`].
     #[inline]
     pub const fn new(id: u32) -> Self {
         match Self::from_raw(id) {
@@ -40,7 +59,7 @@ impl ClapId {
 
     /// Creates a new CLAP identifier from its raw numeric value.
     ///
-    /// Returns `None` if the given `id` is [`u32::MAX`].
+    /// Returns `None` if the given `id` is [`ClapId::INVALID_RAW`].
     ///
     /// For a panicking version that returns the [`ClapId`] directly, see [`ClapId::new`].
     #[inline]
@@ -51,6 +70,15 @@ impl ClapId {
         }
     }
 
+    /// Returns `true` if the given raw value is a valid CLAP identifier, i.e. if it isn't
+    /// [`ClapId::INVALID_RAW`].
+    ///
+    /// There is no equivalent method on [`ClapId`] itself, as a [`ClapId`] is always valid.
+    #[inline]
+    pub const fn is_valid_raw(raw: u32) -> bool {
+        raw != Self::INVALID_RAW
+    }
+
     /// Gets the underlying numerical value of this ID.
     ///
     /// The returned value is guaranteed to never be [`u32::MAX`].
@@ -61,11 +89,12 @@ impl ClapId {
 
     /// Takes an optional identifier, and returns the matching raw, C-FFI compatible value.
     ///
-    /// This returns the underlying numerical value of the ID if present, or [`u32::MAX`] if `None`.
+    /// This returns the underlying numerical value of the ID if present, or [`ClapId::INVALID_RAW`] if
+    /// `None`.
     #[inline]
     pub const fn optional_to_raw(value: Option<ClapId>) -> u32 {
         match value {
-            None => u32::MAX,
+            None => Self::INVALID_RAW,
             Some(value) => value.get(),
         }
     }
@@ -73,22 +102,38 @@ impl ClapId {
 
 impl Debug for ClapId {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("ClapId").field(&self.get()).finish()
     }
 }
 
 impl Display for ClapId {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Display::fmt(&self.get(), f)
     }
 }
 
-impl From<u32> for ClapId {
+/// The error returned when trying to convert [`ClapId::INVALID_RAW`] into a [`ClapId`].
+#[non_exhaustive]
+#[derive(Debug, Default, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct InvalidClapIdError;
+
+impl Display for InvalidClapIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Invalid CLAP ID (u32::MAX)")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidClapIdError {}
+
+impl TryFrom<u32> for ClapId {
+    type Error = InvalidClapIdError;
+
     #[inline]
-    fn from(value: u32) -> Self {
-        ClapId::new(value)
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        ClapId::from_raw(value).ok_or(InvalidClapIdError)
     }
 }
 
@@ -1,6 +1,6 @@
 use clap_sys::version::clap_version;
-use std::cmp::Ordering;
-use std::fmt::{Display, Formatter};
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter};
 
 /// A CLAP version identifier.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -58,7 +58,7 @@ impl Ord for ClapVersion {
 }
 
 impl Display for ClapVersion {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
     }
 }
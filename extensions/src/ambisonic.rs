@@ -0,0 +1,166 @@
+#![deny(missing_docs)]
+
+//! Allows plugins and hosts to agree on the channel ordering and normalization conventions of
+//! ambisonic audio ports.
+//!
+//! Audio ports carrying ambisonic audio have an [`AMBISONIC_PORT_TYPE`] port type. The
+//! [`AmbisonicConfig`] of each of those ports can then be queried from the plugin, and must be
+//! matched exactly by the host for the spatialization to be correct.
+
+use clack_common::extensions::{Extension, HostExtensionSide, PluginExtensionSide, RawExtension};
+use clap_sys::ext::draft::ambisonic::*;
+use std::ffi::CStr;
+
+/// The audio port type of ports carrying ambisonic audio.
+pub const AMBISONIC_PORT_TYPE: &CStr = CLAP_PORT_AMBISONIC;
+
+/// The Plugin-side of the Ambisonic extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginAmbisonic(RawExtension<PluginExtensionSide, clap_plugin_ambisonic>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginAmbisonic {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_AMBISONIC;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The Host-side of the Ambisonic extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct HostAmbisonic(RawExtension<HostExtensionSide, clap_host_ambisonic>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for HostAmbisonic {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_AMBISONIC;
+    type ExtensionSide = HostExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The ordering of the channels of an ambisonic audio port.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum AmbisonicOrdering {
+    /// The Furse-Malham channel ordering.
+    FuMa = CLAP_AMBISONIC_ORDERING_FUMA,
+    /// The Ambisonic Channel Number ordering.
+    Acn = CLAP_AMBISONIC_ORDERING_ACN,
+}
+
+impl AmbisonicOrdering {
+    /// Gets an [`AmbisonicOrdering`] from its raw, C-FFI compatible representation.
+    ///
+    /// This returns [`None`] if the given value does not match any known ordering.
+    #[inline]
+    pub const fn from_raw(raw: clap_ambisonic_ordering) -> Option<Self> {
+        match raw {
+            CLAP_AMBISONIC_ORDERING_FUMA => Some(Self::FuMa),
+            CLAP_AMBISONIC_ORDERING_ACN => Some(Self::Acn),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this ordering.
+    #[inline]
+    pub const fn to_raw(self) -> clap_ambisonic_ordering {
+        self as _
+    }
+}
+
+/// The normalization of the channels of an ambisonic audio port.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum AmbisonicNormalization {
+    /// The maxN normalization, as used by the Furse-Malham (FuMa) format.
+    MaxN = CLAP_AMBISONIC_NORMALIZATION_MAXN,
+    /// The Schmidt semi-normalized (3D) normalization.
+    Sn3d = CLAP_AMBISONIC_NORMALIZATION_SN3D,
+    /// The full 3D normalization.
+    N3d = CLAP_AMBISONIC_NORMALIZATION_N3D,
+    /// The Schmidt semi-normalized (2D) normalization.
+    Sn2d = CLAP_AMBISONIC_NORMALIZATION_SN2D,
+    /// The full 2D normalization.
+    N2d = CLAP_AMBISONIC_NORMALIZATION_N2D,
+}
+
+impl AmbisonicNormalization {
+    /// Gets an [`AmbisonicNormalization`] from its raw, C-FFI compatible representation.
+    ///
+    /// This returns [`None`] if the given value does not match any known normalization.
+    #[inline]
+    pub const fn from_raw(raw: clap_ambisonic_normalization) -> Option<Self> {
+        match raw {
+            CLAP_AMBISONIC_NORMALIZATION_MAXN => Some(Self::MaxN),
+            CLAP_AMBISONIC_NORMALIZATION_SN3D => Some(Self::Sn3d),
+            CLAP_AMBISONIC_NORMALIZATION_N3D => Some(Self::N3d),
+            CLAP_AMBISONIC_NORMALIZATION_SN2D => Some(Self::Sn2d),
+            CLAP_AMBISONIC_NORMALIZATION_N2D => Some(Self::N2d),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this normalization.
+    #[inline]
+    pub const fn to_raw(self) -> clap_ambisonic_normalization {
+        self as _
+    }
+}
+
+/// The ambisonic conventions used by an audio port.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AmbisonicConfig {
+    /// The ordering of the port's channels.
+    pub ordering: AmbisonicOrdering,
+    /// The normalization of the port's channels.
+    pub normalization: AmbisonicNormalization,
+}
+
+impl AmbisonicConfig {
+    /// Gets an [`AmbisonicConfig`] from its raw, C-FFI compatible representation.
+    ///
+    /// This returns [`None`] if either the ordering or the normalization is unknown.
+    #[inline]
+    pub const fn from_raw(raw: &clap_ambisonic_config) -> Option<Self> {
+        let Some(ordering) = AmbisonicOrdering::from_raw(raw.ordering) else {
+            return None;
+        };
+        let Some(normalization) = AmbisonicNormalization::from_raw(raw.normalization) else {
+            return None;
+        };
+
+        Some(Self {
+            ordering,
+            normalization,
+        })
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this configuration.
+    #[inline]
+    pub const fn to_raw(&self) -> clap_ambisonic_config {
+        clap_ambisonic_config {
+            ordering: self.ordering.to_raw(),
+            normalization: self.normalization.to_raw(),
+        }
+    }
+}
+
+#[cfg(feature = "clack-host")]
+mod host;
+
+#[cfg(feature = "clack-host")]
+pub use host::*;
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
@@ -0,0 +1,77 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+
+impl PluginAmbisonic {
+    /// Returns `true` if the plugin supports the given ambisonic configuration.
+    pub fn is_config_supported(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        config: &AmbisonicConfig,
+    ) -> bool {
+        let Some(is_config_supported) = plugin.use_extension(&self.0).is_config_supported else {
+            return false;
+        };
+
+        let config = config.to_raw();
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe { is_config_supported(plugin.as_raw(), &config) }
+    }
+
+    /// Retrieves the ambisonic configuration of the given audio port.
+    ///
+    /// This returns [`None`] if the plugin failed to provide the configuration, or if the
+    /// configuration it provided uses an ordering or normalization unknown to Clack.
+    pub fn get_config(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        port_index: u32,
+        is_input: bool,
+    ) -> Option<AmbisonicConfig> {
+        let get_config = plugin.use_extension(&self.0).get_config?;
+
+        let mut config = clap_ambisonic_config {
+            ordering: u32::MAX,
+            normalization: u32::MAX,
+        };
+
+        // SAFETY: This type ensures the function pointer is valid.
+        let success = unsafe { get_config(plugin.as_raw(), is_input, port_index, &mut config) };
+
+        if !success {
+            return None;
+        }
+
+        AmbisonicConfig::from_raw(&config)
+    }
+}
+
+/// Implementation of the Host-side of the Ambisonic extension.
+pub trait HostAmbisonicImpl {
+    /// Informs the host that the plugin's ambisonic configurations have changed.
+    fn changed(&mut self);
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<H: HostHandlers> ExtensionImplementation<H> for HostAmbisonic
+where
+    for<'h> <H as HostHandlers>::MainThread<'h>: HostAmbisonicImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_host_ambisonic {
+            changed: Some(changed::<H>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn changed<H: HostHandlers>(host: *const clap_host)
+where
+    for<'a> <H as HostHandlers>::MainThread<'a>: HostAmbisonicImpl,
+{
+    HostWrapper::<H>::handle(host, |host| {
+        host.main_thread()?.as_mut().changed();
+
+        Ok(())
+    });
+}
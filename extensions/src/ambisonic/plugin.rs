@@ -0,0 +1,87 @@
+use super::*;
+use clack_plugin::extensions::prelude::*;
+
+/// Implementation of the Plugin-side of the Ambisonic extension.
+pub trait PluginAmbisonicImpl {
+    /// Returns `true` if the plugin supports the given ambisonic configuration.
+    fn is_config_supported(&mut self, config: AmbisonicConfig) -> bool;
+
+    /// Returns the ambisonic configuration of the given audio port, or [`None`] if the port
+    /// does not carry ambisonic audio.
+    fn get_config(&mut self, is_input: bool, port_index: u32) -> Option<AmbisonicConfig>;
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginAmbisonic
+where
+    for<'a> P::MainThread<'a>: PluginAmbisonicImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_ambisonic {
+            is_config_supported: Some(is_config_supported::<P>),
+            get_config: Some(get_config::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn is_config_supported<P: Plugin>(
+    plugin: *const clap_plugin,
+    config: *const clap_ambisonic_config,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginAmbisonicImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        let config = config
+            .as_ref()
+            .ok_or(PluginWrapperError::NulPtr("config"))?;
+
+        // Configurations unknown to Clack can't be supported.
+        let Some(config) = AmbisonicConfig::from_raw(config) else {
+            return Ok(false);
+        };
+
+        Ok(p.main_thread().as_mut().is_config_supported(config))
+    })
+    .unwrap_or(false)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get_config<P: Plugin>(
+    plugin: *const clap_plugin,
+    is_input: bool,
+    port_index: u32,
+    info: *mut clap_ambisonic_config,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginAmbisonicImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        if info.is_null() {
+            return Err(PluginWrapperError::NulPtr("info"));
+        }
+
+        match p.main_thread().as_mut().get_config(is_input, port_index) {
+            Some(config) => {
+                info.write(config.to_raw());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    })
+    .unwrap_or(false)
+}
+
+impl HostAmbisonic {
+    /// Informs the host that the plugin's ambisonic configurations have changed.
+    ///
+    /// This can only be called while the plugin is deactivated.
+    #[inline]
+    pub fn changed(&self, host: &mut HostMainThreadHandle) {
+        if let Some(changed) = host.use_extension(&self.0).changed {
+            // SAFETY: This type ensures the function pointer is valid.
+            unsafe { changed(host.as_raw()) }
+        }
+    }
+}
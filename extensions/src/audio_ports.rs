@@ -95,7 +95,30 @@ unsafe impl Extension for HostAudioPorts {
     }
 }
 
+/// Information about a single audio port.
+///
+/// Plugins can build this using [`new`](Self::new) and the associated builder methods, which
+/// provide defaults for all optional fields:
+///
+/// ```
+/// use clack_extensions::audio_ports::{AudioPortFlags, AudioPortInfo, AudioPortType};
+/// use clack_common::utils::ClapId;
+///
+/// let info = AudioPortInfo::new(ClapId::new(0), b"main")
+///     .with_channels(2)
+///     .with_type(AudioPortType::STEREO)
+///     .main();
+///
+/// assert_eq!(info.channel_count, 2);
+/// assert_eq!(info.port_type, Some(AudioPortType::STEREO));
+/// assert_eq!(info.flags, AudioPortFlags::IS_MAIN);
+/// assert_eq!(info.in_place_pair, None);
+/// ```
+///
+/// This type is marked as `#[non_exhaustive]`, so that fields added by future versions of the
+/// CLAP specification do not break existing plugins.
 #[derive(Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct AudioPortInfo<'a> {
     pub id: ClapId,
     pub name: &'a [u8],
@@ -106,6 +129,62 @@ pub struct AudioPortInfo<'a> {
 }
 
 impl<'a> AudioPortInfo<'a> {
+    /// Creates a new audio port information with the given ID and name.
+    ///
+    /// The port has a single channel, no flags, no port type, and no in-place pair. Use the other
+    /// builder methods to change these.
+    #[inline]
+    pub const fn new(id: ClapId, name: &'a [u8]) -> Self {
+        Self {
+            id,
+            name,
+            channel_count: 1,
+            flags: AudioPortFlags::empty(),
+            port_type: None,
+            in_place_pair: None,
+        }
+    }
+
+    /// Sets the number of channels of this port.
+    #[inline]
+    pub const fn with_channels(mut self, channel_count: u32) -> Self {
+        self.channel_count = channel_count;
+        self
+    }
+
+    /// Sets the type of this port.
+    ///
+    /// Note this does not change the channel count of the port, which must be set separately
+    /// using [`with_channels`](Self::with_channels).
+    #[inline]
+    pub const fn with_type(mut self, port_type: AudioPortType<'a>) -> Self {
+        self.port_type = Some(port_type);
+        self
+    }
+
+    /// Sets the flags of this port, replacing any previously set flags.
+    #[inline]
+    pub const fn with_flags(mut self, flags: AudioPortFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Marks this port as the main port, by adding the [`IS_MAIN`](AudioPortFlags::IS_MAIN)
+    /// flag.
+    #[inline]
+    pub const fn main(mut self) -> Self {
+        self.flags = self.flags.union(AudioPortFlags::IS_MAIN);
+        self
+    }
+
+    /// Sets the ID of the port of the opposite direction this port can be processed in-place
+    /// with.
+    #[inline]
+    pub const fn with_in_place_pair(mut self, in_place_pair: ClapId) -> Self {
+        self.in_place_pair = Some(in_place_pair);
+        self
+    }
+
     /// # Safety
     /// The raw port_type pointer must be a valid C string for the 'a lifetime.
     pub unsafe fn from_raw(raw: &'a clap_audio_port_info) -> Option<Self> {
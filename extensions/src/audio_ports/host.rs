@@ -1,6 +1,10 @@
 use super::*;
 use clack_host::extensions::prelude::*;
+use clack_host::plugin::AudioPortsLayout;
 use core::mem::MaybeUninit;
+use std::error::Error;
+use std::ffi::CString;
+use std::fmt::Display;
 
 #[derive(Clone)]
 pub struct AudioPortInfoBuffer {
@@ -56,8 +60,306 @@ impl PluginAudioPorts {
             None
         }
     }
+
+    /// Returns the plugin's main input port, i.e. the first input port flagged with
+    /// [`AudioPortFlags::IS_MAIN`], or [`None`] if the plugin has no main input port.
+    #[inline]
+    pub fn main_input_port<'b>(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        buffer: &'b mut AudioPortInfoBuffer,
+    ) -> Option<AudioPortInfo<'b>> {
+        self.main_port(plugin, true, buffer)
+    }
+
+    /// Returns the plugin's main output port, i.e. the first output port flagged with
+    /// [`AudioPortFlags::IS_MAIN`], or [`None`] if the plugin has no main output port.
+    #[inline]
+    pub fn main_output_port<'b>(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        buffer: &'b mut AudioPortInfoBuffer,
+    ) -> Option<AudioPortInfo<'b>> {
+        self.main_port(plugin, false, buffer)
+    }
+
+    /// Queries all of the plugin's input and output ports at once, and returns them as an owned
+    /// [`AudioPortsInfo`].
+    ///
+    /// Ports the plugin fails to provide information for are skipped.
+    pub fn layout(&self, plugin: &mut PluginMainThreadHandle) -> AudioPortsInfo {
+        AudioPortsInfo {
+            inputs: self.owned_ports(plugin, true),
+            outputs: self.owned_ports(plugin, false),
+        }
+    }
+
+    fn owned_ports(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        is_input: bool,
+    ) -> Vec<OwnedAudioPortInfo> {
+        let mut buffer = AudioPortInfoBuffer::new();
+
+        (0..self.count(plugin, is_input))
+            .filter_map(|index| {
+                self.get(plugin, index, is_input, &mut buffer)
+                    .map(|info| OwnedAudioPortInfo::from_info(&info))
+            })
+            .collect()
+    }
+
+    fn main_port<'b>(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        is_input: bool,
+        buffer: &'b mut AudioPortInfoBuffer,
+    ) -> Option<AudioPortInfo<'b>> {
+        let index = (0..self.count(plugin, is_input)).find(|&index| {
+            self.get(plugin, index, is_input, buffer)
+                .is_some_and(|port| port.flags.contains(AudioPortFlags::IS_MAIN))
+        })?;
+
+        self.get(plugin, index, is_input, buffer)
+    }
+}
+
+/// An owned version of [`AudioPortInfo`], which doesn't borrow from an [`AudioPortInfoBuffer`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OwnedAudioPortInfo {
+    pub id: ClapId,
+    /// The port's name. Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    pub name: String,
+    pub channel_count: u32,
+    pub flags: AudioPortFlags,
+    pub port_type: Option<CString>,
+    pub in_place_pair: Option<ClapId>,
+}
+
+impl OwnedAudioPortInfo {
+    /// Copies the given [`AudioPortInfo`] into an owned structure.
+    pub fn from_info(info: &AudioPortInfo) -> Self {
+        Self {
+            id: info.id,
+            name: String::from_utf8_lossy(info.name).into_owned(),
+            channel_count: info.channel_count,
+            flags: info.flags,
+            port_type: info.port_type.map(|t| t.0.to_owned()),
+            in_place_pair: info.in_place_pair,
+        }
+    }
+
+    /// Returns `true` if this port is flagged as the main port, i.e. if it has the
+    /// [`IS_MAIN`](AudioPortFlags::IS_MAIN) flag set.
+    #[inline]
+    pub fn is_main(&self) -> bool {
+        self.flags.contains(AudioPortFlags::IS_MAIN)
+    }
+
+    /// Returns the type of this port, if any.
+    #[inline]
+    pub fn port_type(&self) -> Option<AudioPortType<'_>> {
+        self.port_type.as_deref().map(AudioPortType)
+    }
+}
+
+/// All the input and output audio ports of a plugin, as returned by
+/// [`PluginAudioPorts::layout`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AudioPortsInfo {
+    /// The plugin's input ports, in order.
+    pub inputs: Vec<OwnedAudioPortInfo>,
+    /// The plugin's output ports, in order.
+    pub outputs: Vec<OwnedAudioPortInfo>,
 }
 
+impl AudioPortsInfo {
+    /// Returns the index of the main input port, i.e. the first input port flagged with
+    /// [`AudioPortFlags::IS_MAIN`], or [`None`] if there is no main input port.
+    #[inline]
+    pub fn main_input_index(&self) -> Option<usize> {
+        self.inputs.iter().position(OwnedAudioPortInfo::is_main)
+    }
+
+    /// Returns the index of the main output port, i.e. the first output port flagged with
+    /// [`AudioPortFlags::IS_MAIN`], or [`None`] if there is no main output port.
+    #[inline]
+    pub fn main_output_index(&self) -> Option<usize> {
+        self.outputs.iter().position(OwnedAudioPortInfo::is_main)
+    }
+
+    /// Returns the main input port, or [`None`] if there is no main input port.
+    ///
+    /// See [`main_input_index`](Self::main_input_index).
+    #[inline]
+    pub fn main_input(&self) -> Option<&OwnedAudioPortInfo> {
+        self.inputs.get(self.main_input_index()?)
+    }
+
+    /// Returns the main output port, or [`None`] if there is no main output port.
+    ///
+    /// See [`main_output_index`](Self::main_output_index).
+    #[inline]
+    pub fn main_output(&self) -> Option<&OwnedAudioPortInfo> {
+        self.outputs.get(self.main_output_index()?)
+    }
+
+    /// Checks that all the in-place pairs declared by the ports are consistent.
+    ///
+    /// For each port declaring an in-place pair, this checks that:
+    ///
+    /// * the paired port exists, in the opposite direction;
+    /// * the paired port declares this port as its own in-place pair;
+    /// * both ports have the same channel count.
+    ///
+    /// Hosts processing ports in-place must trust the plugin's declarations: this can be used to
+    /// catch buggy plugins beforehand, and to fall back to separate buffers for them.
+    ///
+    /// # Errors
+    ///
+    /// This returns all the inconsistencies that were found, as [`PortPairError`]s.
+    pub fn validate_in_place_pairs(&self) -> Result<(), Vec<PortPairError>> {
+        let mut errors = Vec::new();
+
+        Self::validate_pairs_of(&self.inputs, &self.outputs, true, &mut errors);
+        Self::validate_pairs_of(&self.outputs, &self.inputs, false, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_pairs_of(
+        ports: &[OwnedAudioPortInfo],
+        opposite_ports: &[OwnedAudioPortInfo],
+        is_input: bool,
+        errors: &mut Vec<PortPairError>,
+    ) {
+        for port in ports {
+            let Some(pair_id) = port.in_place_pair else {
+                continue;
+            };
+
+            let Some(pair) = opposite_ports.iter().find(|p| p.id == pair_id) else {
+                errors.push(PortPairError::MissingPort {
+                    is_input,
+                    port_id: port.id,
+                    pair_id,
+                });
+                continue;
+            };
+
+            let is_symmetric = pair.in_place_pair == Some(port.id);
+            if !is_symmetric {
+                errors.push(PortPairError::NotSymmetric {
+                    is_input,
+                    port_id: port.id,
+                    pair_id,
+                });
+            }
+
+            // Symmetric pairs are checked from both sides, only report mismatches once.
+            if pair.channel_count != port.channel_count && (is_input || !is_symmetric) {
+                errors.push(PortPairError::ChannelCountMismatch {
+                    is_input,
+                    port_id: port.id,
+                    pair_id,
+                });
+            }
+        }
+    }
+
+    /// Returns the channel counts of all the ports, as an [`AudioPortsLayout`].
+    ///
+    /// This can be used to allocate the [`AudioPorts`](clack_host::prelude::AudioPorts) matching
+    /// these ports.
+    pub fn channel_layout(&self) -> AudioPortsLayout {
+        AudioPortsLayout::new(
+            self.inputs.iter().map(|p| p.channel_count).collect(),
+            self.outputs.iter().map(|p| p.channel_count).collect(),
+        )
+    }
+}
+
+/// An inconsistency in the in-place pairs declared by a plugin's audio ports.
+///
+/// See [`AudioPortsInfo::validate_in_place_pairs`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PortPairError {
+    /// The port declares an in-place pair with a port that doesn't exist in the opposite
+    /// direction.
+    MissingPort {
+        /// Whether the declaring port is an input port.
+        is_input: bool,
+        /// The ID of the declaring port.
+        port_id: ClapId,
+        /// The ID of the missing paired port.
+        pair_id: ClapId,
+    },
+    /// The port declares an in-place pair with a port that doesn't declare it back.
+    NotSymmetric {
+        /// Whether the declaring port is an input port.
+        is_input: bool,
+        /// The ID of the declaring port.
+        port_id: ClapId,
+        /// The ID of the paired port.
+        pair_id: ClapId,
+    },
+    /// The port declares an in-place pair with a port that has a different channel count.
+    ChannelCountMismatch {
+        /// Whether the declaring port is an input port.
+        is_input: bool,
+        /// The ID of the declaring port.
+        port_id: ClapId,
+        /// The ID of the paired port.
+        pair_id: ClapId,
+    },
+}
+
+impl Display for PortPairError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let direction = |is_input: bool| if is_input { "Input" } else { "Output" };
+        let opposite = |is_input: bool| if is_input { "output" } else { "input" };
+
+        match *self {
+            PortPairError::MissingPort {
+                is_input,
+                port_id,
+                pair_id,
+            } => write!(
+                f,
+                "{} port #{port_id} is paired with {} port #{pair_id}, which doesn't exist",
+                direction(is_input),
+                opposite(is_input)
+            ),
+            PortPairError::NotSymmetric {
+                is_input,
+                port_id,
+                pair_id,
+            } => write!(
+                f,
+                "{} port #{port_id} is paired with {} port #{pair_id}, which isn't paired with it",
+                direction(is_input),
+                opposite(is_input)
+            ),
+            PortPairError::ChannelCountMismatch {
+                is_input,
+                port_id,
+                pair_id,
+            } => write!(
+                f,
+                "{} port #{port_id} is paired with {} port #{pair_id}, which has a different channel count",
+                direction(is_input),
+                opposite(is_input)
+            ),
+        }
+    }
+}
+
+impl Error for PortPairError {}
+
 pub trait HostAudioPortsImpl {
     fn is_rescan_flag_supported(&self, flag: RescanType) -> bool;
     fn rescan(&mut self, flag: RescanType);
@@ -85,7 +387,7 @@ where
 {
     HostWrapper::<H>::handle(host, |host| {
         Ok(host
-            .main_thread()
+            .main_thread()?
             .as_ref()
             .is_rescan_flag_supported(RescanType::from_bits_truncate(flag)))
     })
@@ -98,10 +400,73 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostAudioPortsImpl,
 {
     HostWrapper::<H>::handle(host, |host| {
-        host.main_thread()
+        host.main_thread()?
             .as_mut()
             .rescan(RescanType::from_bits_truncate(flag));
 
         Ok(())
     });
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn port(id: u32, channel_count: u32, in_place_pair: Option<u32>) -> OwnedAudioPortInfo {
+        OwnedAudioPortInfo {
+            id: ClapId::new(id),
+            name: String::new(),
+            channel_count,
+            flags: AudioPortFlags::empty(),
+            port_type: None,
+            in_place_pair: in_place_pair.map(ClapId::new),
+        }
+    }
+
+    #[test]
+    fn valid_in_place_pairs() {
+        let info = AudioPortsInfo {
+            inputs: vec![port(0, 2, Some(10)), port(1, 1, None)],
+            outputs: vec![port(10, 2, Some(0))],
+        };
+
+        assert_eq!(info.validate_in_place_pairs(), Ok(()));
+        assert_eq!(AudioPortsInfo::default().validate_in_place_pairs(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_in_place_pairs() {
+        let info = AudioPortsInfo {
+            inputs: vec![port(0, 2, Some(10)), port(1, 2, Some(42)), port(2, 2, None)],
+            outputs: vec![port(10, 1, Some(0)), port(11, 2, Some(2))],
+        };
+
+        let errors = info.validate_in_place_pairs().unwrap_err();
+
+        assert_eq!(
+            errors,
+            [
+                PortPairError::ChannelCountMismatch {
+                    is_input: true,
+                    port_id: ClapId::new(0),
+                    pair_id: ClapId::new(10),
+                },
+                PortPairError::MissingPort {
+                    is_input: true,
+                    port_id: ClapId::new(1),
+                    pair_id: ClapId::new(42),
+                },
+                PortPairError::NotSymmetric {
+                    is_input: false,
+                    port_id: ClapId::new(11),
+                    pair_id: ClapId::new(2),
+                },
+            ]
+        );
+
+        assert_eq!(
+            errors[1].to_string(),
+            "Input port #1 is paired with output port #42, which doesn't exist"
+        );
+    }
+}
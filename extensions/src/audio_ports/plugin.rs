@@ -120,3 +120,75 @@ impl HostAudioPorts {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::audio_ports::{AudioPortFlags, AudioPortType};
+
+    fn write_raw(info: &AudioPortInfo) -> MaybeUninit<clap_audio_port_info> {
+        let mut raw = MaybeUninit::zeroed();
+
+        // SAFETY: the pointer comes from a valid, well-aligned local
+        let mut writer = unsafe { AudioPortInfoWriter::from_raw(raw.as_mut_ptr()) };
+        writer.set(info);
+        assert!(writer.is_set);
+
+        raw
+    }
+
+    fn raw_bytes(raw: &MaybeUninit<clap_audio_port_info>) -> &[u8] {
+        // SAFETY: the buffer was zeroed before being written to, so all bytes are initialized
+        unsafe {
+            core::slice::from_raw_parts(
+                raw.as_ptr().cast(),
+                core::mem::size_of::<clap_audio_port_info>(),
+            )
+        }
+    }
+
+    #[test]
+    fn builder_matches_struct_literal() {
+        let literal = AudioPortInfo {
+            id: ClapId::new(3),
+            name: b"Sidechain",
+            channel_count: 2,
+            flags: AudioPortFlags::IS_MAIN | AudioPortFlags::SUPPORTS_64BITS,
+            port_type: Some(AudioPortType::STEREO),
+            in_place_pair: Some(ClapId::new(4)),
+        };
+
+        let built = AudioPortInfo::new(ClapId::new(3), b"Sidechain")
+            .with_channels(2)
+            .with_flags(AudioPortFlags::SUPPORTS_64BITS)
+            .with_type(AudioPortType::STEREO)
+            .with_in_place_pair(ClapId::new(4))
+            .main();
+
+        assert_eq!(built, literal);
+        assert_eq!(
+            raw_bytes(&write_raw(&built)),
+            raw_bytes(&write_raw(&literal))
+        );
+    }
+
+    #[test]
+    fn builder_defaults() {
+        let literal = AudioPortInfo {
+            id: ClapId::new(0),
+            name: b"main",
+            channel_count: 1,
+            flags: AudioPortFlags::empty(),
+            port_type: None,
+            in_place_pair: None,
+        };
+
+        let built = AudioPortInfo::new(ClapId::new(0), b"main");
+
+        assert_eq!(built, literal);
+        assert_eq!(
+            raw_bytes(&write_raw(&built)),
+            raw_bytes(&write_raw(&literal))
+        );
+    }
+}
@@ -0,0 +1,45 @@
+#![deny(missing_docs)]
+
+//! Allows hosts to activate and deactivate individual audio ports of a plugin.
+//!
+//! Deactivating an unused audio port (e.g. a sidechain input that isn't connected) allows the
+//! plugin to skip any processing related to it, saving CPU.
+//!
+//! By default, ports are assumed to be active. Some plugins also support activating or
+//! deactivating ports while they are processing audio, which hosts can check using
+//! `PluginAudioPortsActivation::can_activate_while_processing`.
+//!
+//! When changing the activation of a port, hosts also give the sample size (in bits) they intend
+//! to use for that port's buffers: `32` for `f32` buffers, `64` for `f64` buffers, or `0` if it
+//! is not known yet. Plugins may use this information to e.g. only allocate the buffers they need.
+
+use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
+use clap_sys::ext::draft::audio_ports_activation::*;
+use std::ffi::CStr;
+
+/// The Plugin-side of the Audio Ports Activation extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginAudioPortsActivation(
+    RawExtension<PluginExtensionSide, clap_plugin_audio_ports_activation>,
+);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginAudioPortsActivation {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_AUDIO_PORTS_ACTIVATION;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+#[cfg(feature = "clack-host")]
+mod host;
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
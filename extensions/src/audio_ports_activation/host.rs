@@ -0,0 +1,103 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+
+impl PluginAudioPortsActivation {
+    /// Returns `true` if the plugin supports activating and deactivating its ports while it is
+    /// processing audio.
+    ///
+    /// If this returns `false`, port activation can only be changed while the plugin is
+    /// deactivated, using [`set_active`](Self::set_active).
+    pub fn can_activate_while_processing(&self, plugin: &mut PluginMainThreadHandle) -> bool {
+        match plugin.use_extension(&self.0).can_activate_while_processing {
+            None => false,
+            // SAFETY: This type ensures the function pointer is valid.
+            Some(can_activate) => unsafe { can_activate(plugin.as_raw()) },
+        }
+    }
+
+    /// Activates or deactivates the given audio port, while the plugin is deactivated.
+    ///
+    /// The `sample_size` is the size (in bits) of the samples the host intends to use for this
+    /// port's buffers: `32` for `f32`, `64` for `f64`, or `0` if it is not known yet.
+    ///
+    /// This returns `true` if the port activation was successfully changed, `false` otherwise.
+    ///
+    /// # Safety
+    ///
+    /// The plugin instance must be deactivated when this is called.
+    ///
+    /// The plugin cannot tell on which thread this is called, and will handle this call on its
+    /// audio processor if it is active, which would then be concurrent with its audio processing.
+    /// Once the plugin is activated, [`set_active_while_processing`](Self::set_active_while_processing)
+    /// must be used instead.
+    pub unsafe fn set_active(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        port_index: u32,
+        is_input: bool,
+        sample_size: u32,
+        active: bool,
+    ) -> bool {
+        match plugin.use_extension(&self.0).set_active {
+            None => false,
+            // SAFETY: This type ensures the function pointer is valid.
+            Some(set_active) => unsafe {
+                set_active(plugin.as_raw(), is_input, port_index, active, sample_size)
+            },
+        }
+    }
+
+    /// Activates or deactivates the given audio port, while the plugin is processing audio.
+    ///
+    /// This is only supported if [`can_activate_while_processing`](Self::can_activate_while_processing)
+    /// returned `true`. See [`set_active`](Self::set_active) for the meaning of `sample_size`.
+    ///
+    /// Once the plugin is activated, this is the only valid way to change port activation.
+    ///
+    /// This returns `true` if the port activation was successfully changed, `false` otherwise.
+    pub fn set_active_while_processing(
+        &self,
+        plugin: &mut PluginAudioProcessorHandle,
+        port_index: u32,
+        is_input: bool,
+        sample_size: u32,
+        active: bool,
+    ) -> bool {
+        match plugin.use_extension(&self.0).set_active {
+            None => false,
+            // SAFETY: This type ensures the function pointer is valid.
+            Some(set_active) => unsafe {
+                set_active(plugin.as_raw(), is_input, port_index, active, sample_size)
+            },
+        }
+    }
+
+    /// Activates or deactivates multiple audio ports at once, while the plugin is deactivated.
+    ///
+    /// Each item of `ports` is made of a port index, whether the port is an input, and whether it
+    /// should be active. See [`set_active`](Self::set_active) for the meaning of `sample_size`.
+    ///
+    /// All ports are processed, even if some of them fail. This returns the index and direction
+    /// (`true` for inputs) of every port whose activation could not be changed, which is empty if
+    /// all ports were changed successfully.
+    ///
+    /// # Safety
+    ///
+    /// The plugin instance must be deactivated when this is called. See
+    /// [`set_active`](Self::set_active) for more information.
+    pub unsafe fn set_many(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        ports: &[(u32, bool, bool)],
+        sample_size: u32,
+    ) -> Vec<(u32, bool)> {
+        ports
+            .iter()
+            .filter(|&&(port_index, is_input, active)| {
+                // SAFETY: the caller guarantees the plugin is deactivated.
+                !unsafe { self.set_active(plugin, port_index, is_input, sample_size, active) }
+            })
+            .map(|&(port_index, is_input, _)| (port_index, is_input))
+            .collect()
+    }
+}
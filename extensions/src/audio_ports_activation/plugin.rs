@@ -0,0 +1,95 @@
+use super::*;
+use clack_plugin::extensions::prelude::*;
+
+/// Implementation of the Plugin-side of the Audio Ports Activation extension, for the plugin's
+/// main thread.
+pub trait PluginAudioPortsActivationImpl {
+    /// Returns `true` if the plugin supports activating and deactivating its ports while it is
+    /// processing audio.
+    fn can_activate_while_processing(&mut self) -> bool;
+
+    /// Activates or deactivates the given audio port. This is only called while the plugin is
+    /// deactivated.
+    ///
+    /// The `sample_size` is the size (in bits) of the samples the host intends to use for this
+    /// port's buffers: `32` for `f32`, `64` for `f64`, or `0` if it is not known yet.
+    ///
+    /// This returns `true` if the port activation was successfully changed, `false` otherwise.
+    fn set_active(
+        &mut self,
+        is_input: bool,
+        port_index: u32,
+        is_active: bool,
+        sample_size: u32,
+    ) -> bool;
+}
+
+/// Implementation of the Plugin-side of the Audio Ports Activation extension, for the plugin's
+/// audio processor.
+pub trait PluginAudioProcessorAudioPortsActivationImpl {
+    /// Activates or deactivates the given audio port, while the plugin is activated.
+    ///
+    /// This is only called if the plugin returned `true` from
+    /// [`PluginAudioPortsActivationImpl::can_activate_while_processing`]. See
+    /// [`PluginAudioPortsActivationImpl::set_active`] for the meaning of `sample_size`.
+    ///
+    /// This returns `true` if the port activation was successfully changed, `false` otherwise.
+    fn set_active(
+        &mut self,
+        is_input: bool,
+        port_index: u32,
+        is_active: bool,
+        sample_size: u32,
+    ) -> bool;
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginAudioPortsActivation
+where
+    for<'a> P::MainThread<'a>: PluginAudioPortsActivationImpl,
+    for<'a> P::AudioProcessor<'a>: PluginAudioProcessorAudioPortsActivationImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_audio_ports_activation {
+            can_activate_while_processing: Some(can_activate_while_processing::<P>),
+            set_active: Some(set_active::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn can_activate_while_processing<P: Plugin>(plugin: *const clap_plugin) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginAudioPortsActivationImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        Ok(p.main_thread().as_mut().can_activate_while_processing())
+    })
+    .unwrap_or(false)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_active<P: Plugin>(
+    plugin: *const clap_plugin,
+    is_input: bool,
+    port_index: u32,
+    is_active: bool,
+    sample_size: u32,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginAudioPortsActivationImpl,
+    for<'a> P::AudioProcessor<'a>: PluginAudioProcessorAudioPortsActivationImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        if let Ok(mut audio) = p.audio_processor() {
+            Ok(audio
+                .as_mut()
+                .set_active(is_input, port_index, is_active, sample_size))
+        } else {
+            Ok(p.main_thread()
+                .as_mut()
+                .set_active(is_input, port_index, is_active, sample_size))
+        }
+    })
+    .unwrap_or(false)
+}
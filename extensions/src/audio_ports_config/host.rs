@@ -119,7 +119,7 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostAudioPortsConfigImpl,
 {
     HostWrapper::<H>::handle(host, |host| {
-        host.main_thread().as_mut().rescan();
+        host.main_thread()?.as_mut().rescan();
 
         Ok(())
     });
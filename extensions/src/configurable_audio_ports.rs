@@ -0,0 +1,143 @@
+#![deny(missing_docs)]
+
+//! Allows hosts to request a new audio port configuration from a plugin, e.g. to switch a
+//! plugin's main ports from stereo to mono.
+//!
+//! Hosts send a list of [`AudioPortConfigRequest`]s, each describing the desired channel count and
+//! port type of a single audio port. Ports that are not part of the list are left as-is.
+//!
+//! Configuration requests can only be made while the plugin is deactivated.
+
+use crate::audio_ports::AudioPortType;
+use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
+use clap_sys::ext::draft::configurable_audio_ports::*;
+use std::ffi::CStr;
+use std::fmt::{Debug, Formatter};
+
+/// The Plugin-side of the Configurable Audio Ports extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginConfigurableAudioPorts(
+    RawExtension<PluginExtensionSide, clap_plugin_configurable_audio_ports>,
+);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginConfigurableAudioPorts {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_CONFIGURABLE_AUDIO_PORTS;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// A request to change the configuration of a single audio port.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AudioPortConfigRequest<'a> {
+    /// Whether the port to configure is an input port or an output port.
+    pub is_input: bool,
+    /// The index of the port to configure.
+    pub port_index: u32,
+    /// The requested number of channels for this port.
+    pub channel_count: u32,
+    /// The requested type of this port, or [`None`] if it is unspecified.
+    pub port_type: Option<AudioPortType<'a>>,
+}
+
+impl<'a> AudioPortConfigRequest<'a> {
+    /// Gets an [`AudioPortConfigRequest`] from its raw, C-FFI compatible representation.
+    ///
+    /// Any port-type-specific details in the raw request are ignored.
+    ///
+    /// # Safety
+    ///
+    /// The raw `port_type` pointer must either be null, or point to a valid C string for the `'a`
+    /// lifetime.
+    pub unsafe fn from_raw(raw: &clap_audio_port_configuration_request) -> Self {
+        Self {
+            is_input: raw.is_input,
+            port_index: raw.port_index,
+            channel_count: raw.channel_count,
+            port_type: (!raw.port_type.is_null())
+                .then(|| AudioPortType(CStr::from_ptr(raw.port_type)))
+                .filter(|t| !t.0.is_empty()),
+        }
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this request.
+    ///
+    /// The returned struct borrows this request's port type string, and is only valid for as long
+    /// as it is.
+    pub fn to_raw(&self) -> clap_audio_port_configuration_request {
+        clap_audio_port_configuration_request {
+            is_input: self.is_input,
+            port_index: self.port_index,
+            channel_count: self.channel_count,
+            port_type: self.port_type.map_or(core::ptr::null(), |t| t.0.as_ptr()),
+            port_details: core::ptr::null(),
+        }
+    }
+}
+
+/// A list of [`AudioPortConfigRequest`]s, as received from the host.
+#[derive(Copy, Clone)]
+pub struct AudioPortConfigRequests<'a> {
+    raw: &'a [clap_audio_port_configuration_request],
+}
+
+impl<'a> AudioPortConfigRequests<'a> {
+    /// # Safety
+    ///
+    /// All the port type pointers of the given requests must either be null, or point to a valid
+    /// C string for the `'a` lifetime.
+    #[inline]
+    pub unsafe fn from_raw(raw: &'a [clap_audio_port_configuration_request]) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the number of requests in this list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns `true` if this list contains no requests.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns the request at the given index, or [`None`] if it is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<AudioPortConfigRequest<'a>> {
+        // SAFETY: The port type pointer is guaranteed to be valid by from_raw's caller.
+        self.raw
+            .get(index)
+            .map(|raw| unsafe { AudioPortConfigRequest::from_raw(raw) })
+    }
+
+    /// Returns an iterator over all the requests in this list.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = AudioPortConfigRequest<'a>> + 'a {
+        // SAFETY: The port type pointer is guaranteed to be valid by from_raw's caller.
+        self.raw
+            .iter()
+            .map(|raw| unsafe { AudioPortConfigRequest::from_raw(raw) })
+    }
+}
+
+impl Debug for AudioPortConfigRequests<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "clack-host")]
+mod host;
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
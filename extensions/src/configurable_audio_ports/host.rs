@@ -0,0 +1,93 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+
+impl PluginConfigurableAudioPorts {
+    /// Returns `true` if the plugin is able to apply the given configuration requests.
+    ///
+    /// This does not change the plugin's configuration.
+    pub fn can_apply_configuration(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        requests: &[AudioPortConfigRequest],
+    ) -> bool {
+        let Some(can_apply) = plugin.use_extension(&self.0).can_apply_configuration else {
+            return false;
+        };
+
+        let raw_requests = to_raw_requests(requests);
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe {
+            can_apply(
+                plugin.as_raw(),
+                raw_requests.as_ptr(),
+                raw_requests.len() as u32,
+            )
+        }
+    }
+
+    /// Asks the plugin to apply the given configuration requests.
+    ///
+    /// This returns `true` if the plugin successfully applied the new configuration. If it
+    /// failed, the plugin's configuration is left unchanged.
+    pub fn apply_configuration(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        requests: &[AudioPortConfigRequest],
+    ) -> bool {
+        let Some(apply) = plugin.use_extension(&self.0).apply_configuration else {
+            return false;
+        };
+
+        let raw_requests = to_raw_requests(requests);
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe {
+            apply(
+                plugin.as_raw(),
+                raw_requests.as_ptr(),
+                raw_requests.len() as u32,
+            )
+        }
+    }
+
+    /// Checks whether the plugin can apply the given configuration requests, and then applies
+    /// them if it can.
+    ///
+    /// This returns `false` without applying anything if the plugin cannot apply the requested
+    /// configuration, or if it failed to apply it.
+    pub fn apply(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        requests: &[AudioPortConfigRequest],
+    ) -> bool {
+        let extension = plugin.use_extension(&self.0);
+        let (Some(can_apply), Some(apply)) = (
+            extension.can_apply_configuration,
+            extension.apply_configuration,
+        ) else {
+            return false;
+        };
+
+        // The raw requests borrow the port types of the given requests, and are kept alive until
+        // both calls have completed.
+        let raw_requests = to_raw_requests(requests);
+        let len = raw_requests.len() as u32;
+
+        // SAFETY: This type ensures the function pointers are valid.
+        unsafe {
+            can_apply(plugin.as_raw(), raw_requests.as_ptr(), len)
+                && apply(plugin.as_raw(), raw_requests.as_ptr(), len)
+        }
+    }
+}
+
+#[inline]
+fn to_raw_requests(
+    requests: &[AudioPortConfigRequest],
+) -> Vec<clap_audio_port_configuration_request> {
+    requests
+        .iter()
+        .map(AudioPortConfigRequest::to_raw)
+        .collect()
+}
@@ -0,0 +1,78 @@
+use super::*;
+use crate::utils::slice_from_external_parts;
+use clack_plugin::extensions::prelude::*;
+
+/// Implementation of the Plugin-side of the Configurable Audio Ports extension.
+pub trait PluginConfigurableAudioPortsImpl {
+    /// Returns `true` if the plugin is able to apply all the given configuration requests.
+    ///
+    /// This must not change the plugin's configuration.
+    fn can_apply_configuration(&mut self, requests: AudioPortConfigRequests) -> bool;
+
+    /// Applies all the given configuration requests.
+    ///
+    /// This returns `true` if the new configuration was successfully applied. If it wasn't, the
+    /// plugin's configuration must be left unchanged.
+    fn apply_configuration(&mut self, requests: AudioPortConfigRequests) -> bool;
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginConfigurableAudioPorts
+where
+    for<'a> P::MainThread<'a>: PluginConfigurableAudioPortsImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_configurable_audio_ports {
+            can_apply_configuration: Some(can_apply_configuration::<P>),
+            apply_configuration: Some(apply_configuration::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn can_apply_configuration<P: Plugin>(
+    plugin: *const clap_plugin,
+    requests: *const clap_audio_port_configuration_request,
+    request_count: u32,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginConfigurableAudioPortsImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        if requests.is_null() && request_count > 0 {
+            return Err(PluginWrapperError::NulPtr("requests"));
+        }
+
+        let requests = AudioPortConfigRequests::from_raw(slice_from_external_parts(
+            requests,
+            request_count as usize,
+        ));
+
+        Ok(p.main_thread().as_mut().can_apply_configuration(requests))
+    })
+    .unwrap_or(false)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn apply_configuration<P: Plugin>(
+    plugin: *const clap_plugin,
+    requests: *const clap_audio_port_configuration_request,
+    request_count: u32,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginConfigurableAudioPortsImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        if requests.is_null() && request_count > 0 {
+            return Err(PluginWrapperError::NulPtr("requests"));
+        }
+
+        let requests = AudioPortConfigRequests::from_raw(slice_from_external_parts(
+            requests,
+            request_count as usize,
+        ));
+
+        Ok(p.main_thread().as_mut().apply_configuration(requests))
+    })
+    .unwrap_or(false)
+}
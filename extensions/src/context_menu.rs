@@ -0,0 +1,245 @@
+#![deny(missing_docs)]
+
+//! Allows plugins and hosts to populate each other's context menus.
+//!
+//! When the user right-clicks on a parameter (or on the plugin itself) in the host's UI, the
+//! host may ask the plugin to populate the context menu it is about to show with plugin-specific
+//! entries. When the user then selects one of those entries, the host asks the plugin to perform
+//! the matching action.
+//!
+//! Menus are described as a flat, ordered sequence of [`ContextMenuItem`]s, in which submenus are
+//! delimited by [`BeginSubmenu`](ContextMenuItem::BeginSubmenu) and
+//! [`EndSubmenu`](ContextMenuItem::EndSubmenu) items. The [`ContextMenu`] type collects those
+//! items, and ensures submenus are always properly nested.
+//!
+//! Plugins populate menus through a [`ContextMenuBuilder`], while hosts can collect what a plugin
+//! populated into a [`ContextMenu`], and then render it using their own UI toolkit.
+
+use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
+use clack_common::utils::ClapId;
+use clap_sys::ext::draft::context_menu::*;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt::{Display, Formatter};
+
+/// The Plugin-side of the Context Menu extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginContextMenu(RawExtension<PluginExtensionSide, clap_plugin_context_menu>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginContextMenu {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_CONTEXT_MENU;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The target of a context menu, i.e. what the user clicked on to open it.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ContextMenuTarget {
+    /// The menu applies to the plugin as a whole.
+    Global,
+    /// The menu applies to the parameter matching the given ID.
+    Param(ClapId),
+}
+
+impl ContextMenuTarget {
+    /// Reads the target from its raw, C-FFI compatible representation.
+    ///
+    /// This returns [`None`] if the target kind is unknown, or if the parameter ID is invalid.
+    pub fn from_raw(raw: &clap_context_menu_target) -> Option<Self> {
+        match raw.kind {
+            CLAP_CONTEXT_MENU_TARGET_KIND_GLOBAL => Some(Self::Global),
+            CLAP_CONTEXT_MENU_TARGET_KIND_PARAM => Some(Self::Param(ClapId::from_raw(raw.id)?)),
+            _ => None,
+        }
+    }
+
+    /// Returns the target as its raw, C-FFI compatible representation.
+    pub fn to_raw(&self) -> clap_context_menu_target {
+        match self {
+            Self::Global => clap_context_menu_target {
+                kind: CLAP_CONTEXT_MENU_TARGET_KIND_GLOBAL,
+                id: ClapId::optional_to_raw(None),
+            },
+            Self::Param(id) => clap_context_menu_target {
+                kind: CLAP_CONTEXT_MENU_TARGET_KIND_PARAM,
+                id: id.get(),
+            },
+        }
+    }
+}
+
+/// A single item of a context menu.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ContextMenuItem {
+    /// A clickable entry.
+    Entry {
+        /// The label of the entry.
+        label: CString,
+        /// Whether the entry can be clicked or is greyed out.
+        is_enabled: bool,
+        /// The ID of the action to perform when this entry is clicked.
+        action_id: ClapId,
+    },
+    /// A clickable entry, which also displays a check mark.
+    CheckEntry {
+        /// The label of the entry.
+        label: CString,
+        /// Whether the entry can be clicked or is greyed out.
+        is_enabled: bool,
+        /// Whether the entry is displayed as checked.
+        is_checked: bool,
+        /// The ID of the action to perform when this entry is clicked.
+        action_id: ClapId,
+    },
+    /// A separator line.
+    Separator,
+    /// The start of a submenu. All following items are part of the submenu, until the matching
+    /// [`EndSubmenu`](ContextMenuItem::EndSubmenu) item.
+    BeginSubmenu {
+        /// The label of the submenu.
+        label: CString,
+        /// Whether the submenu can be opened or is greyed out.
+        is_enabled: bool,
+    },
+    /// The end of the current submenu.
+    EndSubmenu,
+    /// A title, which is a non-clickable label.
+    Title {
+        /// The title's text.
+        title: CString,
+        /// Whether the title is greyed out.
+        is_enabled: bool,
+    },
+}
+
+impl ContextMenuItem {
+    /// Returns the raw, C-FFI compatible kind of this item.
+    pub fn raw_kind(&self) -> clap_context_menu_item_kind {
+        match self {
+            Self::Entry { .. } => CLAP_CONTEXT_MENU_ITEM_ENTRY,
+            Self::CheckEntry { .. } => CLAP_CONTEXT_MENU_ITEM_CHECK_ENTRY,
+            Self::Separator => CLAP_CONTEXT_MENU_ITEM_SEPARATOR,
+            Self::BeginSubmenu { .. } => CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU,
+            Self::EndSubmenu => CLAP_CONTEXT_MENU_ITEM_END_SUBMENU,
+            Self::Title { .. } => CLAP_CONTEXT_MENU_ITEM_TITLE,
+        }
+    }
+}
+
+/// An ordered collection of [`ContextMenuItem`]s, forming a context menu.
+///
+/// This type keeps track of the current submenu nesting depth, and rejects any item that would
+/// result in mismatched [`BeginSubmenu`](ContextMenuItem::BeginSubmenu) and
+/// [`EndSubmenu`](ContextMenuItem::EndSubmenu) items.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct ContextMenu {
+    items: Vec<ContextMenuItem>,
+    depth: usize,
+}
+
+impl ContextMenu {
+    /// Creates a new, empty context menu.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// Appends the given item to the menu.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`ContextMenuError::UnexpectedSubmenuEnd`] if the given item is an
+    /// [`EndSubmenu`](ContextMenuItem::EndSubmenu) item but no submenu is currently open. The
+    /// item is not added to the menu in that case.
+    pub fn push(&mut self, item: ContextMenuItem) -> Result<(), ContextMenuError> {
+        match item {
+            ContextMenuItem::BeginSubmenu { .. } => self.depth += 1,
+            ContextMenuItem::EndSubmenu => {
+                self.depth = self
+                    .depth
+                    .checked_sub(1)
+                    .ok_or(ContextMenuError::UnexpectedSubmenuEnd)?
+            }
+            _ => {}
+        }
+
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// Returns all the items of this menu, in order.
+    #[inline]
+    pub fn items(&self) -> &[ContextMenuItem] {
+        &self.items
+    }
+
+    /// Returns the number of currently open submenus, i.e. the current nesting depth.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns `true` if all submenus of this menu have been properly closed, `false` otherwise.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.depth == 0
+    }
+
+    /// Returns `true` if this menu has no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Removes all the items from this menu.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.depth = 0;
+    }
+}
+
+/// Errors that can occur while building, populating or using a context menu.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ContextMenuError {
+    /// A submenu was ended while none was open.
+    UnexpectedSubmenuEnd,
+    /// The menu was completed while some submenus were still open.
+    UnclosedSubmenu,
+    /// A menu item was invalid or of an unknown kind.
+    InvalidItem,
+    /// The menu could not be populated, or the menu's action could not be performed.
+    Failed,
+}
+
+impl Display for ContextMenuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedSubmenuEnd => {
+                f.write_str("Context menu submenu was ended while none was open")
+            }
+            Self::UnclosedSubmenu => f.write_str("Context menu has unclosed submenus"),
+            Self::InvalidItem => f.write_str("Invalid context menu item"),
+            Self::Failed => f.write_str("Context menu operation failed"),
+        }
+    }
+}
+
+impl Error for ContextMenuError {}
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
+
+#[cfg(feature = "clack-host")]
+mod host;
@@ -0,0 +1,271 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+use std::ffi::c_void;
+
+impl PluginContextMenu {
+    /// Asks the plugin to populate the context menu for the given `target`, collecting all of its
+    /// items into the given `menu`.
+    ///
+    /// Items are appended to the given menu, which allows hosts to add their own items before or
+    /// after the plugin's.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the plugin failed to populate the menu, if it provided an invalid
+    /// item, if it ended a submenu it didn't open, or if it left some submenus unclosed. In that
+    /// case, the given menu may still contain the items the plugin managed to populate before the
+    /// error occurred.
+    pub fn populate(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        target: ContextMenuTarget,
+        menu: &mut ContextMenu,
+    ) -> Result<(), ContextMenuError> {
+        let populate = plugin
+            .use_extension(&self.0)
+            .populate
+            .ok_or(ContextMenuError::Failed)?;
+
+        let initial_depth = menu.depth();
+        let mut collector = MenuCollector {
+            menu,
+            min_depth: initial_depth,
+            error: None,
+        };
+
+        let builder = clap_context_menu_builder {
+            ctx: &mut collector as *mut MenuCollector as *mut c_void,
+            add_item: Some(add_item),
+            supports: Some(supports),
+        };
+
+        let target = target.to_raw();
+
+        // SAFETY: This type ensures the function pointer is valid.
+        let success = unsafe { populate(plugin.as_raw(), &target, &builder) };
+
+        if let Some(error) = collector.error {
+            return Err(error);
+        }
+
+        if !success {
+            return Err(ContextMenuError::Failed);
+        }
+
+        // The plugin must not end any submenu the host opened before populating.
+        if collector.min_depth < initial_depth {
+            return Err(ContextMenuError::UnexpectedSubmenuEnd);
+        }
+
+        if collector.menu.depth() != initial_depth {
+            return Err(ContextMenuError::UnclosedSubmenu);
+        }
+
+        Ok(())
+    }
+
+    /// Asks the plugin to perform the action matching the given `action_id`, for the given
+    /// `target`.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`ContextMenuError::Failed`] if the plugin failed to perform the action.
+    pub fn perform(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        target: ContextMenuTarget,
+        action_id: ClapId,
+    ) -> Result<(), ContextMenuError> {
+        let perform = plugin
+            .use_extension(&self.0)
+            .perform
+            .ok_or(ContextMenuError::Failed)?;
+
+        let target = target.to_raw();
+
+        // SAFETY: This type ensures the function pointer is valid.
+        let success = unsafe { perform(plugin.as_raw(), &target, action_id.get()) };
+
+        match success {
+            true => Ok(()),
+            false => Err(ContextMenuError::Failed),
+        }
+    }
+}
+
+struct MenuCollector<'a> {
+    menu: &'a mut ContextMenu,
+    /// The lowest submenu depth the menu reached while it was being populated.
+    min_depth: usize,
+    error: Option<ContextMenuError>,
+}
+
+impl MenuCollector<'_> {
+    /// # Safety
+    ///
+    /// The given item data must be valid for the given item kind.
+    unsafe fn read_item(
+        kind: clap_context_menu_item_kind,
+        data: *const c_void,
+    ) -> Option<ContextMenuItem> {
+        /// # Safety
+        ///
+        /// The given pointer must be either null or point to a valid C string.
+        unsafe fn label(ptr: *const std::os::raw::c_char) -> Option<CString> {
+            if ptr.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(ptr).to_owned())
+            }
+        }
+
+        let item = match kind {
+            CLAP_CONTEXT_MENU_ITEM_ENTRY => {
+                let entry = &*(data as *const clap_context_menu_entry);
+                ContextMenuItem::Entry {
+                    label: label(entry.label)?,
+                    is_enabled: entry.is_enabled,
+                    action_id: ClapId::from_raw(entry.action_id)?,
+                }
+            }
+            CLAP_CONTEXT_MENU_ITEM_CHECK_ENTRY => {
+                let entry = &*(data as *const clap_context_menu_check_entry);
+                ContextMenuItem::CheckEntry {
+                    label: label(entry.label)?,
+                    is_enabled: entry.is_enabled,
+                    is_checked: entry.is_checked,
+                    action_id: ClapId::from_raw(entry.action_id)?,
+                }
+            }
+            CLAP_CONTEXT_MENU_ITEM_SEPARATOR => ContextMenuItem::Separator,
+            CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU => {
+                let submenu = &*(data as *const clap_context_menu_submenu);
+                ContextMenuItem::BeginSubmenu {
+                    label: label(submenu.label)?,
+                    is_enabled: submenu.is_enabled,
+                }
+            }
+            CLAP_CONTEXT_MENU_ITEM_END_SUBMENU => ContextMenuItem::EndSubmenu,
+            CLAP_CONTEXT_MENU_ITEM_TITLE => {
+                let title = &*(data as *const clap_context_menu_item_title);
+                ContextMenuItem::Title {
+                    title: label(title.title)?,
+                    is_enabled: title.is_enabled,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(item)
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn add_item(
+    builder: *const clap_context_menu_builder,
+    item_kind: clap_context_menu_item_kind,
+    item_data: *const c_void,
+) -> bool {
+    let Some(builder) = builder.as_ref() else {
+        return false;
+    };
+    let Some(collector) = (builder.ctx as *mut MenuCollector).as_mut() else {
+        return false;
+    };
+
+    // Only separators and submenu ends don't carry any data.
+    let needs_data = !matches!(
+        item_kind,
+        CLAP_CONTEXT_MENU_ITEM_SEPARATOR | CLAP_CONTEXT_MENU_ITEM_END_SUBMENU
+    );
+
+    let item = if needs_data && item_data.is_null() {
+        None
+    } else {
+        MenuCollector::read_item(item_kind, item_data)
+    };
+
+    let result = match item {
+        Some(item) => collector.menu.push(item),
+        None => Err(ContextMenuError::InvalidItem),
+    };
+
+    match result {
+        Ok(()) => {
+            collector.min_depth = collector.min_depth.min(collector.menu.depth());
+            true
+        }
+        Err(e) => {
+            collector.error.get_or_insert(e);
+            false
+        }
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn supports(
+    _builder: *const clap_context_menu_builder,
+    item_kind: clap_context_menu_item_kind,
+) -> bool {
+    matches!(
+        item_kind,
+        CLAP_CONTEXT_MENU_ITEM_ENTRY
+            | CLAP_CONTEXT_MENU_ITEM_CHECK_ENTRY
+            | CLAP_CONTEXT_MENU_ITEM_SEPARATOR
+            | CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU
+            | CLAP_CONTEXT_MENU_ITEM_END_SUBMENU
+            | CLAP_CONTEXT_MENU_ITEM_TITLE
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tracks_submenus_ended_by_the_plugin() {
+        let label = CStr::from_bytes_with_nul(b"Item\0").unwrap();
+
+        let mut menu = ContextMenu::new();
+        menu.push(ContextMenuItem::BeginSubmenu {
+            label: label.to_owned(),
+            is_enabled: true,
+        })
+        .unwrap();
+
+        let mut collector = MenuCollector {
+            min_depth: menu.depth(),
+            menu: &mut menu,
+            error: None,
+        };
+
+        let builder = clap_context_menu_builder {
+            ctx: &mut collector as *mut MenuCollector as *mut c_void,
+            add_item: Some(add_item),
+            supports: Some(supports),
+        };
+
+        let submenu = clap_context_menu_submenu {
+            label: label.as_ptr(),
+            is_enabled: true,
+        };
+
+        // The plugin closes the host's submenu, and opens another one in its place.
+        // SAFETY: the builder and item data are valid for the duration of the calls
+        unsafe {
+            assert!(add_item(
+                &builder,
+                CLAP_CONTEXT_MENU_ITEM_END_SUBMENU,
+                core::ptr::null()
+            ));
+            assert!(add_item(
+                &builder,
+                CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU,
+                &submenu as *const _ as *const c_void
+            ));
+        }
+
+        assert_eq!(collector.menu.depth(), 1);
+        assert_eq!(collector.min_depth, 0);
+    }
+}
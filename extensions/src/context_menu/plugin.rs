@@ -0,0 +1,419 @@
+use super::*;
+use clack_plugin::extensions::prelude::*;
+use clap_sys::id::clap_id;
+use std::ffi::c_void;
+
+/// A builder to populate a context menu, as requested by the host.
+///
+/// Items are first collected in order, and then emitted to the host all at once, after
+/// [`PluginContextMenuImpl::populate`] returns. All submenus must have been ended by then, and
+/// no submenu must have been ended while none was open, otherwise the menu is not sent to the host
+/// and an error is returned to it.
+pub struct ContextMenuBuilder {
+    menu: ContextMenu,
+    has_unexpected_submenu_end: bool,
+}
+
+impl ContextMenuBuilder {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            menu: ContextMenu::new(),
+            has_unexpected_submenu_end: false,
+        }
+    }
+
+    /// Adds a clickable entry with the given `label`, which triggers the given `action_id` when
+    /// clicked.
+    #[inline]
+    pub fn entry(&mut self, label: &CStr, action_id: ClapId, is_enabled: bool) -> &mut Self {
+        self.push(ContextMenuItem::Entry {
+            label: label.to_owned(),
+            is_enabled,
+            action_id,
+        })
+    }
+
+    /// Adds a clickable entry with a check mark, which triggers the given `action_id` when
+    /// clicked.
+    #[inline]
+    pub fn check_item(
+        &mut self,
+        label: &CStr,
+        action_id: ClapId,
+        is_enabled: bool,
+        is_checked: bool,
+    ) -> &mut Self {
+        self.push(ContextMenuItem::CheckEntry {
+            label: label.to_owned(),
+            is_enabled,
+            is_checked,
+            action_id,
+        })
+    }
+
+    /// Adds a separator line.
+    #[inline]
+    pub fn separator(&mut self) -> &mut Self {
+        self.push(ContextMenuItem::Separator)
+    }
+
+    /// Adds a non-clickable title.
+    #[inline]
+    pub fn title(&mut self, title: &CStr, is_enabled: bool) -> &mut Self {
+        self.push(ContextMenuItem::Title {
+            title: title.to_owned(),
+            is_enabled,
+        })
+    }
+
+    /// Starts a new submenu with the given `label`.
+    ///
+    /// All the following items will be added to this submenu, until
+    /// [`submenu_end`](Self::submenu_end) is called.
+    #[inline]
+    pub fn submenu_begin(&mut self, label: &CStr, is_enabled: bool) -> &mut Self {
+        self.push(ContextMenuItem::BeginSubmenu {
+            label: label.to_owned(),
+            is_enabled,
+        })
+    }
+
+    /// Ends the current submenu.
+    ///
+    /// If no submenu is currently open, this does nothing, and the whole menu will be rejected
+    /// once [`PluginContextMenuImpl::populate`] returns.
+    #[inline]
+    pub fn submenu_end(&mut self) -> &mut Self {
+        if self.menu.push(ContextMenuItem::EndSubmenu).is_err() {
+            self.has_unexpected_submenu_end = true;
+        }
+
+        self
+    }
+
+    /// Returns the menu that has been built so far.
+    #[inline]
+    pub fn menu(&self) -> &ContextMenu {
+        &self.menu
+    }
+
+    #[inline]
+    fn push(&mut self, item: ContextMenuItem) -> &mut Self {
+        // Only EndSubmenu items can fail to be pushed, and those go through submenu_end
+        let _ = self.menu.push(item);
+        self
+    }
+
+    /// # Safety
+    ///
+    /// The given builder must be valid.
+    unsafe fn emit(&self, builder: &clap_context_menu_builder) -> Result<(), PluginWrapperError> {
+        if self.has_unexpected_submenu_end {
+            return Err(PluginWrapperError::Plugin(
+                ContextMenuError::UnexpectedSubmenuEnd.into(),
+            ));
+        }
+
+        if !self.menu.is_complete() {
+            return Err(PluginWrapperError::Plugin(
+                ContextMenuError::UnclosedSubmenu.into(),
+            ));
+        }
+
+        let Some(add_item) = builder.add_item else {
+            return Err(PluginWrapperError::NulPtr(
+                "clap_context_menu_builder.add_item",
+            ));
+        };
+
+        let supports = |kind| {
+            builder
+                .supports
+                .map_or(true, |supports| supports(builder, kind))
+        };
+
+        // The depth of the unsupported submenu currently being skipped, if any.
+        let mut skipped_depth = 0usize;
+
+        for item in self.menu.items() {
+            let kind = item.raw_kind();
+
+            if skipped_depth > 0 {
+                match item {
+                    ContextMenuItem::BeginSubmenu { .. } => skipped_depth += 1,
+                    ContextMenuItem::EndSubmenu => skipped_depth -= 1,
+                    _ => {}
+                }
+
+                continue;
+            }
+
+            match item {
+                // Skip whole submenus (including their contents and matching end) if the host
+                // can't both begin and end them, to keep the emitted menu balanced.
+                ContextMenuItem::BeginSubmenu { .. }
+                    if !supports(CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU)
+                        || !supports(CLAP_CONTEXT_MENU_ITEM_END_SUBMENU) =>
+                {
+                    skipped_depth = 1;
+                    continue;
+                }
+                // Ends of emitted submenus are always supported, as checked above.
+                ContextMenuItem::EndSubmenu => {}
+                _ if !supports(kind) => continue,
+                _ => {}
+            }
+
+            let success = match item {
+                ContextMenuItem::Entry {
+                    label,
+                    is_enabled,
+                    action_id,
+                } => {
+                    let entry = clap_context_menu_entry {
+                        label: label.as_ptr(),
+                        is_enabled: *is_enabled,
+                        action_id: action_id.get(),
+                    };
+                    add_item(builder, kind, &entry as *const _ as *const c_void)
+                }
+                ContextMenuItem::CheckEntry {
+                    label,
+                    is_enabled,
+                    is_checked,
+                    action_id,
+                } => {
+                    let entry = clap_context_menu_check_entry {
+                        label: label.as_ptr(),
+                        is_enabled: *is_enabled,
+                        is_checked: *is_checked,
+                        action_id: action_id.get(),
+                    };
+                    add_item(builder, kind, &entry as *const _ as *const c_void)
+                }
+                ContextMenuItem::BeginSubmenu { label, is_enabled } => {
+                    let submenu = clap_context_menu_submenu {
+                        label: label.as_ptr(),
+                        is_enabled: *is_enabled,
+                    };
+                    add_item(builder, kind, &submenu as *const _ as *const c_void)
+                }
+                ContextMenuItem::Title { title, is_enabled } => {
+                    let title = clap_context_menu_item_title {
+                        title: title.as_ptr(),
+                        is_enabled: *is_enabled,
+                    };
+                    add_item(builder, kind, &title as *const _ as *const c_void)
+                }
+                ContextMenuItem::Separator | ContextMenuItem::EndSubmenu => {
+                    add_item(builder, kind, core::ptr::null())
+                }
+            };
+
+            if !success {
+                return Err(PluginWrapperError::Plugin(ContextMenuError::Failed.into()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Implementation of the Plugin-side of the Context Menu extension.
+pub trait PluginContextMenuImpl {
+    /// Populates the context menu for the given `target`, using the given `builder`.
+    ///
+    /// # Errors
+    ///
+    /// This may return an error if the plugin failed to populate the menu.
+    fn populate(
+        &mut self,
+        target: ContextMenuTarget,
+        builder: &mut ContextMenuBuilder,
+    ) -> Result<(), PluginError>;
+
+    /// Performs the action matching the given `action_id`, for the given `target`.
+    ///
+    /// # Errors
+    ///
+    /// This may return an error if the plugin failed to perform the action.
+    fn perform(&mut self, target: ContextMenuTarget, action_id: ClapId) -> Result<(), PluginError>;
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginContextMenu
+where
+    for<'a> P::MainThread<'a>: PluginContextMenuImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_context_menu {
+            populate: Some(populate::<P>),
+            perform: Some(perform::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn populate<P: Plugin>(
+    plugin: *const clap_plugin,
+    target: *const clap_context_menu_target,
+    builder: *const clap_context_menu_builder,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginContextMenuImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        let target = target
+            .as_ref()
+            .ok_or(PluginWrapperError::NulPtr("clap_context_menu_target"))?;
+        let target = ContextMenuTarget::from_raw(target).ok_or(
+            PluginWrapperError::InvalidParameter("clap_context_menu_target"),
+        )?;
+        let raw_builder = builder
+            .as_ref()
+            .ok_or(PluginWrapperError::NulPtr("clap_context_menu_builder"))?;
+
+        let mut builder = ContextMenuBuilder::new();
+        p.main_thread().as_mut().populate(target, &mut builder)?;
+
+        builder.emit(raw_builder)
+    })
+    .is_some()
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn perform<P: Plugin>(
+    plugin: *const clap_plugin,
+    target: *const clap_context_menu_target,
+    action_id: clap_id,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginContextMenuImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        let target = target
+            .as_ref()
+            .ok_or(PluginWrapperError::NulPtr("clap_context_menu_target"))?;
+        let target = ContextMenuTarget::from_raw(target).ok_or(
+            PluginWrapperError::InvalidParameter("clap_context_menu_target"),
+        )?;
+        let action_id =
+            ClapId::from_raw(action_id).ok_or(PluginWrapperError::InvalidParameter("action_id"))?;
+
+        p.main_thread().as_mut().perform(target, action_id)?;
+        Ok(())
+    })
+    .is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct TestHost {
+        unsupported: clap_context_menu_item_kind,
+        emitted: Vec<clap_context_menu_item_kind>,
+    }
+
+    #[allow(clippy::missing_safety_doc)]
+    unsafe extern "C" fn add_item(
+        builder: *const clap_context_menu_builder,
+        item_kind: clap_context_menu_item_kind,
+        _item_data: *const c_void,
+    ) -> bool {
+        let host = &mut *((*builder).ctx as *mut TestHost);
+        host.emitted.push(item_kind);
+        true
+    }
+
+    #[allow(clippy::missing_safety_doc)]
+    unsafe extern "C" fn supports(
+        builder: *const clap_context_menu_builder,
+        item_kind: clap_context_menu_item_kind,
+    ) -> bool {
+        let host = &*((*builder).ctx as *const TestHost);
+        item_kind != host.unsupported
+    }
+
+    fn emit_without(unsupported: clap_context_menu_item_kind) -> Vec<clap_context_menu_item_kind> {
+        let label = CStr::from_bytes_with_nul(b"Item\0").unwrap();
+
+        let mut menu = ContextMenuBuilder::new();
+        menu.entry(label, ClapId::new(1), true)
+            .submenu_begin(label, true)
+            .entry(label, ClapId::new(2), true)
+            .submenu_begin(label, true)
+            .separator()
+            .submenu_end()
+            .submenu_end()
+            .separator();
+
+        let mut host = TestHost {
+            unsupported,
+            emitted: Vec::new(),
+        };
+
+        let builder = clap_context_menu_builder {
+            ctx: &mut host as *mut TestHost as *mut c_void,
+            add_item: Some(add_item),
+            supports: Some(supports),
+        };
+
+        // SAFETY: the builder is valid for the duration of the call
+        unsafe { menu.emit(&builder) }.unwrap();
+
+        host.emitted
+    }
+
+    #[test]
+    fn skips_unsupported_items() {
+        assert_eq!(
+            emit_without(CLAP_CONTEXT_MENU_ITEM_SEPARATOR),
+            [
+                CLAP_CONTEXT_MENU_ITEM_ENTRY,
+                CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU,
+                CLAP_CONTEXT_MENU_ITEM_ENTRY,
+                CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU,
+                CLAP_CONTEXT_MENU_ITEM_END_SUBMENU,
+                CLAP_CONTEXT_MENU_ITEM_END_SUBMENU,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unexpected_submenu_end() {
+        let label = CStr::from_bytes_with_nul(b"Item\0").unwrap();
+
+        let mut menu = ContextMenuBuilder::new();
+        menu.entry(label, ClapId::new(1), true)
+            .submenu_end()
+            .separator();
+
+        let mut host = TestHost {
+            unsupported: CLAP_CONTEXT_MENU_ITEM_TITLE,
+            emitted: Vec::new(),
+        };
+
+        let builder = clap_context_menu_builder {
+            ctx: &mut host as *mut TestHost as *mut c_void,
+            add_item: Some(add_item),
+            supports: Some(supports),
+        };
+
+        // SAFETY: the builder is valid for the duration of the call
+        assert!(unsafe { menu.emit(&builder) }.is_err());
+        assert!(host.emitted.is_empty());
+    }
+
+    #[test]
+    fn skips_whole_submenus_if_either_end_is_unsupported() {
+        let expected = [
+            CLAP_CONTEXT_MENU_ITEM_ENTRY,
+            CLAP_CONTEXT_MENU_ITEM_SEPARATOR,
+        ];
+
+        assert_eq!(emit_without(CLAP_CONTEXT_MENU_ITEM_BEGIN_SUBMENU), expected);
+        assert_eq!(emit_without(CLAP_CONTEXT_MENU_ITEM_END_SUBMENU), expected);
+    }
+}
@@ -19,25 +19,53 @@ unsafe impl Extension for HostEventRegistry {
 
 #[cfg(feature = "clack-plugin")]
 const _: () = {
-    use clack_common::events::spaces::{EventSpace, EventSpaceId};
+    use clack_common::events::spaces::{CustomEventSpace, EventSpace, EventSpaceId};
     use clack_plugin::host::HostMainThreadHandle;
 
     impl HostEventRegistry {
-        pub fn query<'a, S: EventSpace<'a>>(
+        /// Queries the host for the ID of the event space with the given name.
+        ///
+        /// This returns `None` if the host does not support the given event space.
+        pub fn query_space(
             &self,
             host: &HostMainThreadHandle,
-        ) -> Option<EventSpaceId<S>> {
+            name: &CStr,
+        ) -> Option<EventSpaceId> {
             let mut out = u16::MAX;
             let success =
                 // SAFETY: This type ensures the function pointer is valid.
-                unsafe { host.use_extension(&self.0).query?(host.as_raw(), S::NAME.as_ptr(), &mut out) };
+                unsafe { host.use_extension(&self.0).query?(host.as_raw(), name.as_ptr(), &mut out) };
 
             if !success {
                 return None;
             };
 
+            EventSpaceId::new(out)
+        }
+
+        pub fn query<'a, S: EventSpace<'a>>(
+            &self,
+            host: &HostMainThreadHandle,
+        ) -> Option<EventSpaceId<S>> {
+            let id = self.query_space(host, S::NAME)?;
+
             // SAFETY: the EventSpaceId has been fetched from S's name.
-            unsafe { Some(EventSpaceId::new(out)?.into_unchecked()) }
+            unsafe { Some(id.into_unchecked()) }
+        }
+
+        /// Queries the host for the ID of the event space of the given [`CustomEventSpace`] type.
+        ///
+        /// The returned ID is not tied to any lifetime, which allows plugins to query it once
+        /// (e.g. during initialization), and then cache it for as long as the plugin instance
+        /// lives.
+        ///
+        /// This returns `None` if the host does not support the given event space.
+        #[inline]
+        pub fn query_custom<E: CustomEventSpace + 'static>(
+            &self,
+            host: &HostMainThreadHandle,
+        ) -> Option<EventSpaceId<&'static E>> {
+            self.query(host)
         }
     }
 };
@@ -88,7 +116,7 @@ mod host {
         let result = HostWrapper::<H>::handle(host, |host| {
             let space_name = CStr::from_ptr(space_name);
 
-            let result = host.main_thread().as_ref().query(space_name);
+            let result = host.main_thread()?.as_ref().query(space_name);
             *space_id = EventSpaceId::optional_id(&result);
 
             Ok(result.is_some())
@@ -2,6 +2,12 @@ use clack_common::extensions::*;
 use clap_sys::ext::latency::{clap_host_latency, clap_plugin_latency, CLAP_EXT_LATENCY};
 use std::ffi::CStr;
 
+#[cfg(feature = "clack-plugin")]
+mod reporter;
+
+#[cfg(feature = "clack-plugin")]
+pub use reporter::LatencyReporter;
+
 #[derive(Copy, Clone)]
 #[allow(dead_code)]
 pub struct PluginLatency(RawExtension<PluginExtensionSide, clap_plugin_latency>);
@@ -69,7 +75,7 @@ mod host {
         for<'a> <H as HostHandlers>::MainThread<'a>: HostLatencyImpl,
     {
         HostWrapper::<H>::handle(host, |host| {
-            host.main_thread().as_mut().changed();
+            host.main_thread()?.as_mut().changed();
             Ok(())
         });
     }
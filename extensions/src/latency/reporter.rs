@@ -0,0 +1,125 @@
+use super::HostLatency;
+use clack_plugin::prelude::{HostMainThreadHandle, HostSharedHandle};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A helper to keep track of a plugin's latency, and to report its changes to the host.
+///
+/// This holds both the latency currently reported to the host, which is what
+/// [`PluginLatencyImpl::get`](super::PluginLatencyImpl::get) implementations should return, and
+/// the latency the plugin would like to report next.
+///
+/// # Changing the latency
+///
+/// As per the CLAP specification, the latency reported to the host may only change while the
+/// plugin is being activated. Changing it (e.g. because of a "lookahead" parameter) therefore
+/// requires the following steps:
+///
+/// 1. The plugin calls [`set`](Self::set) with the new latency (e.g. when the parameter changes).
+///    This requests the host to restart the plugin, i.e. to deactivate and then re-activate it.
+///    The reported latency doesn't change yet.
+/// 2. The host deactivates the plugin, and then activates it again.
+/// 3. During activation, the plugin calls [`apply_pending`](Self::apply_pending). This updates
+///    the reported latency, and notifies the host that it changed.
+/// 4. The host queries the new latency, and adjusts its delay compensation accordingly.
+///
+/// If the plugin is deactivated when [`set`](Self::set) is called, the host may choose to not
+/// activate it right away. The new latency is then applied on the next activation.
+///
+/// # Example
+///
+/// ```
+/// use clack_extensions::latency::{LatencyReporter, PluginLatencyImpl};
+/// use clack_plugin::prelude::*;
+///
+/// pub struct MyPluginShared<'a> {
+///     latency: LatencyReporter<'a>,
+/// }
+///
+/// pub struct MyPluginMainThread<'a> {
+///     shared: &'a MyPluginShared<'a>,
+/// }
+///
+/// impl PluginLatencyImpl for MyPluginMainThread<'_> {
+///     fn get(&mut self) -> u32 {
+///         self.shared.latency.get()
+///     }
+/// }
+///
+/// impl MyPluginMainThread<'_> {
+///     fn set_lookahead(&mut self, lookahead_samples: u32) {
+///         self.shared.latency.set(lookahead_samples);
+///     }
+/// }
+/// ```
+pub struct LatencyReporter<'a> {
+    host: HostSharedHandle<'a>,
+    reported: AtomicU32,
+    pending: AtomicU32,
+}
+
+impl<'a> LatencyReporter<'a> {
+    /// Creates a new latency reporter for the given host, with the given initial latency, in
+    /// samples.
+    #[inline]
+    pub fn new(host: HostSharedHandle<'a>, initial_latency: u32) -> Self {
+        Self {
+            host,
+            reported: AtomicU32::new(initial_latency),
+            pending: AtomicU32::new(initial_latency),
+        }
+    }
+
+    /// Returns the latency currently reported to the host, in samples.
+    ///
+    /// This is the value [`PluginLatencyImpl::get`](super::PluginLatencyImpl::get)
+    /// implementations should return. It only changes when
+    /// [`apply_pending`](Self::apply_pending) is called.
+    #[inline]
+    pub fn get(&self) -> u32 {
+        self.reported.load(Ordering::Acquire)
+    }
+
+    /// Sets the new latency of the plugin, in samples.
+    ///
+    /// If it differs from the currently reported latency, this requests the host to restart the
+    /// plugin, so that the new latency can be applied by [`apply_pending`](Self::apply_pending)
+    /// during the next activation.
+    ///
+    /// This method is thread-safe, and can be called from any thread.
+    pub fn set(&self, samples: u32) {
+        let previous = self.pending.swap(samples, Ordering::AcqRel);
+
+        if samples != previous && samples != self.get() {
+            self.host.request_restart();
+        }
+    }
+
+    /// Returns `true` if a new latency was [`set`](Self::set), but wasn't
+    /// [applied](Self::apply_pending) yet.
+    #[inline]
+    pub fn is_change_pending(&self) -> bool {
+        self.pending.load(Ordering::Acquire) != self.get()
+    }
+
+    /// Applies the latency given to [`set`](Self::set), and notifies the host that it changed.
+    ///
+    /// This must only be called while the plugin is being activated, i.e. in
+    /// [`PluginAudioProcessor::activate`](clack_plugin::plugin::PluginAudioProcessor::activate).
+    ///
+    /// This returns `true` if the latency changed, or `false` if there was no change to apply, in
+    /// which case the host isn't notified.
+    pub fn apply_pending(&self, host: &mut HostMainThreadHandle) -> bool {
+        let pending = self.pending.load(Ordering::Acquire);
+        let previous = self.reported.swap(pending, Ordering::AcqRel);
+
+        if pending == previous {
+            return false;
+        }
+
+        if let Some(latency) = host.get_extension::<HostLatency>() {
+            latency.changed(host);
+        }
+
+        true
+    }
+}
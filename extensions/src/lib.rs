@@ -1,10 +1,18 @@
 #![doc(html_logo_url = "https://raw.githubusercontent.com/prokopyl/clack/main/logo.svg")]
 #![deny(clippy::undocumented_unsafe_blocks)]
 
+#[cfg(feature = "ambisonic")]
+pub mod ambisonic;
 #[cfg(feature = "audio-ports")]
 pub mod audio_ports;
+#[cfg(feature = "audio-ports-activation")]
+pub mod audio_ports_activation;
 #[cfg(feature = "audio-ports-config")]
 pub mod audio_ports_config;
+#[cfg(feature = "configurable-audio-ports")]
+pub mod configurable_audio_ports;
+#[cfg(feature = "context-menu")]
+pub mod context_menu;
 #[cfg(feature = "event-registry")]
 pub mod event_registry;
 #[cfg(feature = "gui")]
@@ -17,14 +25,24 @@ pub mod log;
 pub mod note_name;
 #[cfg(feature = "note-ports")]
 pub mod note_ports;
+#[cfg(feature = "param-indication")]
+pub mod param_indication;
 #[cfg(feature = "params")]
 pub mod params;
 #[cfg(all(unix, feature = "posix-fd"))]
 pub mod posix_fd;
+#[cfg(feature = "preset-load")]
+pub mod preset_load;
+#[cfg(feature = "remote-controls")]
+pub mod remote_controls;
 #[cfg(feature = "render")]
 pub mod render;
 #[cfg(feature = "state")]
 pub mod state;
+#[cfg(feature = "state-context")]
+pub mod state_context;
+#[cfg(feature = "surround")]
+pub mod surround;
 #[cfg(feature = "tail")]
 pub mod tail;
 #[cfg(feature = "thread-check")]
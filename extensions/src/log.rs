@@ -12,6 +12,12 @@ mod host;
 #[cfg(feature = "clack-host")]
 pub use host::*;
 
+#[cfg(all(feature = "clack-host", feature = "log-forwarder"))]
+mod forwarder;
+
+#[cfg(all(feature = "clack-host", feature = "log-forwarder"))]
+pub use forwarder::LogForwarder;
+
 #[repr(i32)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum LogSeverity {
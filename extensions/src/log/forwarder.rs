@@ -0,0 +1,88 @@
+use super::host::HostLogImpl;
+use super::LogSeverity;
+
+/// A [`HostLogImpl`] implementation that forwards all plugin log messages to the
+/// [`log`](https://docs.rs/log) crate facade.
+///
+/// Messages are emitted with a `clap::<plugin-id>` target, so that they can be filtered per
+/// plugin using the host's logger configuration.
+///
+/// CLAP log severities are mapped to [`log::Level`]s as follows:
+///
+/// * [`LogSeverity::Debug`] to [`log::Level::Debug`];
+/// * [`LogSeverity::Info`] to [`log::Level::Info`];
+/// * [`LogSeverity::Warning`] to [`log::Level::Warn`];
+/// * [`LogSeverity::Error`] and [`LogSeverity::Fatal`] to [`log::Level::Error`].
+///
+/// Messages with the [`LogSeverity::HostMisbehaving`] or [`LogSeverity::PluginMisbehaving`]
+/// severities are also emitted as [`log::Level::Error`], but are prefixed with
+/// `"[host misbehaving]"` or `"[plugin misbehaving]"`, respectively.
+///
+/// # Example
+///
+/// ```
+/// use clack_extensions::log::{HostLogImpl, LogForwarder, LogSeverity};
+///
+/// struct MyHostShared {
+///     logger: LogForwarder,
+/// }
+///
+/// impl HostLogImpl for MyHostShared {
+///     fn log(&self, severity: LogSeverity, message: &str) {
+///         self.logger.log(severity, message)
+///     }
+/// }
+///
+/// let shared = MyHostShared {
+///     logger: LogForwarder::new("com.u-he.diva"),
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogForwarder {
+    target: String,
+}
+
+impl LogForwarder {
+    /// Creates a new log forwarder for the plugin with the given ID.
+    pub fn new(plugin_id: &str) -> Self {
+        Self {
+            target: format!("clap::{plugin_id}"),
+        }
+    }
+
+    /// Returns the target all log messages are emitted with.
+    #[inline]
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns the [`log::Level`] messages of the given severity are emitted with.
+    pub fn level(severity: LogSeverity) -> log::Level {
+        match severity {
+            LogSeverity::Debug => log::Level::Debug,
+            LogSeverity::Info => log::Level::Info,
+            LogSeverity::Warning => log::Level::Warn,
+            LogSeverity::Error
+            | LogSeverity::Fatal
+            | LogSeverity::HostMisbehaving
+            | LogSeverity::PluginMisbehaving => log::Level::Error,
+        }
+    }
+}
+
+impl HostLogImpl for LogForwarder {
+    fn log(&self, severity: LogSeverity, message: &str) {
+        let level = Self::level(severity);
+        let target = self.target.as_str();
+
+        match severity {
+            LogSeverity::HostMisbehaving => {
+                log::log!(target: target, level, "[host misbehaving] {message}")
+            }
+            LogSeverity::PluginMisbehaving => {
+                log::log!(target: target, level, "[plugin misbehaving] {message}")
+            }
+            _ => log::log!(target: target, level, "{message}"),
+        }
+    }
+}
@@ -83,6 +83,84 @@ impl<'a> NoteName<'a> {
     }
 }
 
+/// An owned [`NoteName`], as returned by `PluginNoteName::names`.
+///
+/// Any of the `port`, `channel` and `key` fields can be set to [`Match::All`], meaning the name
+/// applies to all ports, channels or keys, respectively. For example, a drum machine plugin could
+/// provide the name `"Kick"` for key `36` on all channels.
+///
+/// When multiple entries match the same note, hosts should prefer the most specific one, i.e. the
+/// one with the fewest wildcards. See [`NoteNameEntry::matches`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct NoteNameEntry {
+    /// A user-facing display name for the note.
+    pub name: Vec<u8>,
+
+    /// The Port this note name applies to, or [`Match::All`] if it applies to every port.
+    pub port: Match<u16>,
+
+    /// The MIDI Channel this note name applies to, or [`Match::All`] if it applies to every channel.
+    pub channel: Match<u16>,
+
+    /// The Key this note name applies to, or [`Match::All`] if it applies to every key.
+    pub key: Match<u16>,
+}
+
+impl NoteNameEntry {
+    /// Returns `true` if this note name applies to the note on the given port, channel and key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_common::events::Match;
+    /// use clack_extensions::note_name::NoteNameEntry;
+    ///
+    /// let kick = NoteNameEntry {
+    ///     name: b"Kick".to_vec(),
+    ///     port: Match::All,
+    ///     channel: Match::All,
+    ///     key: Match::Specific(36),
+    /// };
+    ///
+    /// assert!(kick.matches(0, 9, 36));
+    /// assert!(!kick.matches(0, 9, 38));
+    /// ```
+    pub fn matches(&self, port: u16, channel: u16, key: u16) -> bool {
+        fn matches_value(matcher: Match<u16>, value: u16) -> bool {
+            match matcher {
+                Match::All => true,
+                Match::Specific(v) => v == value,
+            }
+        }
+
+        matches_value(self.port, port)
+            && matches_value(self.channel, channel)
+            && matches_value(self.key, key)
+    }
+
+    /// Returns the number of wildcards ([`Match::All`]) in this entry's port, channel and key.
+    ///
+    /// This can be used to find the most specific entry among multiple matching ones.
+    pub fn wildcard_count(&self) -> usize {
+        [self.port, self.channel, self.key]
+            .iter()
+            .filter(|m| m.is_all())
+            .count()
+    }
+}
+
+impl From<NoteName<'_>> for NoteNameEntry {
+    #[inline]
+    fn from(name: NoteName<'_>) -> Self {
+        Self {
+            name: name.name.to_vec(),
+            port: name.port,
+            channel: name.channel,
+            key: name.key,
+        }
+    }
+}
+
 #[cfg(feature = "clack-host")]
 mod host;
 
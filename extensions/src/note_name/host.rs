@@ -56,6 +56,22 @@ impl PluginNoteName {
             None
         }
     }
+
+    /// Retrieves all of the plugin's [`NoteName`]s, as owned [`NoteNameEntry`] values.
+    ///
+    /// Entries the plugin fails to provide are skipped. See the [`NoteNameEntry`] documentation
+    /// for how to interpret entries that apply to all ports, channels or keys.
+    pub fn names(&self, plugin: &mut PluginMainThreadHandle) -> Vec<NoteNameEntry> {
+        let count = self.count(plugin);
+        let mut buffer = NoteNameBuffer::new();
+
+        (0..count)
+            .filter_map(|index| {
+                self.get(plugin, index, &mut buffer)
+                    .map(NoteNameEntry::from)
+            })
+            .collect()
+    }
 }
 
 /// Implementation of the Host-side of the Note Name extension.
@@ -83,7 +99,7 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostNoteNameImpl,
 {
     HostWrapper::<H>::handle(host, |host| {
-        host.main_thread().as_mut().changed();
+        host.main_thread()?.as_mut().changed();
 
         Ok(())
     });
@@ -50,10 +50,127 @@ impl PluginNotePorts {
             None
         }
     }
+
+    /// Queries all of the plugin's input and output note ports at once, and returns them as an
+    /// owned [`NotePortsInfo`].
+    ///
+    /// Ports the plugin fails to provide information for are skipped.
+    pub fn layout(&self, plugin: &mut PluginMainThreadHandle) -> NotePortsInfo {
+        NotePortsInfo {
+            inputs: self.owned_ports(plugin, true),
+            outputs: self.owned_ports(plugin, false),
+        }
+    }
+
+    /// Updates the given [`NotePortsInfo`], following a
+    /// [`rescan`](HostNotePortsImpl::rescan) request from the plugin with the given `flags`.
+    ///
+    /// If the [`ALL`](NotePortRescanFlags::ALL) flag is set, the whole layout is queried again.
+    /// Otherwise, if the [`NAMES`](NotePortRescanFlags::NAMES) flag is set, only the names of
+    /// the ports are updated, matching them by ID.
+    pub fn update_layout(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        layout: &mut NotePortsInfo,
+        flags: NotePortRescanFlags,
+    ) {
+        if flags.contains(NotePortRescanFlags::ALL) {
+            *layout = self.layout(plugin);
+        } else if flags.contains(NotePortRescanFlags::NAMES) {
+            let new_layout = self.layout(plugin);
+
+            Self::update_names(&mut layout.inputs, &new_layout.inputs);
+            Self::update_names(&mut layout.outputs, &new_layout.outputs);
+        }
+    }
+
+    fn update_names(ports: &mut [OwnedNotePortInfo], new_ports: &[OwnedNotePortInfo]) {
+        for port in ports {
+            if let Some(new_port) = new_ports.iter().find(|p| p.id == port.id) {
+                port.name.clone_from(&new_port.name);
+            }
+        }
+    }
+
+    fn owned_ports(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        is_input: bool,
+    ) -> Vec<OwnedNotePortInfo> {
+        let mut buffer = NotePortInfoBuffer::new();
+
+        (0..self.count(plugin, is_input))
+            .filter_map(|index| {
+                self.get(plugin, index, is_input, &mut buffer)
+                    .map(|info| OwnedNotePortInfo::from_info(&info))
+            })
+            .collect()
+    }
+}
+
+/// An owned version of [`NotePortInfo`], which doesn't borrow from a [`NotePortInfoBuffer`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OwnedNotePortInfo {
+    pub id: ClapId,
+    /// The port's name. Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    pub name: String,
+    pub supported_dialects: NoteDialects,
+    pub preferred_dialect: Option<NoteDialect>,
+}
+
+impl OwnedNotePortInfo {
+    /// Copies the given [`NotePortInfo`] into an owned structure.
+    pub fn from_info(info: &NotePortInfo) -> Self {
+        Self {
+            id: info.id,
+            name: String::from_utf8_lossy(info.name).into_owned(),
+            supported_dialects: info.supported_dialects,
+            preferred_dialect: info.preferred_dialect,
+        }
+    }
+
+    /// Picks the note dialect the host should use to send or receive events on this port, among
+    /// the given dialects the host supports.
+    ///
+    /// This returns the port's preferred dialect if the host supports it. Otherwise, this returns
+    /// the first dialect supported by both the port and the host, in this order: CLAP, MIDI 2.0,
+    /// MIDI MPE, then MIDI 1.0.
+    ///
+    /// This returns [`None`] if the port and the host have no dialect in common.
+    pub fn dialect_for(&self, host_dialects: NoteDialects) -> Option<NoteDialect> {
+        let common = self.supported_dialects & host_dialects;
+
+        if let Some(preferred) = self.preferred_dialect.filter(|d| common.supports(*d)) {
+            return Some(preferred);
+        }
+
+        [
+            NoteDialect::Clap,
+            NoteDialect::Midi2,
+            NoteDialect::MidiMpe,
+            NoteDialect::Midi,
+        ]
+        .into_iter()
+        .find(|d| common.supports(*d))
+    }
+}
+
+/// All the input and output note ports of a plugin, as returned by
+/// [`PluginNotePorts::layout`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct NotePortsInfo {
+    /// The plugin's input note ports, in order.
+    pub inputs: Vec<OwnedNotePortInfo>,
+    /// The plugin's output note ports, in order.
+    pub outputs: Vec<OwnedNotePortInfo>,
 }
 
 pub trait HostNotePortsImpl {
     fn supported_dialects(&self) -> NoteDialects;
+    /// Called when the plugin's note ports changed, as described by the given `flags`.
+    ///
+    /// Hosts keeping a [`NotePortsInfo`] around can refresh it using
+    /// [`PluginNotePorts::update_layout`].
     fn rescan(&mut self, flags: NotePortRescanFlags);
 }
 
@@ -75,7 +192,7 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostNotePortsImpl,
 {
     HostWrapper::<H>::handle(host, |host| {
-        Ok(host.main_thread().as_ref().supported_dialects().bits())
+        Ok(host.main_thread()?.as_ref().supported_dialects().bits())
     })
     .unwrap_or(0)
 }
@@ -86,7 +203,7 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostNotePortsImpl,
 {
     HostWrapper::<H>::handle(host, |host| {
-        host.main_thread()
+        host.main_thread()?
             .as_mut()
             .rescan(NotePortRescanFlags::from_bits_truncate(flag));
 
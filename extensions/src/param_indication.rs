@@ -0,0 +1,79 @@
+#![deny(missing_docs)]
+
+//! Allows hosts to inform plugins that their parameters are mapped to a physical control, or are
+//! being automated.
+//!
+//! Plugins can use this information to display an indication next to the matching parameters in
+//! their GUI, e.g. using the color the host provided.
+
+use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
+use clack_common::utils::Color;
+use clap_sys::ext::draft::param_indication::*;
+use std::ffi::CStr;
+
+/// The Plugin-side of the Param Indication extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginParamIndication(RawExtension<PluginExtensionSide, clap_plugin_param_indication>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginParamIndication {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_PARAM_INDICATION;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The automation state of a parameter, as indicated by the host.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum AutomationState {
+    /// The host does not have any automation for this parameter.
+    None = CLAP_PARAM_INDICATION_AUTOMATION_NONE,
+    /// The host has automation for this parameter, but it isn't playing.
+    Present = CLAP_PARAM_INDICATION_AUTOMATION_PRESENT,
+    /// The host is playing back automation for this parameter.
+    Playing = CLAP_PARAM_INDICATION_AUTOMATION_PLAYING,
+    /// The host is recording automation for this parameter.
+    Recording = CLAP_PARAM_INDICATION_AUTOMATION_RECORDING,
+    /// The host should play automation for this parameter, but the user has started to adjust it
+    /// and is overriding it.
+    Overriding = CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING,
+}
+
+impl AutomationState {
+    /// Gets an [`AutomationState`] from its raw, C-FFI compatible representation.
+    ///
+    /// This returns [`None`] if the given value does not match any known automation state.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Option<Self> {
+        use AutomationState::*;
+
+        match raw {
+            CLAP_PARAM_INDICATION_AUTOMATION_NONE => Some(None),
+            CLAP_PARAM_INDICATION_AUTOMATION_PRESENT => Some(Present),
+            CLAP_PARAM_INDICATION_AUTOMATION_PLAYING => Some(Playing),
+            CLAP_PARAM_INDICATION_AUTOMATION_RECORDING => Some(Recording),
+            CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING => Some(Overriding),
+            _ => Option::None,
+        }
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this automation state.
+    #[inline]
+    pub const fn to_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+#[cfg(feature = "clack-host")]
+mod host;
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
@@ -0,0 +1,70 @@
+use super::*;
+use clack_common::utils::ClapId;
+use clack_host::extensions::prelude::*;
+
+impl PluginParamIndication {
+    /// Informs the plugin whether the given parameter is mapped to a physical control, e.g. a
+    /// MIDI controller knob.
+    ///
+    /// The `color` is a hint for the plugin to use when displaying the mapping indication. If it
+    /// is [`None`], the plugin picks its own color.
+    ///
+    /// The `label` is a short name for the control the parameter is mapped to (e.g. `"Knob 3"`),
+    /// while the `description` can give more detail about the mapping (e.g. `"Pad Controller"`).
+    /// Both are ignored by the plugin if `has_mapping` is `false`.
+    pub fn set_mapping(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        param_id: ClapId,
+        has_mapping: bool,
+        color: Option<Color>,
+        label: Option<&CStr>,
+        description: Option<&CStr>,
+    ) {
+        let Some(set_mapping) = plugin.use_extension(&self.0).set_mapping else {
+            return;
+        };
+
+        let color = color.map(Color::to_raw);
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe {
+            set_mapping(
+                plugin.as_raw(),
+                param_id.get(),
+                has_mapping,
+                color.as_ref().map_or(core::ptr::null(), |c| c as *const _),
+                label.map_or(core::ptr::null(), CStr::as_ptr),
+                description.map_or(core::ptr::null(), CStr::as_ptr),
+            )
+        }
+    }
+
+    /// Informs the plugin of the automation state of the given parameter.
+    ///
+    /// The `color` is a hint for the plugin to use when displaying the automation indication. If
+    /// it is [`None`], the plugin picks its own color.
+    pub fn set_automation(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        param_id: ClapId,
+        state: AutomationState,
+        color: Option<Color>,
+    ) {
+        let Some(set_automation) = plugin.use_extension(&self.0).set_automation else {
+            return;
+        };
+
+        let color = color.map(Color::to_raw);
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe {
+            set_automation(
+                plugin.as_raw(),
+                param_id.get(),
+                state.to_raw(),
+                color.as_ref().map_or(core::ptr::null(), |c| c as *const _),
+            )
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use super::*;
+use clack_common::utils::ClapId;
+use clack_plugin::extensions::prelude::*;
+use clap_sys::color::clap_color;
+use clap_sys::id::clap_id;
+use std::ffi::c_char;
+
+/// Implementation of the Plugin-side of the Param Indication extension.
+pub trait PluginParamIndicationImpl {
+    /// Called when the host maps the given parameter to a physical control, or removes such a
+    /// mapping.
+    ///
+    /// The `color` is a hint for displaying the mapping indication, and may be [`None`] if the
+    /// host did not provide any. The `label` and `description` are only meaningful if
+    /// `has_mapping` is `true`.
+    fn set_mapping(
+        &mut self,
+        param_id: ClapId,
+        has_mapping: bool,
+        color: Option<Color>,
+        label: Option<&CStr>,
+        description: Option<&CStr>,
+    );
+
+    /// Called when the automation state of the given parameter changes.
+    ///
+    /// The `color` is a hint for displaying the automation indication, and may be [`None`] if
+    /// the host did not provide any.
+    fn set_automation(&mut self, param_id: ClapId, state: AutomationState, color: Option<Color>);
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginParamIndication
+where
+    for<'a> P::MainThread<'a>: PluginParamIndicationImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_param_indication {
+            set_mapping: Some(set_mapping::<P>),
+            set_automation: Some(set_automation::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_mapping<P: Plugin>(
+    plugin: *const clap_plugin,
+    param_id: clap_id,
+    has_mapping: bool,
+    color: *const clap_color,
+    label: *const c_char,
+    description: *const c_char,
+) where
+    for<'a> P::MainThread<'a>: PluginParamIndicationImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        let param_id = ClapId::from_raw(param_id)
+            .ok_or(PluginWrapperError::InvalidParameter("Invalid parameter ID"))?;
+
+        let color = color.as_ref().copied().map(Color::from_raw);
+        let label = (!label.is_null()).then(|| CStr::from_ptr(label));
+        let description = (!description.is_null()).then(|| CStr::from_ptr(description));
+
+        p.main_thread()
+            .as_mut()
+            .set_mapping(param_id, has_mapping, color, label, description);
+
+        Ok(())
+    });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_automation<P: Plugin>(
+    plugin: *const clap_plugin,
+    param_id: clap_id,
+    automation_state: u32,
+    color: *const clap_color,
+) where
+    for<'a> P::MainThread<'a>: PluginParamIndicationImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        let param_id = ClapId::from_raw(param_id)
+            .ok_or(PluginWrapperError::InvalidParameter("Invalid parameter ID"))?;
+        let state = AutomationState::from_raw(automation_state).ok_or(
+            PluginWrapperError::InvalidParameter("Invalid parameter automation state"),
+        )?;
+
+        let color = color.as_ref().copied().map(Color::from_raw);
+
+        p.main_thread()
+            .as_mut()
+            .set_automation(param_id, state, color);
+
+        Ok(())
+    });
+}
@@ -131,6 +131,53 @@ impl<'a> ParamInfo<'a> {
         })
     }
 
+    /// Clamps the given value to this parameter's `[min_value, max_value]` range.
+    ///
+    /// If this parameter is [stepped](ParamInfoFlags::IS_STEPPED), the value is also rounded to
+    /// the nearest integer.
+    ///
+    /// Plugins can use this to ensure the values they receive from e.g. `ParamValue` events are
+    /// always valid, as hosts may send values that are out of range.
+    ///
+    /// A `NaN` value is clamped to `min_value`. If `min_value` is greater than `max_value`, the
+    /// parameter's range is invalid and the result is unspecified, but this method never panics:
+    /// the bounds are applied as given, and `max_value` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_extensions::params::{ParamInfo, ParamInfoFlags};
+    /// use clack_common::utils::ClapId;
+    ///
+    /// let info = ParamInfo {
+    ///     id: ClapId::new(1),
+    ///     flags: ParamInfoFlags::IS_AUTOMATABLE | ParamInfoFlags::IS_STEPPED,
+    ///     cookie: Default::default(),
+    ///     name: b"Delay",
+    ///     module: b"",
+    ///     min_value: 0.0,
+    ///     max_value: 1000.0,
+    ///     default_value: 500.0,
+    /// };
+    ///
+    /// assert_eq!(info.clamp_value(-5.0), 0.0);
+    /// assert_eq!(info.clamp_value(420.4), 420.0);
+    /// assert_eq!(info.clamp_value(420.5), 421.0);
+    /// assert_eq!(info.clamp_value(1000.2), 1000.0);
+    /// assert_eq!(info.clamp_value(5000.0), 1000.0);
+    /// assert_eq!(info.clamp_value(f64::NAN), 0.0);
+    /// ```
+    pub fn clamp_value(&self, value: f64) -> f64 {
+        let value = if self.flags.contains(ParamInfoFlags::IS_STEPPED) {
+            value.round()
+        } else {
+            value
+        };
+
+        // f64::clamp would panic if min_value > max_value.
+        value.max(self.min_value).min(self.max_value)
+    }
+
     pub fn diff_for_rescan(&self, other: &ParamInfo) -> ParamRescanFlags {
         #[inline]
         fn flags_differ(
@@ -170,12 +217,25 @@ impl<'a> ParamInfo<'a> {
     }
 }
 
+mod ramp;
+pub use ramp::*;
+
 #[cfg(feature = "clack-host")]
 mod host;
 #[cfg(feature = "clack-host")]
 pub use host::*;
 
+#[cfg(feature = "clack-host")]
+mod snapshot;
+#[cfg(feature = "clack-host")]
+pub use snapshot::*;
+
 #[cfg(feature = "clack-plugin")]
 mod plugin;
 #[cfg(feature = "clack-plugin")]
 pub use plugin::*;
+
+#[cfg(feature = "clack-plugin")]
+mod store;
+#[cfg(feature = "clack-plugin")]
+pub use store::*;
@@ -128,6 +128,40 @@ impl PluginParams {
         }
     }
 
+    /// Reads the current values of all of the plugin's parameters, and formats each of them
+    /// into an owned [`String`] using the plugin's [`value_to_text`](Self::value_to_text).
+    ///
+    /// This returns the ID, value and text of each parameter, in the order the plugin declares
+    /// them. This is useful to display a generic parameter view for plugins that do not have a GUI.
+    ///
+    /// Parameters for which the plugin fails to provide a value are omitted. If the plugin fails
+    /// to format a value, the raw numeric value is used as its text instead. Text that is not
+    /// valid UTF-8 is decoded lossily.
+    pub fn formatted_values(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+    ) -> Vec<(ClapId, f64, String)> {
+        let mut info_buffer = ParamInfoBuffer::new();
+        let mut text_buffer = [MaybeUninit::uninit(); 256];
+
+        let ids: Vec<ClapId> = (0..self.count(plugin))
+            .filter_map(|index| Some(self.get_info(plugin, index, &mut info_buffer)?.id))
+            .collect();
+
+        ids.into_iter()
+            .filter_map(|id| {
+                let value = self.get_value(plugin, id)?;
+
+                let text = match self.value_to_text(plugin, id, value, &mut text_buffer) {
+                    Ok(text) => String::from_utf8_lossy(text).into_owned(),
+                    Err(_) => value.to_string(),
+                };
+
+                Some((id, value, text))
+            })
+            .collect()
+    }
+
     pub fn flush(
         &self,
         plugin: &mut PluginMainThreadHandle,
@@ -171,12 +205,24 @@ unsafe fn assume_init_slice<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
     &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
 }
 
+/// Implementation of the thread-safe part of the Host-side of the Params extension.
 pub trait HostParamsImplShared {
+    /// Requests the host to flush the plugin's parameters, either by calling the plugin's `flush`
+    /// method if it is inactive, or by calling `process` if it is active.
     fn request_flush(&self);
 }
 
+/// Implementation of the main-thread part of the Host-side of the Params extension.
 pub trait HostParamsImplMainThread {
+    /// Informs the host that the plugin's parameters have changed and need to be rescanned.
+    ///
+    /// The given `flags` indicate what needs to be rescanned (e.g. values, text or info).
+    /// If [`ParamRescanFlags::requires_restart`] returns `true`, the plugin instance will have to
+    /// be deactivated before the rescan can happen.
     fn rescan(&mut self, flags: ParamRescanFlags);
+
+    /// Informs the host that any references it holds to the given parameter (automation,
+    /// modulation, etc.) must be cleared, as described by the given `flags`.
     fn clear(&mut self, param_id: ClapId, flags: ParamClearFlags);
 }
 
@@ -200,7 +246,7 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostParamsImplMainThread,
 {
     HostWrapper::<H>::handle(host, |host| {
-        host.main_thread()
+        host.main_thread()?
             .as_mut()
             .rescan(ParamRescanFlags::from_bits_truncate(flags));
 
@@ -219,7 +265,7 @@ unsafe extern "C" fn clear<H: HostHandlers>(
     HostWrapper::<H>::handle(host, |host| {
         let param_id = ClapId::from_raw(param_id)
             .ok_or(HostWrapperError::InvalidParameter("Invalid param_id"))?;
-        host.main_thread()
+        host.main_thread()?
             .as_mut()
             .clear(param_id, ParamClearFlags::from_bits_truncate(flags));
 
@@ -0,0 +1,201 @@
+use clack_common::events::event_types::ParamValueEvent;
+use clack_common::events::Event;
+
+/// The interpolation mode used by a [`ParamRamp`] between two parameter value changes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum ParamRampMode {
+    /// No interpolation is performed: the value changes instantly at the sample the parameter
+    /// change event occurs.
+    #[default]
+    None,
+    /// The value is linearly interpolated from its current value, so that it reaches the target
+    /// value exactly at the sample the parameter change event occurs.
+    Linear,
+}
+
+/// A helper to produce sample-accurate, optionally interpolated parameter values from
+/// [`ParamValueEvent`]s.
+///
+/// At the start of each processing block, the parameter changes for that block are pushed into
+/// the ramp (using [`push_event`](Self::push_event) or [`push`](Self::push)), in chronological
+/// order. Then, [`next_sample`](Self::next_sample) must be called once for every sample of the
+/// block, and returns the value the parameter has at that sample.
+///
+/// Multiple parameter changes can occur within a single block: in [`Linear`](ParamRampMode::Linear)
+/// mode, the value glides from one change to the next, starting from the value the parameter had
+/// on the last sample of the previous block. Once all changes have been reached, the
+/// value stays constant, until [`end_block`](Self::end_block) is called to prepare for the next
+/// block.
+///
+/// This type only performs allocations if more changes are pushed in a single block than its
+/// capacity allows. Use [`with_capacity`](Self::with_capacity) to pre-allocate a big enough
+/// buffer outside of the audio thread.
+///
+/// # Example
+///
+/// ```
+/// use clack_extensions::params::{ParamRamp, ParamRampMode};
+///
+/// let mut ramp = ParamRamp::with_capacity(0.0, ParamRampMode::Linear, 16);
+///
+/// // The parameter must reach 1.0 at sample 3, then 0.0 at sample 5.
+/// ramp.push(3, 1.0);
+/// ramp.push(5, 0.0);
+///
+/// let values: Vec<f64> = (0..8).map(|_| ramp.next_sample()).collect();
+/// assert_eq!(values, [0.25, 0.5, 0.75, 1.0, 0.5, 0.0, 0.0, 0.0]);
+///
+/// ramp.end_block();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParamRamp {
+    mode: ParamRampMode,
+    current: f64,
+    increment: f64,
+    position: u32,
+    next_change: usize,
+    changes: Vec<(u32, f64)>,
+}
+
+impl ParamRamp {
+    /// Creates a new ramp, starting at the given `value` and using the given interpolation `mode`.
+    #[inline]
+    pub const fn new(value: f64, mode: ParamRampMode) -> Self {
+        Self {
+            mode,
+            current: value,
+            increment: 0.0,
+            position: 0,
+            next_change: 0,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Creates a new ramp, starting at the given `value` and using the given interpolation `mode`.
+    ///
+    /// The ramp will be able to hold at least `capacity` value changes per block without
+    /// allocating.
+    #[inline]
+    pub fn with_capacity(value: f64, mode: ParamRampMode, capacity: usize) -> Self {
+        Self {
+            changes: Vec::with_capacity(capacity),
+            ..Self::new(value, mode)
+        }
+    }
+
+    /// Returns the interpolation mode of this ramp.
+    #[inline]
+    pub fn mode(&self) -> ParamRampMode {
+        self.mode
+    }
+
+    /// Sets the interpolation mode of this ramp.
+    ///
+    /// The new mode only takes effect starting from the next value change.
+    #[inline]
+    pub fn set_mode(&mut self, mode: ParamRampMode) {
+        self.mode = mode;
+    }
+
+    /// Returns the current value of the ramp, i.e. the last value returned by
+    /// [`next_sample`](Self::next_sample).
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.current
+    }
+
+    /// Immediately sets the ramp to the given `value`, discarding all pending value changes.
+    #[inline]
+    pub fn set_value(&mut self, value: f64) {
+        self.current = value;
+        self.increment = 0.0;
+        self.next_change = 0;
+        self.changes.clear();
+    }
+
+    /// Returns `true` if value changes are still pending in the current block, `false` otherwise.
+    #[inline]
+    pub fn is_ramping(&self) -> bool {
+        self.next_change < self.changes.len()
+    }
+
+    /// Queues a value change to the given `value` at the given `time`, in samples, relative to
+    /// the start of the current block.
+    ///
+    /// Changes must be pushed in chronological order. The time of a change occurring before the
+    /// last pushed change is clamped to the time of that last change: as changes are applied in
+    /// the order they were pushed, its value then overrides the last change's value. Changes
+    /// occurring at a sample that was already processed are applied on the next sample.
+    pub fn push(&mut self, time: u32, value: f64) {
+        let time = match self.changes.last() {
+            Some(&(last_time, _)) => time.max(last_time),
+            None => time,
+        };
+
+        self.changes.push((time, value));
+
+        if self.next_change == self.changes.len() - 1 {
+            self.update_increment();
+        }
+    }
+
+    /// Queues the value change contained in the given [`ParamValueEvent`].
+    ///
+    /// This does not check the event's parameter ID, or its target port, channel, key or note ID.
+    /// It is up to the caller to only push the events relevant to this ramp.
+    #[inline]
+    pub fn push_event(&mut self, event: &ParamValueEvent) {
+        self.push(event.header().time(), event.value())
+    }
+
+    /// Advances the ramp by one sample, and returns the value of the parameter at that sample.
+    pub fn next_sample(&mut self) -> f64 {
+        let mut changed = false;
+
+        while let Some(&(time, value)) = self.changes.get(self.next_change) {
+            if time > self.position {
+                break;
+            }
+
+            self.current = value;
+            self.next_change += 1;
+            changed = true;
+        }
+
+        if !changed {
+            self.current += self.increment;
+        }
+
+        self.position = self.position.saturating_add(1);
+
+        if changed {
+            self.update_increment();
+        }
+
+        self.current
+    }
+
+    /// Ends the current block, and prepares the ramp for the next one.
+    ///
+    /// All value changes that have not been reached yet are applied immediately.
+    pub fn end_block(&mut self) {
+        if let Some(&(_, value)) = self.changes[self.next_change..].last() {
+            self.current = value;
+        }
+
+        self.increment = 0.0;
+        self.position = 0;
+        self.next_change = 0;
+        self.changes.clear();
+    }
+
+    fn update_increment(&mut self) {
+        self.increment = match (self.mode, self.changes.get(self.next_change)) {
+            // The current value is the one of the sample right before the current position.
+            (ParamRampMode::Linear, Some(&(time, value))) if time >= self.position => {
+                (value - self.current) / (time - self.position + 1) as f64
+            }
+            _ => 0.0,
+        };
+    }
+}
@@ -0,0 +1,94 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+use std::collections::hash_map::{HashMap, Iter};
+
+/// A point-in-time copy of the values of all of a plugin's parameters.
+///
+/// This is an owned, [`Send`] and [`Clone`] snapshot, that can be captured on the main thread
+/// and then sent to another thread (e.g. a UI thread) to be displayed, without having to access
+/// the plugin instance itself.
+///
+/// Note that this is not a live view of the plugin's parameters: values changed by the plugin or
+/// the host after the snapshot was captured are not reflected in it. A new snapshot has to be
+/// captured to get up-to-date values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParamSnapshot {
+    values: HashMap<ClapId, f64>,
+}
+
+impl ParamSnapshot {
+    /// Captures the current values of all of the given plugin's parameters.
+    ///
+    /// If the plugin does not implement the Params extension, this returns an empty snapshot.
+    /// Parameters for which the plugin fails to provide a value are omitted from the snapshot.
+    pub fn capture(plugin: &mut PluginMainThreadHandle<'_>) -> Self {
+        match plugin.get_extension::<PluginParams>() {
+            Some(params) => params.snapshot(plugin),
+            None => Self::default(),
+        }
+    }
+
+    /// Returns the value of the parameter with the given ID, or [`None`] if the snapshot doesn't
+    /// contain it.
+    #[inline]
+    pub fn get(&self, param_id: ClapId) -> Option<f64> {
+        self.values.get(&param_id).copied()
+    }
+
+    /// Returns the number of parameter values in this snapshot.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if this snapshot doesn't contain any parameter value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over all the parameter IDs and values in this snapshot, in arbitrary
+    /// order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, ClapId, f64> {
+        self.values.iter()
+    }
+
+    /// Returns the underlying map of parameter IDs to their values.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<ClapId, f64> {
+        self.values
+    }
+}
+
+impl<'a> IntoIterator for &'a ParamSnapshot {
+    type Item = (&'a ClapId, &'a f64);
+    type IntoIter = Iter<'a, ClapId, f64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PluginParams {
+    /// Captures the current values of all of the plugin's parameters.
+    ///
+    /// Parameters for which the plugin fails to provide a value are omitted from the snapshot.
+    ///
+    /// See [`ParamSnapshot`] for more information.
+    pub fn snapshot(&self, plugin: &mut PluginMainThreadHandle<'_>) -> ParamSnapshot {
+        let mut buffer = ParamInfoBuffer::new();
+
+        let ids: Vec<ClapId> = (0..self.count(plugin))
+            .filter_map(|index| Some(self.get_info(plugin, index, &mut buffer)?.id))
+            .collect();
+
+        let values = ids
+            .into_iter()
+            .filter_map(|id| Some((id, self.get_value(plugin, id)?)))
+            .collect();
+
+        ParamSnapshot { values }
+    }
+}
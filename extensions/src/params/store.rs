@@ -0,0 +1,510 @@
+use super::*;
+use clack_common::events::event_types::ParamValueEvent;
+use clack_common::events::io::InputEvents;
+use clack_common::events::spaces::CoreEventSpace;
+use clack_common::events::UnknownEvent;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A single plugin parameter, with its description and its current value.
+///
+/// The value is stored in an atomic cell, so that it can be shared between the main thread and
+/// the audio thread (e.g. in the plugin's `Shared` type), and read or updated from either side
+/// without locking.
+///
+/// Values are always kept within the parameter's range: any value that is
+/// [`set`](Self::set) is clamped to it, and rounded if the parameter is
+/// [stepped](ParamInfoFlags::IS_STEPPED), as with [`ParamInfo::clamp_value`].
+///
+/// Parameters are usually grouped together in a [`ParamStore`], which also implements most of
+/// the boilerplate of the Params extension.
+#[derive(Debug)]
+pub struct AtomicParam {
+    id: ClapId,
+    flags: ParamInfoFlags,
+    name: Box<str>,
+    module: Box<str>,
+    unit: Box<str>,
+    min_value: f64,
+    max_value: f64,
+    default_value: f64,
+    value: AtomicU64,
+}
+
+impl AtomicParam {
+    /// Creates a new, automatable parameter with the given ID, name, range and default value.
+    ///
+    /// The parameter's current value is initialized to the default value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `min_value` is greater than `max_value`, or if `default_value` is outside
+    /// of that range.
+    pub fn new(id: ClapId, name: &str, min_value: f64, max_value: f64, default_value: f64) -> Self {
+        assert!(
+            min_value <= max_value,
+            "Invalid range for parameter {name:?}: {min_value} > {max_value}"
+        );
+        assert!(
+            (min_value..=max_value).contains(&default_value),
+            "Default value for parameter {name:?} is out of range: {default_value}"
+        );
+
+        Self {
+            id,
+            flags: ParamInfoFlags::IS_AUTOMATABLE,
+            name: name.into(),
+            module: "".into(),
+            unit: "".into(),
+            min_value,
+            max_value,
+            default_value,
+            value: AtomicU64::new(default_value.to_bits()),
+        }
+    }
+
+    /// Sets the flags of this parameter. The default is [`ParamInfoFlags::IS_AUTOMATABLE`].
+    #[inline]
+    pub fn with_flags(mut self, flags: ParamInfoFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the module path of this parameter, e.g. `"Oscillators/Osc 1"`.
+    #[inline]
+    pub fn with_module(mut self, module: &str) -> Self {
+        self.module = module.into();
+        self
+    }
+
+    /// Sets the unit displayed after this parameter's value, e.g. `"dB"`.
+    ///
+    /// See [`value_to_text`](Self::value_to_text).
+    #[inline]
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// The unique ID of this parameter.
+    #[inline]
+    pub fn id(&self) -> ClapId {
+        self.id
+    }
+
+    /// The flags of this parameter.
+    #[inline]
+    pub fn flags(&self) -> ParamInfoFlags {
+        self.flags
+    }
+
+    /// The user-friendly name of this parameter.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The module path of this parameter. This is empty if no module was set.
+    #[inline]
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// The unit of this parameter's values. This is empty if no unit was set.
+    #[inline]
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// The minimum value of this parameter.
+    #[inline]
+    pub fn min_value(&self) -> f64 {
+        self.min_value
+    }
+
+    /// The maximum value of this parameter.
+    #[inline]
+    pub fn max_value(&self) -> f64 {
+        self.max_value
+    }
+
+    /// The default value of this parameter.
+    #[inline]
+    pub fn default_value(&self) -> f64 {
+        self.default_value
+    }
+
+    /// Returns the [`ParamInfo`] describing this parameter to the host.
+    #[inline]
+    pub fn info(&self) -> ParamInfo<'_> {
+        ParamInfo {
+            id: self.id,
+            flags: self.flags,
+            cookie: Default::default(),
+            name: self.name.as_bytes(),
+            module: self.module.as_bytes(),
+            min_value: self.min_value,
+            max_value: self.max_value,
+            default_value: self.default_value,
+        }
+    }
+
+    /// Returns the current value of this parameter.
+    ///
+    /// This is realtime-safe.
+    #[inline]
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.value.load(Ordering::Relaxed))
+    }
+
+    /// Sets the current value of this parameter, clamped to the parameter's range and rounded if
+    /// it is [stepped](ParamInfoFlags::IS_STEPPED). See [`ParamInfo::clamp_value`].
+    ///
+    /// `NaN` values are ignored.
+    ///
+    /// This is realtime-safe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_extensions::params::*;
+    /// use clack_plugin::prelude::*;
+    ///
+    /// let voices = AtomicParam::new(ClapId::new(1), "Voices", 1.0, 16.0, 8.0)
+    ///     .with_flags(ParamInfoFlags::IS_AUTOMATABLE | ParamInfoFlags::IS_STEPPED);
+    ///
+    /// voices.set(4.6);
+    /// assert_eq!(voices.get(), 5.0);
+    ///
+    /// voices.set(42.0);
+    /// assert_eq!(voices.get(), 16.0);
+    /// ```
+    #[inline]
+    pub fn set(&self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+
+        let value = self.info().clamp_value(value);
+        self.value.store(value.to_bits(), Ordering::Relaxed)
+    }
+
+    /// Resets the current value of this parameter to its default value.
+    #[inline]
+    pub fn reset(&self) {
+        self.set(self.default_value)
+    }
+
+    /// Applies the given parameter value event to this parameter, if it targets it.
+    ///
+    /// Only events targeting this parameter's ID and applying to all notes, keys, channels and
+    /// ports are applied: more specific (e.g. per-note) values do not change the parameter's
+    /// global value, and have to be handled by the plugin itself.
+    ///
+    /// Returns `true` if the event was applied, `false` otherwise.
+    #[inline]
+    pub fn handle_event(&self, event: &ParamValueEvent) -> bool {
+        if event.param_id() != Some(self.id) || !event.pckn().matches_all() {
+            return false;
+        }
+
+        self.set(event.value());
+        true
+    }
+
+    /// Writes the given value as text, followed by the parameter's unit, if any.
+    ///
+    /// Values of [stepped](ParamInfoFlags::IS_STEPPED) parameters are written without decimals,
+    /// while other values are written with two decimals.
+    pub fn value_to_text(&self, value: f64, writer: &mut impl Write) -> std::fmt::Result {
+        if self.flags.contains(ParamInfoFlags::IS_STEPPED) {
+            write!(writer, "{value:.0}")?;
+        } else {
+            write!(writer, "{value:.2}")?;
+        }
+
+        if !self.unit.is_empty() {
+            write!(writer, " {}", self.unit)?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses the given text into a value, clamped to this parameter's range and rounded if it is
+    /// [stepped](ParamInfoFlags::IS_STEPPED), like [`set`](Self::set).
+    ///
+    /// This accepts the format written by [`value_to_text`](Self::value_to_text), but the unit
+    /// is optional. Surrounding whitespace is ignored.
+    ///
+    /// Returns [`None`] if the text could not be parsed.
+    pub fn text_to_value(&self, text: &CStr) -> Option<f64> {
+        let text = text.to_str().ok()?.trim();
+        let text = text.strip_suffix(&*self.unit).unwrap_or(text).trim_end();
+
+        let value: f64 = text.parse().ok()?;
+
+        if value.is_nan() {
+            return None;
+        }
+
+        Some(self.info().clamp_value(value))
+    }
+}
+
+/// A collection of [`AtomicParam`]s, keyed by their [`ClapId`].
+///
+/// Parameters are exposed to the host in the order they were added to the store.
+///
+/// Alongside thread-safe storage for the parameters' values, this type also provides methods
+/// matching those of the [`PluginMainThreadParams`] and [`PluginAudioProcessorParams`] traits,
+/// so that implementing the Params extension is reduced to delegating to the store.
+///
+/// # Example
+///
+/// ```
+/// use clack_extensions::params::*;
+/// use clack_plugin::events::io::EventBuffer;
+/// use clack_plugin::prelude::*;
+/// use std::ffi::CStr;
+///
+/// pub const PARAM_VOLUME_ID: ClapId = ClapId::new(1);
+///
+/// let params = ParamStore::new()
+///     .with_param(AtomicParam::new(PARAM_VOLUME_ID, "Volume", 0.0, 1.0, 1.0));
+///
+/// // Apply an automation event received from the host.
+/// let mut events = EventBuffer::new();
+/// events.push_param_value(0, PARAM_VOLUME_ID, 0.5);
+/// params.handle_events(&events.as_input());
+///
+/// assert_eq!(params.value(PARAM_VOLUME_ID), Some(0.5));
+///
+/// // Values are always kept within the parameter's range.
+/// params.set_value(PARAM_VOLUME_ID, 2.0);
+/// assert_eq!(params.value(PARAM_VOLUME_ID), Some(1.0));
+///
+/// // Default text conversions.
+/// let mut text = String::new();
+/// params.value_to_text(PARAM_VOLUME_ID, 0.25, &mut text).unwrap();
+/// assert_eq!(text, "0.25");
+///
+/// let text = CStr::from_bytes_with_nul(b" 0.75 \0").unwrap();
+/// assert_eq!(params.text_to_value(PARAM_VOLUME_ID, text), Some(0.75));
+///
+/// // The Params extension implementation can then simply delegate to the store.
+/// struct MyPluginMainThread<'a> {
+///     params: &'a ParamStore,
+/// }
+///
+/// impl PluginMainThreadParams for MyPluginMainThread<'_> {
+///     fn count(&mut self) -> u32 {
+///         self.params.count()
+///     }
+///
+///     fn get_info(&mut self, param_index: u32, info: &mut ParamInfoWriter) {
+///         self.params.get_info(param_index, info)
+///     }
+///
+///     fn get_value(&mut self, param_id: ClapId) -> Option<f64> {
+///         self.params.value(param_id)
+///     }
+///
+///     fn value_to_text(
+///         &mut self,
+///         param_id: ClapId,
+///         value: f64,
+///         writer: &mut ParamDisplayWriter,
+///     ) -> std::fmt::Result {
+///         self.params.value_to_text(param_id, value, writer)
+///     }
+///
+///     fn text_to_value(&mut self, param_id: ClapId, text: &CStr) -> Option<f64> {
+///         self.params.text_to_value(param_id, text)
+///     }
+///
+///     fn flush(&mut self, input_events: &InputEvents, _output_events: &mut OutputEvents) {
+///         self.params.handle_events(input_events)
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ParamStore {
+    params: Vec<AtomicParam>,
+    indexes: HashMap<ClapId, usize>,
+}
+
+impl ParamStore {
+    /// Creates a new, empty parameter store.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given parameter to this store.
+    ///
+    /// # Panics
+    ///
+    /// This panics if a parameter with the same ID was already added to this store.
+    #[inline]
+    pub fn with_param(mut self, param: AtomicParam) -> Self {
+        self.add(param);
+        self
+    }
+
+    /// Adds the given parameter to this store.
+    ///
+    /// # Panics
+    ///
+    /// This panics if a parameter with the same ID was already added to this store.
+    pub fn add(&mut self, param: AtomicParam) {
+        let index = self.params.len();
+
+        if self.indexes.insert(param.id(), index).is_some() {
+            panic!("Duplicate parameter ID: {}", param.id());
+        }
+
+        self.params.push(param);
+    }
+
+    /// Returns the number of parameters in this store.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns `true` if this store doesn't contain any parameter.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Returns the parameter with the given ID, or [`None`] if there is none.
+    #[inline]
+    pub fn get(&self, param_id: ClapId) -> Option<&AtomicParam> {
+        self.params.get(*self.indexes.get(&param_id)?)
+    }
+
+    /// Returns the parameter at the given index, or [`None`] if the index is out of bounds.
+    #[inline]
+    pub fn get_by_index(&self, index: u32) -> Option<&AtomicParam> {
+        self.params.get(index as usize)
+    }
+
+    /// Returns an iterator over all the parameters in this store, in order.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, AtomicParam> {
+        self.params.iter()
+    }
+
+    /// Returns the current value of the parameter with the given ID, or [`None`] if there is
+    /// none.
+    #[inline]
+    pub fn value(&self, param_id: ClapId) -> Option<f64> {
+        Some(self.get(param_id)?.get())
+    }
+
+    /// Sets the current value of the parameter with the given ID, as with [`AtomicParam::set`].
+    ///
+    /// Returns `false` if there is no parameter with the given ID.
+    #[inline]
+    pub fn set_value(&self, param_id: ClapId, value: f64) -> bool {
+        match self.get(param_id) {
+            Some(param) => {
+                param.set(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resets all the parameters in this store to their default value.
+    pub fn reset(&self) {
+        for param in &self.params {
+            param.reset()
+        }
+    }
+
+    /// Applies the given event to the matching parameter, if it is a parameter value event.
+    ///
+    /// See [`AtomicParam::handle_event`] for which events are applied.
+    ///
+    /// Returns `true` if the event was applied, `false` otherwise.
+    #[inline]
+    pub fn handle_event(&self, event: &UnknownEvent) -> bool {
+        let Some(CoreEventSpace::ParamValue(event)) = event.as_core_event() else {
+            return false;
+        };
+
+        event
+            .param_id()
+            .and_then(|id| self.get(id))
+            .is_some_and(|param| param.handle_event(event))
+    }
+
+    /// Applies all the parameter value events in the given list.
+    ///
+    /// This is suitable for implementing both [`PluginMainThreadParams::flush`] and
+    /// [`PluginAudioProcessorParams::flush`].
+    #[inline]
+    pub fn handle_events(&self, events: &InputEvents) {
+        for event in events {
+            self.handle_event(event);
+        }
+    }
+
+    /// Returns the number of parameters, as required by [`PluginMainThreadParams::count`].
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.params.len() as u32
+    }
+
+    /// Writes the info of the parameter at the given index, as required by
+    /// [`PluginMainThreadParams::get_info`].
+    ///
+    /// Nothing is written if the index is out of bounds.
+    #[inline]
+    pub fn get_info(&self, param_index: u32, info: &mut ParamInfoWriter) {
+        if let Some(param) = self.get_by_index(param_index) {
+            info.set(&param.info())
+        }
+    }
+
+    /// Writes the given value of the given parameter as text, as required by
+    /// [`PluginMainThreadParams::value_to_text`].
+    ///
+    /// See [`AtomicParam::value_to_text`] for the format used.
+    ///
+    /// Returns an error if there is no parameter with the given ID.
+    #[inline]
+    pub fn value_to_text(
+        &self,
+        param_id: ClapId,
+        value: f64,
+        writer: &mut impl Write,
+    ) -> std::fmt::Result {
+        match self.get(param_id) {
+            Some(param) => param.value_to_text(value, writer),
+            None => Err(std::fmt::Error),
+        }
+    }
+
+    /// Parses the given text into a value for the given parameter, as required by
+    /// [`PluginMainThreadParams::text_to_value`].
+    ///
+    /// See [`AtomicParam::text_to_value`] for the accepted format.
+    #[inline]
+    pub fn text_to_value(&self, param_id: ClapId, text: &CStr) -> Option<f64> {
+        self.get(param_id)?.text_to_value(text)
+    }
+}
+
+impl<'a> IntoIterator for &'a ParamStore {
+    type Item = &'a AtomicParam;
+    type IntoIter = std::slice::Iter<'a, AtomicParam>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -88,6 +88,10 @@ impl Display for FdError {
 mod host {
     use super::*;
     use clack_host::extensions::prelude::*;
+    use std::cell::RefCell;
+    use std::collections::hash_map::Entry;
+    use std::collections::HashMap;
+    use std::rc::Rc;
 
     impl PluginPosixFd {
         /// A callback that gets called for every event on each registered File Descriptor.
@@ -141,7 +145,7 @@ mod host {
     {
         HostWrapper::<H>::handle(host, |host| {
             Ok(host
-                .main_thread()
+                .main_thread()?
                 .as_mut()
                 .register_fd(fd, FdFlags::from_bits_truncate(flags))
                 .is_ok())
@@ -160,7 +164,7 @@ mod host {
     {
         HostWrapper::<H>::handle(host, |host| {
             Ok(host
-                .main_thread()
+                .main_thread()?
                 .as_mut()
                 .modify_fd(fd, FdFlags::from_bits_truncate(flags))
                 .is_ok())
@@ -173,10 +177,158 @@ mod host {
         for<'a> <H as HostHandlers>::MainThread<'a>: HostPosixFdImpl,
     {
         HostWrapper::<H>::handle(host, |host| {
-            Ok(host.main_thread().as_mut().unregister_fd(fd).is_ok())
+            Ok(host.main_thread()?.as_mut().unregister_fd(fd).is_ok())
         })
         .unwrap_or(false)
     }
+
+    /// A ready-made registry of the File Descriptors a plugin asked the host to watch.
+    ///
+    /// This type implements [`HostPosixFdImpl`], and keeps track of every File Descriptor the plugin
+    /// registered, along with the events it is interested in. Hosts can then use [`fds`](Self::fds)
+    /// to add them to their own event loop (e.g. `epoll`, `poll` or `mio`), and
+    /// [`ready_fds`](Self::ready_fds) and [`notify`](Self::notify) to forward the resulting
+    /// events to the plugin.
+    ///
+    /// Cloning this registry returns a new handle to the same, shared set of File Descriptors.
+    /// This allows the host to keep a handle in its main thread handler to receive the plugin's
+    /// requests, and another in its event loop, which can notify the plugin without
+    /// borrowing the handler.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_extensions::posix_fd::*;
+    /// use clack_host::prelude::*;
+    ///
+    /// fn dispatch_events(
+    ///     registry: &PosixFdRegistry,
+    ///     plugin: &mut PluginMainThreadHandle,
+    ///     posix_fd: &PluginPosixFd,
+    ///     poll_results: &[(std::os::unix::io::RawFd, FdFlags)],
+    /// ) {
+    ///     for (fd, flags) in registry.ready_fds(poll_results.iter().copied()) {
+    ///         registry.notify(plugin, posix_fd, fd, flags);
+    ///     }
+    /// }
+    /// ```
+    #[derive(Clone, Default, Debug)]
+    pub struct PosixFdRegistry {
+        fds: Rc<RefCell<HashMap<RawFd, FdFlags>>>,
+    }
+
+    impl PosixFdRegistry {
+        /// Creates a new, empty registry.
+        #[inline]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Returns all the currently registered File Descriptors, along with the events they
+        /// should be watched for.
+        pub fn fds(&self) -> Vec<(RawFd, FdFlags)> {
+            self.fds
+                .borrow()
+                .iter()
+                .map(|(&fd, &flags)| (fd, flags))
+                .collect()
+        }
+
+        /// Returns the events the given File Descriptor is registered for, or [`None`] if it is
+        /// not registered.
+        #[inline]
+        pub fn flags(&self, fd: RawFd) -> Option<FdFlags> {
+            self.fds.borrow().get(&fd).copied()
+        }
+
+        /// Returns the number of currently registered File Descriptors.
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.fds.borrow().len()
+        }
+
+        /// Returns `true` if no File Descriptors are currently registered.
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.fds.borrow().is_empty()
+        }
+
+        /// Filters the given events, as reported by the host's event loop, down to the ones the
+        /// plugin is interested in.
+        ///
+        /// Events on File Descriptors that are not registered are discarded, and the flags of the
+        /// others are restricted to the ones they were registered for.
+        ///
+        /// This allocates: the filtered events are collected before being returned, so that the
+        /// registry isn't borrowed anymore when they are passed on to [`notify`](Self::notify),
+        /// which lets the plugin modify its File Descriptors.
+        pub fn ready_fds(
+            &self,
+            events: impl IntoIterator<Item = (RawFd, FdFlags)>,
+        ) -> impl Iterator<Item = (RawFd, FdFlags)> {
+            let fds = self.fds.borrow();
+
+            events
+                .into_iter()
+                .filter_map(|(fd, flags)| {
+                    let flags = flags & *fds.get(&fd)?;
+                    (!flags.is_empty()).then_some((fd, flags))
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        /// Notifies the plugin that the given events occurred on the given File Descriptor,
+        /// by calling its [`on_fd`](PluginPosixFd::on_fd) callback.
+        ///
+        /// Nothing is done if the File Descriptor is not registered. The plugin is allowed to
+        /// modify or unregister File Descriptors from within this callback.
+        pub fn notify(
+            &self,
+            plugin: &mut PluginMainThreadHandle,
+            posix_fd: &PluginPosixFd,
+            fd: RawFd,
+            flags: FdFlags,
+        ) {
+            // The borrow must be released before calling the plugin, as it may call back into us.
+            let is_registered = self.fds.borrow().contains_key(&fd);
+
+            if is_registered {
+                posix_fd.on_fd(plugin, fd, flags);
+            }
+        }
+    }
+
+    impl HostPosixFdImpl for PosixFdRegistry {
+        fn register_fd(&mut self, fd: RawFd, flags: FdFlags) -> Result<(), HostError> {
+            match self.fds.borrow_mut().entry(fd) {
+                Entry::Occupied(_) => {
+                    Err(HostError::Message("File descriptor is already registered"))
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(flags);
+                    Ok(())
+                }
+            }
+        }
+
+        fn modify_fd(&mut self, fd: RawFd, flags: FdFlags) -> Result<(), HostError> {
+            match self.fds.borrow_mut().get_mut(&fd) {
+                Some(registered) => {
+                    *registered = flags;
+                    Ok(())
+                }
+                None => Err(HostError::Message("File descriptor is not registered")),
+            }
+        }
+
+        fn unregister_fd(&mut self, fd: RawFd) -> Result<(), HostError> {
+            match self.fds.borrow_mut().remove(&fd) {
+                Some(_) => Ok(()),
+                None => Err(HostError::Message("File descriptor is not registered")),
+            }
+        }
+    }
 }
 
 #[cfg(feature = "clack-host")]
@@ -0,0 +1,210 @@
+#![deny(missing_docs)]
+
+//! Allows hosts to ask plugins to load presets from a given location.
+//!
+//! Presets can be stored in their own file, inside a container file alongside other presets (in
+//! which case a load key identifies the preset inside the container), or be built into the plugin
+//! itself (in which case only the load key is used).
+//!
+//! Plugins notify the host when a preset has been loaded, or if loading it failed.
+
+use clack_common::extensions::{Extension, HostExtensionSide, PluginExtensionSide, RawExtension};
+use clap_sys::ext::draft::preset_load::*;
+use clap_sys::factory::draft::preset_discovery::*;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt::{Display, Formatter};
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+
+/// The Plugin-side of the Preset Load extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginPresetLoad(RawExtension<PluginExtensionSide, clap_plugin_preset_load>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginPresetLoad {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_PRESET_LOAD;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The Host-side of the Preset Load extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct HostPresetLoad(RawExtension<HostExtensionSide, clap_host_preset_load>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for HostPresetLoad {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_PRESET_LOAD;
+    type ExtensionSide = HostExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The location of a preset.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PresetLocation {
+    /// The preset is stored in its own file, at the given path.
+    File(PathBuf),
+    /// The preset is built into the plugin itself.
+    Plugin {
+        /// The key identifying the preset within the plugin.
+        load_key: CString,
+    },
+    /// The preset is stored inside a container file, alongside other presets.
+    Container {
+        /// The path to the container file.
+        path: PathBuf,
+        /// The key identifying the preset within the container file.
+        load_key: CString,
+    },
+}
+
+impl PresetLocation {
+    /// Returns the raw, C-FFI compatible location kind of this location.
+    #[inline]
+    pub fn raw_kind(&self) -> clap_preset_discovery_location_kind {
+        match self {
+            Self::File(_) | Self::Container { .. } => CLAP_PRESET_DISCOVERY_LOCATION_FILE,
+            Self::Plugin { .. } => CLAP_PRESET_DISCOVERY_LOCATION_PLUGIN,
+        }
+    }
+
+    /// Reads a location from its raw, C-FFI compatible parts.
+    ///
+    /// A file location with a null or empty load key is returned as a [`File`](Self::File)
+    /// location, while a file location with a non-empty load key is returned as a
+    /// [`Container`](Self::Container) location.
+    ///
+    /// This returns [`None`] if the location kind is unknown, if a required part is missing, or
+    /// if the location path is not a valid path on the current platform.
+    ///
+    /// # Safety
+    ///
+    /// The given `location` and `load_key` pointers must either be null, or point to valid
+    /// null-terminated C strings.
+    pub unsafe fn from_raw(
+        kind: clap_preset_discovery_location_kind,
+        location: *const c_char,
+        load_key: *const c_char,
+    ) -> Option<Self> {
+        let load_key = if load_key.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(load_key)).filter(|k| !k.is_empty())
+        };
+
+        match kind {
+            CLAP_PRESET_DISCOVERY_LOCATION_FILE => {
+                if location.is_null() {
+                    return None;
+                }
+
+                let path = path_from_c_str(CStr::from_ptr(location))?;
+
+                match load_key {
+                    None => Some(Self::File(path)),
+                    Some(load_key) => Some(Self::Container {
+                        path,
+                        load_key: load_key.to_owned(),
+                    }),
+                }
+            }
+            CLAP_PRESET_DISCOVERY_LOCATION_PLUGIN => Some(Self::Plugin {
+                load_key: load_key?.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Converts this location into the C strings to be passed as the raw `location` and
+    /// `load_key` parameters.
+    fn to_raw_parts(&self) -> Result<(Option<CString>, Option<&CStr>), PresetLoadError> {
+        match self {
+            Self::File(path) => Ok((Some(path_to_c_string(path)?), None)),
+            Self::Plugin { load_key } => Ok((None, Some(load_key))),
+            Self::Container { path, load_key } => {
+                Ok((Some(path_to_c_string(path)?), Some(load_key)))
+            }
+        }
+    }
+}
+
+fn path_to_c_string(path: &Path) -> Result<CString, PresetLoadError> {
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or(PresetLoadError::InvalidLocation)?
+        .as_bytes();
+
+    CString::new(bytes).map_err(|_| PresetLoadError::InvalidLocation)
+}
+
+fn path_from_c_str(path: &CStr) -> Option<PathBuf> {
+    #[cfg(unix)]
+    let path = <std::ffi::OsStr as std::os::unix::ffi::OsStrExt>::from_bytes(path.to_bytes());
+    #[cfg(not(unix))]
+    let path = path.to_str().ok()?;
+
+    Some(PathBuf::from(path))
+}
+
+/// Errors that can occur while loading a preset.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PresetLoadError {
+    /// The preset location could not be represented as a C string (e.g. it contains a nul byte).
+    InvalidLocation,
+    /// The plugin failed to load the preset.
+    LoadFailed,
+    /// The plugin reported an error while loading the preset.
+    Reported {
+        /// The operating system error code, if any.
+        os_error: Option<i32>,
+        /// The error message provided by the plugin, if any.
+        message: Option<CString>,
+    },
+}
+
+impl Display for PresetLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLocation => f.write_str("Invalid preset location"),
+            Self::LoadFailed => f.write_str("Failed to load preset"),
+            Self::Reported { os_error, message } => {
+                f.write_str("Failed to load preset")?;
+
+                if let Some(message) = message {
+                    write!(f, ": {}", message.to_string_lossy())?;
+                }
+
+                if let Some(os_error) = os_error {
+                    write!(f, " (OS error {os_error})")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for PresetLoadError {}
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
+
+#[cfg(feature = "clack-host")]
+mod host;
+#[cfg(feature = "clack-host")]
+pub use host::*;
@@ -0,0 +1,175 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+use clap_sys::plugin::clap_plugin;
+use std::cell::Cell;
+
+/// A [`PluginPresetLoad::load`] call in progress on this thread.
+struct PendingLoad {
+    plugin: *const clap_plugin,
+    location: PresetLocation,
+    error: Option<PresetLoadError>,
+}
+
+thread_local! {
+    /// The [`PluginPresetLoad::load`] call in progress on this thread, if any.
+    static PENDING_LOAD: Cell<Option<PendingLoad>> = const { Cell::new(None) };
+}
+
+impl PluginPresetLoad {
+    /// Asks the plugin to load the preset at the given `location`.
+    ///
+    /// Loading may be performed asynchronously by the plugin: a successful result only means the
+    /// plugin accepted the request. The plugin then notifies the host through the
+    /// [`HostPresetLoadImpl`] callbacks once the preset is actually loaded, or if it failed to.
+    ///
+    /// If the plugin reports an error for this same `location` through the host's `on_error`
+    /// callback before this call returns, that error is returned here instead of being passed to
+    /// [`HostPresetLoadImpl::on_error`]. Errors reported for any other location, or by any other
+    /// plugin instance, are always passed to [`HostPresetLoadImpl::on_error`].
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PresetLoadError::InvalidLocation`] if the given location's path cannot be
+    /// passed to the plugin. If the plugin failed to load the preset, this returns the
+    /// [`PresetLoadError::Reported`] error the plugin reported during this call, or
+    /// [`PresetLoadError::LoadFailed`] if it didn't report any.
+    pub fn load(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        location: &PresetLocation,
+    ) -> Result<(), PresetLoadError> {
+        let from_location = plugin
+            .use_extension(&self.0)
+            .from_location
+            .ok_or(PresetLoadError::LoadFailed)?;
+
+        let (path, load_key) = location.to_raw_parts()?;
+
+        // Calls may be nested if the plugin loads a preset from within a host callback.
+        let previous = PENDING_LOAD.with(|p| {
+            p.replace(Some(PendingLoad {
+                plugin: plugin.as_raw_ptr(),
+                location: location.clone(),
+                error: None,
+            }))
+        });
+
+        // SAFETY: This type ensures the function pointer is valid.
+        let success = unsafe {
+            from_location(
+                plugin.as_raw(),
+                location.raw_kind(),
+                path.as_deref().map_or(core::ptr::null(), CStr::as_ptr),
+                load_key.map_or(core::ptr::null(), CStr::as_ptr),
+            )
+        };
+
+        let reported = PENDING_LOAD
+            .with(|p| p.replace(previous))
+            .and_then(|pending| pending.error);
+
+        match (success, reported) {
+            (_, Some(error)) => Err(error),
+            (true, None) => Ok(()),
+            (false, None) => Err(PresetLoadError::LoadFailed),
+        }
+    }
+}
+
+/// Implementation of the Host-side of the Preset Load extension.
+pub trait HostPresetLoadImpl {
+    /// Called by the plugin when it failed to load the preset at the given `location`.
+    ///
+    /// The given `error` is always a [`PresetLoadError::Reported`] error.
+    ///
+    /// This is not called for errors the plugin reports for the location it is currently loading
+    /// through [`PluginPresetLoad::load`]: those are returned by it instead.
+    fn on_error(&mut self, location: PresetLocation, error: PresetLoadError);
+
+    /// Called by the plugin when it successfully loaded the preset at the given `location`.
+    fn loaded(&mut self, location: PresetLocation);
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<H: HostHandlers> ExtensionImplementation<H> for HostPresetLoad
+where
+    for<'a> <H as HostHandlers>::MainThread<'a>: HostPresetLoadImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_host_preset_load {
+            on_error: Some(on_error::<H>),
+            loaded: Some(loaded::<H>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn on_error<H: HostHandlers>(
+    host: *const clap_host,
+    location_kind: clap_preset_discovery_location_kind,
+    location: *const c_char,
+    load_key: *const c_char,
+    os_error: i32,
+    msg: *const c_char,
+) where
+    for<'a> <H as HostHandlers>::MainThread<'a>: HostPresetLoadImpl,
+{
+    HostWrapper::<H>::handle(host, |host| {
+        let location = PresetLocation::from_raw(location_kind, location, load_key).ok_or(
+            HostWrapperError::InvalidParameter("Invalid preset location"),
+        )?;
+
+        let error = PresetLoadError::Reported {
+            os_error: if os_error == 0 { None } else { Some(os_error) },
+            message: if msg.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(msg).to_owned())
+            },
+        };
+
+        let error = PENDING_LOAD.with(|p| {
+            let mut pending = p.take();
+
+            let error = match &mut pending {
+                Some(pending)
+                    if pending.error.is_none()
+                        && Some(pending.plugin) == host.plugin_ptr()
+                        && pending.location == location =>
+                {
+                    pending.error = Some(error);
+                    None
+                }
+                _ => Some(error),
+            };
+
+            p.set(pending);
+            error
+        });
+
+        if let Some(error) = error {
+            host.main_thread()?.as_mut().on_error(location, error);
+        }
+
+        Ok(())
+    });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn loaded<H: HostHandlers>(
+    host: *const clap_host,
+    location_kind: clap_preset_discovery_location_kind,
+    location: *const c_char,
+    load_key: *const c_char,
+) where
+    for<'a> <H as HostHandlers>::MainThread<'a>: HostPresetLoadImpl,
+{
+    HostWrapper::<H>::handle(host, |host| {
+        let location = PresetLocation::from_raw(location_kind, location, load_key).ok_or(
+            HostWrapperError::InvalidParameter("Invalid preset location"),
+        )?;
+
+        host.main_thread()?.as_mut().loaded(location);
+        Ok(())
+    });
+}
@@ -0,0 +1,100 @@
+use super::*;
+use clack_plugin::extensions::prelude::*;
+
+impl HostPresetLoad {
+    /// Notifies the host that the preset at the given `location` failed to load.
+    ///
+    /// The `os_error` code should be provided if the failure was caused by an operating system
+    /// error (e.g. an I/O error), and a human-readable error `message` can also be given.
+    pub fn on_error(
+        &self,
+        host: &mut HostMainThreadHandle,
+        location: &PresetLocation,
+        os_error: Option<i32>,
+        message: Option<&CStr>,
+    ) {
+        let Some(on_error) = host.use_extension(&self.0).on_error else {
+            return;
+        };
+
+        let Ok((path, load_key)) = location.to_raw_parts() else {
+            return;
+        };
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe {
+            on_error(
+                host.as_raw(),
+                location.raw_kind(),
+                path.as_deref().map_or(core::ptr::null(), CStr::as_ptr),
+                load_key.map_or(core::ptr::null(), CStr::as_ptr),
+                os_error.unwrap_or(0),
+                message.map_or(core::ptr::null(), CStr::as_ptr),
+            )
+        }
+    }
+
+    /// Notifies the host that the preset at the given `location` was successfully loaded.
+    pub fn loaded(&self, host: &mut HostMainThreadHandle, location: &PresetLocation) {
+        let Some(loaded) = host.use_extension(&self.0).loaded else {
+            return;
+        };
+
+        let Ok((path, load_key)) = location.to_raw_parts() else {
+            return;
+        };
+
+        // SAFETY: This type ensures the function pointer is valid.
+        unsafe {
+            loaded(
+                host.as_raw(),
+                location.raw_kind(),
+                path.as_deref().map_or(core::ptr::null(), CStr::as_ptr),
+                load_key.map_or(core::ptr::null(), CStr::as_ptr),
+            )
+        }
+    }
+}
+
+/// Implementation of the Plugin-side of the Preset Load extension.
+pub trait PluginPresetLoadImpl {
+    /// Loads the preset at the given `location`.
+    ///
+    /// # Errors
+    ///
+    /// This may return an error if the plugin failed to load the preset.
+    fn load(&mut self, location: PresetLocation) -> Result<(), PluginError>;
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginPresetLoad
+where
+    for<'a> P::MainThread<'a>: PluginPresetLoadImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_preset_load {
+            from_location: Some(from_location::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn from_location<P: Plugin>(
+    plugin: *const clap_plugin,
+    location_kind: clap_preset_discovery_location_kind,
+    location: *const c_char,
+    load_key: *const c_char,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginPresetLoadImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        let location = PresetLocation::from_raw(location_kind, location, load_key).ok_or(
+            PluginWrapperError::InvalidParameter("Invalid preset location"),
+        )?;
+
+        p.main_thread().as_mut().load(location)?;
+        Ok(())
+    })
+    .is_some()
+}
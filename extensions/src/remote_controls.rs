@@ -0,0 +1,124 @@
+#![deny(missing_docs)]
+
+//! Allows plugins to group their parameters into pages of remote controls, for hosts to map them
+//! onto hardware controllers.
+//!
+//! Each page holds up to [`REMOTE_CONTROLS_COUNT`] controls, each mapped to a parameter. Some of
+//! those control slots can be left unset.
+
+use clack_common::extensions::{Extension, HostExtensionSide, PluginExtensionSide, RawExtension};
+use clack_common::utils::ClapId;
+use clap_sys::ext::draft::remote_controls::*;
+use clap_sys::string_sizes::CLAP_NAME_SIZE;
+use std::ffi::CStr;
+use std::fmt::{Debug, Formatter};
+
+/// The number of controls in a single [`RemoteControlsPage`].
+pub const REMOTE_CONTROLS_COUNT: usize = CLAP_REMOTE_CONTROLS_COUNT;
+
+/// The Plugin-side of the Remote Controls extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginRemoteControls(RawExtension<PluginExtensionSide, clap_plugin_remote_controls>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginRemoteControls {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_REMOTE_CONTROLS;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The Host-side of the Remote Controls extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct HostRemoteControls(RawExtension<HostExtensionSide, clap_host_remote_controls>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for HostRemoteControls {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_REMOTE_CONTROLS;
+    type ExtensionSide = HostExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// A page of remote controls.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct RemoteControlsPage {
+    /// The name of the section this page belongs to, used by hosts to group pages together.
+    pub section_name: Vec<u8>,
+    /// The unique identifier of this page.
+    pub page_id: ClapId,
+    /// The user-facing display name of this page.
+    pub page_name: Vec<u8>,
+    /// The identifiers of the parameters mapped to each control of this page, or [`None`] if
+    /// the control is unset.
+    pub param_ids: [Option<ClapId>; REMOTE_CONTROLS_COUNT],
+    /// Whether this page is specific to the currently loaded preset.
+    pub is_for_preset: bool,
+}
+
+impl RemoteControlsPage {
+    /// Creates a new [`RemoteControlsPage`] from the given raw C ABI-compatible page.
+    ///
+    /// This returns [`None`] if the raw page's identifier is invalid.
+    pub fn from_raw(raw: &clap_remote_controls_page) -> Option<Self> {
+        Some(Self {
+            section_name: crate::utils::data_from_array_buf(&raw.section_name).to_vec(),
+            page_id: ClapId::from_raw(raw.page_id)?,
+            page_name: crate::utils::data_from_array_buf(&raw.page_name).to_vec(),
+            param_ids: raw.param_ids.map(ClapId::from_raw),
+            is_for_preset: raw.is_for_preset,
+        })
+    }
+
+    /// Creates a new raw C ABI-compatible page from this [`RemoteControlsPage`].
+    pub fn to_raw(&self) -> clap_remote_controls_page {
+        let mut section_name = [0; CLAP_NAME_SIZE];
+        let mut page_name = [0; CLAP_NAME_SIZE];
+
+        // SAFETY: both names are valid pointers, as they come from &mut references.
+        unsafe {
+            crate::utils::write_to_array_buf(&mut section_name, &self.section_name);
+            crate::utils::write_to_array_buf(&mut page_name, &self.page_name);
+        }
+
+        clap_remote_controls_page {
+            section_name,
+            page_id: self.page_id.get(),
+            page_name,
+            param_ids: self.param_ids.map(ClapId::optional_to_raw),
+            is_for_preset: self.is_for_preset,
+        }
+    }
+}
+
+impl Debug for RemoteControlsPage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteControlsPage")
+            .field("section_name", &String::from_utf8_lossy(&self.section_name))
+            .field("page_id", &self.page_id)
+            .field("page_name", &String::from_utf8_lossy(&self.page_name))
+            .field("param_ids", &self.param_ids)
+            .field("is_for_preset", &self.is_for_preset)
+            .finish()
+    }
+}
+
+#[cfg(feature = "clack-host")]
+mod host;
+
+#[cfg(feature = "clack-host")]
+pub use host::*;
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
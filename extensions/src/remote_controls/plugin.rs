@@ -0,0 +1,101 @@
+use super::*;
+use clack_plugin::extensions::prelude::*;
+use std::mem::MaybeUninit;
+
+/// Implementation of the Plugin-side of the Remote Controls extension.
+pub trait PluginRemoteControlsImpl {
+    /// Returns the number of remote controls pages.
+    fn count(&mut self) -> u32;
+
+    /// Retrieves a specific [`RemoteControlsPage`] from its index.
+    ///
+    /// The page is written into the given host-provided `writer`.
+    fn get(&mut self, page_index: u32, writer: &mut RemoteControlsPageWriter);
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginRemoteControls
+where
+    for<'a> P::MainThread<'a>: PluginRemoteControlsImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_remote_controls {
+            count: Some(count::<P>),
+            get: Some(get::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn count<P: Plugin>(plugin: *const clap_plugin) -> u32
+where
+    for<'a> P::MainThread<'a>: PluginRemoteControlsImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| Ok(p.main_thread().as_mut().count())).unwrap_or(0)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get<P: Plugin>(
+    plugin: *const clap_plugin,
+    page_index: u32,
+    page: *mut clap_remote_controls_page,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginRemoteControlsImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        if page.is_null() {
+            return Err(PluginWrapperError::NulPtr(
+                "clap_remote_controls_page output",
+            ));
+        };
+
+        let mut writer = RemoteControlsPageWriter {
+            // SAFETY: we just checked the pointer is non-null, and the host guarantees it is
+            // valid for writes. It doesn't have to be initialized.
+            buf: &mut *page.cast(),
+            is_set: false,
+        };
+
+        p.main_thread().as_mut().get(page_index, &mut writer);
+        Ok(writer.is_set)
+    })
+    .unwrap_or(false)
+}
+
+/// A helper struct to write a [`RemoteControlsPage`] into the host's provided buffer.
+pub struct RemoteControlsPageWriter<'a> {
+    buf: &'a mut MaybeUninit<clap_remote_controls_page>,
+    is_set: bool,
+}
+
+impl RemoteControlsPageWriter<'_> {
+    /// Writes the given [`RemoteControlsPage`] into the host's buffer.
+    #[inline]
+    pub fn write(&mut self, page: &RemoteControlsPage) {
+        self.buf.write(page.to_raw());
+        self.is_set = true;
+    }
+}
+
+impl HostRemoteControls {
+    /// Informs the host that the remote controls pages have changed and need to be rescanned.
+    #[inline]
+    pub fn changed(&self, host: &mut HostMainThreadHandle) {
+        if let Some(changed) = host.use_extension(&self.0).changed {
+            // SAFETY: This type ensures the function pointer is valid.
+            unsafe { changed(host.as_raw()) }
+        }
+    }
+
+    /// Suggests the host to display the page matching the given `page_id`.
+    ///
+    /// The host may ignore this suggestion.
+    #[inline]
+    pub fn suggest_page(&self, host: &mut HostMainThreadHandle, page_id: ClapId) {
+        if let Some(suggest_page) = host.use_extension(&self.0).suggest_page {
+            // SAFETY: This type ensures the function pointer is valid.
+            unsafe { suggest_page(host.as_raw(), page_id.get()) }
+        }
+    }
+}
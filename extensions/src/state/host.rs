@@ -4,6 +4,21 @@ use clack_host::extensions::prelude::*;
 use std::io::{Read, Write};
 
 impl PluginState {
+    /// Loads the plugin's state from the given [reader](Read).
+    ///
+    /// The state is streamed directly from the reader: it is wrapped into an [`InputStream`] which
+    /// is handed over to the plugin, so that the plugin pulls bytes from it incrementally, as it
+    /// needs them. This means the whole state never has to be buffered in memory by the host,
+    /// which is useful for plugins with very large states (e.g. sample-based instruments), where
+    /// the reader can be backed by a file or a network stream.
+    ///
+    /// Note that plugins may perform many small reads: it is recommended to use a buffered reader
+    /// (e.g. [`BufReader`](std::io::BufReader)) when reading from a file or a socket.
+    ///
+    /// # Errors
+    ///
+    /// This returns a [`StateError`] if the plugin failed to load its state, which may also happen
+    /// if the given reader returned an error.
     pub fn load<R: Read>(
         &self,
         plugin: &mut PluginMainThreadHandle,
@@ -71,7 +86,7 @@ where
     for<'a> <H as HostHandlers>::MainThread<'a>: HostStateImpl,
 {
     HostWrapper::<H>::handle(host, |host| {
-        host.main_thread().as_mut().mark_dirty();
+        host.main_thread()?.as_mut().mark_dirty();
 
         Ok(())
     });
@@ -0,0 +1,249 @@
+#![deny(missing_docs)]
+
+//! Allows plugins to save and restore state differently depending on the context the state is
+//! used in.
+//!
+//! This extension complements the [State extension](crate::state): in addition to the stream,
+//! the plugin is given the [`StateContextType`] the state is saved or loaded for, i.e. whether it
+//! is for a preset, for duplicating the plugin instance, or for saving a whole project.
+//!
+//! This allows plugins to e.g. only save their user-facing parameters into presets, but also
+//! include some instance-specific data (such as randomization seeds) in project saves.
+//!
+//! Plugins implementing this extension must also implement the [State extension](crate::state).
+//!
+//! This implements the final version of the extension (`clap.state-context/2`), not the earlier
+//! draft, which did not include the project context.
+
+use crate::state::StateError;
+use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
+use clap_sys::ext::draft::state_context::{
+    clap_plugin_state_context, clap_plugin_state_context_type,
+};
+use std::ffi::CStr;
+
+// clap-sys only exposes the draft version of this extension, which has no project context and
+// uses a different numbering. The ABI of the extension struct is the same in the final version.
+// SAFETY: this has a null byte at the end
+const CLAP_EXT_STATE_CONTEXT: &CStr =
+    unsafe { CStr::from_bytes_with_nul_unchecked(b"clap.state-context/2\0") };
+const CLAP_STATE_CONTEXT_FOR_PRESET: clap_plugin_state_context_type = 1;
+const CLAP_STATE_CONTEXT_FOR_DUPLICATE: clap_plugin_state_context_type = 2;
+const CLAP_STATE_CONTEXT_FOR_PROJECT: clap_plugin_state_context_type = 3;
+
+/// The Plugin-side of the State Context extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginStateContext(RawExtension<PluginExtensionSide, clap_plugin_state_context>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginStateContext {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_STATE_CONTEXT;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The different contexts a plugin's state can be saved or loaded for.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum StateContextType {
+    /// The state is saved or loaded for a preset.
+    ///
+    /// Plugins should only include data that is meaningful to share across instances and
+    /// projects.
+    Preset = CLAP_STATE_CONTEXT_FOR_PRESET,
+    /// The state is saved or loaded to duplicate the plugin instance, e.g. to copy it to another
+    /// track.
+    Duplicate = CLAP_STATE_CONTEXT_FOR_DUPLICATE,
+    /// The state is saved or loaded as part of a whole project.
+    ///
+    /// This is the same as the regular [State extension](crate::state) behavior.
+    Project = CLAP_STATE_CONTEXT_FOR_PROJECT,
+}
+
+impl StateContextType {
+    /// Returns the context type as the raw C-FFI-compatible integer type.
+    #[inline]
+    pub fn to_raw(self) -> clap_plugin_state_context_type {
+        self as _
+    }
+
+    /// Reads the context type from the raw C-FFI-compatible integer type.
+    ///
+    /// This may return [`None`] if the given integer's value doesn't match any known context type.
+    #[inline]
+    pub fn from_raw(raw: clap_plugin_state_context_type) -> Option<Self> {
+        match raw {
+            CLAP_STATE_CONTEXT_FOR_PRESET => Some(Self::Preset),
+            CLAP_STATE_CONTEXT_FOR_DUPLICATE => Some(Self::Duplicate),
+            CLAP_STATE_CONTEXT_FOR_PROJECT => Some(Self::Project),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "clack-plugin")]
+mod plugin {
+    use super::*;
+    use clack_common::stream::{InputStream, OutputStream};
+    use clack_plugin::extensions::prelude::*;
+    use clap_sys::stream::{clap_istream, clap_ostream};
+
+    /// Implementation of the Plugin-side of the State Context extension.
+    pub trait PluginStateContextImpl {
+        /// Saves the plugin's state into the given output stream, for the given context.
+        ///
+        /// # Errors
+        ///
+        /// This may return an error if the plugin failed to save its state.
+        fn save(
+            &mut self,
+            output: &mut OutputStream,
+            context: StateContextType,
+        ) -> Result<(), PluginError>;
+
+        /// Loads the plugin's state from the given input stream, for the given context.
+        ///
+        /// # Errors
+        ///
+        /// This may return an error if the plugin failed to load its state.
+        fn load(
+            &mut self,
+            input: &mut InputStream,
+            context: StateContextType,
+        ) -> Result<(), PluginError>;
+    }
+
+    // SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+    unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginStateContext
+    where
+        for<'a> P::MainThread<'a>: PluginStateContextImpl,
+    {
+        #[doc(hidden)]
+        const IMPLEMENTATION: RawExtensionImplementation =
+            RawExtensionImplementation::new(&clap_plugin_state_context {
+                save: Some(save::<P>),
+                load: Some(load::<P>),
+            });
+    }
+
+    #[allow(clippy::missing_safety_doc)]
+    unsafe extern "C" fn save<P: Plugin>(
+        plugin: *const clap_plugin,
+        stream: *const clap_ostream,
+        context_type: clap_plugin_state_context_type,
+    ) -> bool
+    where
+        for<'a> P::MainThread<'a>: PluginStateContextImpl,
+    {
+        PluginWrapper::<P>::handle(plugin, |p| {
+            let context = StateContextType::from_raw(context_type).ok_or(
+                PluginWrapperError::InvalidParameter("clap_plugin_state_context_type"),
+            )?;
+
+            let output = OutputStream::from_raw_mut(&mut *(stream as *mut _));
+            p.main_thread().as_mut().save(output, context)?;
+            Ok(())
+        })
+        .is_some()
+    }
+
+    #[allow(clippy::missing_safety_doc)]
+    unsafe extern "C" fn load<P: Plugin>(
+        plugin: *const clap_plugin,
+        stream: *const clap_istream,
+        context_type: clap_plugin_state_context_type,
+    ) -> bool
+    where
+        for<'a> P::MainThread<'a>: PluginStateContextImpl,
+    {
+        PluginWrapper::<P>::handle(plugin, |p| {
+            let context = StateContextType::from_raw(context_type).ok_or(
+                PluginWrapperError::InvalidParameter("clap_plugin_state_context_type"),
+            )?;
+
+            let input = InputStream::from_raw_mut(&mut *(stream as *mut _));
+            p.main_thread().as_mut().load(input, context)?;
+            Ok(())
+        })
+        .is_some()
+    }
+}
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
+
+#[cfg(feature = "clack-host")]
+mod host {
+    use super::*;
+    use clack_common::stream::{InputStream, OutputStream};
+    use clack_host::extensions::prelude::*;
+    use std::io::{Read, Write};
+
+    impl PluginStateContext {
+        /// Saves the plugin's state for the given context into the given [writer](Write).
+        ///
+        /// # Errors
+        ///
+        /// This returns a [`StateError`] if the plugin failed to save its state.
+        pub fn save_with_context<W: Write>(
+            &self,
+            plugin: &mut PluginMainThreadHandle,
+            writer: &mut W,
+            context: StateContextType,
+        ) -> Result<(), StateError> {
+            let mut stream = OutputStream::from_writer(writer);
+
+            // SAFETY: This type ensures the function pointer is valid.
+            let success = unsafe {
+                plugin
+                    .use_extension(&self.0)
+                    .save
+                    .ok_or(StateError::saving())?(
+                    plugin.as_raw(),
+                    stream.as_raw_mut(),
+                    context.to_raw(),
+                )
+            };
+
+            match success {
+                true => Ok(()),
+                false => Err(StateError::saving()),
+            }
+        }
+
+        /// Loads the plugin's state for the given context from the given [reader](Read).
+        ///
+        /// # Errors
+        ///
+        /// This returns a [`StateError`] if the plugin failed to load its state.
+        pub fn load_with_context<R: Read>(
+            &self,
+            plugin: &mut PluginMainThreadHandle,
+            reader: &mut R,
+            context: StateContextType,
+        ) -> Result<(), StateError> {
+            let mut stream = InputStream::from_reader(reader);
+
+            // SAFETY: This type ensures the function pointer is valid.
+            let success = unsafe {
+                plugin
+                    .use_extension(&self.0)
+                    .load
+                    .ok_or(StateError::loading())?(
+                    plugin.as_raw(),
+                    stream.as_raw_mut(),
+                    context.to_raw(),
+                )
+            };
+
+            match success {
+                true => Ok(()),
+                false => Err(StateError::loading()),
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+#![deny(missing_docs)]
+
+//! Allows plugins and hosts to agree on the speaker assignment of each channel of surround audio
+//! ports.
+//!
+//! Audio ports using surround layouts have a [`SURROUND_PORT_TYPE`] port type. Each of their
+//! channels is then mapped to a [`SurroundChannel`], which hosts can query using the plugin's
+//! channel map.
+//!
+//! Layouts can also be represented as a channel mask, in which each bit is set if the matching
+//! [`SurroundChannel`] is present in the layout. See [`SurroundChannel::mask`].
+
+use clack_common::extensions::{Extension, HostExtensionSide, PluginExtensionSide, RawExtension};
+use clap_sys::ext::draft::surround::*;
+use std::ffi::CStr;
+
+/// The audio port type of ports using a surround layout.
+pub const SURROUND_PORT_TYPE: &CStr = CLAP_PORT_SURROUND;
+
+/// The Plugin-side of the Surround extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct PluginSurround(RawExtension<PluginExtensionSide, clap_plugin_surround>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for PluginSurround {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_SURROUND;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// The Host-side of the Surround extension.
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+pub struct HostSurround(RawExtension<HostExtensionSide, clap_host_surround>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for HostSurround {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_SURROUND;
+    type ExtensionSide = HostExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+/// A speaker position a surround channel can be assigned to.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u8)]
+pub enum SurroundChannel {
+    /// Front Left.
+    FrontLeft = CLAP_SURROUND_FL as u8,
+    /// Front Right.
+    FrontRight = CLAP_SURROUND_FR as u8,
+    /// Front Center.
+    FrontCenter = CLAP_SURROUND_FC as u8,
+    /// Low Frequency Effects.
+    LowFrequency = CLAP_SURROUND_LFE as u8,
+    /// Back Left.
+    BackLeft = CLAP_SURROUND_BL as u8,
+    /// Back Right.
+    BackRight = CLAP_SURROUND_BR as u8,
+    /// Front Left of Center.
+    FrontLeftCenter = CLAP_SURROUND_FLC as u8,
+    /// Front Right of Center.
+    FrontRightCenter = CLAP_SURROUND_FRC as u8,
+    /// Back Center.
+    BackCenter = CLAP_SURROUND_BC as u8,
+    /// Side Left.
+    SideLeft = CLAP_SURROUND_SL as u8,
+    /// Side Right.
+    SideRight = CLAP_SURROUND_SR as u8,
+    /// Top Center.
+    TopCenter = CLAP_SURROUND_TC as u8,
+    /// Top Front Left.
+    TopFrontLeft = CLAP_SURROUND_TFL as u8,
+    /// Top Front Center.
+    TopFrontCenter = CLAP_SURROUND_TFC as u8,
+    /// Top Front Right.
+    TopFrontRight = CLAP_SURROUND_TFR as u8,
+    /// Top Back Left.
+    TopBackLeft = CLAP_SURROUND_TBL as u8,
+    /// Top Back Center.
+    TopBackCenter = CLAP_SURROUND_TBC as u8,
+    /// Top Back Right.
+    TopBackRight = CLAP_SURROUND_TBR as u8,
+}
+
+impl SurroundChannel {
+    /// Gets a [`SurroundChannel`] from its raw, C-FFI compatible representation.
+    ///
+    /// This returns [`None`] if the given value does not match any known speaker position.
+    #[inline]
+    pub const fn from_raw(raw: u8) -> Option<Self> {
+        use SurroundChannel::*;
+
+        let channel = match raw as u32 {
+            CLAP_SURROUND_FL => FrontLeft,
+            CLAP_SURROUND_FR => FrontRight,
+            CLAP_SURROUND_FC => FrontCenter,
+            CLAP_SURROUND_LFE => LowFrequency,
+            CLAP_SURROUND_BL => BackLeft,
+            CLAP_SURROUND_BR => BackRight,
+            CLAP_SURROUND_FLC => FrontLeftCenter,
+            CLAP_SURROUND_FRC => FrontRightCenter,
+            CLAP_SURROUND_BC => BackCenter,
+            CLAP_SURROUND_SL => SideLeft,
+            CLAP_SURROUND_SR => SideRight,
+            CLAP_SURROUND_TC => TopCenter,
+            CLAP_SURROUND_TFL => TopFrontLeft,
+            CLAP_SURROUND_TFC => TopFrontCenter,
+            CLAP_SURROUND_TFR => TopFrontRight,
+            CLAP_SURROUND_TBL => TopBackLeft,
+            CLAP_SURROUND_TBC => TopBackCenter,
+            CLAP_SURROUND_TBR => TopBackRight,
+            _ => return None,
+        };
+
+        Some(channel)
+    }
+
+    /// Returns the raw, C-FFI compatible representation of this speaker position.
+    #[inline]
+    pub const fn to_raw(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the bit matching this speaker position in a channel mask.
+    #[inline]
+    pub const fn bit(self) -> u64 {
+        1 << self.to_raw()
+    }
+
+    /// Builds a channel mask from the given list of speaker positions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_extensions::surround::SurroundChannel::*;
+    /// use clack_extensions::surround::SurroundChannel;
+    ///
+    /// let stereo = SurroundChannel::mask(&[FrontLeft, FrontRight]);
+    /// assert_eq!(stereo, 0b11);
+    /// ```
+    #[inline]
+    pub fn mask(channels: &[SurroundChannel]) -> u64 {
+        channels
+            .iter()
+            .fold(0, |mask, channel| mask | channel.bit())
+    }
+}
+
+#[cfg(feature = "clack-host")]
+mod host;
+
+#[cfg(feature = "clack-host")]
+pub use host::*;
+
+#[cfg(feature = "clack-plugin")]
+mod plugin;
+
+#[cfg(feature = "clack-plugin")]
+pub use plugin::*;
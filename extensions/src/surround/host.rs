@@ -0,0 +1,101 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+
+/// The maximum number of channels [`PluginSurround::channel_map`] can retrieve for a single port.
+pub const MAX_CHANNEL_MAP_LEN: usize = 64;
+
+impl PluginSurround {
+    /// Returns `true` if the plugin supports the layout described by the given channel mask.
+    ///
+    /// See [`SurroundChannel::mask`] to build a channel mask from a list of speaker positions.
+    pub fn is_channel_mask_supported(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        channel_mask: u64,
+    ) -> bool {
+        match plugin.use_extension(&self.0).is_channel_mask_supported {
+            None => false,
+            // SAFETY: This type ensures the function pointer is valid.
+            Some(is_supported) => unsafe { is_supported(plugin.as_raw(), channel_mask) },
+        }
+    }
+
+    /// Returns `true` if the plugin supports the layout made of the given speaker positions.
+    #[inline]
+    pub fn is_layout_supported(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        channels: &[SurroundChannel],
+    ) -> bool {
+        self.is_channel_mask_supported(plugin, SurroundChannel::mask(channels))
+    }
+
+    /// Retrieves the speaker position of each channel of the given audio port.
+    ///
+    /// Each item of the returned list matches the channel at the same index in the port. Channels
+    /// with a speaker position unknown to Clack are returned as [`None`], so that the indices of
+    /// the other channels are preserved.
+    ///
+    /// At most [`MAX_CHANNEL_MAP_LEN`] channels can be retrieved. If the plugin does not
+    /// implement this function, an empty list is returned.
+    pub fn channel_map(
+        &self,
+        plugin: &mut PluginMainThreadHandle,
+        port_index: u32,
+        is_input: bool,
+    ) -> Vec<Option<SurroundChannel>> {
+        let Some(get_channel_map) = plugin.use_extension(&self.0).get_channel_map else {
+            return Vec::new();
+        };
+
+        let mut buffer = [0u8; MAX_CHANNEL_MAP_LEN];
+
+        // SAFETY: This type ensures the function pointer is valid.
+        let len = unsafe {
+            get_channel_map(
+                plugin.as_raw(),
+                is_input,
+                port_index,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+            )
+        };
+
+        let len = (len as usize).min(buffer.len());
+
+        buffer[..len]
+            .iter()
+            .map(|&raw| SurroundChannel::from_raw(raw))
+            .collect()
+    }
+}
+
+/// Implementation of the Host-side of the Surround extension.
+pub trait HostSurroundImpl {
+    /// Informs the host that the plugin's channel maps have changed.
+    fn changed(&mut self);
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<H: HostHandlers> ExtensionImplementation<H> for HostSurround
+where
+    for<'h> <H as HostHandlers>::MainThread<'h>: HostSurroundImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_host_surround {
+            changed: Some(changed::<H>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn changed<H: HostHandlers>(host: *const clap_host)
+where
+    for<'a> <H as HostHandlers>::MainThread<'a>: HostSurroundImpl,
+{
+    HostWrapper::<H>::handle(host, |host| {
+        host.main_thread()?.as_mut().changed();
+
+        Ok(())
+    });
+}
@@ -0,0 +1,118 @@
+use super::*;
+use crate::utils::slice_from_external_parts_mut;
+use clack_plugin::extensions::prelude::*;
+use std::mem::MaybeUninit;
+
+/// Implementation of the Plugin-side of the Surround extension.
+pub trait PluginSurroundImpl {
+    /// Returns `true` if the plugin supports the layout described by the given channel mask.
+    fn is_channel_mask_supported(&mut self, channel_mask: u64) -> bool;
+
+    /// Writes the speaker position of each channel of the given audio port into the given
+    /// `writer`.
+    fn channel_map(
+        &mut self,
+        is_input: bool,
+        port_index: u32,
+        writer: &mut SurroundChannelMapWriter,
+    );
+}
+
+// SAFETY: The given struct is the CLAP extension struct for the matching side of this extension.
+unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginSurround
+where
+    for<'a> P::MainThread<'a>: PluginSurroundImpl,
+{
+    #[doc(hidden)]
+    const IMPLEMENTATION: RawExtensionImplementation =
+        RawExtensionImplementation::new(&clap_plugin_surround {
+            is_channel_mask_supported: Some(is_channel_mask_supported::<P>),
+            get_channel_map: Some(get_channel_map::<P>),
+        });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn is_channel_mask_supported<P: Plugin>(
+    plugin: *const clap_plugin,
+    channel_mask: u64,
+) -> bool
+where
+    for<'a> P::MainThread<'a>: PluginSurroundImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        Ok(p.main_thread()
+            .as_mut()
+            .is_channel_mask_supported(channel_mask))
+    })
+    .unwrap_or(false)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get_channel_map<P: Plugin>(
+    plugin: *const clap_plugin,
+    is_input: bool,
+    port_index: u32,
+    channel_map: *mut u8,
+    channel_map_capacity: u32,
+) -> u32
+where
+    for<'a> P::MainThread<'a>: PluginSurroundImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |p| {
+        if channel_map.is_null() && channel_map_capacity > 0 {
+            return Err(PluginWrapperError::NulPtr("channel_map"));
+        }
+
+        let mut writer = SurroundChannelMapWriter {
+            buf: slice_from_external_parts_mut(channel_map.cast(), channel_map_capacity as usize),
+            len: 0,
+        };
+
+        p.main_thread()
+            .as_mut()
+            .channel_map(is_input, port_index, &mut writer);
+
+        Ok(writer.len as u32)
+    })
+    .unwrap_or(0)
+}
+
+/// A helper struct to write a channel map into the host's provided buffer.
+pub struct SurroundChannelMapWriter<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    len: usize,
+}
+
+impl SurroundChannelMapWriter<'_> {
+    /// Returns the maximum number of channels the host's buffer can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Writes the given channel map into the host's buffer, replacing anything previously
+    /// written.
+    ///
+    /// If the given channel map is larger than the host's buffer, it is truncated to the
+    /// buffer's [`capacity`](Self::capacity).
+    pub fn write(&mut self, channels: &[SurroundChannel]) {
+        self.len = channels.len().min(self.buf.len());
+
+        for (dst, channel) in self.buf.iter_mut().zip(channels) {
+            dst.write(channel.to_raw());
+        }
+    }
+}
+
+impl HostSurround {
+    /// Informs the host that the plugin's channel maps have changed.
+    ///
+    /// This can only be called while the plugin is deactivated.
+    #[inline]
+    pub fn changed(&self, host: &mut HostMainThreadHandle) {
+        if let Some(changed) = host.use_extension(&self.0).changed {
+            // SAFETY: This type ensures the function pointer is valid.
+            unsafe { changed(host.as_raw()) }
+        }
+    }
+}
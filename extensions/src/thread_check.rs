@@ -23,17 +23,59 @@ mod plugin {
     use clack_plugin::host::HostSharedHandle;
 
     impl HostThreadCheck {
+        /// Asks the host whether the current thread is the main thread.
+        ///
+        /// This returns [`None`] if the host does not provide this information.
         #[inline]
         pub fn is_main_thread(&self, host: &HostSharedHandle) -> Option<bool> {
             // SAFETY: This type ensures the function pointer is valid.
             Some(unsafe { host.use_extension(&self.0).is_main_thread?(host.as_raw()) })
         }
 
+        /// Asks the host whether the current thread is an audio thread.
+        ///
+        /// This returns [`None`] if the host does not provide this information.
         #[inline]
         pub fn is_audio_thread(&self, host: &HostSharedHandle) -> Option<bool> {
             // SAFETY: This type ensures the function pointer is valid.
             Some(unsafe { host.use_extension(&self.0).is_audio_thread?(host.as_raw()) })
         }
+
+        /// Panics if the host reports the current thread is not the main thread.
+        ///
+        /// This check is best-effort: it relies entirely on the host's implementation of the
+        /// Thread Check extension, and does nothing if the host cannot tell which thread is the
+        /// current one. It is also only performed in debug builds, and compiles down to nothing
+        /// in release builds.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use clack_extensions::thread_check::HostThreadCheck;
+        /// use clack_plugin::prelude::*;
+        ///
+        /// fn rescan_everything(host: &HostSharedHandle) {
+        ///     if let Some(thread_check) = host.get_extension::<HostThreadCheck>() {
+        ///         thread_check.debug_assert_is_main_thread(host);
+        ///     }
+        ///
+        ///     /* ... */
+        /// }
+        /// ```
+        #[inline]
+        #[track_caller]
+        pub fn debug_assert_is_main_thread(&self, host: &HostSharedHandle) {
+            #[cfg(debug_assertions)]
+            if self.is_main_thread(host) == Some(false) {
+                panic!(
+                    "Expected to be called on the main thread, but the host reports thread {:?} is not the main thread",
+                    std::thread::current().id()
+                );
+            }
+
+            #[cfg(not(debug_assertions))]
+            let _ = host;
+        }
     }
 }
 
@@ -192,3 +192,8 @@ mod host {
 
 #[cfg(feature = "clack-host")]
 pub use host::*;
+
+#[cfg(feature = "clack-host")]
+mod executor;
+#[cfg(feature = "clack-host")]
+pub use executor::*;
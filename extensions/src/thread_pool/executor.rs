@@ -0,0 +1,285 @@
+use super::*;
+use clack_host::extensions::prelude::*;
+use clap_sys::plugin::clap_plugin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, TryLockError};
+use std::thread::JoinHandle;
+
+type ExecFn = unsafe extern "C" fn(*const clap_plugin, u32);
+
+/// A ready-made thread pool for hosts to execute the tasks plugins request through the
+/// Thread Pool extension.
+///
+/// This executor is backed by a fixed amount of worker threads, which are spawned when it is
+/// created, and stopped when it is dropped.
+///
+/// When [`exec`](Self::exec) is called, the requested tasks are dispatched to the worker threads,
+/// as well as to the calling thread itself, which also processes tasks instead of just idling.
+/// Each thread keeps fetching the next task to process until they are all done, and
+/// [`exec`](Self::exec) then only returns once all of them have completed.
+///
+/// The same executor can be shared between multiple plugin instances. However, it can only process
+/// the tasks of a single request at a time: if another request is already being processed,
+/// the tasks are all executed on the calling thread instead.
+///
+/// # Realtime Safety
+///
+/// Executing tasks does not allocate. However, it does synchronize with the worker threads, which
+/// requires locking.
+///
+/// # Example
+///
+/// ```
+/// use clack_extensions::thread_pool::{HostThreadPoolImpl, PluginThreadPool, ThreadPoolExecutor};
+/// use clack_host::prelude::*;
+/// use std::sync::Arc;
+///
+/// struct MyHostAudioProcessor<'a> {
+///     executor: Arc<ThreadPoolExecutor>,
+///     plugin: InitializedPluginHandle<'a>,
+///     thread_pool: PluginThreadPool,
+/// }
+///
+/// impl HostThreadPoolImpl for MyHostAudioProcessor<'_> {
+///     fn request_exec(&mut self, task_count: u32) -> Result<(), HostError> {
+///         self.plugin
+///             .access(|plugin| self.executor.exec(plugin, self.thread_pool, task_count))
+///             .ok_or(HostError::Message("Plugin was destroyed"))?
+///             .map_err(|_| HostError::Message("Plugin does not support thread pool execution"))
+///     }
+/// }
+/// ```
+pub struct ThreadPoolExecutor {
+    shared: Arc<ExecutorShared>,
+    request_lock: Mutex<()>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPoolExecutor {
+    /// Creates a new executor, spawning the given amount of worker threads.
+    ///
+    /// If `worker_count` is zero, all tasks are executed on the thread calling
+    /// [`exec`](Self::exec).
+    ///
+    /// # Panics
+    ///
+    /// This panics if the operating system failed to spawn a worker thread.
+    pub fn new(worker_count: usize) -> Self {
+        let shared = Arc::new(ExecutorShared {
+            state: Mutex::new(ExecutorState {
+                job: None,
+                generation: 0,
+                active_workers: 0,
+                is_shutting_down: false,
+            }),
+            work_available: Condvar::new(),
+            work_done: Condvar::new(),
+            next_task: AtomicUsize::new(0),
+        });
+
+        let workers = (0..worker_count)
+            .map(|i| {
+                let shared = Arc::clone(&shared);
+                std::thread::Builder::new()
+                    .name(format!("clack-thread-pool-{i}"))
+                    .spawn(move || shared.run_worker())
+                    .expect("Failed to spawn thread pool worker")
+            })
+            .collect();
+
+        Self {
+            shared,
+            request_lock: Mutex::new(()),
+            workers,
+        }
+    }
+
+    /// Returns the number of worker threads of this executor.
+    #[inline]
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Executes `task_count` tasks of the given `plugin`, by calling its `exec` callback once with
+    /// each task index in the `0..task_count` range.
+    ///
+    /// This method blocks the current thread until all tasks have been executed.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`ThreadPoolRequestError`] if the plugin does not provide the `exec` callback,
+    /// in which case no task is executed.
+    pub fn exec(
+        &self,
+        plugin: PluginSharedHandle<'_>,
+        thread_pool: PluginThreadPool,
+        task_count: u32,
+    ) -> Result<(), ThreadPoolRequestError> {
+        let exec = plugin
+            .use_extension(&thread_pool.0)
+            .exec
+            .ok_or(ThreadPoolRequestError)?;
+
+        let job = Job {
+            plugin: plugin.as_raw_ptr(),
+            exec,
+            task_count: task_count as usize,
+        };
+
+        if task_count == 0 {
+            return Ok(());
+        }
+
+        // If another request is already in progress, we just do all the work ourselves.
+        let _request_guard = match self.request_lock.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                job.run_all_inline();
+                return Ok(());
+            }
+        };
+
+        if self.workers.is_empty() {
+            job.run_all_inline();
+        } else {
+            self.shared.dispatch(job);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for ThreadPoolExecutor {
+    fn drop(&mut self) {
+        self.shared.lock_state().is_shutting_down = true;
+        self.shared.work_available.notify_all();
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Job {
+    plugin: *const clap_plugin,
+    exec: ExecFn,
+    task_count: usize,
+}
+
+// SAFETY: The plugin's exec callback is thread-safe, and the dispatching thread waits for all tasks
+// to complete, ensuring the plugin pointer stays valid for as long as the job is being processed.
+unsafe impl Send for Job {}
+
+impl Job {
+    /// Runs tasks until all of them have been picked up.
+    fn run_tasks(&self, next_task: &AtomicUsize) {
+        loop {
+            let task_index = next_task.fetch_add(1, Ordering::Relaxed);
+            if task_index >= self.task_count {
+                return;
+            }
+
+            self.run_task(task_index as u32);
+        }
+    }
+
+    fn run_all_inline(&self) {
+        self.run_tasks(&AtomicUsize::new(0))
+    }
+
+    #[inline]
+    fn run_task(&self, task_index: u32) {
+        // Panics can't unwind out of the plugin's extern "C" callback: they abort the process
+        // instead, so there is nothing to catch here.
+        // SAFETY: The plugin pointer and exec function are valid until all tasks are completed.
+        unsafe { (self.exec)(self.plugin, task_index) }
+    }
+}
+
+struct ExecutorState {
+    job: Option<Job>,
+    generation: u64,
+    active_workers: usize,
+    is_shutting_down: bool,
+}
+
+struct ExecutorShared {
+    state: Mutex<ExecutorState>,
+    work_available: Condvar,
+    work_done: Condvar,
+    next_task: AtomicUsize,
+}
+
+impl ExecutorShared {
+    #[inline]
+    fn lock_state(&self) -> MutexGuard<'_, ExecutorState> {
+        // The state is never left inconsistent, as no user code runs while it is locked.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn dispatch(&self, job: Job) {
+        {
+            let mut state = self.lock_state();
+            self.next_task.store(0, Ordering::Relaxed);
+            state.job = Some(job);
+            state.generation = state.generation.wrapping_add(1);
+        }
+
+        self.work_available.notify_all();
+
+        // Do some of the work ourselves while the workers wake up.
+        job.run_tasks(&self.next_task);
+
+        let mut state = self.lock_state();
+        // All tasks have been picked up, there is no need for other workers to join in.
+        state.job = None;
+
+        while state.active_workers > 0 {
+            state = self
+                .work_done
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    fn run_worker(&self) {
+        let mut last_generation = 0;
+
+        loop {
+            let job = {
+                let mut state = self.lock_state();
+
+                loop {
+                    if state.is_shutting_down {
+                        return;
+                    }
+
+                    if state.generation != last_generation {
+                        last_generation = state.generation;
+
+                        if let Some(job) = state.job {
+                            state.active_workers += 1;
+                            break job;
+                        }
+                    }
+
+                    state = self
+                        .work_available
+                        .wait(state)
+                        .unwrap_or_else(|e| e.into_inner());
+                }
+            };
+
+            job.run_tasks(&self.next_task);
+
+            let mut state = self.lock_state();
+            state.active_workers -= 1;
+
+            if state.active_workers == 0 {
+                self.work_done.notify_all();
+            }
+        }
+    }
+}
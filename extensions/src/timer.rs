@@ -228,7 +228,7 @@ mod host {
         for<'a> <H as HostHandlers>::MainThread<'a>: HostTimerImpl,
     {
         HostWrapper::<H>::handle(host, |host| {
-            match host.main_thread().as_mut().register_timer(period_ms) {
+            match host.main_thread()?.as_mut().register_timer(period_ms) {
                 Ok(id) => {
                     *timer_id = id.0;
                     Ok(true)
@@ -252,7 +252,7 @@ mod host {
     {
         HostWrapper::<H>::handle(host, |host| {
             Ok(host
-                .main_thread()
+                .main_thread()?
                 .as_mut()
                 .unregister_timer(TimerId(timer_id))
                 .is_ok())
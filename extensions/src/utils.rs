@@ -26,6 +26,27 @@ pub(crate) unsafe fn write_to_array_buf<const N: usize>(dst: *mut [c_char; N], v
     dst.add(max_len).write(0)
 }
 
+/// A safer form of [`core::slice::from_raw_parts`] that returns a properly aligned slice in case
+/// the length is 0.
+///
+/// In C it is common for empty slices to be represented using a null pointer, but this is UB in
+/// Rust, as all references must be aligned and non-null.
+///
+/// This helper avoids that pitfall by ignoring the pointer if the length is zero.
+///
+/// # Safety
+///
+/// Same requirements as [`core::slice::from_raw_parts`], except the pointer *can* be null or
+/// dangling if `len == 0`.
+#[inline]
+pub(crate) unsafe fn slice_from_external_parts<'a, T>(data: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        return &[];
+    }
+
+    core::slice::from_raw_parts(data, len)
+}
+
 /// A safer form of [`core::slice::from_raw_parts_mut`] that returns a properly aligned slice in case
 /// the length is 0.
 ///
@@ -137,7 +137,7 @@ mod host {
         for<'a> <H as HostHandlers>::MainThread<'a>: HostVoiceInfoImpl,
     {
         HostWrapper::<H>::handle(host, |host| {
-            host.main_thread().as_mut().changed();
+            host.main_thread()?.as_mut().changed();
             Ok(())
         });
     }
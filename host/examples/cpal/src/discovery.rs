@@ -5,10 +5,10 @@ use clack_host::bundle::PluginBundleError;
 use clack_host::prelude::*;
 use rayon::prelude::*;
 use std::error::Error;
-use std::ffi::{OsStr, OsString};
+use std::ffi::{CStr, OsStr, OsString};
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
-use walkdir::{DirEntry, WalkDir};
+use walkdir::WalkDir;
 
 /// The descriptor of a plugin that was found, alongside the bundle it was loaded from, as well
 /// as its path.
@@ -23,51 +23,126 @@ pub struct FoundBundlePlugin {
 
 /// Scans the CLAP standard paths for all the plugin descriptors that match the given ID.
 pub fn scan_for_plugin_id(id: &str) -> Vec<FoundBundlePlugin> {
-    let standard_paths = standard_clap_paths();
+    let search_paths = clap_search_paths();
 
     println!("Scanning the following directories for CLAP plugin with ID {id}:");
-    for path in &standard_paths {
-        println!("\t - {}", path.display())
+    for search_path in &search_paths {
+        println!(
+            "\t - {} ({})",
+            search_path.path.display(),
+            search_path.source
+        )
     }
 
+    let standard_paths: Vec<PathBuf> = search_paths.into_iter().map(|p| p.path).collect();
+
     let found_bundles = search_for_potential_bundles(&standard_paths);
     println!("\t * Found {} potential CLAP bundles.", found_bundles.len());
 
     scan_plugins(&found_bundles, id)
 }
 
-/// Returns a list of all the standard CLAP search paths, per the CLAP specification.
-fn standard_clap_paths() -> Vec<PathBuf> {
+/// Where a given CLAP search path comes from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PathSource {
+    /// The path was given in the `CLAP_PATH` environment variable.
+    ///
+    /// Like the `PATH` variable, it may contain multiple paths, separated by `:` on Unix-like
+    /// platforms, and by `;` on Windows.
+    EnvVar,
+    /// The path is a standard, per-user directory:
+    ///
+    /// * `~/.clap` on all platforms;
+    /// * `~/Library/Audio/Plug-Ins/CLAP` on macOS;
+    /// * `%LOCALAPPDATA%\Programs\Common\CLAP` on Windows.
+    User,
+    /// The path is a standard, system-wide directory:
+    ///
+    /// * `/usr/lib/clap` on Unix-like platforms (including macOS);
+    /// * `/Library/Audio/Plug-Ins/CLAP` on macOS;
+    /// * `%COMMONPROGRAMFILES%\CLAP` on Windows.
+    System,
+}
+
+impl Display for PathSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PathSource::EnvVar => "from CLAP_PATH",
+            PathSource::User => "user",
+            PathSource::System => "system",
+        })
+    }
+}
+
+/// A CLAP search path, alongside where it comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClapSearchPath {
+    /// The directory to search CLAP bundles in.
+    pub path: PathBuf,
+    /// Where this path comes from.
+    pub source: PathSource,
+}
+
+impl ClapSearchPath {
+    fn new(path: impl Into<PathBuf>, source: PathSource) -> Self {
+        Self {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+/// Returns a list of all the standard CLAP search paths, per the CLAP specification, alongside
+/// their source.
+///
+/// User paths come first, followed by system paths, and finally paths from the `CLAP_PATH`
+/// environment variable, in the order they appear in it.
+pub fn clap_search_paths() -> Vec<ClapSearchPath> {
     let mut paths = vec![];
 
     if let Some(home_dir) = dirs::home_dir() {
-        paths.push(home_dir.join(".clap"));
+        paths.push(ClapSearchPath::new(
+            home_dir.join(".clap"),
+            PathSource::User,
+        ));
 
         #[cfg(target_os = "macos")]
         {
-            paths.push(home_dir.join("Library/Audio/Plug-Ins/CLAP"));
+            paths.push(ClapSearchPath::new(
+                home_dir.join("Library/Audio/Plug-Ins/CLAP"),
+                PathSource::User,
+            ));
         }
     }
 
     #[cfg(windows)]
     {
         if let Some(val) = std::env::var_os("CommonProgramFiles") {
-            paths.push(PathBuf::from(val).join("CLAP"))
+            paths.push(ClapSearchPath::new(
+                PathBuf::from(val).join("CLAP"),
+                PathSource::System,
+            ))
         }
 
         if let Some(dir) = dirs::config_local_dir() {
-            paths.push(dir.join("Programs\\Common\\CLAP"));
+            paths.push(ClapSearchPath::new(
+                dir.join("Programs\\Common\\CLAP"),
+                PathSource::User,
+            ));
         }
     }
 
     #[cfg(target_os = "macos")]
     {
-        paths.push(PathBuf::from("/Library/Audio/Plug-Ins/CLAP"));
+        paths.push(ClapSearchPath::new(
+            "/Library/Audio/Plug-Ins/CLAP",
+            PathSource::System,
+        ));
     }
 
     #[cfg(target_family = "unix")]
     {
-        paths.push("/usr/lib/clap".into())
+        paths.push(ClapSearchPath::new("/usr/lib/clap", PathSource::System))
     }
 
     /// Splits a PATH-like variable
@@ -90,22 +165,99 @@ fn standard_clap_paths() -> Vec<PathBuf> {
     }
 
     if let Some(env_var) = std::env::var_os("CLAP_PATH") {
-        paths.extend(split_path(&env_var).into_iter().map(PathBuf::from))
+        paths.extend(
+            split_path(&env_var)
+                .into_iter()
+                .map(|path| ClapSearchPath::new(path, PathSource::EnvVar)),
+        )
     }
 
     paths
 }
 
-/// Returns `true` if the given entry could refer to a CLAP bundle.
+/// Returns a list of all the standard CLAP search paths, per the CLAP specification.
+///
+/// See [`clap_search_paths`] to also know where each of these paths come from.
+#[allow(dead_code)]
+pub fn standard_clap_paths() -> Vec<PathBuf> {
+    clap_search_paths().into_iter().map(|p| p.path).collect()
+}
+
+/// A file or directory that may be a CLAP bundle.
 ///
-/// CLAP bundles are files that end with the `.clap` extension.
-fn is_clap_bundle(dir_entry: &DirEntry) -> bool {
-    dir_entry.file_type().is_file() && dir_entry.file_name().to_string_lossy().ends_with(".clap")
+/// On Linux and Windows, CLAP bundles are single dynamic library files with the `.clap` extension.
+/// On macOS however, they are bundle directories with the `.clap` extension, which contain the
+/// actual dynamic library at `Contents/MacOS/<name>`, where `<name>` is the bundle's name without
+/// its extension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PotentialClapFile {
+    path: PathBuf,
+}
+
+impl PotentialClapFile {
+    /// Wraps the given path, which may or may not point to a CLAP bundle.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The path of the bundle itself, i.e. the `.clap` file or directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if this could refer to a CLAP bundle on the current platform.
+    ///
+    /// This only checks the name and structure of the file. The bundle may still fail to load.
+    pub fn may_be_clap_bundle(&self) -> bool {
+        if self.path.extension() != Some(OsStr::new("clap")) {
+            return false;
+        }
+
+        if cfg!(target_os = "macos") {
+            self.path.is_dir() && self.dylib_path().is_file()
+        } else {
+            self.path.is_file()
+        }
+    }
+
+    /// Returns the path of the dynamic library to actually load for this bundle.
+    ///
+    /// On macOS, this is the binary inside of the bundle directory. On other platforms (or if the
+    /// bundle isn't a directory), this is the bundle's path itself.
+    pub fn dylib_path(&self) -> PathBuf {
+        #[cfg(target_os = "macos")]
+        if self.path.is_dir() {
+            if let Some(name) = self.path.file_stem() {
+                return self.path.join("Contents").join("MacOS").join(name);
+            }
+        }
+
+        self.path.clone()
+    }
+
+    /// Loads this bundle.
+    ///
+    /// The [dynamic library](Self::dylib_path) is loaded, but the bundle's entry is initialized
+    /// with the [bundle's path](Self::path) itself, as required by the CLAP specification. Plugins
+    /// may rely on it to locate their resources.
+    ///
+    /// # Safety
+    ///
+    /// This loads and initializes an external library, see [`PluginBundle::load`].
+    pub unsafe fn load(&self) -> Result<PluginBundle, PluginBundleError> {
+        // SAFETY: this has a null byte at the end
+        let entry_symbol = CStr::from_bytes_with_nul_unchecked(b"clap_entry\0");
+
+        let library = libloading::Library::new(self.dylib_path())
+            .map_err(PluginBundleError::LibraryLoadingError)?;
+
+        PluginBundle::load_from_symbol_in_library(&self.path, library, entry_symbol)
+    }
 }
 
 /// Search the given directories' contents, and returns a list of all the files that could be CLAP
 /// bundles.
-fn search_for_potential_bundles(search_dirs: &[PathBuf]) -> Vec<DirEntry> {
+fn search_for_potential_bundles(search_dirs: &[PathBuf]) -> Vec<PotentialClapFile> {
     search_dirs
         .iter()
         .flat_map(|path| {
@@ -113,23 +265,24 @@ fn search_for_potential_bundles(search_dirs: &[PathBuf]) -> Vec<DirEntry> {
                 .follow_links(true)
                 .into_iter()
                 .filter_map(|e| e.ok())
-                .filter(is_clap_bundle)
+                .map(|e| PotentialClapFile::new(e.into_path()))
+                .filter(PotentialClapFile::may_be_clap_bundle)
         })
         .collect()
 }
 
 /// Loads all the given bundles, and returns a list of all the plugins that match the given ID.
-fn scan_plugins(bundles: &[DirEntry], searched_id: &str) -> Vec<FoundBundlePlugin> {
+fn scan_plugins(bundles: &[PotentialClapFile], searched_id: &str) -> Vec<FoundBundlePlugin> {
     bundles
         .par_iter()
-        .filter_map(|p| scan_plugin(p.path(), searched_id))
+        .filter_map(|p| scan_plugin(p, searched_id))
         .collect()
 }
 
 /// Scans a given bundle, looking for a plugin matching the given ID.
 /// If this file wasn't a bundle or doesn't contain a plugin with a given ID, this returns `None`.
-fn scan_plugin(path: &Path, searched_id: &str) -> Option<FoundBundlePlugin> {
-    let Ok(bundle) = (unsafe { PluginBundle::load(path) }) else {
+fn scan_plugin(file: &PotentialClapFile, searched_id: &str) -> Option<FoundBundlePlugin> {
+    let Ok(bundle) = (unsafe { file.load() }) else {
         return None;
     };
     for plugin in bundle.get_plugin_factory()?.plugin_descriptors() {
@@ -141,7 +294,7 @@ fn scan_plugin(path: &Path, searched_id: &str) -> Option<FoundBundlePlugin> {
             return Some(FoundBundlePlugin {
                 plugin,
                 bundle,
-                path: path.to_path_buf(),
+                path: file.path().to_path_buf(),
             });
         }
     }
@@ -198,7 +351,7 @@ impl Display for PluginDescriptor {
 pub fn list_plugins_in_bundle(
     bundle_path: &Path,
 ) -> Result<Vec<FoundBundlePlugin>, DiscoveryError> {
-    let bundle = unsafe { PluginBundle::load(bundle_path)? };
+    let bundle = unsafe { PotentialClapFile::new(bundle_path).load()? };
     let Some(plugin_factory) = bundle.get_plugin_factory() else {
         return Err(DiscoveryError::MissingPluginFactory);
     };
@@ -245,7 +398,7 @@ pub fn load_plugin_id_from_path(
     bundle_path: &Path,
     id: &str,
 ) -> Result<Option<FoundBundlePlugin>, DiscoveryError> {
-    let bundle = unsafe { PluginBundle::load(bundle_path)? };
+    let bundle = unsafe { PotentialClapFile::new(bundle_path).load()? };
     let Some(plugin_factory) = bundle.get_plugin_factory() else {
         return Err(DiscoveryError::MissingPluginFactory);
     };
@@ -260,3 +413,71 @@ pub fn load_plugin_id_from_path(
             path: bundle_path.to_path_buf(),
         }))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    /// Creates an empty fixture directory, unique to the given test.
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "clack-cpal-discovery-{}-{name}",
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn ignores_files_without_clap_extension() {
+        let dir = fixture_dir("extension");
+        fs::write(dir.join("Diva.so"), b"").unwrap();
+
+        let file = PotentialClapFile::new(dir.join("Diva.so"));
+        assert!(!file.may_be_clap_bundle());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn file_bundles_are_loaded_directly() {
+        let dir = fixture_dir("file-bundle");
+        fs::write(dir.join("Diva.clap"), b"").unwrap();
+        fs::create_dir_all(dir.join("Directory.clap")).unwrap();
+
+        let file = PotentialClapFile::new(dir.join("Diva.clap"));
+        assert!(file.may_be_clap_bundle());
+        assert_eq!(file.dylib_path(), dir.join("Diva.clap"));
+
+        let directory = PotentialClapFile::new(dir.join("Directory.clap"));
+        assert!(!directory.may_be_clap_bundle());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn directory_bundles_load_their_inner_binary() {
+        let dir = fixture_dir("directory-bundle");
+        fs::create_dir_all(dir.join("Diva.clap/Contents/MacOS")).unwrap();
+        fs::write(dir.join("Diva.clap/Contents/MacOS/Diva"), b"").unwrap();
+        fs::create_dir_all(dir.join("Empty.clap/Contents/MacOS")).unwrap();
+        fs::write(dir.join("File.clap"), b"").unwrap();
+
+        let bundle = PotentialClapFile::new(dir.join("Diva.clap"));
+        assert!(bundle.may_be_clap_bundle());
+        assert_eq!(
+            bundle.dylib_path(),
+            dir.join("Diva.clap/Contents/MacOS/Diva")
+        );
+
+        assert!(!PotentialClapFile::new(dir.join("Empty.clap")).may_be_clap_bundle());
+        assert!(!PotentialClapFile::new(dir.join("File.clap")).may_be_clap_bundle());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
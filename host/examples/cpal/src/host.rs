@@ -158,7 +158,13 @@ impl<'a> MainThreadHandler<'a> for CpalHostMainThread<'a> {
 /// running until the window is closed.
 ///
 /// Otherwise, the plugin runs headless, and will keep running until the process is killed.
-pub fn run(plugin: FoundBundlePlugin) -> Result<(), Box<dyn Error>> {
+///
+/// If a `plugin_sample_rate` is given, the plugin runs at that sample rate instead of the audio
+/// device's.
+pub fn run(
+    plugin: FoundBundlePlugin,
+    plugin_sample_rate: Option<u32>,
+) -> Result<(), Box<dyn Error>> {
     let host_info = host_info();
     let plugin_id = CString::new(plugin.plugin.id.as_str())?;
     let (sender, receiver) = unbounded();
@@ -171,7 +177,7 @@ pub fn run(plugin: FoundBundlePlugin) -> Result<(), Box<dyn Error>> {
         &host_info,
     )?;
 
-    let _stream = activate_to_stream(&mut instance)?;
+    let _stream = activate_to_stream(&mut instance, plugin_sample_rate)?;
 
     let gui = instance
         .access_handler(|h| h.gui)
@@ -3,7 +3,8 @@ use clack_host::prelude::*;
 use clack_host::process::StartedPluginAudioProcessor;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{
-    BuildStreamError, Device, FromSample, OutputCallbackInfo, SampleFormat, Stream, StreamConfig,
+    BuildStreamError, Device, FromSample, OutputCallbackInfo, Sample, SampleFormat, Stream,
+    StreamConfig,
 };
 use std::error::Error;
 
@@ -13,21 +14,32 @@ mod buffers;
 mod config;
 /// MIDI handling.
 mod midi;
+/// Sample rate conversion, if the plugin and the device run at different sample rates.
+mod resampler;
 
 use buffers::*;
 use config::*;
 use midi::*;
+use resampler::*;
 
 /// Activates the given plugin instance, and outputs its processed audio to a new CPAL stream.
+///
+/// If a `plugin_sample_rate` is given, the plugin runs at that sample rate, and its output is
+/// resampled to the device's sample rate if they differ.
 pub fn activate_to_stream(
     instance: &mut PluginInstance<CpalHost>,
+    plugin_sample_rate: Option<u32>,
 ) -> Result<Stream, Box<dyn Error>> {
     // Initialize CPAL
     let cpal_host = cpal::default_host();
 
     let output_device = cpal_host.default_output_device().unwrap();
 
-    let config = FullAudioConfig::find_best_from(&output_device, instance)?;
+    let mut config = FullAudioConfig::find_best_from(&output_device, instance)?;
+    if let Some(plugin_sample_rate) = plugin_sample_rate {
+        config.plugin_sample_rate = plugin_sample_rate;
+    }
+
     println!("Using negociated audio output settings: {config}");
 
     let midi = MidiReceiver::new(44_100, instance)?;
@@ -108,6 +120,12 @@ struct StreamAudioProcessor {
     audio_processor: StartedPluginAudioProcessor<CpalHost>,
     /// The audio buffers.
     buffers: HostAudioBuffers,
+    /// The resampler, if the plugin runs at a different sample rate than the CPAL stream.
+    resampler: Option<Resampler>,
+    /// The resampled output data, ready to be sent to CPAL's interleaved buffer.
+    resampled: Vec<f32>,
+    /// The number of output channels of the CPAL stream.
+    output_channel_count: usize,
     /// The MIDI event receiver.
     midi_receiver: Option<MidiReceiver>,
     /// A steady frame counter, used by the plugin's process() method.
@@ -121,9 +139,24 @@ impl StreamAudioProcessor {
         midi_receiver: Option<MidiReceiver>,
         config: FullAudioConfig,
     ) -> Self {
+        let output_channel_count = config.output_channel_count;
+        let max_buffer_len = config.max_likely_buffer_size as usize * output_channel_count;
+
+        let resampler = config.needs_resampling().then(|| {
+            Resampler::new(
+                config.plugin_sample_rate,
+                config.sample_rate,
+                output_channel_count,
+                config.max_likely_buffer_size as usize,
+            )
+        });
+
         Self {
             audio_processor: plugin_instance,
             buffers: HostAudioBuffers::from_config(config),
+            resampler,
+            resampled: Vec::with_capacity(max_buffer_len),
+            output_channel_count,
             midi_receiver,
             steady_counter: 0,
         }
@@ -137,11 +170,44 @@ impl StreamAudioProcessor {
     ///
     /// This method also collects all the MIDI events that have been received since the last
     /// process call., and feeds them to the plugin.
+    ///
+    /// If the plugin runs at a different sample rate than the CPAL stream, the plugin processes
+    /// exactly as many frames as the resampler needs to fill the CPAL buffer.
     pub fn process<S: FromSample<f32>>(&mut self, data: &mut [S]) {
-        self.buffers.ensure_buffer_size_matches(data.len());
-        let sample_count = self.buffers.cpal_buf_len_to_frame_count(data.len());
+        let Some(mut resampler) = self.resampler.take() else {
+            if self.process_plugin(data.len()) {
+                self.buffers.write_to_cpal_buffer(data);
+            }
+            return;
+        };
 
-        let (ins, mut outs) = self.buffers.prepare_plugin_buffers(data.len());
+        let frame_count = data.len() / self.output_channel_count;
+        let plugin_buf_len = resampler.input_frames_needed(frame_count) * self.output_channel_count;
+
+        if plugin_buf_len > 0 {
+            // If processing fails, the output buffers have still been cleared beforehand: this
+            // feeds the resampler with silence instead.
+            self.process_plugin(plugin_buf_len);
+            resampler.push_input(self.buffers.mux_output(plugin_buf_len));
+        }
+
+        self.resampled.resize(data.len(), 0.0);
+        resampler.process(&mut self.resampled);
+        self.resampler = Some(resampler);
+
+        for (out, resampled) in data.iter_mut().zip(&self.resampled) {
+            *out = resampled.to_sample();
+        }
+    }
+
+    /// Makes the plugin process enough frames to fill an interleaved buffer of the given length.
+    ///
+    /// Returns `true` if processing succeeded, `false` otherwise.
+    fn process_plugin(&mut self, cpal_buf_len: usize) -> bool {
+        self.buffers.ensure_buffer_size_matches(cpal_buf_len);
+        let sample_count = self.buffers.cpal_buf_len_to_frame_count(cpal_buf_len);
+
+        let (ins, mut outs) = self.buffers.prepare_plugin_buffers(cpal_buf_len);
 
         let events = if let Some(midi) = self.midi_receiver.as_mut() {
             midi.receive_all_events(sample_count as u64)
@@ -149,18 +215,23 @@ impl StreamAudioProcessor {
             InputEvents::empty()
         };
 
-        match self.audio_processor.process(
+        let result = self.audio_processor.process(
             &ins,
             &mut outs,
             &events,
             &mut OutputEvents::void(),
             Some(self.steady_counter),
             None,
-        ) {
-            Ok(_) => self.buffers.write_to_cpal_buffer(data),
-            Err(e) => eprintln!("{e}"),
-        }
+        );
 
         self.steady_counter += sample_count as u64;
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("{e}");
+                false
+            }
+        }
     }
 }
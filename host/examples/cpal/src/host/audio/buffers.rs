@@ -44,7 +44,7 @@ impl HostAudioBuffers {
 
         let total_input_channel_count = config.plugin_input_port_config.total_channel_count();
         let total_output_channel_count = config.plugin_output_port_config.total_channel_count();
-        let frame_count = config.max_likely_buffer_size as usize;
+        let frame_count = config.plugin_max_frames_count() as usize;
 
         Self {
             input_ports: AudioPorts::with_capacity(
@@ -163,9 +163,21 @@ impl HostAudioBuffers {
     /// Writes the internal output buffer to the given CPAL buffer, also performing any downmixing
     /// or interleaving if necessary.
     pub fn write_to_cpal_buffer<S: FromSample<f32>>(&mut self, destination: &mut [S]) {
+        let muxed = self.mux_output(destination.len());
+
+        for (out, muxed) in destination.iter_mut().zip(muxed) {
+            *out = muxed.to_sample();
+        }
+    }
+
+    /// Downmixes or interleaves the internal output buffer to match the CPAL stream's channel
+    /// layout, and returns the resulting interleaved samples.
+    ///
+    /// The given length is the total number of interleaved samples to output, across all channels.
+    pub fn mux_output(&mut self, muxed_len: usize) -> &[f32] {
         let main_output = &self.output_port_channels
             [self.config.plugin_output_port_config.main_port_index as usize];
-        let muxed = &mut self.muxed[..destination.len()];
+        let muxed = &mut self.muxed[..muxed_len];
 
         let plugin_output_channel_count = self
             .config
@@ -190,9 +202,7 @@ impl HostAudioBuffers {
             (_, _) => unreachable!(),
         }
 
-        for (out, muxed) in destination.iter_mut().zip(muxed) {
-            *out = muxed.to_sample();
-        }
+        muxed
     }
 }
 
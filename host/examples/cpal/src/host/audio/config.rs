@@ -32,6 +32,10 @@ pub struct FullAudioConfig {
     pub max_likely_buffer_size: u32,
     /// The sample rate the stream will run at.
     pub sample_rate: u32,
+    /// The sample rate the plugin will run at.
+    ///
+    /// If this differs from the stream's `sample_rate`, the plugin's output will be resampled.
+    pub plugin_sample_rate: u32,
     /// The sample format the stream will use.
     pub sample_format: SampleFormat,
 }
@@ -67,10 +71,33 @@ impl FullAudioConfig {
     /// Returns the CLAP plugin audio configuration describing this configuration.
     pub fn as_clack_plugin_config(&self) -> PluginAudioConfiguration {
         PluginAudioConfiguration {
-            sample_rate: self.sample_rate as f64,
-            min_frames_count: self.min_buffer_size,
-            max_frames_count: self.max_likely_buffer_size,
+            sample_rate: self.plugin_sample_rate as f64,
+            // When resampling, the plugin may have to process any number of frames at once.
+            min_frames_count: if self.needs_resampling() {
+                1
+            } else {
+                self.min_buffer_size
+            },
+            max_frames_count: self.plugin_max_frames_count(),
+        }
+    }
+
+    /// Returns whether the plugin runs at a different sample rate than the stream.
+    pub fn needs_resampling(&self) -> bool {
+        self.plugin_sample_rate != self.sample_rate
+    }
+
+    /// Returns the likely maximum number of frames the plugin will have to process at once.
+    ///
+    /// When resampling, this accounts for the extra frames the resampler may need to produce
+    /// a full stream buffer.
+    pub fn plugin_max_frames_count(&self) -> u32 {
+        if !self.needs_resampling() {
+            return self.max_likely_buffer_size;
         }
+
+        let ratio = self.plugin_sample_rate as f64 / self.sample_rate as f64;
+        (self.max_likely_buffer_size as f64 * ratio).ceil() as u32 + 2
     }
 }
 
@@ -85,7 +112,17 @@ impl Display for FullAudioConfig {
             self.max_likely_buffer_size,
             &self.plugin_output_port_config.main_port().name,
             self.plugin_output_port_config.main_port().port_layout
-        )
+        )?;
+
+        if self.needs_resampling() {
+            write!(
+                f,
+                ", resampled from {:.1}kHz",
+                self.plugin_sample_rate as f64 / 1_000.0
+            )?;
+        }
+
+        Ok(())
     }
 }
 
@@ -380,14 +417,17 @@ fn find_matching_output_config(
         SupportedBufferSize::Unknown => (1, 1024),
     };
 
+    let sample_rate = 44_100.clamp(
+        best_stream_config.min_sample_rate().0,
+        best_stream_config.max_sample_rate().0,
+    );
+
     FullAudioConfig {
         output_channel_count: best_stream_config.channels() as usize,
         min_buffer_size,
         max_likely_buffer_size: max_buffer_size,
-        sample_rate: 44_100.clamp(
-            best_stream_config.min_sample_rate().0,
-            best_stream_config.max_sample_rate().0,
-        ),
+        sample_rate,
+        plugin_sample_rate: sample_rate,
         plugin_output_port_config,
         plugin_input_port_config,
         sample_format: best_stream_config.sample_format(),
@@ -0,0 +1,177 @@
+/// A linear-interpolation resampler, converting interleaved audio from a source sample rate to a
+/// target sample rate.
+///
+/// This resampler is pull-based: before producing each block of output frames,
+/// [`input_frames_needed`](Self::input_frames_needed) tells how many new input frames have to be
+/// [pushed](Self::push_input) for the block to be computed.
+///
+/// The fractional read position is carried over from one block to the next, so that the output
+/// stays continuous no matter how the stream is split into blocks.
+///
+/// This isn't meant to be studio-quality (there is no anti-aliasing filtering), but it's good
+/// enough for this example to be usable with devices running at a different sample rate than
+/// the plugin.
+pub struct Resampler {
+    /// The number of interleaved channels in both the input and output.
+    channel_count: usize,
+    /// How many input frames to advance for each output frame, i.e. `source_rate / target_rate`.
+    step: f64,
+    /// The read position of the next output frame, relative to the first pending input frame.
+    position: f64,
+    /// The interleaved input frames that have been pushed but not fully consumed yet.
+    pending: Vec<f32>,
+}
+
+impl Resampler {
+    /// Creates a new resampler from the given `source_rate` to the given `target_rate`.
+    ///
+    /// The `max_output_frames` is used to pre-allocate the internal buffer, so that it's unlikely
+    /// to ever need reallocating on the audio thread.
+    pub fn new(
+        source_rate: u32,
+        target_rate: u32,
+        channel_count: usize,
+        max_output_frames: usize,
+    ) -> Self {
+        assert!(channel_count > 0);
+        assert!(source_rate > 0 && target_rate > 0);
+
+        let step = source_rate as f64 / target_rate as f64;
+        let max_input_frames = (max_output_frames as f64 * step).ceil() as usize + 2;
+
+        Self {
+            channel_count,
+            step,
+            position: 0.0,
+            pending: Vec::with_capacity(max_input_frames * channel_count),
+        }
+    }
+
+    /// Returns how many input frames must be pushed to compute the given number of output frames.
+    pub fn input_frames_needed(&self, output_frames: usize) -> usize {
+        if output_frames == 0 {
+            return 0;
+        }
+
+        // The last output frame interpolates between the frame at its position and the next one.
+        let last_position = self.position + (output_frames - 1) as f64 * self.step;
+        let required_frames = last_position.floor() as usize + 2;
+
+        required_frames.saturating_sub(self.pending_frames())
+    }
+
+    /// Pushes the given interleaved input frames, to be consumed by the next calls to
+    /// [`process`](Self::process).
+    pub fn push_input(&mut self, input: &[f32]) {
+        debug_assert_eq!(input.len() % self.channel_count, 0);
+
+        self.pending.extend_from_slice(input);
+    }
+
+    /// Fills the given interleaved output buffer with resampled frames.
+    ///
+    /// # Panics
+    ///
+    /// This panics if not enough input frames were pushed beforehand, as returned by
+    /// [`input_frames_needed`](Self::input_frames_needed).
+    pub fn process(&mut self, output: &mut [f32]) {
+        let channel_count = self.channel_count;
+        let output_frames = output.len() / channel_count;
+
+        assert_eq!(
+            self.input_frames_needed(output_frames),
+            0,
+            "Not enough input frames were pushed to the resampler"
+        );
+
+        for (index, frame) in output.chunks_exact_mut(channel_count).enumerate() {
+            let position = self.position + index as f64 * self.step;
+            let input_index = position as usize;
+            let fraction = (position - input_index as f64) as f32;
+
+            let start = input_index * channel_count;
+            let current = &self.pending[start..start + channel_count];
+            let next = &self.pending[start + channel_count..start + 2 * channel_count];
+
+            for ((output, current), next) in frame.iter_mut().zip(current).zip(next) {
+                *output = current + (next - current) * fraction;
+            }
+        }
+
+        // Discard the input frames that won't be needed anymore, and rebase the read position on
+        // the remaining ones.
+        let end_position = self.position + output_frames as f64 * self.step;
+        let consumed_frames = (end_position.floor() as usize).min(self.pending_frames());
+
+        self.pending.drain(..consumed_frames * channel_count);
+        self.position = end_position - consumed_frames as f64;
+    }
+
+    /// Returns the number of input frames that have been pushed but not fully consumed yet.
+    fn pending_frames(&self) -> usize {
+        self.pending.len() / self.channel_count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Resamples a mono ramp in blocks of the given sizes, and returns the output.
+    fn resample_ramp(source_rate: u32, target_rate: u32, block_sizes: &[usize]) -> Vec<f32> {
+        let mut resampler = Resampler::new(source_rate, target_rate, 1, 64);
+        let mut next_input = 0.0;
+        let mut output = vec![];
+
+        for &block_size in block_sizes {
+            let needed = resampler.input_frames_needed(block_size);
+            let input: Vec<f32> = (0..needed).map(|i| next_input + i as f32).collect();
+            next_input += needed as f32;
+
+            resampler.push_input(&input);
+
+            let mut block = vec![0.0; block_size];
+            resampler.process(&mut block);
+            output.extend_from_slice(&block);
+        }
+
+        output
+    }
+
+    #[test]
+    fn same_rate_is_passthrough() {
+        let output = resample_ramp(48_000, 48_000, &[3, 5, 1, 7]);
+        let expected: Vec<f32> = (0..16).map(|i| i as f32).collect();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn output_is_continuous_across_blocks() {
+        for (source_rate, target_rate) in [(48_000, 44_100), (44_100, 48_000), (96_000, 32_000)] {
+            let step = source_rate as f64 / target_rate as f64;
+            let output = resample_ramp(source_rate, target_rate, &[7, 1, 13, 2, 31, 5]);
+
+            for (index, sample) in output.iter().enumerate() {
+                let expected = (index as f64 * step) as f32;
+                assert!(
+                    (sample - expected).abs() < 1e-3,
+                    "{source_rate}Hz -> {target_rate}Hz: expected {expected} at frame {index}, got {sample}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn interpolates_interleaved_channels_separately() {
+        let mut resampler = Resampler::new(1, 2, 2, 4);
+
+        assert_eq!(resampler.input_frames_needed(4), 3);
+        resampler.push_input(&[0.0, 10.0, 1.0, 20.0, 2.0, 30.0]);
+
+        let mut output = [0.0; 8];
+        resampler.process(&mut output);
+
+        assert_eq!(output, [0.0, 10.0, 0.5, 15.0, 1.0, 20.0, 1.5, 25.0]);
+    }
+}
@@ -35,6 +35,12 @@ struct Cli {
     /// plugin from.
     #[arg(short = 'p', long = "plugin-id")]
     plugin_id: Option<String>,
+    /// Runs the plugin at the given sample rate (in Hz).
+    ///
+    /// If the audio device runs at a different sample rate, the plugin's output will be resampled
+    /// to match it. By default, the plugin runs at the same sample rate as the audio device.
+    #[arg(short = 'r', long = "sample-rate", value_parser = clap::value_parser!(u32).range(1..))]
+    sample_rate: Option<u32>,
 }
 
 fn main() {
@@ -42,9 +48,9 @@ fn main() {
 
     // Select the loading strategy depending on the given arguments
     let result = match (&args.bundle_path, &args.plugin_id) {
-        (Some(path), None) => run_from_path(path),
-        (None, Some(id)) => run_from_id(id),
-        (Some(path), Some(id)) => run_specific(path, id),
+        (Some(path), None) => run_from_path(path, args.sample_rate),
+        (None, Some(id)) => run_from_id(id, args.sample_rate),
+        (Some(path), Some(id)) => run_specific(path, id, args.sample_rate),
         (None, None) => Err(MainError::UnspecifiedOptions.into()),
     };
 
@@ -57,7 +63,7 @@ fn main() {
 /// Loads the plugin contained in a bundle, given through its path.
 ///
 /// Returns an error if there is more than one plugin in the bundle.
-fn run_from_path(path: &Path) -> Result<(), Box<dyn Error>> {
+fn run_from_path(path: &Path, sample_rate: Option<u32>) -> Result<(), Box<dyn Error>> {
     let plugins = discovery::list_plugins_in_bundle(path)?;
 
     if plugins.is_empty() {
@@ -76,7 +82,7 @@ fn run_from_path(path: &Path) -> Result<(), Box<dyn Error>> {
 
     if plugins.len() == 1 {
         let plugin = plugins.into_iter().next().unwrap();
-        host::run(plugin)
+        host::run(plugin, sample_rate)
     } else {
         Err(MainError::MultiplePluginsInPath(path.to_path_buf()).into())
     }
@@ -85,7 +91,7 @@ fn run_from_path(path: &Path) -> Result<(), Box<dyn Error>> {
 /// Scans the filesystem to find a plugin with a given ID.
 ///
 /// Returns an error if there is more than one plugin with this ID on the system.
-fn run_from_id(id: &str) -> Result<(), Box<dyn Error>> {
+fn run_from_id(id: &str, sample_rate: Option<u32>) -> Result<(), Box<dyn Error>> {
     let plugins = discovery::scan_for_plugin_id(id);
 
     if plugins.is_empty() {
@@ -100,7 +106,7 @@ fn run_from_id(id: &str) -> Result<(), Box<dyn Error>> {
 
     if plugins.len() == 1 {
         let plugin = plugins.into_iter().next().unwrap();
-        host::run(plugin)
+        host::run(plugin, sample_rate)
     } else {
         Err(MainError::MultiplePluginsWithId(id.to_string()).into())
     }
@@ -109,11 +115,11 @@ fn run_from_id(id: &str) -> Result<(), Box<dyn Error>> {
 /// Loads a specific plugin matching the given ID, from a specific bundle's path.
 ///
 /// Returns an error if that specific plugin isn't present in the bundle file.
-fn run_specific(path: &Path, id: &str) -> Result<(), Box<dyn Error>> {
+fn run_specific(path: &Path, id: &str, sample_rate: Option<u32>) -> Result<(), Box<dyn Error>> {
     let bundle = discovery::load_plugin_id_from_path(path, id)?;
 
     if let Some(bundle) = bundle {
-        host::run(bundle)
+        host::run(bundle, sample_rate)
     } else {
         Err(MainError::NoPluginInPathWithId(path.to_path_buf(), id.to_string()).into())
     }
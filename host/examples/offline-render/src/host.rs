@@ -0,0 +1,100 @@
+// Loading plugins is unsafe
+#![allow(unsafe_code)]
+
+use clack_extensions::log::{HostLog, HostLogImpl, LogSeverity};
+use clack_host::prelude::*;
+use std::error::Error;
+use std::ffi::CString;
+use std::path::Path;
+
+/// Our host implementation.
+pub struct OfflineHost;
+
+impl HostHandlers for OfflineHost {
+    type Shared<'a> = OfflineHostShared;
+    type MainThread<'a> = OfflineHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostLog>();
+    }
+}
+
+/// Data, accessible by all the plugin's threads.
+pub struct OfflineHostShared;
+
+impl SharedHandler<'_> for OfflineHostShared {
+    fn request_restart(&self) {
+        // We don't support restarting plugins
+    }
+
+    fn request_process(&self) {
+        // We always process until the end of the rendering anyway
+    }
+
+    fn request_callback(&self) {
+        // Callbacks are serviced between each processed block, see PluginInstance::call_on_main_thread
+    }
+}
+
+impl HostLogImpl for OfflineHostShared {
+    fn log(&self, severity: LogSeverity, message: &str) {
+        // We're rendering offline, so printing from the audio thread is fine.
+        eprintln!("[{severity}] {message}")
+    }
+}
+
+/// Data only accessible by the main thread.
+pub struct OfflineHostMainThread;
+
+impl MainThreadHandler<'_> for OfflineHostMainThread {}
+
+/// Loads the plugin with the given ID from the bundle at the given path, and instantiates it.
+///
+/// If no ID is given, the bundle must contain a single plugin.
+pub fn instantiate(
+    bundle_path: &Path,
+    plugin_id: Option<&str>,
+) -> Result<PluginInstance<OfflineHost>, Box<dyn Error>> {
+    let bundle = unsafe { PluginBundle::load(bundle_path)? };
+
+    let plugin_id = match plugin_id {
+        Some(id) => CString::new(id)?,
+        None => single_plugin_id(&bundle)?,
+    };
+
+    let host_info = HostInfo::new(
+        "Clack example offline renderer",
+        "Clack",
+        "https://github.com/prokopyl/clack",
+        "0.0.0",
+    )?;
+
+    let instance = PluginInstance::<OfflineHost>::new(
+        |_| OfflineHostShared,
+        |_| OfflineHostMainThread,
+        &bundle,
+        &plugin_id,
+        &host_info,
+    )?;
+
+    Ok(instance)
+}
+
+/// Returns the ID of the only plugin in the given bundle.
+fn single_plugin_id(bundle: &PluginBundle) -> Result<CString, Box<dyn Error>> {
+    let factory = bundle
+        .get_plugin_factory()
+        .ok_or("The bundle has no plugin factory")?;
+
+    let mut ids = factory.plugin_descriptors().filter_map(|d| d.id());
+
+    match (ids.next(), ids.next()) {
+        (Some(id), None) => Ok(id.to_owned()),
+        (None, _) => Err("The bundle contains no plugins".into()),
+        (Some(_), Some(_)) => Err(
+            "The bundle contains multiple plugins. Specify which one to load using the -p option."
+                .into(),
+        ),
+    }
+}
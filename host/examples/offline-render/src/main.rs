@@ -0,0 +1,97 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs, clippy::missing_docs_in_private_items, unsafe_code)]
+
+/// The host handlers, and the loading of the plugin instance.
+mod host;
+/// Parsing and scheduling of the note events to inject.
+mod notes;
+/// The offline processing of the plugin.
+mod render;
+/// Reading and writing WAV files.
+mod wav;
+
+use crate::notes::Note;
+use crate::render::RenderConfig;
+use clap::Parser;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::exit;
+
+/// A simple CLI host to render the output of a single CLAP plugin to a WAV file, offline.
+#[derive(Parser)]
+#[command(about, long_about)]
+struct Cli {
+    /// Loads the plugin found in the CLAP bundle at the given path.
+    #[arg(short = 'b', long = "bundle-path")]
+    bundle_path: PathBuf,
+    /// Loads the CLAP plugin with the given unique ID from the bundle.
+    ///
+    /// This can be omitted if the bundle only contains a single plugin.
+    #[arg(short = 'p', long = "plugin-id")]
+    plugin_id: Option<String>,
+    /// The path of the WAV file to write the plugin's output to.
+    #[arg(short = 'o', long = "output")]
+    output: PathBuf,
+    /// The path of a WAV file to feed to the plugin's main input.
+    ///
+    /// Its sample rate must match the rendering sample rate. If this is omitted, or if the file is
+    /// shorter than the rendering duration, the plugin receives silence instead.
+    #[arg(short = 'i', long = "input")]
+    input: Option<PathBuf>,
+    /// The duration of the output to render, in seconds.
+    #[arg(short = 'd', long = "duration", default_value_t = 5.0)]
+    duration: f64,
+    /// The sample rate to render at, in Hz.
+    #[arg(short = 'r', long = "sample-rate", default_value_t = 48_000, value_parser = clap::value_parser!(u32).range(1..))]
+    sample_rate: u32,
+    /// A note to send to the plugin, as `KEY:START:LENGTH`.
+    ///
+    /// `KEY` is the MIDI key number (0-127), and `START` and `LENGTH` are in seconds. For instance,
+    /// `60:0.5:2` plays a middle C for two seconds, starting half a second in.
+    ///
+    /// This can be used multiple times to play multiple notes.
+    #[arg(short = 'n', long = "note")]
+    notes: Vec<Note>,
+}
+
+fn main() {
+    let args = Cli::parse();
+
+    if let Err(e) = run(args) {
+        eprintln!("{e}");
+        exit(1);
+    }
+}
+
+/// Loads the plugin, renders its output, and writes it to the output file.
+fn run(args: Cli) -> Result<(), Box<dyn Error>> {
+    if args.duration.is_nan() || args.duration <= 0.0 {
+        return Err("The duration must be a positive number of seconds.".into());
+    }
+
+    let input = match &args.input {
+        Some(path) => Some(wav::read(path, args.sample_rate)?),
+        None => None,
+    };
+
+    let mut instance = host::instantiate(&args.bundle_path, args.plugin_id.as_deref())?;
+
+    let config = RenderConfig {
+        sample_rate: args.sample_rate,
+        frames_count: (args.duration * args.sample_rate as f64).round() as u64,
+        notes: args.notes,
+        input,
+    };
+
+    let output = render::render(&mut instance, &config)?;
+    wav::write(&args.output, args.sample_rate, &output)?;
+
+    println!(
+        "Rendered {:.2}s of audio ({} channels) to {}",
+        args.duration,
+        output.len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use clack_host::events::event_types::{NoteOffEvent, NoteOnEvent};
+use clack_host::events::io::EventBuffer;
+use clack_host::events::{Match, Pckn};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A note to play, as given on the command line.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Note {
+    /// The MIDI key number of the note.
+    key: u8,
+    /// When the note starts, in seconds.
+    start: f64,
+    /// How long the note lasts, in seconds.
+    length: f64,
+}
+
+impl FromStr for Note {
+    type Err = NoteParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(key), Some(start), Some(length), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(NoteParseError::Format);
+        };
+
+        let key = key
+            .parse()
+            .ok()
+            .filter(|k| *k <= 127)
+            .ok_or(NoteParseError::Key)?;
+        let start = start
+            .parse()
+            .ok()
+            .filter(|s: &f64| *s >= 0.0)
+            .ok_or(NoteParseError::Start)?;
+        let length = length
+            .parse()
+            .ok()
+            .filter(|l: &f64| *l > 0.0)
+            .ok_or(NoteParseError::Length)?;
+
+        Ok(Self { key, start, length })
+    }
+}
+
+/// Errors that can occur when parsing a [`Note`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NoteParseError {
+    /// The note isn't in the `KEY:START:LENGTH` format.
+    Format,
+    /// The key isn't a valid MIDI key number.
+    Key,
+    /// The start isn't a valid, non-negative number.
+    Start,
+    /// The length isn't a valid, positive number.
+    Length,
+}
+
+impl Display for NoteParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoteParseError::Format => f.write_str("expected a KEY:START:LENGTH note"),
+            NoteParseError::Key => f.write_str("the key must be a number between 0 and 127"),
+            NoteParseError::Start => {
+                f.write_str("the start must be a non-negative number of seconds")
+            }
+            NoteParseError::Length => {
+                f.write_str("the length must be a positive number of seconds")
+            }
+        }
+    }
+}
+
+impl Error for NoteParseError {}
+
+/// A single Note On or Note Off event, scheduled at a given frame.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct ScheduledNoteEvent {
+    /// The frame at which the event happens, from the start of the rendering.
+    frame: u64,
+    /// `true` for a Note On event, `false` for a Note Off event.
+    is_on: bool,
+    /// The MIDI key number of the note.
+    key: u8,
+}
+
+/// All the note events to send to the plugin during the rendering, ordered by time.
+pub struct NoteSchedule {
+    /// The scheduled events, sorted by frame.
+    events: Vec<ScheduledNoteEvent>,
+    /// The index of the next event to send.
+    next_event: usize,
+}
+
+impl NoteSchedule {
+    /// Schedules the Note On and Note Off events of the given notes, at the given sample rate.
+    pub fn new(notes: &[Note], sample_rate: u32) -> Self {
+        let to_frame = |seconds: f64| (seconds * sample_rate as f64).round() as u64;
+
+        let mut events: Vec<_> = notes
+            .iter()
+            .flat_map(|note| {
+                let start = to_frame(note.start);
+                // Ensure the note lasts at least one frame.
+                let end = to_frame(note.start + note.length).max(start + 1);
+
+                [
+                    ScheduledNoteEvent {
+                        frame: start,
+                        is_on: true,
+                        key: note.key,
+                    },
+                    ScheduledNoteEvent {
+                        frame: end,
+                        is_on: false,
+                        key: note.key,
+                    },
+                ]
+            })
+            .collect();
+
+        // Note Offs are sent before Note Ons happening at the same time, so that repeated notes
+        // don't cut each other off.
+        events.sort_by_key(|e| (e.frame, e.is_on));
+
+        Self {
+            events,
+            next_event: 0,
+        }
+    }
+
+    /// Pushes all the events happening in the block starting at `block_start` and lasting
+    /// `block_len` frames into the given buffer, with times relative to the block's start.
+    pub fn push_block_events(
+        &mut self,
+        buffer: &mut EventBuffer,
+        block_start: u64,
+        block_len: u64,
+    ) {
+        let block_end = block_start + block_len;
+
+        while let Some(event) = self.events.get(self.next_event) {
+            if event.frame >= block_end {
+                break;
+            }
+
+            let time = event.frame.saturating_sub(block_start) as u32;
+            // All notes are sent on the first note port and the first channel, without note IDs.
+            let pckn = Pckn::new(0u16, 0u16, event.key, Match::All);
+
+            if event.is_on {
+                buffer.push(&NoteOnEvent::new(time, pckn, 1.0));
+            } else {
+                buffer.push(&NoteOffEvent::new(time, pckn, 0.0));
+            }
+
+            self.next_event += 1;
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::host::OfflineHost;
+use crate::notes::{Note, NoteSchedule};
+use clack_extensions::render::{PluginRender, RenderMode};
+use clack_host::plugin::PluginActivation;
+use clack_host::prelude::*;
+use std::error::Error;
+
+/// The maximum number of frames processed by the plugin in a single call.
+const BLOCK_SIZE: u32 = 512;
+
+/// The parameters of the rendering.
+pub struct RenderConfig {
+    /// The sample rate to run the plugin at, in Hz.
+    pub sample_rate: u32,
+    /// The total number of frames to render.
+    pub frames_count: u64,
+    /// The notes to send to the plugin.
+    pub notes: Vec<Note>,
+    /// The input audio to feed to the plugin's main input port, one buffer per channel.
+    pub input: Option<Vec<Vec<f32>>>,
+}
+
+/// Renders the output of the given plugin instance, offline.
+///
+/// This returns the samples of the plugin's main output port, one buffer per channel.
+pub fn render(
+    instance: &mut PluginInstance<OfflineHost>,
+    config: &RenderConfig,
+) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    set_offline_mode(instance);
+
+    let PluginActivation {
+        audio_processor,
+        layout,
+        mut input_ports,
+        mut output_ports,
+    } = instance.activate_with_config_and_ports(
+        |_, _| (),
+        PluginAudioConfiguration {
+            sample_rate: config.sample_rate as f64,
+            min_frames_count: 1,
+            max_frames_count: BLOCK_SIZE,
+        },
+    )?;
+
+    let Some(&output_channel_count) = layout.output_channel_counts().first() else {
+        instance.deactivate(audio_processor);
+        return Err("The plugin has no audio output port".into());
+    };
+
+    // A WAV file can't have zero channels.
+    if output_channel_count == 0 {
+        instance.deactivate(audio_processor);
+        return Err("The plugin's main audio output port has no channels".into());
+    }
+
+    let mut input_buffers = allocate_buffers(layout.input_channel_counts());
+    let mut output_buffers = allocate_buffers(layout.output_channel_counts());
+
+    let mut rendered =
+        vec![Vec::with_capacity(config.frames_count as usize); output_channel_count as usize];
+
+    let mut notes = NoteSchedule::new(&config.notes, config.sample_rate);
+    let mut input_events = EventBuffer::with_capacity(16);
+    let mut output_events = EventBuffer::with_capacity(16);
+
+    let mut audio_processor = match audio_processor.start_processing() {
+        Ok(processor) => processor,
+        Err(e) => {
+            instance.deactivate(e.into_stopped_processor());
+            return Err("The plugin failed to start processing".into());
+        }
+    };
+
+    let mut position = 0u64;
+    let mut result = Ok(());
+
+    while position < config.frames_count {
+        let block_len = (config.frames_count - position).min(BLOCK_SIZE as u64);
+
+        fill_input_block(&mut input_buffers, config.input.as_deref(), position);
+
+        input_events.clear();
+        output_events.clear();
+        notes.push_block_events(&mut input_events, position, block_len);
+
+        let block_len = block_len as usize;
+
+        let inputs = input_ports.with_input_buffers(input_buffers.iter_mut().map(|port| {
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_input_only(
+                    port.iter_mut()
+                        .map(|channel| InputChannel::variable(&mut channel[..block_len])),
+                ),
+            }
+        }));
+
+        let mut outputs = output_ports.with_output_buffers(output_buffers.iter_mut().map(|port| {
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_output_only(
+                    port.iter_mut().map(|channel| &mut channel[..block_len]),
+                ),
+            }
+        }));
+
+        if let Err(e) = audio_processor.process(
+            &inputs,
+            &mut outputs,
+            &input_events.as_input(),
+            &mut output_events.as_output(),
+            Some(position),
+            None,
+        ) {
+            result = Err(e);
+            break;
+        }
+
+        for (rendered, channel) in rendered.iter_mut().zip(&output_buffers[0]) {
+            rendered.extend_from_slice(&channel[..block_len]);
+        }
+
+        position += block_len as u64;
+
+        // The plugin may have requested a callback on the main thread while processing. We are
+        // both the main and the audio thread here, so we service it between blocks.
+        instance.call_on_main_thread();
+    }
+
+    instance.deactivate(audio_processor.stop_processing());
+    result?;
+
+    Ok(rendered)
+}
+
+/// Switches the plugin to the offline render mode, if it supports the `render` extension.
+fn set_offline_mode(instance: &mut PluginInstance<OfflineHost>) {
+    let mut plugin = instance.plugin_handle();
+
+    let Some(render) = plugin.get_extension::<PluginRender>() else {
+        return;
+    };
+
+    if render.has_realtime_requirement(&mut plugin) {
+        eprintln!("Warning: this plugin has a hard real-time requirement, its output may be off.");
+    }
+
+    if render.set(&mut plugin, RenderMode::Offline).is_err() {
+        eprintln!("Warning: this plugin refused to switch to offline rendering mode.");
+    }
+}
+
+/// Allocates one buffer of [`BLOCK_SIZE`] samples for each channel of each of the given ports.
+fn allocate_buffers(channel_counts: &[u32]) -> Vec<Vec<Vec<f32>>> {
+    channel_counts
+        .iter()
+        .map(|&count| vec![vec![0.0; BLOCK_SIZE as usize]; count as usize])
+        .collect()
+}
+
+/// Fills the buffers of the main input port with the input samples starting at `position`.
+///
+/// Input channels are spread over the port's channels, repeating if the port has more channels
+/// than the input (e.g. a mono file is fed to both channels of a stereo port). Any frames past the
+/// end of the input, as well as all other ports, are filled with silence.
+fn fill_input_block(ports: &mut [Vec<Vec<f32>>], input: Option<&[Vec<f32>]>, position: u64) {
+    for (port_index, port) in ports.iter_mut().enumerate() {
+        for (channel_index, channel) in port.iter_mut().enumerate() {
+            channel.fill(0.0);
+
+            let Some(input) = input.filter(|i| port_index == 0 && !i.is_empty()) else {
+                continue;
+            };
+
+            let source = &input[channel_index % input.len()];
+            let start = (position as usize).min(source.len());
+            let available = &source[start..(start + channel.len()).min(source.len())];
+
+            channel[..available.len()].copy_from_slice(available);
+        }
+    }
+}
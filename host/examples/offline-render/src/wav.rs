@@ -0,0 +1,68 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::error::Error;
+use std::path::Path;
+
+/// Reads the WAV file at the given path, and returns its samples as one buffer per channel.
+///
+/// Integer samples are converted to `f32` samples in the `[-1.0, 1.0]` range. This returns an
+/// error if the file's sample rate doesn't match the given one, as this example doesn't do any
+/// resampling.
+pub fn read(path: &Path, sample_rate: u32) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let reader = WavReader::open(path)?;
+    let spec = reader.spec();
+
+    if spec.sample_rate != sample_rate {
+        return Err(format!(
+            "The input file's sample rate ({}Hz) doesn't match the rendering sample rate ({}Hz)",
+            spec.sample_rate, sample_rate
+        )
+        .into());
+    }
+
+    let interleaved = match spec.sample_format {
+        SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .collect::<Result<Vec<_>, _>>()?,
+        SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|s| s as f32 * scale))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+
+    let channel_count = spec.channels as usize;
+    let mut channels = vec![Vec::with_capacity(interleaved.len() / channel_count); channel_count];
+
+    for frame in interleaved.chunks_exact(channel_count) {
+        for (channel, sample) in channels.iter_mut().zip(frame) {
+            channel.push(*sample);
+        }
+    }
+
+    Ok(channels)
+}
+
+/// Writes the given channels to a 32-bit float WAV file at the given path.
+pub fn write(path: &Path, sample_rate: u32, channels: &[Vec<f32>]) -> Result<(), Box<dyn Error>> {
+    let spec = WavSpec {
+        channels: channels.len() as u16,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: SampleFormat::Float,
+    };
+
+    let mut writer = WavWriter::create(path, spec)?;
+    let frames_count = channels.iter().map(Vec::len).min().unwrap_or(0);
+
+    for frame in 0..frames_count {
+        for channel in channels {
+            writer.write_sample(channel[frame])?;
+        }
+    }
+
+    writer.finalize()?;
+    Ok(())
+}
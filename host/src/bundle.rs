@@ -13,7 +13,7 @@
 //! documentation to learn more about factories.
 //!
 //! Clack handles all of this functionality through the [`PluginBundle`] type, which exposes all
-//! the bundle's factory implementation, and allow the bundle to be loaded in two different ways:
+//! the bundle's factory implementation, and allow the bundle to be loaded in three different ways:
 //!
 //! * From a file, using [`PluginBundle::load`].
 //!
@@ -21,6 +21,14 @@
 //!   third-party CLAP bundles present anywhere on the file system, which is most likely the
 //!   functionality "CLAP plugin support" implies for most hosts.
 //!
+//!   [`PluginBundle::load_with_options`] can also be used to retry loading on transient failures,
+//!   which can happen e.g. on network filesystems.
+//!
+//! * From the contents of a bundle file held in memory, using [`PluginBundle::load_from_bytes`].
+//!
+//!   This allows hosts to verify the exact bytes they are about to load (e.g. using a hash or a
+//!   signature), without relying on the bundle file not being modified in the meantime.
+//!
 //! * From a static [`EntryDescriptor`] reference, using [`PluginBundle::load_from_raw`].
 //!   
 //!   This is a more advanced usage, and it allows to load plugins that have been statically built
@@ -63,6 +71,8 @@ use std::ffi::NulError;
 use std::fmt::{Display, Formatter};
 
 use std::ptr::NonNull;
+#[cfg(feature = "libloading")]
+use std::time::Duration;
 
 mod cache;
 mod entry;
@@ -75,7 +85,7 @@ mod library;
 pub mod diva_stub;
 
 use crate::bundle::cache::CachedEntry;
-use crate::factory::{FactoryPointer, PluginFactory};
+use crate::factory::{FactoryPointer, PluginFactory, PresetDiscoveryFactory};
 pub use clack_common::entry::*;
 use clack_common::utils::ClapVersion;
 
@@ -115,6 +125,9 @@ pub struct PluginBundle {
 impl PluginBundle {
     /// Loads a CLAP bundle from a file located at the given path.
     ///
+    /// Loading is never retried. To retry loading on transient failures, use
+    /// [`load_with_options`](PluginBundle::load_with_options) instead.
+    ///
     /// # Safety
     ///
     /// This function loads an external library object file, which is inherently unsafe, as even
@@ -154,6 +167,85 @@ impl PluginBundle {
         Ok(Self { inner })
     }
 
+    /// Loads a CLAP bundle from a file located at the given path, using the given [`LoadOptions`].
+    ///
+    /// Depending on the given options, loading may be retried if it failed because of a
+    /// [transient](PluginBundleError::is_transient) error, e.g. if the file is located on a slow
+    /// network filesystem, or is temporarily locked by an antivirus scanner. Errors caused by the
+    /// bundle itself (e.g. if it isn't a valid library) are returned right away.
+    ///
+    /// Note that the calling thread is blocked while waiting between retries.
+    ///
+    /// # Safety
+    ///
+    /// This function loads an external library object file, which is inherently unsafe, as even
+    /// just loading it can trigger any behavior in your application, including Undefined Behavior.
+    ///
+    /// Additionally, loading a non-compliant CLAP bundle may invalidate safety assumptions other
+    /// APIs in this library rely on. See the [module docs](self)'s Safety section for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if loading the bundle fails, after all retries have been
+    /// exhausted. See [`PluginBundleError`] for all the possible errors that may occur.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use clack_host::bundle::LoadOptions;
+    /// use clack_host::prelude::PluginBundle;
+    /// use std::time::Duration;
+    ///
+    /// let options = LoadOptions::new()
+    ///     .with_retry_count(3)
+    ///     .with_retry_delay(Duration::from_millis(200));
+    ///
+    /// let bundle =
+    ///     unsafe { PluginBundle::load_with_options("/mnt/shared/.clap/libdiva.so", options)? };
+    ///
+    /// println!("Loaded bundle CLAP version: {}", bundle.version());
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "libloading")]
+    pub unsafe fn load_with_options<P: AsRef<std::ffi::OsStr>>(
+        path: P,
+        options: LoadOptions,
+    ) -> Result<Self, PluginBundleError> {
+        use crate::bundle::library::PluginEntryLibrary;
+
+        let path = path.as_ref();
+        let path_str = path.to_str().ok_or(PluginBundleError::InvalidUtf8Path)?;
+
+        let mut retries_left = options.retry_count;
+
+        let library = loop {
+            let result = PluginEntryLibrary::load(path).map_err(|e| match e {
+                // Library loading errors aren't detailed on all platforms. If the file itself
+                // can't be opened, report that instead, so that it can be told apart from an
+                // invalid library.
+                PluginBundleError::LibraryLoadingError(_) => match std::fs::File::open(path) {
+                    Err(access_error) => PluginBundleError::FileAccessError(access_error),
+                    Ok(_) => e,
+                },
+                e => e,
+            });
+
+            match result {
+                Err(e) if e.is_transient() && retries_left > 0 => {
+                    retries_left -= 1;
+                    std::thread::sleep(options.retry_delay);
+                }
+                result => break result?,
+            }
+        };
+
+        let inner = cache::load_from_library(library, path_str)?;
+
+        Ok(Self { inner })
+    }
+
     /// Loads a CLAP bundle from a given symbol in a given [`libloading::Library`].
     ///
     /// This function takes ownership of the [`libloading::Library`] object, ensuring it stays
@@ -209,6 +301,84 @@ impl PluginBundle {
         Ok(Self { inner })
     }
 
+    /// Loads a CLAP bundle from the contents of a bundle file held in memory.
+    ///
+    /// This is useful for hosts that do not trust the filesystem the bundle originated from,
+    /// and prefer to first read its contents, verify them (e.g. by checking a hash or a
+    /// signature), and then load the exact bytes that were verified.
+    ///
+    /// The given `fake_path` is never read from. It is only passed to the bundle's entry
+    /// initialization as its bundle path, as plugins may rely on it to e.g. locate resource
+    /// files next to the bundle. It should therefore be a plausible path for the bundle, such as
+    /// the path it was originally read from.
+    ///
+    /// # Platform limitations
+    ///
+    /// Dynamic libraries cannot be loaded from memory directly on most platforms, so the bytes
+    /// still have to be exposed as a file to the OS loader:
+    ///
+    /// * On Linux, an anonymous, memory-backed file is used (through `memfd_create`). The bundle
+    ///   never touches the filesystem. If the running kernel does not support memory-backed
+    ///   files, this falls back to the temporary file method below.
+    /// * On all other platforms, the bytes are written to a newly-created file (named after
+    ///   `fake_path`'s file name) in a new, private temporary directory, which is removed once
+    ///   the bundle is unloaded. While this file is only accessible to the current user on
+    ///   Unix-like platforms, it may still be observed or tampered with by other processes
+    ///   running as the same user.
+    ///
+    /// Note this only covers the bundle's binary itself: any other dependent library or resource
+    /// file the plugin loads afterward is still read from the filesystem as usual.
+    ///
+    /// # Safety
+    ///
+    /// This function loads an external library object file, which is inherently unsafe, as even
+    /// just loading it can trigger any behavior in your application, including Undefined Behavior.
+    ///
+    /// Additionally, loading a non-compliant CLAP bundle may invalidate safety assumptions other
+    /// APIs in this library rely on. See the [module docs](self)'s Safety section for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if writing the bundle to a temporary file or loading the
+    /// bundle fails.
+    /// See [`PluginBundleError`] for all the possible errors that may occur.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use clack_host::prelude::PluginBundle;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("/home/user/.clap/u-he/libdiva.so");
+    /// let bytes = std::fs::read(path)?;
+    /// // Verify the bundle's contents here...
+    ///
+    /// let bundle = unsafe { PluginBundle::load_from_bytes(&bytes, path)? };
+    ///
+    /// println!("Loaded bundle CLAP version: {}", bundle.version());
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "libloading")]
+    pub unsafe fn load_from_bytes(
+        bytes: &[u8],
+        fake_path: &std::path::Path,
+    ) -> Result<Self, PluginBundleError> {
+        use crate::bundle::library::PluginEntryLibrary;
+
+        let path_str = fake_path
+            .to_str()
+            .ok_or(PluginBundleError::InvalidUtf8Path)?;
+
+        let file_name = fake_path.file_name().unwrap_or_default();
+        let library = PluginEntryLibrary::load_from_bytes(bytes, file_name)?;
+
+        let inner = cache::load_from_library(library, path_str)?;
+
+        Ok(Self { inner })
+    }
+
     /// Loads a CLAP bundle from a `'static` [`EntryDescriptor`].
     ///
     /// Note that CLAP plugins loaded this way still need a valid path, as they may perform various
@@ -258,7 +428,12 @@ impl PluginBundle {
 
     /// Returns the [`FactoryPointer`] of type `F` exposed by this bundle, if it exists.
     ///
-    /// If this bundle does not expose a factory of the requested type, [`None`] is returned.
+    /// If this bundle does not expose a factory of the requested type, or if it returns a null
+    /// pointer for it, [`None`] is returned.
+    ///
+    /// Any factory type can be retrieved this way, as long as it implements [`FactoryPointer`].
+    /// Clack provides the [`PluginFactory`] and [`PresetDiscoveryFactory`] types, but custom
+    /// factory types can also be implemented by hosts.
     ///
     /// If you are looking to fetch the bundle's [`PluginFactory`], you can also use the
     /// [`get_plugin_factory`](PluginBundle::get_plugin_factory) method, which is just a convenience
@@ -308,6 +483,17 @@ impl PluginBundle {
         self.get_factory()
     }
 
+    /// Returns the [`PresetDiscoveryFactory`] exposed by this bundle, if it exists.
+    ///
+    /// If this bundle does not expose a [`PresetDiscoveryFactory`], [`None`] is returned.
+    ///
+    /// This is a convenience method, and is equivalent to calling
+    /// [`get_factory`](PluginBundle::get_factory) with a [`PresetDiscoveryFactory`] type parameter.
+    #[inline]
+    pub fn get_preset_discovery_factory(&self) -> Option<PresetDiscoveryFactory<'_>> {
+        self.get_factory()
+    }
+
     /// Returns the CLAP version used by this bundle.
     #[inline]
     pub fn version(&self) -> ClapVersion {
@@ -315,12 +501,74 @@ impl PluginBundle {
     }
 }
 
+/// Options to customize how a bundle file is loaded by [`PluginBundle::load_with_options`].
+///
+/// By default, loading is attempted only once.
+#[cfg(feature = "libloading")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LoadOptions {
+    retry_count: u32,
+    retry_delay: Duration,
+}
+
+#[cfg(feature = "libloading")]
+impl LoadOptions {
+    /// Creates the default loading options, which never retry loading.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            retry_count: 0,
+            retry_delay: Duration::from_millis(100),
+        }
+    }
+
+    /// Sets how many times loading is retried after a [transient](PluginBundleError::is_transient)
+    /// failure.
+    ///
+    /// The default is `0`, i.e. loading is never retried.
+    #[inline]
+    pub const fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry_count = retry_count;
+        self
+    }
+
+    /// Sets how long to wait before each retry.
+    ///
+    /// The default is 100 milliseconds.
+    #[inline]
+    pub const fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Returns how many times loading is retried after a transient failure.
+    #[inline]
+    pub const fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
+    /// Returns how long to wait before each retry.
+    #[inline]
+    pub const fn retry_delay(&self) -> Duration {
+        self.retry_delay
+    }
+}
+
+#[cfg(feature = "libloading")]
+impl Default for LoadOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Errors that can occur while loading a [`PluginBundle`].
 ///
 /// See [`PluginBundle::load`] and [`PluginBundle::load_from_raw`].
 #[derive(Debug)]
 pub enum PluginBundleError {
-    /// The path given to [`PluginBundle::load`] is not valid UTF-8.
+    /// The path given to [`PluginBundle::load`] or [`PluginBundle::load_from_bytes`] is not
+    /// valid UTF-8.
     InvalidUtf8Path,
     /// The dynamic library file could not be loaded.
     ///
@@ -328,6 +576,20 @@ pub enum PluginBundleError {
     /// [`libloading`](https://crates.io/crates/libloading) library.
     #[cfg(feature = "libloading")]
     LibraryLoadingError(libloading::Error),
+    /// The bundle file could not be accessed.
+    ///
+    /// Unlike [`LibraryLoadingError`](PluginBundleError::LibraryLoadingError), this means the
+    /// file itself couldn't be opened, regardless of its contents. Some of these errors may be
+    /// [transient](PluginBundleError::is_transient).
+    ///
+    /// This error is only returned by [`PluginBundle::load_with_options`], which checks whether
+    /// the file can be opened after loading it failed.
+    #[cfg(feature = "libloading")]
+    FileAccessError(std::io::Error),
+    /// The in-memory bundle given to [`PluginBundle::load_from_bytes`] could not be written to a
+    /// file to be loaded from.
+    #[cfg(feature = "libloading")]
+    TemporaryFileError(std::io::Error),
     /// The entry pointer exposed by the dynamic library file is `null`.
     NullEntryPointer,
     /// The exposed entry used an incompatible CLAP version.
@@ -343,12 +605,57 @@ pub enum PluginBundleError {
     EntryInitFailed,
 }
 
+impl PluginBundleError {
+    /// Returns `true` if this error may be transient, i.e. if attempting to load the same bundle
+    /// again later may succeed.
+    ///
+    /// This is the case for some I/O errors, e.g. if the file is temporarily locked by another
+    /// process, or if accessing a network filesystem timed out. Errors caused by the bundle itself
+    /// (e.g. if it isn't a valid library, or if its entry failed to initialize) are never
+    /// considered transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(feature = "libloading")]
+            PluginBundleError::FileAccessError(e) => is_transient_io_error(e),
+            // On Windows, the underlying OS error is available, so we can tell if it is transient.
+            // This isn't the case on other platforms, where only a message is available.
+            #[cfg(feature = "libloading")]
+            PluginBundleError::LibraryLoadingError(e) => e
+                .source()
+                .and_then(|e| e.downcast_ref::<std::io::Error>())
+                .is_some_and(is_transient_io_error),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "libloading")]
+fn is_transient_io_error(error: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION: the file is currently opened by another
+    // process, e.g. an antivirus scanner.
+    #[cfg(windows)]
+    if matches!(error.raw_os_error(), Some(32 | 33)) {
+        return true;
+    }
+
+    matches!(
+        error.kind(),
+        ErrorKind::Interrupted | ErrorKind::TimedOut | ErrorKind::WouldBlock
+    )
+}
+
 impl Error for PluginBundleError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             PluginBundleError::InvalidNulPath(e) => Some(e),
             #[cfg(feature = "libloading")]
             PluginBundleError::LibraryLoadingError(e) => Some(e),
+            #[cfg(feature = "libloading")]
+            PluginBundleError::FileAccessError(e) => Some(e),
+            #[cfg(feature = "libloading")]
+            PluginBundleError::TemporaryFileError(e) => Some(e),
             _ => None,
         }
     }
@@ -365,6 +672,14 @@ impl Display for PluginBundleError {
             PluginBundleError::LibraryLoadingError(e) => {
                 write!(f, "Failed to load plugin descriptor library: {e}")
             }
+            #[cfg(feature = "libloading")]
+            PluginBundleError::FileAccessError(e) => {
+                write!(f, "Failed to access plugin bundle file: {e}")
+            }
+            #[cfg(feature = "libloading")]
+            PluginBundleError::TemporaryFileError(e) => {
+                write!(f, "Failed to write in-memory bundle to a file: {e}")
+            }
             PluginBundleError::InvalidUtf8Path => {
                 f.write_str("Plugin descriptor path contains invalid UTF-8")
             }
@@ -2,12 +2,19 @@ use crate::bundle::PluginBundleError;
 use clack_common::entry::EntryDescriptor;
 use libloading::Library;
 use std::ffi::{CStr, OsStr};
+use std::fs::File;
+use std::io::Write;
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) struct PluginEntryLibrary {
     _library: Library,
     entry_ptr: NonNull<EntryDescriptor>,
+    // SAFETY: drop order is important! The library must be unloaded before its file is removed.
+    _temp_file: Option<TempLibraryFile>,
 }
 
 // SAFETY: this has a null byte at the end
@@ -49,9 +56,46 @@ impl PluginEntryLibrary {
         Ok(Self {
             _library: library,
             entry_ptr,
+            _temp_file: None,
         })
     }
 
+    /// Loads a library from the given in-memory object file contents.
+    ///
+    /// On Linux, this uses an anonymous memory-backed file (`memfd_create`), which never touches
+    /// the filesystem. If that is unavailable, or on other platforms, the bytes are written to a
+    /// newly-created file inside a private temporary directory, which is removed once the library
+    /// is unloaded.
+    ///
+    /// # Safety
+    ///
+    /// Loading an external library is inherently unsafe. Users must try their best to load only
+    /// valid CLAP bundles.
+    pub unsafe fn load_from_bytes(
+        bytes: &[u8],
+        file_name: &OsStr,
+    ) -> Result<Self, PluginBundleError> {
+        #[cfg(target_os = "linux")]
+        if let Some(memfd) = memfd::create(bytes)? {
+            let library =
+                Library::new(memfd.path()).map_err(PluginBundleError::LibraryLoadingError)?;
+
+            // The file descriptor can be closed now: the library keeps its own mapping of it.
+            drop(memfd);
+
+            return Self::load_from_library(library);
+        }
+
+        let temp_file = TempLibraryFile::create(bytes, file_name)?;
+        let library =
+            Library::new(&temp_file.file_path).map_err(PluginBundleError::LibraryLoadingError)?;
+
+        let mut library = Self::load_from_library(library)?;
+        library._temp_file = Some(temp_file);
+
+        Ok(library)
+    }
+
     #[inline]
     pub fn entry(&self) -> &EntryDescriptor {
         // SAFETY: this type's only constructor guarantees this pointer is valid
@@ -72,3 +116,104 @@ impl Deref for PluginEntryLibrary {
 unsafe impl Send for PluginEntryLibrary {}
 // SAFETY: Entries and factories are all thread-safe by the CLAP spec
 unsafe impl Sync for PluginEntryLibrary {}
+
+/// A library file written to a private temporary directory, which is removed on drop.
+struct TempLibraryFile {
+    directory: PathBuf,
+    file_path: PathBuf,
+}
+
+impl TempLibraryFile {
+    fn create(bytes: &[u8], file_name: &OsStr) -> Result<Self, PluginBundleError> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+
+        let directory = std::env::temp_dir().join(format!(
+            "clack-bundle-{}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            nanos
+        ));
+
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        // This fails if the directory already exists, so nobody else can have prepared it.
+        builder
+            .create(&directory)
+            .map_err(PluginBundleError::TemporaryFileError)?;
+
+        let file_name = if file_name.is_empty() {
+            OsStr::new("bundle.clap")
+        } else {
+            file_name
+        };
+
+        let temp_file = Self {
+            file_path: directory.join(file_name),
+            directory,
+        };
+
+        let mut file = File::options()
+            .write(true)
+            .create_new(true)
+            .open(&temp_file.file_path)
+            .map_err(PluginBundleError::TemporaryFileError)?;
+
+        file.write_all(bytes)
+            .and_then(|_| file.sync_all())
+            .map_err(PluginBundleError::TemporaryFileError)?;
+
+        Ok(temp_file)
+    }
+}
+
+impl Drop for TempLibraryFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.file_path);
+        let _ = std::fs::remove_dir(&self.directory);
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod memfd {
+    use super::*;
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    /// An anonymous, memory-backed file.
+    pub struct MemFd(File);
+
+    impl MemFd {
+        /// The path to this file, as seen from the current process.
+        pub fn path(&self) -> PathBuf {
+            PathBuf::from(format!("/proc/self/fd/{}", self.0.as_raw_fd()))
+        }
+    }
+
+    /// Creates a memory-backed file containing the given bytes.
+    ///
+    /// This returns `None` if memory-backed files are not supported by the running kernel.
+    pub fn create(bytes: &[u8]) -> Result<Option<MemFd>, PluginBundleError> {
+        const NAME: &[u8] = b"clack-bundle\0";
+
+        // SAFETY: NAME is a valid, nul-terminated C string.
+        let fd = unsafe { libc::memfd_create(NAME.as_ptr().cast(), libc::MFD_CLOEXEC) };
+
+        if fd < 0 {
+            return Ok(None);
+        }
+
+        // SAFETY: memfd_create returned a new, valid file descriptor that we now own.
+        let mut file = unsafe { File::from_raw_fd(fd) };
+
+        file.write_all(bytes)
+            .map_err(PluginBundleError::TemporaryFileError)?;
+
+        Ok(Some(MemFd(file)))
+    }
+}
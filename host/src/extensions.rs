@@ -136,7 +136,12 @@
 //!
 //! # Creating custom extensions
 //!
-//! TODO: document custom extensions.
+//! Custom extensions are declared by wrapping a [`RawExtension`] pointing to the extension's
+//! raw, C-FFI compatible struct, and implementing the [`Extension`] trait to tie it to its
+//! identifier. The [`clack_extension!`] macro can generate this boilerplate, as well as the
+//! [`ExtensionImplementation`] for either side.
+//!
+//! The example below implements the `latency` extension manually.
 //!
 //! ## Example
 //!
@@ -207,7 +212,7 @@
 //!     where for<'a> <H as HostHandlers>::MainThread<'a>: HostLatencyImpl,
 //! {
 //!     HostWrapper::<H>::handle(host, |host| {
-//!         host.main_thread().as_mut().changed();
+//!         host.main_thread()?.as_mut().changed();
 //!         Ok(())
 //!     });
 //! }
@@ -223,8 +228,8 @@ pub mod prelude {
     pub use crate::{
         extensions::wrapper::{HostWrapper, HostWrapperError},
         extensions::{
-            Extension, ExtensionImplementation, HostExtensionSide, PluginExtensionSide,
-            RawExtension, RawExtensionImplementation,
+            clack_extension, Extension, ExtensionImplementation, HostExtensionSide,
+            PluginExtensionSide, RawExtension, RawExtensionImplementation,
         },
         host::{HostError, HostHandlers},
         plugin::{PluginAudioProcessorHandle, PluginMainThreadHandle, PluginSharedHandle},
@@ -4,7 +4,7 @@ use crate::plugin::DestroyLock;
 use crate::prelude::*;
 use crate::util::UnsafeOptionCell;
 use clap_sys::ext::log::{
-    clap_log_severity, CLAP_LOG_HOST_MISBEHAVING, CLAP_LOG_PLUGIN_MISBEHAVING,
+    clap_log_severity, CLAP_LOG_HOST_MISBEHAVING, CLAP_LOG_PLUGIN_MISBEHAVING, CLAP_LOG_WARNING,
 };
 use clap_sys::host::clap_host;
 use clap_sys::plugin::clap_plugin;
@@ -42,6 +42,13 @@ pub struct HostWrapper<H: HostHandlers> {
 
     // Drop stuff
     destroy_lock: Arc<DestroyLock>,
+
+    // Set when the plugin requested an on_main_thread callback that wasn't performed yet
+    callback_requested: AtomicBool,
+
+    // Debug checks
+    #[cfg(debug_assertions)]
+    main_thread_id: std::thread::ThreadId,
 }
 
 // SAFETY: The only non-thread-safe methods on this type are unsafe
@@ -89,8 +96,36 @@ impl<H: HostHandlers> HostWrapper<H> {
     ///
     /// The pointer is safe to mutably dereference, as long as the caller ensures it is not being
     /// aliased, as per usual safety rules.
+    ///
+    /// # Errors
+    ///
+    /// In debug builds, this method returns [`HostWrapperError::WrongThread`] if it is called from
+    /// a different thread than the one the plugin instance was created on. When returned from
+    /// within [`handle`](Self::handle), the error is then logged to the plugin.
+    ///
+    /// This check is not performed in release builds, where this method never fails.
     #[inline]
-    pub unsafe fn main_thread(&self) -> NonNull<<H as HostHandlers>::MainThread<'_>> {
+    pub unsafe fn main_thread(
+        &self,
+    ) -> Result<NonNull<<H as HostHandlers>::MainThread<'_>>, HostWrapperError> {
+        #[cfg(debug_assertions)]
+        if std::thread::current().id() != self.main_thread_id {
+            return Err(HostWrapperError::WrongThread);
+        }
+
+        Ok(self.main_thread_unchecked())
+    }
+
+    /// Same as [`main_thread`](Self::main_thread), but without the debug thread check.
+    ///
+    /// This is used by the host-side types, which cannot be sent to other threads.
+    ///
+    /// # Safety
+    /// The caller must ensure this method is only called on the main thread.
+    #[inline]
+    pub(crate) unsafe fn main_thread_unchecked(
+        &self,
+    ) -> NonNull<<H as HostHandlers>::MainThread<'_>> {
         self.main_thread.as_ptr_unchecked().cast()
     }
 
@@ -121,6 +156,14 @@ impl<H: HostHandlers> HostWrapper<H> {
         unsafe { shrink_shared_ref::<H>(&self.shared) }
     }
 
+    /// Returns a raw pointer to the plugin instance this host is attached to.
+    ///
+    /// This returns [`None`] if the plugin instance hasn't been created yet.
+    #[inline]
+    pub fn plugin_ptr(&self) -> Option<*const clap_plugin> {
+        self.plugin_ptr.get().map(|p| p.as_ptr().cast_const())
+    }
+
     pub(crate) fn new<FS, FH>(shared: FS, main_thread: FH) -> Pin<Arc<Self>>
     where
         FS: for<'s> FnOnce(&'s ()) -> <H as HostHandlers>::Shared<'s>,
@@ -138,6 +181,9 @@ impl<H: HostHandlers> HostWrapper<H> {
             init_started: AtomicBool::new(false),
             plugin_ptr: OnceLock::new(),
             destroy_lock: Arc::new(DestroyLock::new()),
+            callback_requested: AtomicBool::new(false),
+            #[cfg(debug_assertions)]
+            main_thread_id: std::thread::current().id(),
         });
 
         // PANIC: we have the only Arc copy of this wrapper data.
@@ -170,7 +216,7 @@ impl<H: HostHandlers> HostWrapper<H> {
         let instance = *self.plugin_ptr.get().unwrap();
 
         // SAFETY: At this point there is no way main_thread could not have been set.
-        self.main_thread()
+        self.main_thread_unchecked()
             .as_mut()
             .initialized(InitializedPluginHandle::new(
                 self.destroy_lock.clone(),
@@ -206,7 +252,7 @@ impl<H: HostHandlers> HostWrapper<H> {
             unsafe { extend_shared_ref(&self.shared) },
             // SAFETY: The user enforces that this is only called on the main thread, and
             // non-concurrently to any other main-thread method.
-            unsafe { self.main_thread().cast().as_mut() },
+            unsafe { self.main_thread_unchecked().cast().as_mut() },
         ));
         Ok(())
     }
@@ -229,11 +275,26 @@ impl<H: HostHandlers> HostWrapper<H> {
                 audio_processor,
                 // SAFETY: The user enforces that this is only called on the main thread, and
                 // non-concurrently to any other main-thread method.
-                unsafe { self.main_thread().cast().as_mut() },
+                unsafe { self.main_thread_unchecked().cast().as_mut() },
             )),
         }
     }
 
+    #[inline]
+    pub(crate) fn set_callback_requested(&self) {
+        self.callback_requested.store(true, Ordering::Release);
+    }
+
+    #[inline]
+    pub(crate) fn is_callback_requested(&self) -> bool {
+        self.callback_requested.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub(crate) fn take_callback_request(&self) -> bool {
+        self.callback_requested.swap(false, Ordering::AcqRel)
+    }
+
     /// # Safety
     /// the user must ensure this is not called concurrently
     /// to [`Self::setup_audio_processor`] or [`Self::teardown_audio_processor`]
@@ -292,6 +353,14 @@ pub enum HostWrapperError {
     NullHostData,
     Panic,
     HostError(PluginInstanceError),
+    /// A main-thread host method was called from another thread.
+    ///
+    /// This is only detected in debug builds. See [`HostWrapper::main_thread`].
+    WrongThread,
+    /// The host registered the same extension more than once.
+    ///
+    /// Only the first registration is used. See [`HostExtensions::register`](crate::host::HostExtensions::register).
+    DuplicateExtension,
 }
 
 impl HostWrapperError {
@@ -302,6 +371,12 @@ impl HostWrapperError {
             HostWrapperError::InvalidParameter(s) => s,
             HostWrapperError::Panic => "Host callback panicked",
             HostWrapperError::HostError(e) => e.msg(),
+            HostWrapperError::WrongThread => {
+                "Plugin called a main-thread host method from another thread"
+            }
+            HostWrapperError::DuplicateExtension => {
+                "Host registered the same extension more than once, only the first one is used"
+            }
         }
     }
 
@@ -312,6 +387,8 @@ impl HostWrapperError {
             HostWrapperError::NullHostData => CLAP_LOG_HOST_MISBEHAVING,
             HostWrapperError::Panic => CLAP_LOG_HOST_MISBEHAVING,
             HostWrapperError::HostError(e) => e.severity(),
+            HostWrapperError::WrongThread => CLAP_LOG_PLUGIN_MISBEHAVING,
+            HostWrapperError::DuplicateExtension => CLAP_LOG_WARNING,
         }
     }
 }
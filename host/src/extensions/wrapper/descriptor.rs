@@ -53,7 +53,7 @@ unsafe extern "C" fn get_extension<H: HostHandlers>(
 
     HostWrapper::<H>::handle(host, |h| {
         H::declare_extensions(&mut builder, h.shared());
-        Ok(())
+        builder.check_duplicates()
     });
     builder.found()
 }
@@ -77,6 +77,7 @@ unsafe extern "C" fn request_process<H: HostHandlers>(host: *const clap_host) {
 #[allow(clippy::missing_safety_doc)]
 unsafe extern "C" fn request_callback<H: HostHandlers>(host: *const clap_host) {
     HostWrapper::<H>::handle(host, |h| {
+        h.set_callback_requested();
         h.shared().request_callback();
         Ok(())
     });
@@ -1,6 +1,7 @@
 use crate::extensions::prelude::HostWrapperError;
 use clap_sys::ext::log::{clap_host_log, clap_log_severity, CLAP_EXT_LOG};
 use clap_sys::host::clap_host;
+use std::cell::Cell;
 use std::os::raw::c_char;
 use std::{error::Error, ffi::CString, fmt::Write};
 
@@ -29,7 +30,21 @@ fn log_display(error: &HostWrapperError) -> Result<CString, Box<dyn Error>> {
 /// Plugin pointer must be non-dangling (but can be NULL).
 /// It *must* point to a plugin instance created by Clack.
 pub unsafe fn host_log(host: *const clap_host, e: &HostWrapperError) {
-    if let Some(logger) = get_logger(host) {
+    thread_local! {
+        static IS_LOGGING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    // Fetching the logger calls back into the host's declare_extensions, which may itself fail and
+    // log an error. In that case, fall back to printing it instead of recursing endlessly.
+    if IS_LOGGING.with(|l| l.replace(true)) {
+        eprintln!("[CLAP_HOST_ERROR] {}", e.msg());
+        return;
+    }
+
+    let logger = get_logger(host);
+    IS_LOGGING.with(|l| l.set(false));
+
+    if let Some(logger) = logger {
         match log_display(e) {
             Ok(cstr) => {
                 logger(host, e.severity(), cstr.as_ptr());
@@ -19,6 +19,14 @@
 //!
 //! See the [`PluginFactory`]'s type documentation for more detail and examples on how to
 //! list plugins.
+//!
+//! Bundles may also expose other factory types. Clack also supports the (draft)
+//! [`PresetDiscoveryFactory`], which allows hosts to index the presets known to a bundle. See the
+//! [`preset_discovery`] module for more information.
+//!
+//! Note that a bundle may also return a null pointer for a factory it claims to support. In that
+//! case, [`PluginBundle::get_factory`](crate::bundle::PluginBundle::get_factory) returns [`None`],
+//! just like for a factory the bundle does not know about.
 
 use crate::plugin::PluginInstanceError;
 use clap_sys::factory::plugin_factory::{clap_plugin_factory, CLAP_PLUGIN_FACTORY_ID};
@@ -31,6 +39,9 @@ use std::ptr::NonNull;
 mod plugin_descriptor;
 pub use plugin_descriptor::*;
 
+pub mod preset_discovery;
+pub use preset_discovery::PresetDiscoveryFactory;
+
 /// A custom factory pointer type.
 ///
 /// # Safety
@@ -139,6 +150,62 @@ impl<'a> PluginFactory<'a> {
         }
     }
 
+    /// Returns an iterator of all the [`PluginDescriptor`s](PluginDescriptor) exposed by this
+    /// plugin factory that list the given feature.
+    ///
+    /// Descriptors are returned in the same order as
+    /// [`plugin_descriptors`](PluginFactory::plugin_descriptors), and each one is only fetched
+    /// once from the factory.
+    ///
+    /// See [`PluginDescriptor::has_feature`] and the [`features`](crate::plugin::features) module
+    /// for a list of standard features.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_host::factory::PluginFactory;
+    /// use clack_host::plugin::features::SYNTHESIZER;
+    ///
+    /// # fn x(factory: &PluginFactory) {
+    /// let factory: &PluginFactory = /* ... */
+    /// # unreachable!();
+    /// for synth in factory.plugin_descriptors_with_feature(SYNTHESIZER) {
+    ///     println!("Found synthesizer: {:?}", synth.name());
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn plugin_descriptors_with_feature<'f>(
+        &self,
+        feature: &'f CStr,
+    ) -> impl Iterator<Item = PluginDescriptor<'a>> + 'f
+    where
+        'a: 'f,
+    {
+        self.plugin_descriptors()
+            .filter(move |d| d.has_feature(feature))
+    }
+
+    /// Returns an iterator of all the [`PluginDescriptor`s](PluginDescriptor) exposed by this
+    /// plugin factory whose [`vendor`](PluginDescriptor::vendor) is exactly the given one.
+    ///
+    /// Descriptors are returned in the same order as
+    /// [`plugin_descriptors`](PluginFactory::plugin_descriptors), and each one is only fetched
+    /// once from the factory. Descriptors without a vendor are never returned.
+    #[inline]
+    pub fn plugin_descriptors_by_vendor<'v>(
+        &self,
+        vendor: &'v str,
+    ) -> impl Iterator<Item = PluginDescriptor<'a>> + 'v
+    where
+        'a: 'v,
+    {
+        self.plugin_descriptors().filter(move |d| {
+            d.vendor()
+                .is_some_and(|v| v.to_bytes() == vendor.as_bytes())
+        })
+    }
+
     /// # Safety
     ///
     /// User must pass a valid clap_host pointer, which has to stay valid for the lifetime of the
@@ -1,5 +1,7 @@
+use crate::plugin::{features, PluginCategory};
 use clap_sys::plugin::clap_plugin_descriptor;
 use std::ffi::CStr;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 /// Various textual information about a plugin.
@@ -25,7 +27,7 @@ pub struct PluginDescriptor<'a> {
 /// # Safety
 ///
 /// Same as [`CStr::from_ptr`], except the given pointer *can* be null.
-unsafe fn cstr_to_str<'a>(ptr: *const std::os::raw::c_char) -> Option<&'a CStr> {
+pub(super) unsafe fn cstr_to_str<'a>(ptr: *const std::os::raw::c_char) -> Option<&'a CStr> {
     if ptr.is_null() {
         return None;
     }
@@ -184,6 +186,33 @@ impl<'a> PluginDescriptor<'a> {
         unsafe { cstr_to_str(self.descriptor.version) }
     }
 
+    /// Parses the [`version`](PluginDescriptor::version) of this plugin as a
+    /// `(major, minor, patch)` semantic version triple.
+    ///
+    /// This is a best-effort parser: only the leading, dot-separated numeric components of the
+    /// version string are considered, and anything after them (e.g. a `-beta` pre-release suffix)
+    /// is ignored. Missing `minor` or `patch` components default to `0`, and any components after
+    /// the `patch` component are ignored.
+    ///
+    /// For instance, `1.4.4-beta` is parsed as `(1, 4, 4)`, and `2021.1` as `(2021, 1, 0)`.
+    ///
+    /// This returns [`None`] if the version is not set, or if it doesn't start with a number.
+    ///
+    /// # Example
+    /// ```
+    /// use clack_host::factory::PluginDescriptor;
+    ///
+    /// # fn x(descriptor: &PluginDescriptor) {
+    /// let descriptor: &PluginDescriptor = /* ... */
+    /// # unreachable!();
+    /// assert_eq!(b"1.4.4", descriptor.version().unwrap().to_bytes());
+    /// assert_eq!(Some((1, 4, 4)), descriptor.semver());
+    /// # }
+    /// ```
+    pub fn semver(&self) -> Option<(u64, u64, u64)> {
+        parse_semver(self.version()?.to_bytes())
+    }
+
     /// A short description of this plugin.
     ///
     /// # Example
@@ -221,6 +250,129 @@ impl<'a> PluginDescriptor<'a> {
             _lifetime: PhantomData,
         }
     }
+
+    /// Returns `true` if this plugin lists the given feature.
+    ///
+    /// See the [`features`] module for a list of standard features.
+    ///
+    /// # Example
+    /// ```
+    /// use clack_host::factory::PluginDescriptor;
+    /// use clack_host::plugin::features::SYNTHESIZER;
+    ///
+    /// # fn x(descriptor: &PluginDescriptor) {
+    /// let descriptor: &PluginDescriptor = /* ... */
+    /// # unreachable!();
+    /// assert!(descriptor.has_feature(SYNTHESIZER));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn has_feature(&self, feature: &CStr) -> bool {
+        self.features().any(|f| f == feature)
+    }
+
+    /// Returns `true` if this plugin lists the [`INSTRUMENT`](features::INSTRUMENT) feature.
+    #[inline]
+    pub fn is_instrument(&self) -> bool {
+        self.has_feature(features::INSTRUMENT)
+    }
+
+    /// Returns `true` if this plugin lists the [`AUDIO_EFFECT`](features::AUDIO_EFFECT) feature.
+    #[inline]
+    pub fn is_audio_effect(&self) -> bool {
+        self.has_feature(features::AUDIO_EFFECT)
+    }
+
+    /// Returns `true` if this plugin lists the [`NOTE_EFFECT`](features::NOTE_EFFECT) feature.
+    #[inline]
+    pub fn is_note_effect(&self) -> bool {
+        self.has_feature(features::NOTE_EFFECT)
+    }
+
+    /// Returns `true` if this plugin lists the [`ANALYZER`](features::ANALYZER) feature.
+    #[inline]
+    pub fn is_analyzer(&self) -> bool {
+        self.has_feature(features::ANALYZER)
+    }
+
+    /// Returns the main category of this plugin, derived from its features.
+    ///
+    /// Plugins may list multiple main category features (e.g. both `instrument` and
+    /// `audio-effect`). In that case, the category of the first one in the feature list is
+    /// returned.
+    ///
+    /// This returns [`None`] if the plugin does not list any main category feature.
+    ///
+    /// # Example
+    /// ```
+    /// use clack_host::factory::PluginDescriptor;
+    /// use clack_host::plugin::PluginCategory;
+    ///
+    /// # fn x(descriptor: &PluginDescriptor) {
+    /// let descriptor: &PluginDescriptor = /* ... */
+    /// # unreachable!();
+    /// assert_eq!(Some(PluginCategory::Instrument), descriptor.primary_category());
+    /// # }
+    /// ```
+    pub fn primary_category(&self) -> Option<PluginCategory> {
+        self.features().find_map(PluginCategory::from_feature)
+    }
+}
+
+/// Plugin descriptors are compared by [`id`](PluginDescriptor::id) only, as it is the unique
+/// identifier of a plugin per the CLAP specification. All other fields (name, version, etc.) are
+/// ignored.
+///
+/// This allows hosts to e.g. deduplicate plugins found in multiple bundles during scanning, by
+/// storing their descriptors in a [`HashSet`](std::collections::HashSet).
+///
+/// Note that all descriptors that do not have an ID (i.e. for which [`id`](PluginDescriptor::id)
+/// returns [`None`]) are considered equal.
+impl PartialEq for PluginDescriptor<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for PluginDescriptor<'_> {}
+
+/// Plugin descriptors are hashed by [`id`](PluginDescriptor::id) only, consistently with their
+/// [`PartialEq`] implementation.
+impl Hash for PluginDescriptor<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state)
+    }
+}
+
+fn parse_semver(version: &[u8]) -> Option<(u64, u64, u64)> {
+    fn parse_component(component: &[u8]) -> Option<u64> {
+        if component.is_empty() {
+            return None;
+        }
+
+        component.iter().try_fold(0u64, |value, &digit| {
+            value.checked_mul(10)?.checked_add((digit - b'0') as u64)
+        })
+    }
+
+    let prefix_len = version
+        .iter()
+        .position(|c| !c.is_ascii_digit() && *c != b'.')
+        .unwrap_or(version.len());
+
+    // Stop at the first empty component, e.g. in "1..3" or "1.2."
+    let mut components = version[..prefix_len]
+        .split(|c| *c == b'.')
+        .map(parse_component)
+        .take_while(Option::is_some);
+
+    let major = components.next()??;
+    let minor = components.next().flatten().unwrap_or(0);
+    let patch = components.next().flatten().unwrap_or(0);
+
+    Some((major, minor, patch))
 }
 
 struct FeaturesIter<'a> {
@@ -249,3 +401,78 @@ impl<'a> Iterator for FeaturesIter<'a> {
         Some(cstr)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_semver() {
+        assert_eq!(parse_semver(b"1.4.4"), Some((1, 4, 4)));
+        assert_eq!(parse_semver(b"1.4.4-beta"), Some((1, 4, 4)));
+        assert_eq!(parse_semver(b"1.4.4+build.5"), Some((1, 4, 4)));
+        assert_eq!(parse_semver(b"2021.1"), Some((2021, 1, 0)));
+        assert_eq!(parse_semver(b"3"), Some((3, 0, 0)));
+        assert_eq!(parse_semver(b"1.2.3.4"), Some((1, 2, 3)));
+        assert_eq!(parse_semver(b"1.2 (build 5)"), Some((1, 2, 0)));
+        assert_eq!(parse_semver(b"1..3"), Some((1, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_non_numeric_versions() {
+        assert_eq!(parse_semver(b""), None);
+        assert_eq!(parse_semver(b"v1.2.3"), None);
+        assert_eq!(parse_semver(b"beta"), None);
+        assert_eq!(parse_semver(b".1"), None);
+        assert_eq!(parse_semver(b"99999999999999999999.1"), None);
+    }
+
+    fn raw_descriptor(id: *const std::os::raw::c_char, name: &CStr) -> clap_plugin_descriptor {
+        clap_plugin_descriptor {
+            clap_version: clap_sys::version::CLAP_VERSION,
+            id,
+            name: name.as_ptr(),
+            vendor: core::ptr::null(),
+            url: core::ptr::null(),
+            manual_url: core::ptr::null(),
+            support_url: core::ptr::null(),
+            version: core::ptr::null(),
+            description: core::ptr::null(),
+            features: core::ptr::null(),
+        }
+    }
+
+    #[test]
+    fn descriptors_are_compared_by_id() {
+        use std::collections::HashSet;
+
+        let diva = CStr::from_bytes_with_nul(b"com.u-he.diva\0").unwrap();
+        let zebra = CStr::from_bytes_with_nul(b"com.u-he.zebra\0").unwrap();
+        let name = CStr::from_bytes_with_nul(b"Diva\0").unwrap();
+        let other_name = CStr::from_bytes_with_nul(b"Diva (old)\0").unwrap();
+        let empty = CStr::from_bytes_with_nul(b"\0").unwrap();
+
+        let raw = [
+            raw_descriptor(diva.as_ptr(), name),
+            raw_descriptor(diva.as_ptr(), other_name),
+            raw_descriptor(zebra.as_ptr(), name),
+            raw_descriptor(core::ptr::null(), name),
+            raw_descriptor(empty.as_ptr(), name),
+        ];
+
+        // SAFETY: all the descriptors and their pointers are valid.
+        let descriptors: Vec<_> = raw
+            .iter()
+            .map(|d| unsafe { PluginDescriptor::from_raw(d) })
+            .collect();
+
+        assert!(descriptors[0] == descriptors[1]);
+        assert!(descriptors[0] != descriptors[2]);
+        assert!(descriptors[0] != descriptors[3]);
+        // Missing and empty IDs are both treated as no ID.
+        assert!(descriptors[3] == descriptors[4]);
+
+        let unique: HashSet<_> = descriptors.iter().copied().collect();
+        assert_eq!(unique.len(), 3);
+    }
+}
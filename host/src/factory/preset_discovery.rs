@@ -0,0 +1,829 @@
+//! Types related to the preset discovery factory.
+//!
+//! This factory allows hosts to index all the presets that a plugin bundle ships or knows about,
+//! without having to instantiate any plugin.
+//!
+//! Preset discovery is split into providers: each provider is listed by the
+//! [`PresetDiscoveryFactory`] using a [`PresetProviderDescriptor`], and can then be created using
+//! [`PresetDiscoveryFactory::create_provider`].
+//!
+//! When it is created, a [`PresetProvider`] declares the preset file types, locations and sound
+//! packs it knows about. The host can then retrieve the metadata of all the presets stored at
+//! each of those [locations](PresetProvider::locations), using [`PresetProvider::presets_at`].
+//!
+//! Note that this factory is still a draft in the CLAP specification, and its API may change.
+
+use super::plugin_descriptor::cstr_to_str;
+use super::FactoryPointer;
+use crate::host::HostInfo;
+use clack_common::utils::ClapVersion;
+use clap_sys::factory::draft::preset_discovery::*;
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::ffi::{c_void, CStr, CString};
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+/// A factory pointer that exposes a list of preset providers.
+///
+/// # Example
+///
+/// ```no_run
+/// # pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use clack_host::prelude::*;
+///
+/// let bundle = unsafe { PluginBundle::load("/home/user/.clap/u-he/libdiva.so")? };
+/// let host_info = HostInfo::new("Preset browser", "Clack", "https://example.com", "1.0")?;
+///
+/// let Some(factory) = bundle.get_preset_discovery_factory() else {
+///     return Ok(());
+/// };
+///
+/// for descriptor in factory.provider_descriptors() {
+///     let Some(id) = descriptor.id() else { continue };
+///     let provider = factory.create_provider(&host_info, id)?;
+///
+///     for location in provider.locations() {
+///         for preset in provider.presets_at(location)? {
+///             println!("Found preset: {:?}", preset.name);
+///         }
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PresetDiscoveryFactory<'a> {
+    inner: *const clap_preset_discovery_factory,
+    _lifetime: PhantomData<&'a clap_preset_discovery_factory>,
+}
+
+// SAFETY: This takes a clap_preset_discovery_factory pointer, which matches
+// CLAP_PRESET_DISCOVERY_FACTORY_ID
+unsafe impl<'a> FactoryPointer<'a> for PresetDiscoveryFactory<'a> {
+    const IDENTIFIER: &'static CStr = CLAP_PRESET_DISCOVERY_FACTORY_ID;
+
+    #[inline]
+    unsafe fn from_raw(raw: NonNull<c_void>) -> Self {
+        Self {
+            inner: raw.as_ptr() as *const _,
+            _lifetime: PhantomData,
+        }
+    }
+}
+
+impl<'a> PresetDiscoveryFactory<'a> {
+    /// Returns the number of preset providers exposed by this factory.
+    #[inline]
+    pub fn provider_count(&self) -> u32 {
+        // SAFETY: no special safety considerations
+        match unsafe { (*self.inner).count } {
+            None => 0,
+            // SAFETY: this type ensures the function pointer is valid
+            Some(count) => unsafe { count(self.inner) },
+        }
+    }
+
+    /// Returns the [`PresetProviderDescriptor`] exposed by this factory at a given index, or
+    /// `None` if there is no provider descriptor at the given index.
+    ///
+    /// See also the [`provider_descriptors`](PresetDiscoveryFactory::provider_descriptors) method
+    /// for a convenient iterator of all the provider descriptors exposed by this factory.
+    #[inline]
+    pub fn provider_descriptor(&self, index: u32) -> Option<PresetProviderDescriptor<'a>> {
+        // SAFETY: descriptor is guaranteed not to outlive the entry
+        unsafe { (*self.inner).get_descriptor?(self.inner, index).as_ref() }
+            .map(|descriptor| PresetProviderDescriptor { descriptor })
+    }
+
+    /// Returns an iterator of all the [`PresetProviderDescriptor`s](PresetProviderDescriptor)
+    /// exposed by this factory.
+    #[inline]
+    pub fn provider_descriptors(&self) -> PresetProviderDescriptorsIter<'a> {
+        PresetProviderDescriptorsIter {
+            factory: *self,
+            count: self.provider_count(),
+            current_index: 0,
+        }
+    }
+
+    /// Creates and initializes the preset provider matching the given identifier.
+    ///
+    /// The given `indexer_info` is exposed to the provider to identify the host performing the
+    /// indexing.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PresetDiscoveryError::ProviderNotFound`] if the factory did not create a
+    /// provider for the given identifier, and [`PresetDiscoveryError::InitFailed`] if the
+    /// provider failed to initialize.
+    pub fn create_provider(
+        &self,
+        indexer_info: &HostInfo,
+        provider_id: &CStr,
+    ) -> Result<PresetProvider<'a>, PresetDiscoveryError> {
+        let indexer = Indexer::new(indexer_info);
+
+        // SAFETY: this type ensures the function pointer is valid. The indexer is boxed and lives
+        // as long as the provider.
+        let raw = unsafe {
+            (*self.inner)
+                .create
+                .ok_or(PresetDiscoveryError::NullFactoryCreateFunction)?(
+                self.inner,
+                &indexer.raw,
+                provider_id.as_ptr(),
+            )
+        };
+
+        let raw = NonNull::new(raw as *mut clap_preset_discovery_provider)
+            .ok_or(PresetDiscoveryError::ProviderNotFound)?;
+
+        let mut provider = PresetProvider {
+            raw,
+            indexer,
+            declarations: Declarations::default(),
+            _lifetime: PhantomData,
+        };
+
+        // SAFETY: the provider pointer was just created by the factory.
+        let init = unsafe { raw.as_ref() }.init;
+
+        provider.indexer.is_initializing.set(true);
+        // SAFETY: the provider pointer is valid and has not been initialized yet.
+        let initialized = init.is_some_and(|init| unsafe { init(raw.as_ptr()) });
+        provider.indexer.is_initializing.set(false);
+
+        if !initialized {
+            return Err(PresetDiscoveryError::InitFailed);
+        }
+
+        provider.declarations = provider.indexer.declarations.take();
+        Ok(provider)
+    }
+}
+
+/// An [`Iterator`] over all the [`PresetProviderDescriptor`s](PresetProviderDescriptor) exposed
+/// by a preset discovery factory.
+///
+/// See the [`PresetDiscoveryFactory::provider_descriptors`] method that produces this iterator.
+pub struct PresetProviderDescriptorsIter<'a> {
+    factory: PresetDiscoveryFactory<'a>,
+    current_index: u32,
+    count: u32,
+}
+
+impl<'a> Iterator for PresetProviderDescriptorsIter<'a> {
+    type Item = PresetProviderDescriptor<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current_index >= self.count {
+                return None;
+            }
+
+            let descriptor = self.factory.provider_descriptor(self.current_index);
+            self.current_index += 1;
+
+            // Skip all none-returning indexes
+            if let Some(d) = descriptor {
+                return Some(d);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count as usize, Some(self.count as usize))
+    }
+}
+
+/// Returns an iterator of all the [`PresetProviderDescriptor`s](PresetProviderDescriptor)
+/// exposed by this factory.
+impl<'a> IntoIterator for PresetDiscoveryFactory<'a> {
+    type Item = PresetProviderDescriptor<'a>;
+    type IntoIter = PresetProviderDescriptorsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.provider_descriptors()
+    }
+}
+
+/// Textual information about a preset provider.
+///
+/// All fields of this type as exposed as optional, however the CLAP specification requires the
+/// [`id`](PresetProviderDescriptor::id) and [`name`](PresetProviderDescriptor::name) fields to be
+/// present.
+#[derive(Copy, Clone)]
+pub struct PresetProviderDescriptor<'a> {
+    descriptor: &'a clap_preset_discovery_provider_descriptor,
+}
+
+impl<'a> PresetProviderDescriptor<'a> {
+    /// The CLAP version this provider implements.
+    #[inline]
+    pub fn clap_version(&self) -> ClapVersion {
+        ClapVersion::from_raw(self.descriptor.clap_version)
+    }
+
+    /// An arbitrary string identifier that is unique to this provider.
+    ///
+    /// This is the identifier to give to [`PresetDiscoveryFactory::create_provider`].
+    #[inline]
+    pub fn id(&self) -> Option<&'a CStr> {
+        // SAFETY: the factory ensures the string pointer is valid
+        unsafe { cstr_to_str(self.descriptor.id) }
+    }
+
+    /// The user-friendly name of this provider.
+    #[inline]
+    pub fn name(&self) -> Option<&'a CStr> {
+        // SAFETY: the factory ensures the string pointer is valid
+        unsafe { cstr_to_str(self.descriptor.name) }
+    }
+
+    /// The vendor of this provider.
+    #[inline]
+    pub fn vendor(&self) -> Option<&'a CStr> {
+        // SAFETY: the factory ensures the string pointer is valid
+        unsafe { cstr_to_str(self.descriptor.vendor) }
+    }
+}
+
+/// A preset file type declared by a [`PresetProvider`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PresetFiletype {
+    /// The user-friendly name of this file type.
+    pub name: CString,
+    /// An optional description of this file type.
+    pub description: Option<CString>,
+    /// The file extension of this file type, without the leading dot.
+    ///
+    /// If this is [`None`], any file within the declared locations may be a preset file.
+    pub file_extension: Option<CString>,
+}
+
+/// The kind of a [`PresetDiscoveryLocation`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PresetDiscoveryLocationKind {
+    /// Presets are stored in files, either at the given path or inside the directory at the
+    /// given path.
+    File(CString),
+    /// Presets are built into the plugin itself.
+    Plugin,
+}
+
+/// A preset location declared by a [`PresetProvider`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PresetDiscoveryLocation {
+    /// Flags applying to all the presets in this location.
+    ///
+    /// See the `CLAP_PRESET_DISCOVERY_IS_*` constants.
+    pub flags: u32,
+    /// The user-friendly name of this location.
+    pub name: CString,
+    /// The kind of this location.
+    pub kind: PresetDiscoveryLocationKind,
+}
+
+/// A sound pack declared by a [`PresetProvider`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PresetSoundpack {
+    /// Flags applying to all the presets in this sound pack.
+    ///
+    /// See the `CLAP_PRESET_DISCOVERY_IS_*` constants.
+    pub flags: u32,
+    /// The unique identifier of this sound pack.
+    pub id: CString,
+    /// The user-friendly name of this sound pack.
+    pub name: CString,
+    /// An optional description of this sound pack.
+    pub description: Option<CString>,
+    /// An optional URL to this sound pack's homepage.
+    pub homepage_url: Option<CString>,
+    /// An optional vendor name for this sound pack.
+    pub vendor: Option<CString>,
+    /// An optional path to an image representing this sound pack.
+    pub image_path: Option<CString>,
+    /// The release date of this sound pack, in seconds since the UNIX epoch, if known.
+    pub release_timestamp: Option<u64>,
+}
+
+/// An identifier of a plugin a preset can be loaded into.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PresetPluginId {
+    /// The plugin ABI, e.g. `clap`.
+    pub abi: CString,
+    /// The plugin identifier, in the given ABI.
+    pub id: CString,
+}
+
+/// The metadata of a preset, as returned by [`PresetProvider::presets_at`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PresetMetadata {
+    /// The user-friendly name of this preset, if any.
+    pub name: Option<CString>,
+    /// The key identifying this preset within a container file or within the plugin, if any.
+    pub load_key: Option<CString>,
+    /// The identifiers of all the plugins this preset can be loaded into.
+    pub plugin_ids: Vec<PresetPluginId>,
+    /// The identifier of the sound pack this preset belongs to, if any.
+    pub soundpack_id: Option<CString>,
+    /// Flags of this preset.
+    ///
+    /// See the `CLAP_PRESET_DISCOVERY_IS_*` constants.
+    pub flags: u32,
+    /// The creators of this preset.
+    pub creators: Vec<CString>,
+    /// An optional description of this preset.
+    pub description: Option<CString>,
+    /// The creation date of this preset, in seconds since the UNIX epoch, if known.
+    pub creation_time: Option<u64>,
+    /// The last modification date of this preset, in seconds since the UNIX epoch, if known.
+    pub modification_time: Option<u64>,
+    /// The features of this preset, using the same values as plugin features.
+    pub features: Vec<CString>,
+    /// Additional key-value information about this preset.
+    pub extra_info: Vec<(CString, CString)>,
+}
+
+/// An initialized preset provider, created by [`PresetDiscoveryFactory::create_provider`].
+///
+/// The provider is destroyed when this is dropped.
+pub struct PresetProvider<'a> {
+    raw: NonNull<clap_preset_discovery_provider>,
+    indexer: Box<Indexer>,
+    declarations: Declarations,
+    _lifetime: PhantomData<&'a clap_preset_discovery_factory>,
+}
+
+impl<'a> PresetProvider<'a> {
+    /// Returns the descriptor of this provider, if the provider exposes one.
+    #[inline]
+    pub fn descriptor(&self) -> Option<PresetProviderDescriptor<'_>> {
+        // SAFETY: this type ensures the provider pointer is valid
+        unsafe { self.raw.as_ref().desc.as_ref() }
+            .map(|descriptor| PresetProviderDescriptor { descriptor })
+    }
+
+    /// Returns all the preset file types this provider declared during initialization.
+    #[inline]
+    pub fn filetypes(&self) -> &[PresetFiletype] {
+        &self.declarations.filetypes
+    }
+
+    /// Returns all the preset locations this provider declared during initialization.
+    #[inline]
+    pub fn locations(&self) -> &[PresetDiscoveryLocation] {
+        &self.declarations.locations
+    }
+
+    /// Returns all the sound packs this provider declared during initialization.
+    #[inline]
+    pub fn soundpacks(&self) -> &[PresetSoundpack] {
+        &self.declarations.soundpacks
+    }
+
+    /// Retrieves the metadata of all the presets stored at the given location.
+    ///
+    /// For [file](PresetDiscoveryLocationKind::File) locations, the location may be either one
+    /// of the declared [`locations`](Self::locations), or a single file found inside of one.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PresetDiscoveryError::GetMetadataFailed`] if the provider failed to read
+    /// the presets' metadata, alongside any error the provider may have reported.
+    pub fn presets_at(
+        &self,
+        location: &PresetDiscoveryLocation,
+    ) -> Result<Vec<PresetMetadata>, PresetDiscoveryError> {
+        let (kind, location) = match &location.kind {
+            PresetDiscoveryLocationKind::File(path) => {
+                (CLAP_PRESET_DISCOVERY_LOCATION_FILE, path.as_ptr())
+            }
+            PresetDiscoveryLocationKind::Plugin => {
+                (CLAP_PRESET_DISCOVERY_LOCATION_PLUGIN, core::ptr::null())
+            }
+        };
+
+        let state = RefCell::new(ReceiverState::default());
+        let receiver = clap_preset_discovery_metadata_receiver {
+            receiver_data: &state as *const _ as *mut c_void,
+            on_error: Some(on_error),
+            begin_preset: Some(begin_preset),
+            add_plugin_id: Some(add_plugin_id),
+            set_soundpack_id: Some(set_soundpack_id),
+            set_flags: Some(set_flags),
+            add_creator: Some(add_creator),
+            set_description: Some(set_description),
+            set_timestamps: Some(set_timestamps),
+            add_feature: Some(add_feature),
+            add_extra_info: Some(add_extra_info),
+        };
+
+        // SAFETY: this type ensures the provider pointer is valid
+        let get_metadata = unsafe { self.raw.as_ref() }
+            .get_metadata
+            .ok_or(PresetDiscoveryError::NullGetMetadataFunction)?;
+
+        // SAFETY: the provider pointer is valid, and the receiver outlives this call.
+        let success = unsafe { get_metadata(self.raw.as_ptr(), kind, location, &receiver) };
+
+        let state = state.into_inner();
+
+        if let (true, None) = (success, &state.error) {
+            Ok(state.presets)
+        } else {
+            let (os_error, message) = state.error.unwrap_or((0, None));
+            Err(PresetDiscoveryError::GetMetadataFailed { os_error, message })
+        }
+    }
+}
+
+impl<'a> Drop for PresetProvider<'a> {
+    fn drop(&mut self) {
+        // SAFETY: this type ensures the provider pointer is valid
+        if let Some(destroy) = unsafe { self.raw.as_ref() }.destroy {
+            // SAFETY: the provider is never used after this point. The indexer is only dropped
+            // after this call.
+            unsafe { destroy(self.raw.as_ptr()) }
+        }
+    }
+}
+
+/// Errors that can occur while discovering presets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PresetDiscoveryError {
+    /// The factory's `create` function is null.
+    NullFactoryCreateFunction,
+    /// The factory did not create a provider for the given identifier.
+    ProviderNotFound,
+    /// The provider failed to initialize.
+    InitFailed,
+    /// The provider's `get_metadata` function is null.
+    NullGetMetadataFunction,
+    /// The provider failed to retrieve the metadata of the presets at the given location.
+    GetMetadataFailed {
+        /// The OS error code reported by the provider, or 0 if none was reported.
+        os_error: i32,
+        /// The error message reported by the provider, if any.
+        message: Option<CString>,
+    },
+}
+
+impl Display for PresetDiscoveryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NullFactoryCreateFunction => {
+                f.write_str("Preset discovery factory's create function is null")
+            }
+            Self::ProviderNotFound => f.write_str("Specified preset provider was not found"),
+            Self::InitFailed => f.write_str("Preset provider failed to initialize"),
+            Self::NullGetMetadataFunction => {
+                f.write_str("Preset provider's get_metadata function is null")
+            }
+            Self::GetMetadataFailed { os_error, message } => {
+                f.write_str("Preset provider failed to read preset metadata")?;
+
+                if let Some(message) = message {
+                    write!(f, ": {}", message.to_string_lossy())?;
+                }
+
+                if *os_error != 0 {
+                    write!(f, " (OS error {os_error})")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for PresetDiscoveryError {}
+
+#[derive(Default)]
+struct Declarations {
+    filetypes: Vec<PresetFiletype>,
+    locations: Vec<PresetDiscoveryLocation>,
+    soundpacks: Vec<PresetSoundpack>,
+}
+
+/// The host-side indexer, given to providers when they are created.
+struct Indexer {
+    raw: clap_preset_discovery_indexer,
+    _info: HostInfo,
+    is_initializing: Cell<bool>,
+    declarations: RefCell<Declarations>,
+}
+
+impl Indexer {
+    fn new(info: &HostInfo) -> Box<Self> {
+        // Like in clap_host, empty fields are passed as empty strings, never as null pointers.
+        let [name, vendor, url, version] = info.raw_strings();
+
+        let mut indexer = Box::new(Self {
+            raw: clap_preset_discovery_indexer {
+                clap_version: ClapVersion::CURRENT.to_raw(),
+                name: name.as_ptr(),
+                vendor: vendor.as_ptr(),
+                url: url.as_ptr(),
+                version: version.as_ptr(),
+                indexer_data: core::ptr::null_mut(),
+                declare_filetype: Some(declare_filetype),
+                declare_location: Some(declare_location),
+                declare_soundpack: Some(declare_soundpack),
+                get_extension: Some(get_extension),
+            },
+            _info: info.clone(),
+            is_initializing: Cell::new(false),
+            declarations: RefCell::new(Declarations::default()),
+        });
+
+        indexer.raw.indexer_data = &*indexer as *const Self as *mut c_void;
+        indexer
+    }
+
+    /// # Safety
+    ///
+    /// The given pointer must be null or point to an indexer created by [`Indexer::new`].
+    unsafe fn declare(
+        indexer: *const clap_preset_discovery_indexer,
+        declare: impl FnOnce(&mut Declarations) -> Option<()>,
+    ) -> bool {
+        let Some(indexer) = indexer
+            .as_ref()
+            .and_then(|i| (i.indexer_data as *const Self).as_ref())
+        else {
+            return false;
+        };
+
+        // Declarations are only allowed during initialization.
+        if !indexer.is_initializing.get() {
+            return false;
+        }
+
+        let Ok(mut declarations) = indexer.declarations.try_borrow_mut() else {
+            return false;
+        };
+
+        declare(&mut declarations).is_some()
+    }
+}
+
+/// # Safety
+///
+/// Same as [`CStr::from_ptr`], except the given pointer *can* be null.
+unsafe fn owned_str(ptr: *const c_char) -> Option<CString> {
+    cstr_to_str(ptr).map(CString::from)
+}
+
+#[inline]
+fn timestamp(timestamp: clap_timestamp) -> Option<u64> {
+    (timestamp != CLAP_TIMESTAMP_UNKNOWN).then_some(timestamp)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn declare_filetype(
+    indexer: *const clap_preset_discovery_indexer,
+    filetype: *const clap_preset_discovery_filetype,
+) -> bool {
+    Indexer::declare(indexer, |declarations| {
+        let filetype = filetype.as_ref()?;
+
+        declarations.filetypes.push(PresetFiletype {
+            name: owned_str(filetype.name)?,
+            description: owned_str(filetype.description),
+            file_extension: owned_str(filetype.file_extension),
+        });
+
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn declare_location(
+    indexer: *const clap_preset_discovery_indexer,
+    location: *const clap_preset_discovery_location,
+) -> bool {
+    Indexer::declare(indexer, |declarations| {
+        let location = location.as_ref()?;
+
+        let kind = match location.kind {
+            CLAP_PRESET_DISCOVERY_LOCATION_FILE => {
+                PresetDiscoveryLocationKind::File(owned_str(location.location)?)
+            }
+            CLAP_PRESET_DISCOVERY_LOCATION_PLUGIN => PresetDiscoveryLocationKind::Plugin,
+            _ => return None,
+        };
+
+        declarations.locations.push(PresetDiscoveryLocation {
+            flags: location.flags,
+            name: owned_str(location.name)?,
+            kind,
+        });
+
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn declare_soundpack(
+    indexer: *const clap_preset_discovery_indexer,
+    soundpack: *const clap_preset_discovery_soundpack,
+) -> bool {
+    Indexer::declare(indexer, |declarations| {
+        let soundpack = soundpack.as_ref()?;
+
+        declarations.soundpacks.push(PresetSoundpack {
+            flags: soundpack.flags,
+            id: owned_str(soundpack.id)?,
+            name: owned_str(soundpack.name)?,
+            description: owned_str(soundpack.description),
+            homepage_url: owned_str(soundpack.homepage_url),
+            vendor: owned_str(soundpack.vendor),
+            image_path: owned_str(soundpack.image_path),
+            release_timestamp: timestamp(soundpack.release_timestamp),
+        });
+
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get_extension(
+    _indexer: *const clap_preset_discovery_indexer,
+    _extension_id: *const c_char,
+) -> *const c_void {
+    core::ptr::null()
+}
+
+#[derive(Default)]
+struct ReceiverState {
+    presets: Vec<PresetMetadata>,
+    error: Option<(i32, Option<CString>)>,
+}
+
+/// # Safety
+///
+/// The given pointer must be null or point to a receiver created by
+/// [`PresetProvider::presets_at`].
+unsafe fn with_state(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    handler: impl FnOnce(&mut ReceiverState) -> Option<()>,
+) -> bool {
+    let Some(state) = receiver
+        .as_ref()
+        .and_then(|r| (r.receiver_data as *const RefCell<ReceiverState>).as_ref())
+    else {
+        return false;
+    };
+
+    let Ok(mut state) = state.try_borrow_mut() else {
+        return false;
+    };
+
+    handler(&mut state).is_some()
+}
+
+/// # Safety
+///
+/// Same as [`with_state`].
+unsafe fn with_current_preset(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    handler: impl FnOnce(&mut PresetMetadata) -> Option<()>,
+) {
+    with_state(receiver, |state| handler(state.presets.last_mut()?));
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn on_error(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    os_error: i32,
+    error_message: *const c_char,
+) {
+    with_state(receiver, |state| {
+        // Only keep the first error, which is likely to be the most relevant one.
+        if state.error.is_none() {
+            state.error = Some((os_error, owned_str(error_message)));
+        }
+
+        Some(())
+    });
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn begin_preset(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    name: *const c_char,
+    load_key: *const c_char,
+) -> bool {
+    with_state(receiver, |state| {
+        state.presets.push(PresetMetadata {
+            name: owned_str(name),
+            load_key: owned_str(load_key),
+            ..Default::default()
+        });
+
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn add_plugin_id(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    plugin_id: *const clap_plugin_id,
+) {
+    with_current_preset(receiver, |preset| {
+        let plugin_id = plugin_id.as_ref()?;
+
+        preset.plugin_ids.push(PresetPluginId {
+            abi: owned_str(plugin_id.abi)?,
+            id: owned_str(plugin_id.id)?,
+        });
+
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_soundpack_id(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    soundpack_id: *const c_char,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.soundpack_id = owned_str(soundpack_id);
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_flags(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    flags: u32,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.flags = flags;
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn add_creator(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    creator: *const c_char,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.creators.push(owned_str(creator)?);
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_description(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    description: *const c_char,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.description = owned_str(description);
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn set_timestamps(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    creation_time: clap_timestamp,
+    modification_time: clap_timestamp,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.creation_time = timestamp(creation_time);
+        preset.modification_time = timestamp(modification_time);
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn add_feature(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    feature: *const c_char,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.features.push(owned_str(feature)?);
+        Some(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn add_extra_info(
+    receiver: *const clap_preset_discovery_metadata_receiver,
+    key: *const c_char,
+    value: *const c_char,
+) {
+    with_current_preset(receiver, |preset| {
+        preset.extra_info.push((owned_str(key)?, owned_str(value)?));
+        Some(())
+    })
+}
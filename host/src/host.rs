@@ -196,7 +196,7 @@ mod info;
 
 pub use error::HostError;
 pub use extensions::HostExtensions;
-pub use info::HostInfo;
+pub use info::{HostInfo, HostInfoError};
 
 use crate::plugin::{InitializedPluginHandle, InitializingPluginHandle};
 
@@ -272,7 +272,9 @@ pub trait SharedHandler<'a>: Send + Sync {
 
     /// Called by the plugin when it requests to be deactivated and then restarted by the host.
     ///
-    /// This operation may be delayed by the host.
+    /// This operation may be delayed by the host. Once the plugin's audio processor is back on the
+    /// main thread, the restart itself can be performed using
+    /// [`PluginInstance::restart`](crate::plugin::PluginInstance::restart).
     fn request_restart(&self);
 
     /// Called by the plugin when it requests to be activated and/or to start processing.
@@ -281,6 +283,10 @@ pub trait SharedHandler<'a>: Send + Sync {
     /// Called by the plugin when it requests a call to the
     /// [`on_main_thread` callback](crate::plugin::PluginInstance::call_on_main_thread_callback)
     /// to be scheduled on the main thread.
+    ///
+    /// The request is also tracked by the plugin instance itself, so hosts can use
+    /// [`PluginInstance::call_on_main_thread`](crate::plugin::PluginInstance::call_on_main_thread)
+    /// to only perform the call if it is still pending.
     fn request_callback(&self);
 }
 
@@ -1,3 +1,4 @@
+use crate::extensions::wrapper::HostWrapperError;
 use crate::host::HostHandlers;
 use clack_common::extensions::*;
 use std::ffi::{c_void, CStr};
@@ -9,9 +10,14 @@ use std::ptr::NonNull;
 /// Host can declare the different extensions they support by using the
 /// [`register`](HostExtensions::register) method on this struct, during a call to
 /// [`declare_extensions`](HostHandlers::declare_extensions).
+///
+/// Extensions are matched in registration order: if the same extension is registered more than
+/// once, only the first registration is used. Because extensions are only declared when the plugin
+/// queries them, such duplicates are only detected once the plugin queries that extension.
 pub struct HostExtensions<'a, H: ?Sized> {
     found: Option<NonNull<c_void>>,
     requested: &'a CStr,
+    has_duplicate: bool,
     plugin_type: PhantomData<H>,
 }
 
@@ -21,6 +27,7 @@ impl<'a, H: HostHandlers> HostExtensions<'a, H> {
         Self {
             found: None,
             requested,
+            has_duplicate: false,
             plugin_type: PhantomData,
         }
     }
@@ -32,18 +39,46 @@ impl<'a, H: HostHandlers> HostExtensions<'a, H> {
             .unwrap_or(core::ptr::null_mut())
     }
 
-    /// Adds a given extension implementation to the list of extensions this plugin supports.
+    /// Returns an error if the requested extension was registered more than once.
+    #[inline]
+    pub(crate) fn check_duplicates(&self) -> Result<(), HostWrapperError> {
+        if self.has_duplicate {
+            Err(HostWrapperError::DuplicateExtension)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Adds a given extension implementation to the list of extensions this host supports.
+    ///
+    /// If an implementation of the same extension was already registered, the first one is kept
+    /// and this registration is ignored. In release builds, this is reported to the host's log.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if an implementation of the same extension was already
+    /// registered, and the plugin is querying that extension.
     pub fn register<E: ExtensionImplementation<H, ExtensionSide = HostExtensionSide>>(
         &mut self,
     ) -> &mut Self {
-        if self.found.is_some() {
+        if E::IDENTIFIER != self.requested {
             return self;
         }
 
-        if E::IDENTIFIER == self.requested {
-            self.found = Some(E::IMPLEMENTATION.as_ptr())
+        if self.found.is_some() {
+            self.has_duplicate = true;
+
+            if cfg!(debug_assertions) {
+                panic!(
+                    "Extension {:?} was registered more than once. Only the first registration is used.",
+                    self.requested
+                );
+            }
+
+            return self;
         }
 
+        self.found = Some(E::IMPLEMENTATION.as_ptr());
         self
     }
 }
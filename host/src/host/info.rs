@@ -1,5 +1,7 @@
 use clap_sys::host::clap_host;
+use std::error::Error;
 use std::ffi::{CStr, CString, NulError};
+use std::fmt::{Display, Formatter};
 use std::pin::Pin;
 use std::sync::Arc;
 
@@ -15,7 +17,8 @@ struct HostInfoInner {
 ///
 /// This information is passed to plugins at instantiation time by [`PluginInstance::new`](crate::prelude::PluginInstance::new).
 ///
-/// See the [`new`](HostInfo::new) method's documentation for an example of how to instantiate it.
+/// See the [`try_new`](HostInfo::try_new) method's documentation for an example of how to
+/// instantiate it.
 #[derive(Debug, Clone)]
 pub struct HostInfo {
     inner: Arc<HostInfoInner>,
@@ -31,6 +34,11 @@ impl HostInfo {
     ///
     /// All parameters are copied into new string buffers.
     ///
+    /// Note that this method accepts empty strings for all fields, even though the CLAP
+    /// specification requires the `name`, `vendor` and `version` fields to be set. Prefer using
+    /// [`try_new`](HostInfo::try_new) instead, which also validates those. This method is only
+    /// kept lenient for compatibility, and may be made stricter in the future.
+    ///
     /// # Errors
     ///
     /// All parameters must not contain the null (`\0`) character. If any of them do, a [`NulError`] is returned.
@@ -52,6 +60,51 @@ impl HostInfo {
         ))
     }
 
+    /// Creates a new host information container from its components, validating them.
+    ///
+    /// See the documentation for [`new`](HostInfo::new) for more information about the arguments.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`HostInfoError::InvalidNul`] if any of the parameters contain the null
+    /// (`\0`) character, or one of the other [`HostInfoError`] variants if any of the
+    /// `name`, `vendor` or `version` parameters is empty. The `url` parameter may be empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_host::host::{HostInfo, HostInfoError};
+    ///
+    /// let info = HostInfo::try_new("Bitwig Studio", "Bitwig GmbH", "https://bitwig.com", "4.3.2")?;
+    /// assert_eq!(info.name().unwrap().to_bytes(), b"Bitwig Studio");
+    ///
+    /// assert!(matches!(
+    ///     HostInfo::try_new("Bitwig Studio", "", "", "4.3.2"),
+    ///     Err(HostInfoError::EmptyVendor)
+    /// ));
+    /// # Ok::<_, HostInfoError>(())
+    /// ```
+    pub fn try_new(
+        name: &str,
+        vendor: &str,
+        url: &str,
+        version: &str,
+    ) -> Result<Self, HostInfoError> {
+        if name.is_empty() {
+            return Err(HostInfoError::EmptyName);
+        }
+
+        if vendor.is_empty() {
+            return Err(HostInfoError::EmptyVendor);
+        }
+
+        if version.is_empty() {
+            return Err(HostInfoError::EmptyVersion);
+        }
+
+        Self::new(name, vendor, url, version).map_err(HostInfoError::InvalidNul)
+    }
+
     /// An infallible version of [`new`](HostInfo::new).
     ///
     /// This method takes ownership of preexisting [`CString`] buffers, and therefore cannot fail
@@ -92,10 +145,89 @@ impl HostInfo {
         )
     }
 
+    /// The user-friendly name of the host, or [`None`] if it was left empty.
+    #[inline]
+    pub fn name(&self) -> Option<&CStr> {
+        non_empty(&self.inner.name)
+    }
+
+    /// The software vendor of the host, or [`None`] if it was left empty.
+    #[inline]
+    pub fn vendor(&self) -> Option<&CStr> {
+        non_empty(&self.inner.vendor)
+    }
+
+    /// The URL of the host, or [`None`] if it was left empty.
+    #[inline]
+    pub fn url(&self) -> Option<&CStr> {
+        non_empty(&self.inner.url)
+    }
+
+    /// The version string of the host, or [`None`] if it was left empty.
+    #[inline]
+    pub fn version(&self) -> Option<&CStr> {
+        non_empty(&self.inner.version)
+    }
+
     pub(crate) fn write_to_raw(&self, host: &mut clap_host) {
         host.name = self.inner.name.as_ptr();
         host.vendor = self.inner.vendor.as_ptr();
         host.url = self.inner.url.as_ptr();
         host.version = self.inner.version.as_ptr();
     }
+
+    /// Returns the raw name, vendor, URL and version strings, in that order.
+    ///
+    /// Unlike the public getters, empty fields are returned as empty strings, as the CLAP structs
+    /// these are written into do not allow null pointers.
+    pub(crate) fn raw_strings(&self) -> [&CStr; 4] {
+        [
+            &self.inner.name,
+            &self.inner.vendor,
+            &self.inner.url,
+            &self.inner.version,
+        ]
+    }
+}
+
+#[inline]
+fn non_empty(str: &CStr) -> Option<&CStr> {
+    if str.to_bytes().is_empty() {
+        None
+    } else {
+        Some(str)
+    }
+}
+
+/// Errors that can occur while creating a [`HostInfo`] using [`HostInfo::try_new`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum HostInfoError {
+    /// One of the given strings contains a null (`\0`) character.
+    InvalidNul(NulError),
+    /// The given host name is empty.
+    EmptyName,
+    /// The given host vendor is empty.
+    EmptyVendor,
+    /// The given host version is empty.
+    EmptyVersion,
+}
+
+impl Error for HostInfoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HostInfoError::InvalidNul(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for HostInfoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HostInfoError::InvalidNul(e) => write!(f, "Invalid host information string: {e}"),
+            HostInfoError::EmptyName => f.write_str("Host name must not be empty"),
+            HostInfoError::EmptyVendor => f.write_str("Host vendor must not be empty"),
+            HostInfoError::EmptyVersion => f.write_str("Host version must not be empty"),
+        }
+    }
 }
@@ -183,8 +183,8 @@
 //!
 //! // Event buffers
 //! // For this example, we'll only have a single input event.
-//! let note_on_event = NoteOnEvent::new(0, Pckn::new(0u16, 0u16, 12u16, 60u32), 4.2);
-//! let input_events_buffer = [note_on_event];
+//! let mut input_events_buffer = EventBuffer::new();
+//! input_events_buffer.push_note_on(0, Pckn::new(0u16, 0u16, 12u16, 60u32), 4.2);
 //! let mut output_events_buffer = EventBuffer::new();
 //!
 //! // Audio buffers
@@ -251,6 +251,7 @@
 //! assert_eq!(&[42.0f32, 69.0, 21.0, 34.5], &output_audio_buffers[1]);
 //!
 //! // The input note event has been passed through
+//! let note_on_event = NoteOnEvent::new(0, Pckn::new(0u16, 0u16, 12u16, 60u32), 4.2);
 //! assert_eq!(output_events_buffer.get(0).unwrap(), &note_on_event);
 //! assert_eq!(output_events_buffer.len(), 1);
 //!
@@ -266,6 +267,10 @@ pub mod factory;
 pub mod host;
 pub mod plugin;
 pub mod process;
+#[cfg(feature = "simple")]
+pub mod simple;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod util;
 
 pub use clack_common::events;
@@ -285,13 +290,14 @@ pub mod prelude {
             MainThreadHandler, SharedHandler,
         },
         plugin::{
-            InitializedPluginHandle, InitializingPluginHandle, PluginAudioProcessorHandle,
-            PluginInstance, PluginInstanceError, PluginMainThreadHandle, PluginSharedHandle,
+            AudioPortsLayout, InitializedPluginHandle, InitializingPluginHandle,
+            PluginAudioProcessorHandle, PluginInstance, PluginInstanceError,
+            PluginMainThreadHandle, PluginSharedHandle,
         },
         process::{
             audio_buffers::{
                 AudioPortBuffer, AudioPortBufferType, AudioPorts, InputAudioBuffers, InputChannel,
-                OutputAudioBuffers,
+                OutputAudioBuffers, SharedScratch,
             },
             AudioPortProcessingInfo, PluginAudioConfiguration, ProcessStatus,
             StoppedPluginAudioProcessor,
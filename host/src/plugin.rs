@@ -1,14 +1,18 @@
 use crate::prelude::*;
+use crate::process::PluginAudioProcessor;
 use clap_sys::plugin::clap_plugin;
 use std::ffi::CStr;
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 use std::sync::Arc;
 
+mod audio_ports_layout;
 mod error;
 mod handle;
 pub(crate) mod instance;
 
+pub use audio_ports_layout::{AudioPortsLayout, PluginActivation};
 pub use error::PluginInstanceError;
 pub use handle::*;
 use instance::*;
@@ -16,6 +20,25 @@ use instance::*;
 pub use clack_common::plugin::*;
 
 /// A plugin instance.
+///
+/// # Threading
+///
+/// A plugin instance can neither be sent nor shared across threads: it stays on the thread it was
+/// created on, which is the plugin's main thread. This also applies to the
+/// [`PluginMainThreadHandle`]s it gives out, so main-thread plugin methods can't be called from
+/// another thread without any runtime check being needed.
+///
+/// ```compile_fail
+/// use clack_host::prelude::PluginMainThreadHandle;
+///
+/// fn assert_send<T: Send>() {}
+/// assert_send::<PluginMainThreadHandle<'static>>();
+/// ```
+///
+/// Audio-thread methods are only accessible through the [`PluginAudioProcessorHandle`] given out
+/// by the plugin's audio processor (e.g.
+/// [`StartedPluginAudioProcessor`](crate::process::StartedPluginAudioProcessor)), which is unique
+/// and requires exclusive access.
 pub struct PluginInstance<H: HostHandlers> {
     pub(crate) inner: ManuallyDrop<Arc<PluginInstanceInner<H>>>,
     _no_send: PhantomData<*const ()>,
@@ -49,6 +72,70 @@ impl<H: HostHandlers> PluginInstance<H> {
         })
     }
 
+    /// Instantiates the plugin whose descriptor is at the given `index` in the bundle's
+    /// [`PluginFactory`](crate::factory::PluginFactory).
+    ///
+    /// This is a convenience for hosts that already listed the factory's
+    /// [descriptors](crate::factory::PluginFactory::plugin_descriptors) and kept track of their
+    /// index. It is otherwise equivalent to calling [`new`](Self::new) with the
+    /// [`id`](crate::factory::PluginDescriptor::id) of that descriptor, which remains the preferred
+    /// way of identifying plugins (e.g. when saving a project).
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PluginInstanceError::InvalidPluginIndex`] if the factory has no plugin
+    /// descriptor at the given `index`, or [`PluginInstanceError::PluginNotFound`] if that
+    /// descriptor has no ID. Otherwise, this returns the same errors as [`new`](Self::new).
+    pub fn new_by_index<FS, FH>(
+        shared: FS,
+        main_thread: FH,
+        bundle: &PluginBundle,
+        index: u32,
+        host: &HostInfo,
+    ) -> Result<Self, PluginInstanceError>
+    where
+        FS: for<'b> FnOnce(&'b ()) -> <H as HostHandlers>::Shared<'b>,
+        FH: for<'b> FnOnce(
+            &'b <H as HostHandlers>::Shared<'b>,
+        ) -> <H as HostHandlers>::MainThread<'b>,
+    {
+        let factory = bundle
+            .get_plugin_factory()
+            .ok_or(PluginInstanceError::MissingPluginFactory)?;
+
+        if index >= factory.plugin_count() {
+            return Err(PluginInstanceError::InvalidPluginIndex);
+        }
+
+        let plugin_id = factory
+            .plugin_descriptor(index)
+            .ok_or(PluginInstanceError::InvalidPluginIndex)?
+            .id()
+            .ok_or(PluginInstanceError::PluginNotFound)?;
+
+        Self::new(shared, main_thread, bundle, plugin_id, host)
+    }
+
+    /// Activates the plugin instance with the given audio configuration.
+    ///
+    /// The `audio_processor` closure is called to create the host's
+    /// [`AudioProcessor`](HostHandlers::AudioProcessor) handler before the plugin itself is
+    /// activated. On success, this returns a [`StoppedPluginAudioProcessor`], which can then be
+    /// sent to the audio thread to start processing.
+    ///
+    /// # Errors
+    ///
+    /// If the plugin fails to activate, a [`PluginInstanceError::ActivationFailed`] error is
+    /// returned. As the CLAP ABI does not allow the plugin to report why it rejected the given
+    /// configuration, the plugin may have logged the reason through the host's `log` extension
+    /// instead, which hosts can then display to the user.
+    ///
+    /// In this case, the host's audio processor handler is immediately dropped, and this instance is
+    /// left in the same deactivated state it was before the call. Another activation attempt can
+    /// then be made, e.g. with a different [`PluginAudioConfiguration`] the plugin may support.
+    ///
+    /// This method also returns an error if the plugin is already active, in which case it is left
+    /// untouched.
     pub fn activate<FA>(
         &mut self,
         audio_processor: FA,
@@ -67,6 +154,198 @@ impl<H: HostHandlers> PluginInstance<H> {
         Ok(StoppedPluginAudioProcessor::new(Arc::clone(&self.inner)))
     }
 
+    /// Returns the layout of the plugin's audio ports, as declared through the `audio-ports`
+    /// extension.
+    ///
+    /// If the plugin does not implement the `audio-ports` extension, this returns a
+    /// [stereo layout](AudioPortsLayout::stereo) instead.
+    pub fn audio_ports_layout(&mut self) -> AudioPortsLayout {
+        AudioPortsLayout::from_plugin(&mut self.plugin_handle())
+            .unwrap_or_else(AudioPortsLayout::stereo)
+    }
+
+    /// Activates the plugin instance with the given audio configuration, and allocates the
+    /// [`AudioPorts`] matching the plugin's [audio ports layout](Self::audio_ports_layout).
+    ///
+    /// This behaves like [`activate`](Self::activate), but also returns input and output
+    /// [`AudioPorts`] whose port and channel capacities match the plugin's declarations, as well as
+    /// the [`AudioPortsLayout`] they were created from.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`activate`](Self::activate).
+    pub fn activate_with_config_and_ports<FA>(
+        &mut self,
+        audio_processor: FA,
+        configuration: PluginAudioConfiguration,
+    ) -> Result<PluginActivation<H>, PluginInstanceError>
+    where
+        FA: for<'a> FnOnce(
+            &'a <H as HostHandlers>::Shared<'a>,
+            &mut <H as HostHandlers>::MainThread<'a>,
+        ) -> <H as HostHandlers>::AudioProcessor<'a>,
+    {
+        if Arc::get_mut(&mut self.inner).is_none() {
+            return Err(PluginInstanceError::AlreadyActivatedPlugin);
+        }
+
+        // The audio ports configuration can only change while the plugin is inactive.
+        let layout = self.audio_ports_layout();
+        let audio_processor = self.activate(audio_processor, configuration)?;
+
+        Ok(PluginActivation {
+            audio_processor,
+            input_ports: layout.input_ports(),
+            output_ports: layout.output_ports(),
+            layout,
+        })
+    }
+
+    /// Probes which of the given `candidates` sample rates the plugin can be activated with.
+    ///
+    /// For each candidate sample rate, this tries to [`activate`](Self::activate) the plugin with
+    /// the given `configuration`, in which the sample rate is replaced by the candidate. If the
+    /// activation succeeds, the plugin is immediately deactivated again.
+    ///
+    /// The `audio_processor` closure is called before each activation attempt to create the host's
+    /// [`AudioProcessor`](HostHandlers::AudioProcessor) handler, which is dropped right after.
+    ///
+    /// This returns the sample rates the plugin successfully activated with, in the same order as
+    /// the given `candidates`.
+    ///
+    /// Note that CLAP plugins do not report why their activation failed: a candidate sample rate
+    /// missing from the result may also be caused by the other parameters of the given
+    /// `configuration`.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PluginInstanceError::AlreadyActivatedPlugin`] if the plugin is already
+    /// active, in which case it is left untouched.
+    pub fn probe_sample_rates<FA>(
+        &mut self,
+        candidates: &[f64],
+        configuration: PluginAudioConfiguration,
+        mut audio_processor: FA,
+    ) -> Result<Vec<f64>, PluginInstanceError>
+    where
+        FA: for<'a> FnMut(
+            &'a <H as HostHandlers>::Shared<'a>,
+            &mut <H as HostHandlers>::MainThread<'a>,
+        ) -> <H as HostHandlers>::AudioProcessor<'a>,
+    {
+        if Arc::get_mut(&mut self.inner).is_none() {
+            return Err(PluginInstanceError::AlreadyActivatedPlugin);
+        }
+
+        let mut supported = Vec::with_capacity(candidates.len());
+
+        for &sample_rate in candidates {
+            let configuration = PluginAudioConfiguration {
+                sample_rate,
+                ..configuration
+            };
+
+            match self.activate(&mut audio_processor, configuration) {
+                Ok(processor) => {
+                    self.deactivate(processor);
+                    supported.push(sample_rate);
+                }
+                Err(PluginInstanceError::ActivationFailed) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(supported)
+    }
+
+    /// Restarts the plugin instance, i.e. deactivates it, and then re-activates it with the given
+    /// audio configuration.
+    ///
+    /// This is what plugins expect hosts to do when they call
+    /// [`SharedHandler::request_restart`], e.g. because their latency changed. Because plugins can
+    /// only be deactivated and activated on the main thread, the given `processor` must first be
+    /// moved back from the audio thread.
+    ///
+    /// The given `processor` is stopped if it was still processing, and then deactivated, which
+    /// drops the host's current [`AudioProcessor`](HostHandlers::AudioProcessor) handler. The
+    /// `audio_processor` closure is then called to create a new one, like in
+    /// [`activate`](Self::activate).
+    ///
+    /// On success, this returns a new [`StoppedPluginAudioProcessor`], which can then be sent back
+    /// to the audio thread to start processing again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_host::prelude::*;
+    /// use clack_host::process::PluginAudioProcessor;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// struct MyHost;
+    ///
+    /// struct MyHostShared {
+    ///     restart_requested: AtomicBool,
+    /// }
+    ///
+    /// impl SharedHandler<'_> for MyHostShared {
+    ///     fn request_restart(&self) {
+    ///         // This may be called from any thread: the restart is deferred to the main thread.
+    ///         self.restart_requested.store(true, Ordering::Release);
+    ///     }
+    ///
+    ///     fn request_process(&self) {}
+    ///     fn request_callback(&self) {}
+    /// }
+    ///
+    /// impl HostHandlers for MyHost {
+    ///     type Shared<'a> = MyHostShared;
+    ///     type MainThread<'a> = ();
+    ///     type AudioProcessor<'a> = ();
+    /// }
+    ///
+    /// // Called on the main thread, once the processor was moved back from the audio thread.
+    /// fn handle_restart_request(
+    ///     instance: &mut PluginInstance<MyHost>,
+    ///     processor: PluginAudioProcessor<MyHost>,
+    ///     configuration: PluginAudioConfiguration,
+    /// ) -> Result<PluginAudioProcessor<MyHost>, PluginInstanceError> {
+    ///     let restart_requested = instance
+    ///         .access_shared_handler(|h| h.restart_requested.swap(false, Ordering::AcqRel));
+    ///
+    ///     if !restart_requested {
+    ///         return Ok(processor);
+    ///     }
+    ///
+    ///     let processor = instance.restart(processor, |_, _| (), configuration)?;
+    ///     Ok(processor.into())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the plugin fails to re-activate, this returns the same errors as
+    /// [`activate`](Self::activate). In that case, the plugin instance is left deactivated, and
+    /// another activation attempt can be made using [`activate`](Self::activate).
+    ///
+    /// # Panics
+    ///
+    /// This panics if the given `processor` doesn't belong to this plugin instance.
+    pub fn restart<FA>(
+        &mut self,
+        processor: PluginAudioProcessor<H>,
+        audio_processor: FA,
+        configuration: PluginAudioConfiguration,
+    ) -> Result<StoppedPluginAudioProcessor<H>, PluginInstanceError>
+    where
+        FA: for<'a> FnOnce(
+            &'a <H as HostHandlers>::Shared<'a>,
+            &mut <H as HostHandlers>::MainThread<'a>,
+        ) -> <H as HostHandlers>::AudioProcessor<'a>,
+    {
+        self.deactivate(processor.into_stopped());
+        self.activate(audio_processor, configuration)
+    }
+
     #[inline]
     pub fn deactivate(&mut self, processor: StoppedPluginAudioProcessor<H>) {
         self.deactivate_with(processor, |_, _| ())
@@ -111,23 +390,89 @@ impl<H: HostHandlers> PluginInstance<H> {
         wrapper.deactivate_with(drop_with)
     }
 
+    /// Calls the plugin's `on_main_thread` callback, whether it requested it or not.
+    ///
+    /// This also clears any pending callback request (see
+    /// [`needs_main_thread_callback`](Self::needs_main_thread_callback)).
     // FIXME: this should be on the handle?
     #[inline]
     pub fn call_on_main_thread_callback(&mut self) {
+        // Cleared before the call, so that requests made during the callback aren't lost.
+        self.inner.wrapper().take_callback_request();
+
         // SAFETY: this is done on the main thread, and the &mut reference guarantees no aliasing
         unsafe { self.inner.on_main_thread() }
     }
 
+    /// Returns `true` if the plugin requested a call to its `on_main_thread` callback (using
+    /// `request_callback`), and that call wasn't performed yet.
+    ///
+    /// Note that the [`SharedHandler::request_callback`] handler is still called on every request,
+    /// which hosts can use to wake up their main thread.
+    #[inline]
+    pub fn needs_main_thread_callback(&self) -> bool {
+        self.inner.wrapper().is_callback_requested()
+    }
+
+    /// Calls the plugin's `on_main_thread` callback, but only if the plugin requested it
+    /// and that call wasn't performed yet.
+    ///
+    /// This returns `true` if the callback was called, `false` otherwise.
+    ///
+    /// Hosts can call this method regularly on their main thread (e.g. after being woken up by
+    /// [`SharedHandler::request_callback`]) to service all of the plugin's callback requests.
+    /// If the plugin requests another callback while this one is running, it will be serviced by
+    /// the next call to this method.
+    #[inline]
+    pub fn call_on_main_thread(&mut self) -> bool {
+        if !self.inner.wrapper().take_callback_request() {
+            return false;
+        }
+
+        // SAFETY: this is done on the main thread, and the &mut reference guarantees no aliasing
+        unsafe { self.inner.on_main_thread() };
+        true
+    }
+
     #[inline]
     pub fn raw_instance(&self) -> &clap_plugin {
         self.inner.raw_instance()
     }
 
+    /// Returns `true` if the plugin instance was successfully initialized.
+    ///
+    /// Because [`PluginInstance::new`] returns an error if the plugin's initialization fails, this
+    /// is always `true` for an existing instance. It is provided for symmetry with the other state
+    /// inspection methods, to make lifecycle assertions more explicit.
+    ///
+    /// Like all methods of [`PluginInstance`], this can only be called on the main thread.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.inner.is_initialized()
+    }
+
+    /// Returns `true` if the plugin instance is currently active, i.e. if it has been successfully
+    /// [activated](Self::activate) and not deactivated since.
+    ///
+    /// Like all methods of [`PluginInstance`], this can only be called on the main thread. As
+    /// activation and deactivation also happen on the main thread, the returned value cannot
+    /// change concurrently.
     #[inline]
     pub fn is_active(&self) -> bool {
         self.inner.is_active()
     }
 
+    /// Returns `true` if the plugin instance's audio processor is currently
+    /// [started](crate::process::StartedPluginAudioProcessor).
+    ///
+    /// Like all methods of [`PluginInstance`], this can only be called on the main thread. However,
+    /// processing is started and stopped by the audio processor on the audio thread: the returned
+    /// value is only a snapshot, which may already be outdated if the audio thread is running.
+    #[inline]
+    pub fn is_processing(&self) -> bool {
+        self.inner.is_processing()
+    }
+
     #[inline]
     pub fn access_shared_handler<'s, R>(
         &'s self,
@@ -143,7 +488,7 @@ impl<H: HostHandlers> PluginInstance<H> {
     ) -> R {
         // SAFETY: we take &self, the only reference to the wrapper on the main thread, therefore
         // we can guarantee there are no mutable reference anywhere
-        unsafe { access(self.inner.wrapper().main_thread().as_ref()) }
+        unsafe { access(self.inner.wrapper().main_thread_unchecked().as_ref()) }
     }
 
     #[inline]
@@ -153,14 +498,82 @@ impl<H: HostHandlers> PluginInstance<H> {
     ) -> R {
         // SAFETY: we take &mut self, the only reference to the wrapper on the main thread, therefore
         // we can guarantee there are no mutable reference anywhere
-        unsafe { access(self.inner.wrapper().main_thread().as_mut()) }
+        unsafe { access(self.inner.wrapper().main_thread_unchecked().as_mut()) }
     }
 
+    /// Returns a handle to the plugin instance, which can be used from any thread.
+    ///
+    /// This handle can only be used to call the plugin's thread-safe functions. To call
+    /// main-thread functions (e.g. most extension methods), use [`plugin_handle`](Self::plugin_handle)
+    /// instead.
     #[inline]
     pub fn plugin_shared_handle(&self) -> PluginSharedHandle {
         self.inner.plugin_shared()
     }
 
+    /// Checks which of the given extension identifiers are implemented by this plugin instance.
+    ///
+    /// This returns a list of booleans, one for each of the given `identifiers` and in the same
+    /// order, which is set to `true` if the plugin supports the matching extension.
+    ///
+    /// This is a convenience batch version of [`PluginSharedHandle::supports`], which is useful
+    /// when hosts need to check the full set of extensions a plugin implements right after
+    /// instantiation (e.g. to configure their UI or buffers accordingly).
+    ///
+    /// # Threading
+    ///
+    /// Per the CLAP specification, a plugin's extensions can only be queried after the plugin has
+    /// been initialized. A [`PluginInstance`] is always initialized, and this method can only be
+    /// called from the main thread it lives on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_host::prelude::*;
+    /// use clap_sys::ext::{latency::CLAP_EXT_LATENCY, state::CLAP_EXT_STATE};
+    ///
+    /// # fn foo(plugin_instance: &PluginInstance<()>) {
+    /// let plugin_instance: &PluginInstance<()> = /* ... */
+    /// # plugin_instance;
+    /// let supported = plugin_instance.query_extensions(&[CLAP_EXT_STATE, CLAP_EXT_LATENCY]);
+    ///
+    /// if supported[0] {
+    ///     // The plugin supports the State extension
+    /// }
+    /// # }
+    /// ```
+    pub fn query_extensions(&self, identifiers: &[&CStr]) -> Vec<bool> {
+        let handle = self.plugin_shared_handle();
+
+        identifiers
+            .iter()
+            .map(|id| handle.supports_extension_id(id))
+            .collect()
+    }
+
+    /// Returns a handle to the plugin instance, to be used to call its main-thread functions.
+    ///
+    /// This is the handle that needs to be passed to the methods of plugin extensions that can
+    /// only be called on the main thread, such as `PluginLatency::get` or `PluginState::save` from
+    /// the `clack-extensions` crate.
+    ///
+    /// Because [`PluginInstance`] can only exist on the main thread, the returned handle is
+    /// guaranteed to be used on the main thread as well. It borrows this instance mutably, and
+    /// therefore can't be kept around: hosts should instead call this method again whenever they
+    /// need to call the plugin.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_extensions::latency::PluginLatency;
+    /// use clack_host::prelude::*;
+    ///
+    /// fn get_plugin_latency<H: HostHandlers>(instance: &mut PluginInstance<H>) -> Option<u32> {
+    ///     let latency: PluginLatency = instance.plugin_handle().get_extension()?;
+    ///
+    ///     Some(latency.get(&mut instance.plugin_handle()))
+    /// }
+    /// ```
     #[inline]
     pub fn plugin_handle(&mut self) -> PluginMainThreadHandle {
         // SAFETY: this type can only exist on the main thread.
@@ -168,6 +581,16 @@ impl<H: HostHandlers> PluginInstance<H> {
     }
 }
 
+impl<H: HostHandlers> Debug for PluginInstance<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginInstance")
+            .field("plugin_id", &self.inner.plugin_id())
+            .field("is_active", &self.is_active())
+            .field("is_processing", &self.is_processing())
+            .finish()
+    }
+}
+
 impl<H: HostHandlers> Drop for PluginInstance<H> {
     fn drop(&mut self) {
         // Only drop our Arc if we are the sole owner.
@@ -177,7 +600,34 @@ impl<H: HostHandlers> Drop for PluginInstance<H> {
             // SAFETY: We can only call this once (as we're in Drop), and we never use the inner
             // value again afterward.
             unsafe { ManuallyDrop::drop(&mut self.inner) }
-        };
+        } else {
+            #[cfg(any(debug_assertions, feature = "drop-diagnostics"))]
+            self.warn_leaked_instance();
+        }
+    }
+}
+
+impl<H: HostHandlers> PluginInstance<H> {
+    /// Warns the user that this instance is being dropped while its audio processor is still
+    /// alive, which leaks the plugin instance.
+    #[cfg(any(debug_assertions, feature = "drop-diagnostics"))]
+    #[cold]
+    fn warn_leaked_instance(&self) {
+        let plugin_id = self
+            .inner
+            .plugin_id()
+            .map(CStr::to_string_lossy)
+            .unwrap_or_else(|| "<unknown>".into());
+
+        // Our own reference is the one remaining strong reference that isn't an audio processor.
+        let processor_count = Arc::strong_count(&self.inner) - 1;
+
+        eprintln!(
+            "[CLAP_HOST_WARNING] Plugin instance '{plugin_id}' was dropped while {processor_count} \
+            audio processor(s) were not deactivated. The plugin instance will never be deactivated \
+            or destroyed, and its resources will leak. Pass the audio processor to \
+            PluginInstance::deactivate before dropping the instance."
+        );
     }
 }
 
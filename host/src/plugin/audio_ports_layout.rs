@@ -0,0 +1,127 @@
+use crate::prelude::*;
+use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
+use clap_sys::ext::audio_ports::{
+    clap_audio_port_info, clap_plugin_audio_ports, CLAP_EXT_AUDIO_PORTS,
+};
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+
+/// The number of channels of each of a plugin's input and output audio ports.
+///
+/// This can be either queried from the plugin's audio port declarations using
+/// [`PluginInstance::audio_ports_layout`], or built manually using [`new`](Self::new).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AudioPortsLayout {
+    input_channel_counts: Vec<u32>,
+    output_channel_counts: Vec<u32>,
+}
+
+impl AudioPortsLayout {
+    /// Creates a new layout from the given channel counts of each input and output port.
+    #[inline]
+    pub fn new(input_channel_counts: Vec<u32>, output_channel_counts: Vec<u32>) -> Self {
+        Self {
+            input_channel_counts,
+            output_channel_counts,
+        }
+    }
+
+    /// A layout with a single stereo input port, and a single stereo output port.
+    ///
+    /// This is the layout used for plugins that do not implement the `audio-ports` extension.
+    #[inline]
+    pub fn stereo() -> Self {
+        Self::new(vec![2], vec![2])
+    }
+
+    /// Queries the layout declared by the plugin through the `audio-ports` extension.
+    ///
+    /// This returns [`None`] if the plugin does not implement the extension.
+    pub(crate) fn from_plugin(plugin: &mut PluginMainThreadHandle) -> Option<Self> {
+        let audio_ports = plugin.get_extension::<RawPluginAudioPorts>()?;
+
+        Some(Self::new(
+            audio_ports.channel_counts(plugin, true),
+            audio_ports.channel_counts(plugin, false),
+        ))
+    }
+
+    /// The channel count of each input port, in order.
+    #[inline]
+    pub fn input_channel_counts(&self) -> &[u32] {
+        &self.input_channel_counts
+    }
+
+    /// The channel count of each output port, in order.
+    #[inline]
+    pub fn output_channel_counts(&self) -> &[u32] {
+        &self.output_channel_counts
+    }
+
+    /// Allocates [`AudioPorts`] with enough capacity for all the input ports of this layout.
+    #[inline]
+    pub fn input_ports(&self) -> AudioPorts {
+        AudioPorts::with_channel_counts(&self.input_channel_counts)
+    }
+
+    /// Allocates [`AudioPorts`] with enough capacity for all the output ports of this layout.
+    #[inline]
+    pub fn output_ports(&self) -> AudioPorts {
+        AudioPorts::with_channel_counts(&self.output_channel_counts)
+    }
+}
+
+/// The result of [`PluginInstance::activate_with_config_and_ports`].
+pub struct PluginActivation<H: HostHandlers> {
+    /// The audio processor of the newly activated plugin.
+    pub audio_processor: StoppedPluginAudioProcessor<H>,
+    /// The audio ports layout the plugin declared, or a stereo layout if it doesn't implement the
+    /// `audio-ports` extension.
+    pub layout: AudioPortsLayout,
+    /// The [`AudioPorts`] allocated for the plugin's input ports.
+    pub input_ports: AudioPorts,
+    /// The [`AudioPorts`] allocated for the plugin's output ports.
+    pub output_ports: AudioPorts,
+}
+
+/// A minimal, crate-private binding to the plugin side of the `audio-ports` extension.
+///
+/// The full bindings live in the `clack-extensions` crate, which depends on this one.
+#[derive(Copy, Clone)]
+struct RawPluginAudioPorts(RawExtension<PluginExtensionSide, clap_plugin_audio_ports>);
+
+// SAFETY: This type is repr(C) and ABI-compatible with the matching extension type.
+unsafe impl Extension for RawPluginAudioPorts {
+    const IDENTIFIER: &'static CStr = CLAP_EXT_AUDIO_PORTS;
+    type ExtensionSide = PluginExtensionSide;
+
+    #[inline]
+    unsafe fn from_raw(raw: RawExtension<Self::ExtensionSide>) -> Self {
+        Self(raw.cast())
+    }
+}
+
+impl RawPluginAudioPorts {
+    fn channel_counts(&self, plugin: &mut PluginMainThreadHandle, is_input: bool) -> Vec<u32> {
+        let audio_ports = plugin.use_extension(&self.0);
+
+        let (Some(count), Some(get)) = (audio_ports.count, audio_ports.get) else {
+            return Vec::new();
+        };
+
+        // SAFETY: This type ensures the function pointer is valid.
+        let count = unsafe { count(plugin.as_raw(), is_input) };
+
+        (0..count)
+            .filter_map(|index| {
+                let mut info = MaybeUninit::<clap_audio_port_info>::zeroed();
+
+                // SAFETY: This type ensures the function pointer is valid.
+                let success = unsafe { get(plugin.as_raw(), index, is_input, info.as_mut_ptr()) };
+
+                // SAFETY: we checked if the buffer was successfully written to
+                success.then(|| unsafe { info.assume_init_ref() }.channel_count)
+            })
+            .collect()
+    }
+}
@@ -18,9 +18,14 @@ pub enum PluginInstanceError {
     /// not activated yet.
     DeactivatedPlugin,
     /// The plugin instance's audio processor's activation failed.
+    ///
+    /// The plugin may have logged the reason (e.g. an unsupported sample rate) through the host's
+    /// `log` extension.
     ActivationFailed,
     /// No plugin with a matching ID was found during instantiation.
     PluginNotFound,
+    /// No plugin descriptor was found at the given index during instantiation.
+    InvalidPluginIndex,
     /// Tried to instantiate a plugin from a bundle which lacks a [`PluginFactory`](crate::factory::PluginFactory).
     ///
     /// This is a sign of a misbehaving plugin implementation.
@@ -29,8 +34,12 @@ pub enum PluginInstanceError {
     InstantiationFailed,
     /// The plugin has already been destroyed.
     PluginDestroyed,
-    /// The plugin's audio processing failed.
+    /// The plugin's audio processing failed, returning a status that is unknown to this host.
     ProcessingFailed,
+    /// The plugin's audio processing failed, returning the `CLAP_PROCESS_ERROR` status.
+    ///
+    /// The plugin may have logged the reason through the host's `log` extension.
+    ProcessingReturnedError,
     /// Tried to perform or stop processing when the audio processor was not started yet.
     ProcessingStopped,
     /// Tried to start processing when the processing was already started.
@@ -58,12 +67,16 @@ impl PluginInstanceError {
                 "Attempted to deactivate Plugin which still has an active AudioProcessor"
             }
             Self::DeactivatedPlugin => "Plugin is currently deactivated",
-            Self::ActivationFailed => "Unable to activate",
+            Self::ActivationFailed => {
+                "Unable to activate (the plugin may have logged the reason through the host's log extension)"
+            }
             Self::PluginNotFound => "Specified plugin was not found",
+            Self::InvalidPluginIndex => "No plugin exists at the specified index",
             Self::MissingPluginFactory => "No plugin factory was provided",
             Self::InstantiationFailed => "Could not instantiate",
             Self::PluginDestroyed => "Plugin was destroyed",
             Self::ProcessingFailed => "Could not process",
+            Self::ProcessingReturnedError => "Plugin returned an error while processing",
             Self::ProcessingStopped => "Audio Processor is currently stopped",
             Self::ProcessingStarted => "Audio Processor is currently started",
             Self::NullProcessFunction => "Plugin's process function is null",
@@ -1,6 +1,7 @@
 use crate::factory::PluginDescriptor;
 use clack_common::extensions::{Extension, PluginExtensionSide, RawExtension};
 use clap_sys::plugin::clap_plugin;
+use std::ffi::CStr;
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -129,12 +130,22 @@ impl<'a> PluginSharedHandle<'a> {
         self.raw.as_ptr()
     }
 
+    /// Retrieves the plugin's implementation of the given [extension type](Extension) `E`.
+    ///
+    /// This returns `None` if the plugin does not support the given extension.
+    ///
+    /// # Threading
+    ///
+    /// The CLAP specification allows `get_extension` to be called from any thread, but only once
+    /// the plugin has been initialized. This is enforced by this handle type, which can only be
+    /// obtained from an initialized plugin instance.
+    ///
+    /// Note however that while querying the extension is thread-safe, the methods of the returned
+    /// extension may each have their own threading requirements, which are then enforced by the
+    /// handle types they require.
     pub fn get_extension<E: Extension<ExtensionSide = PluginExtensionSide>>(&self) -> Option<E> {
-        // SAFETY: This type ensures the function pointers are valid
-        let ext =
-            unsafe { self.as_raw().get_extension?(self.raw.as_ptr(), E::IDENTIFIER.as_ptr()) };
+        let ext = self.get_raw_extension(E::IDENTIFIER)?;
 
-        let ext = NonNull::new(ext as *mut _)?;
         // SAFETY: The CLAP spec guarantees that the extension lives as long as the instance.
         let raw = unsafe { RawExtension::from_raw_plugin_extension(ext, self.raw) };
 
@@ -142,6 +153,36 @@ impl<'a> PluginSharedHandle<'a> {
         unsafe { Some(E::from_raw(raw)) }
     }
 
+    /// Returns `true` if the plugin implements the given [extension type](Extension) `E`, `false`
+    /// otherwise.
+    ///
+    /// This is a shortcut for checking whether [`get_extension`](Self::get_extension) returns
+    /// anything, and the same threading requirements apply.
+    ///
+    /// To check for multiple extensions at once, see also
+    /// [`PluginInstance::query_extensions`](crate::plugin::PluginInstance::query_extensions).
+    #[inline]
+    pub fn supports<E: Extension<ExtensionSide = PluginExtensionSide>>(&self) -> bool {
+        self.get_raw_extension(E::IDENTIFIER).is_some()
+    }
+
+    /// Returns `true` if the plugin implements the extension matching the given `identifier`,
+    /// `false` otherwise.
+    ///
+    /// This is an untyped version of [`supports`](Self::supports), mostly useful for checking
+    /// extensions that have no matching [`Extension`] type.
+    #[inline]
+    pub fn supports_extension_id(&self, identifier: &CStr) -> bool {
+        self.get_raw_extension(identifier).is_some()
+    }
+
+    fn get_raw_extension(&self, identifier: &CStr) -> Option<NonNull<()>> {
+        // SAFETY: This type ensures the function pointers are valid
+        let ext = unsafe { self.as_raw().get_extension?(self.raw.as_ptr(), identifier.as_ptr()) };
+
+        NonNull::new(ext as *mut _)
+    }
+
     /// Safely dereferences a [`RawExtension`] pointer produced by this plugin instance.
     ///
     /// See the documentation of the [`RawExtension`] type for more information about how this works
@@ -97,6 +97,19 @@ impl<H: HostHandlers> PluginInstanceInner<H> {
         unsafe { self.plugin_ptr.unwrap_unchecked().as_ref() }
     }
 
+    /// Returns the ID of the plugin, as given by its descriptor.
+    ///
+    /// This returns `None` if the plugin didn't provide a descriptor or an ID.
+    pub fn plugin_id(&self) -> Option<&CStr> {
+        // SAFETY: the descriptor pointer is provided by the plugin and must remain valid for the
+        // lifetime of the instance, if not null.
+        unsafe { self.raw_instance().desc.as_ref() }
+            .filter(|desc| !desc.id.is_null())
+            // SAFETY: we just checked the ID pointer isn't null. The CLAP spec requires it to be a
+            // valid C string.
+            .map(|desc| unsafe { CStr::from_ptr(desc.id) })
+    }
+
     #[inline]
     pub fn plugin_shared(&self) -> PluginSharedHandle {
         // SAFETY: the raw instance is guaranteed to be valid
@@ -143,11 +156,21 @@ impl<H: HostHandlers> PluginInstanceInner<H> {
         Ok(())
     }
 
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.plugin_ptr.is_some()
+    }
+
     #[inline]
     pub fn is_active(&self) -> bool {
         self.wrapper().is_active()
     }
 
+    #[inline]
+    pub fn is_processing(&self) -> bool {
+        self.is_started.load(Ordering::Acquire)
+    }
+
     #[inline]
     pub fn deactivate_with<T>(
         &mut self,
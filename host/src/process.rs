@@ -26,12 +26,24 @@ use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::plugin::instance::PluginInstanceInner;
 pub use clack_common::process::*;
 
 #[allow(missing_docs)] // TODO: doc this
 pub mod audio_buffers;
+mod block_processor;
+mod denormals;
+mod pending_start;
+mod steady_time;
+mod watchdog;
+
+pub use block_processor::BlockProcessor;
+pub use denormals::DenormalProtectionGuard;
+pub use pending_start::{PendingProcessingStart, ProcessingStartTask};
+pub use steady_time::SteadyTimeCounter;
+use watchdog::ProcessWatchdog;
 
 /// A handle to a plugin's audio processor that can be in either its `started` or `stopped` state.
 ///
@@ -43,6 +55,9 @@ pub mod audio_buffers;
 /// passed to either [`PluginInstance::deactivate`] or [`PluginInstance::deactivate_with`], *before*
 /// the [`PluginInstance`] itself is dropped. If this is not done, the plugin instance will never
 /// be deactivated or destroyed, and will leak all its associated resources.
+///
+/// In debug builds, or if the `drop-diagnostics` feature is enabled, a warning is printed to
+/// the standard error output when this happens.
 pub enum PluginAudioProcessor<H: HostHandlers> {
     /// The audio processor is in it's `started` state.
     Started(StartedPluginAudioProcessor<H>),
@@ -395,6 +410,9 @@ impl<H: HostHandlers> From<StoppedPluginAudioProcessor<H>> for PluginAudioProces
 /// the [`PluginInstance`] itself is dropped. If this is not done, the plugin instance will never
 /// be deactivated or destroyed, and will leak all its associated resources.
 ///
+/// In debug builds, or if the `drop-diagnostics` feature is enabled, a warning is printed to
+/// the standard error output when this happens.
+///
 /// [`process`]: Self::process
 /// [`stop_processing`]: Self::stop_processing
 /// [shared]: crate::prelude::SharedHandler
@@ -402,6 +420,7 @@ impl<H: HostHandlers> From<StoppedPluginAudioProcessor<H>> for PluginAudioProces
 /// [`destroy`](PluginInstance::deactivate)
 pub struct StartedPluginAudioProcessor<H: HostHandlers> {
     inner: Arc<PluginInstanceInner<H>>,
+    watchdog: Option<ProcessWatchdog>,
     _no_sync: PhantomData<UnsafeCell<()>>,
 }
 
@@ -410,6 +429,7 @@ impl<H: HostHandlers> StartedPluginAudioProcessor<H> {
     fn new(inner: Arc<PluginInstanceInner<H>>) -> Self {
         Self {
             inner,
+            watchdog: None,
             _no_sync: PhantomData,
         }
     }
@@ -441,6 +461,8 @@ impl<H: HostHandlers> StartedPluginAudioProcessor<H> {
     ///   This value can never decrease between two calls to `process`, unless [`reset`]
     ///   is called, or if it was increased beyond [`u64::MAX`] and it wrapped around.
     ///
+    ///   The [`SteadyTimeCounter`] helper can be used to keep track of this value.
+    ///
     ///   This can be set to `None` if not available.
     ///
     /// * `transport`: Transport information, as of sample `0`. See the [`TransportEvent`]
@@ -452,13 +474,21 @@ impl<H: HostHandlers> StartedPluginAudioProcessor<H> {
     /// whether the plugin can be put to sleep or not. See the [`ProcessStatus`] documentation
     /// for more information.
     ///
+    /// The audio buffers must never hold more frames than the `max_frames_count` the plugin was
+    /// activated with. The [`BlockProcessor`] helper can be used to process longer buffers, by
+    /// splitting them into multiple `process` calls.
+    ///
     /// # Errors
     ///
     /// This function can return [`PluginInstanceError::NullProcessFunction`] if the plugin
     /// implementation did not provide a valid underlying `process` function pointer.
     ///
-    /// This can also return [`PluginInstanceError::ProcessingFailed`] if the `process` function
-    /// failed for any reason.
+    /// If the plugin returned the `CLAP_PROCESS_ERROR` status, this returns
+    /// [`PluginInstanceError::ProcessingReturnedError`]. Plugins may report the reason of the
+    /// failure through the host's `log` extension, during this call.
+    ///
+    /// This can also return [`PluginInstanceError::ProcessingFailed`] if the plugin returned a
+    /// status that is unknown to this host.
     ///
     /// [`reset`]: Self::reset
     pub fn process(
@@ -504,15 +534,110 @@ impl<H: HostHandlers> StartedPluginAudioProcessor<H> {
             .process
             .ok_or(PluginInstanceError::NullProcessFunction)?;
 
-        // SAFETY: this type ensures the function pointer is valid
-        let status = unsafe { process_fn(instance, &process) };
+        let status = match &self.watchdog {
+            // SAFETY: this type ensures the function pointer is valid
+            None => unsafe { process_fn(instance, &process) },
+            Some(watchdog) => {
+                watchdog.start_call();
+                // SAFETY: this type ensures the function pointer is valid
+                let status = unsafe { process_fn(instance, &process) };
+                watchdog.end_call();
+                status
+            }
+        };
 
         match ProcessStatus::from_raw(status) {
-            None | Some(Err(())) => Err(PluginInstanceError::ProcessingFailed),
+            None => Err(PluginInstanceError::ProcessingFailed),
+            Some(Err(())) => Err(PluginInstanceError::ProcessingReturnedError),
             Some(Ok(status)) => Ok(status),
         }
     }
 
+    /// Process a chunk of audio frames and events, with denormal protection enabled for the
+    /// duration of the call.
+    ///
+    /// This is the same as [`process`](Self::process), except the CPU's flush-to-zero and
+    /// denormals-are-zero modes are enabled before calling the plugin, and restored to their
+    /// previous state afterwards. See [`DenormalProtectionGuard`] for more information.
+    ///
+    /// Hosts that process multiple plugins in a single audio callback may prefer to hold a
+    /// [`DenormalProtectionGuard`] for the whole callback instead.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`process`](Self::process).
+    #[inline]
+    pub fn process_with_denormal_protection(
+        &mut self,
+        audio_inputs: &InputAudioBuffers,
+        audio_outputs: &mut OutputAudioBuffers,
+        input_events: &InputEvents,
+        output_events: &mut OutputEvents,
+        steady_time: Option<u64>,
+        transport: Option<&TransportEvent>,
+    ) -> Result<ProcessStatus, PluginInstanceError> {
+        let _guard = DenormalProtectionGuard::new();
+
+        self.process(
+            audio_inputs,
+            audio_outputs,
+            input_events,
+            output_events,
+            steady_time,
+            transport,
+        )
+    }
+
+    /// Enables a watchdog that monitors the duration of every [`process`](Self::process) call.
+    ///
+    /// This spawns a separate monitor thread, which calls the given `on_timeout` callback
+    /// whenever a `process` call has been running for longer than the given `timeout`. The
+    /// callback receives the time the call has been running for so far, and is called at most
+    /// once per `process` call. It is always called from the monitor thread, never from the
+    /// audio thread.
+    ///
+    /// This can be used to detect misbehaving plugins that hang the audio thread, e.g. to log
+    /// the issue or to flag the plugin for unloading.
+    ///
+    /// Note that the watchdog can only *observe* slow or stuck `process` calls: it cannot
+    /// forcibly abort them, and the plugin keeps running on the audio thread until it returns
+    /// on its own (if ever).
+    ///
+    /// If a watchdog was already enabled, it is replaced by the new one. The watchdog is disabled
+    /// when processing is [stopped](Self::stop_processing), or if this audio processor is dropped.
+    ///
+    /// Because this spawns a thread, this method is not realtime-safe. However, recording the
+    /// start and end of each `process` call while the watchdog is enabled is.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the monitor thread could not be spawned.
+    pub fn enable_watchdog(
+        &mut self,
+        timeout: Duration,
+        on_timeout: impl FnMut(Duration) + Send + 'static,
+    ) -> std::io::Result<()> {
+        self.watchdog = Some(ProcessWatchdog::spawn(timeout, on_timeout)?);
+        Ok(())
+    }
+
+    /// Disables the watchdog enabled by [`enable_watchdog`](Self::enable_watchdog), stopping its
+    /// monitor thread.
+    ///
+    /// This does nothing if no watchdog is enabled.
+    #[inline]
+    pub fn disable_watchdog(&mut self) {
+        self.watchdog = None;
+    }
+
+    /// Returns `true` if a watchdog is enabled for this audio processor.
+    ///
+    /// See [`enable_watchdog`](Self::enable_watchdog).
+    #[inline]
+    pub fn has_watchdog(&self) -> bool {
+        self.watchdog.is_some()
+    }
+
     /// Resets the plugin's audio processing state.
     ///
     /// This clears all the plugin's internal buffers, kills all voices, and resets all processing
@@ -644,6 +769,9 @@ impl<H: HostHandlers> StartedPluginAudioProcessor<H> {
 /// the [`PluginInstance`] itself is dropped. If this is not done, the plugin instance will never
 /// be deactivated or destroyed, and will leak all its associated resources.
 ///
+/// In debug builds, or if the `drop-diagnostics` feature is enabled, a warning is printed to
+/// the standard error output when this happens.
+///
 /// [`activate`]: PluginInstance::activate
 /// [`process`]: StartedPluginAudioProcessor::process
 /// [`start_processing`]: Self::start_processing
@@ -684,12 +812,19 @@ impl<H: HostHandlers> StoppedPluginAudioProcessor<H> {
     /// If this succeeds, this returns an audio processor in the [started](StartedPluginAudioProcessor)
     /// state.
     ///
+    /// This call blocks until the plugin is done starting. See
+    /// [`start_processing_on`](Self::start_processing_on) to offload it to e.g. a worker thread
+    /// instead.
+    ///
     /// # Errors
     ///
     /// This method can fail if the underlying plugin's implementation fails for any reason.
     /// In this case, a [`ProcessingStartError`] is returned, from which the stopped audio processor
     /// can be recovered.
     ///
+    /// The CLAP ABI does not allow the plugin to report why it failed to start. Plugins may
+    /// however report it through the host's `log` extension, during this call.
+    ///
     /// [`process`]: StartedPluginAudioProcessor::process
     #[inline]
     pub fn start_processing(
@@ -697,10 +832,7 @@ impl<H: HostHandlers> StoppedPluginAudioProcessor<H> {
     ) -> Result<StartedPluginAudioProcessor<H>, ProcessingStartError<H>> {
         // SAFETY: this is called on the audio thread
         match unsafe { self.inner.start_processing() } {
-            Ok(()) => Ok(StartedPluginAudioProcessor {
-                inner: self.inner,
-                _no_sync: PhantomData,
-            }),
+            Ok(()) => Ok(StartedPluginAudioProcessor::new(self.inner)),
             Err(_) => Err(ProcessingStartError { processor: self }),
         }
     }
@@ -792,6 +924,12 @@ impl<H: HostHandlers> StoppedPluginAudioProcessor<H> {
 ///
 /// The [`StoppedPluginAudioProcessor`] can be recovered using the
 /// [`into_stopped_processor`](Self::into_stopped_processor) method.
+///
+/// The CLAP ABI only allows plugins to report that they failed to start processing, not why.
+/// Plugins that want to report a reason (including all plugins made with Clack) will log it using
+/// the host's `log` extension, during the call to
+/// [`start_processing`](StoppedPluginAudioProcessor::start_processing). Hosts should therefore
+/// implement that extension to be able to diagnose these failures.
 pub struct ProcessingStartError<H: HostHandlers> {
     processor: StoppedPluginAudioProcessor<H>,
 }
@@ -804,15 +942,44 @@ impl<H: HostHandlers> ProcessingStartError<H> {
     }
 }
 
+impl<H: HostHandlers> Debug for PluginAudioProcessor<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Started(s) => f.debug_tuple("Started").field(s).finish(),
+            Stopped(s) => f.debug_tuple("Stopped").field(s).finish(),
+        }
+    }
+}
+
+impl<H: HostHandlers> Debug for StartedPluginAudioProcessor<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StartedPluginAudioProcessor")
+            .field("plugin_id", &self.inner.plugin_id())
+            .field("has_watchdog", &self.watchdog.is_some())
+            .finish()
+    }
+}
+
+impl<H: HostHandlers> Debug for StoppedPluginAudioProcessor<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoppedPluginAudioProcessor")
+            .field("plugin_id", &self.inner.plugin_id())
+            .finish()
+    }
+}
+
 impl<H: HostHandlers> Debug for ProcessingStartError<H> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Failed to start plugin processing")
+        Display::fmt(self, f)
     }
 }
 
 impl<H: HostHandlers> Display for ProcessingStartError<H> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Failed to start plugin processing")
+        f.write_str(
+            "Failed to start plugin processing: the plugin's start_processing call returned false \
+            (the plugin may have logged the reason through the host's log extension)",
+        )
     }
 }
 
@@ -1,8 +1,29 @@
 //! Types to manipulate input and output audio buffers for processing.
-
-use clack_common::process::AudioPortProcessingInfo;
+//!
+//! # Bridging with `clack-plugin`
+//!
+//! When the `clack-plugin` feature is enabled, [`InputAudioBuffers`] and [`OutputAudioBuffers`]
+//! can be converted to and from `clack-plugin`'s `Audio` type, without copying any sample data.
+//! This allows to host Clack plugins in-process (e.g. in a plugin chain or graph running within a
+//! single plugin or application), and to pass audio between stages efficiently:
+//!
+//! * [`OutputAudioBuffers::as_plugin_audio_with_inputs`] (or its consuming variant,
+//!   [`to_plugin_audio_with_inputs`](OutputAudioBuffers::to_plugin_audio_with_inputs)) produces
+//!   the `Audio` to pass to a plugin's `process` method.
+//! * [`AudioPorts::from_plugin_audio`] and [`AudioPorts::from_plugin_audio_mut`] do the reverse,
+//!   e.g. to forward the `Audio` a plugin received to a nested plugin instance.
+//! * [`OutputAudioBuffers::as_input_buffers`] allows the output of a stage to be used as the
+//!   input of the next one.
+//!
+//! All of these conversions preserve the frame count, as well as each port's channel count,
+//! latency and constant mask. As they only reinterpret the same underlying CLAP buffer
+//! descriptors, any changes made to a port's constant mask by a plugin (e.g. using
+//! `OutputPort::set_constant_mask`) are visible through all of them.
+
+use clack_common::process::{AudioPortProcessingInfo, ConstantMask};
 use clap_sys::audio_buffer::clap_audio_buffer;
 use core::array::IntoIter;
+use core::fmt::{Debug, Formatter};
 
 pub struct InputChannel<'a, T> {
     pub buffer: &'a mut [T],
@@ -27,6 +48,23 @@ impl<'a, T> InputChannel<'a, T> {
     pub fn constant<D: ?Sized + AsMut<[T]> + 'a>(buffer: &'a mut D) -> Self {
         Self::from_buffer(buffer, true)
     }
+
+    #[inline]
+    fn into_constant(self) -> Self {
+        Self {
+            is_constant: true,
+            ..self
+        }
+    }
+}
+
+impl<T> Debug for InputChannel<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InputChannel")
+            .field("len", &self.buffer.len())
+            .field("is_constant", &self.is_constant)
+            .finish()
+    }
 }
 
 pub enum AudioPortBufferType<I32, I64> {
@@ -67,10 +105,44 @@ pub struct AudioPortBuffer<I32, I64> {
     pub latency: u32,
 }
 
+impl<'a, I32, I64> AudioPortBuffer<I32, I64>
+where
+    I32: IntoIterator<Item = InputChannel<'a, f32>>,
+    I64: IntoIterator<Item = InputChannel<'a, f64>>,
+{
+    /// Marks all the channels of this input port buffer as constant.
+    ///
+    /// This is useful to e.g. mark a whole port as silent, which allows plugins that check the
+    /// port's constant mask to skip processing it entirely.
+    ///
+    /// Note that only the first 64 channels of a port can be marked as constant, as the CLAP
+    /// constant mask is a 64-bit integer.
+    #[inline]
+    pub fn all_constant(
+        self,
+    ) -> AudioPortBuffer<
+        impl Iterator<Item = InputChannel<'a, f32>>,
+        impl Iterator<Item = InputChannel<'a, f64>>,
+    > {
+        AudioPortBuffer {
+            latency: self.latency,
+            channels: match self.channels {
+                AudioPortBufferType::F32(channels) => {
+                    AudioPortBufferType::F32(channels.into_iter().map(InputChannel::into_constant))
+                }
+                AudioPortBufferType::F64(channels) => {
+                    AudioPortBufferType::F64(channels.into_iter().map(InputChannel::into_constant))
+                }
+            },
+        }
+    }
+}
+
 // bikeshed
 pub struct AudioPorts {
     buffer_lists: Vec<*mut f32>, // Can be f32 or f64, cast on-demand
     buffer_configs: Vec<clap_audio_buffer>,
+    silence: Vec<f32>, // Shared by all channels in with_silent_input
 }
 
 // SAFETY: The pointers are only temporary storage, they are not used unless AudioPorts is exclusively borrowed
@@ -79,6 +151,13 @@ unsafe impl Send for AudioPorts {}
 unsafe impl Sync for AudioPorts {}
 
 impl AudioPorts {
+    /// Borrows the input and output buffers of the given `clack-plugin` [`Audio`](clack_plugin::prelude::Audio).
+    ///
+    /// The returned buffers have the same frame count as the `Audio`, and the same channel
+    /// counts, latencies and constant masks for all of their ports.
+    ///
+    /// See the [module documentation](self) for more information about bridging with
+    /// `clack-plugin`.
     #[cfg(feature = "clack-plugin")]
     pub fn from_plugin_audio_mut<'a>(
         audio: &'a mut clack_plugin::prelude::Audio,
@@ -95,6 +174,11 @@ impl AudioPorts {
         }
     }
 
+    /// Converts the given `clack-plugin` [`Audio`](clack_plugin::prelude::Audio) into its input
+    /// and output buffers.
+    ///
+    /// This is the same as [`from_plugin_audio_mut`](Self::from_plugin_audio_mut), but consumes
+    /// the `Audio` instead of borrowing it.
     #[cfg(feature = "clack-plugin")]
     pub fn from_plugin_audio(
         audio: clack_plugin::prelude::Audio,
@@ -115,12 +199,22 @@ impl AudioPorts {
         let mut bufs = Self {
             buffer_configs: Vec::with_capacity(port_count),
             buffer_lists: Vec::with_capacity(total_channel_count),
+            silence: Vec::new(),
         };
         bufs.resize_buffer_configs(port_count);
 
         bufs
     }
 
+    /// Creates a new [`AudioPorts`] with enough capacity for ports with the given channel counts.
+    ///
+    /// Each item of `channel_counts` is the number of channels of a port.
+    pub fn with_channel_counts(channel_counts: &[u32]) -> Self {
+        let total_channel_count = channel_counts.iter().map(|&count| count as usize).sum();
+
+        Self::with_capacity(total_channel_count, channel_counts.len())
+    }
+
     #[inline]
     pub fn port_capacity(&self) -> usize {
         self.buffer_configs.len()
@@ -141,6 +235,20 @@ impl AudioPorts {
         }
     }
 
+    /// Prepares the given input buffers to be passed to a plugin's `process` call.
+    ///
+    /// The CLAP specification requires all the channels of a port to hold at least as many
+    /// samples as the number of frames being processed. The number of frames of the returned
+    /// buffers is therefore the length of the shortest channel across all ports.
+    ///
+    /// All the channels of a single port are expected to have the same length. In debug builds,
+    /// this panics if they don't, as this is most likely a bug in the host's buffer management.
+    /// In release builds, the extra samples in the longer channels are simply ignored.
+    ///
+    /// Channels marked as constant are reported in their port's constant mask. As the CLAP
+    /// constant mask is a 64-bit integer, any channel past the 64th is always reported as
+    /// non-constant, even if it was marked as constant. This is always valid, as the constant
+    /// mask is only a hint.
     pub fn with_input_buffers<'a, I, Iter, ChannelIter32, ChannelIter64>(
         &'a mut self,
         iter: I,
@@ -151,103 +259,81 @@ impl AudioPorts {
         ChannelIter32: IntoIterator<Item = InputChannel<'a, f32>>,
         ChannelIter64: IntoIterator<Item = InputChannel<'a, f64>>,
     {
-        let iter = iter.into_iter();
-        self.resize_buffer_configs(iter.len());
-        self.buffer_lists.clear();
-
-        let mut min_channel_buffer_length = usize::MAX;
-        let mut total = 0;
-        let mut has_reallocated = false;
+        let (total, frames_count) = self.write_buffer_configs(iter.into_iter());
 
-        for (i, port) in iter.enumerate() {
-            total = i + 1;
+        InputAudioBuffers {
+            buffers: &self.buffer_configs[..total],
+            frames_count,
+        }
+    }
 
-            let last = self.buffer_lists.len();
+    /// Prepares silent input buffers to be passed to a plugin's `process` call.
+    ///
+    /// Each item of `channel_counts` is the number of channels of a port, e.g. as returned by
+    /// [`AudioPortsLayout::input_channel_counts`](crate::plugin::AudioPortsLayout::input_channel_counts).
+    /// All channels of all ports hold `frames_count` zeroed 32-bit samples, and are marked as
+    /// constant in their port's constant mask.
+    ///
+    /// All channels share a single zeroed buffer, owned by this [`AudioPorts`]. It is only
+    /// (re-)allocated if `frames_count` is greater than in any previous call.
+    ///
+    /// Note that only the first 64 channels of a port can be marked as constant, as the CLAP
+    /// constant mask is a 64-bit integer.
+    pub fn with_silent_input(
+        &mut self,
+        channel_counts: &[u32],
+        frames_count: u32,
+    ) -> InputAudioBuffers<'_> {
+        let total_channel_count = channel_counts.iter().map(|&count| count as usize).sum();
 
-            let mut constant_mask = 0u64;
-            let is_f64 = match port.channels {
-                AudioPortBufferType::F32(channels) => {
-                    for channel in channels {
-                        min_channel_buffer_length =
-                            min_channel_buffer_length.min(channel.buffer.len());
-                        if channel.is_constant {
-                            constant_mask |= 1 << i as u64
-                        }
-
-                        if self.buffer_lists.len() >= self.buffer_lists.capacity() {
-                            has_reallocated = true;
-                        }
-
-                        self.buffer_lists.push(channel.buffer.as_mut_ptr().cast())
-                    }
-                    false
-                }
-                AudioPortBufferType::F64(channels) => {
-                    for channel in channels {
-                        min_channel_buffer_length =
-                            min_channel_buffer_length.min(channel.buffer.len());
-                        if channel.is_constant {
-                            constant_mask |= 1 << i as u64
-                        }
-
-                        if self.buffer_lists.len() >= self.buffer_lists.capacity() {
-                            has_reallocated = true;
-                        }
-
-                        self.buffer_lists.push(channel.buffer.as_mut_ptr().cast())
-                    }
-                    true
-                }
-            };
+        self.resize_buffer_configs(channel_counts.len());
 
-            let buffers = self.buffer_lists.get_mut(last..).unwrap_or(&mut []);
+        if self.silence.len() < frames_count as usize {
+            self.silence.resize(frames_count as usize, 0.0);
+        }
+        // Plugins aren't supposed to write to their inputs, but we can't trust them not to.
+        self.silence.fill(0.0);
 
-            // PANIC: this can only panic with an invalid implementation of ExactSizeIterator
-            let descriptor = &mut self.buffer_configs[i];
-            descriptor.channel_count = buffers.len() as u32;
-            descriptor.latency = port.latency;
-            descriptor.constant_mask = constant_mask;
+        self.buffer_lists.clear();
+        self.buffer_lists
+            .resize(total_channel_count, self.silence.as_mut_ptr());
 
-            if is_f64 {
-                descriptor.data64 = buffers.as_ptr().cast();
-                descriptor.data32 = core::ptr::null();
-            } else {
-                descriptor.data64 = core::ptr::null();
-                descriptor.data32 = buffers.as_ptr() as *const *const _;
-            }
-        }
+        let mut offset = 0;
+        for (descriptor, &channel_count) in self.buffer_configs.iter_mut().zip(channel_counts) {
+            let buffers = &self.buffer_lists[offset..offset + channel_count as usize];
+            offset += channel_count as usize;
 
-        // If a realloc occurred, we must rewrite all the pointers.
-        // Thankfully, we know we wrote them sequentially, and we stored the lengths, so it's easy
-        // to find them back.
-        if has_reallocated {
-            let mut last_len = 0;
-            for descriptor in &mut self.buffer_configs[..total] {
-                let channel_count = descriptor.channel_count as usize;
-                let buffers = self
-                    .buffer_lists
-                    .get_mut(last_len..channel_count)
-                    .unwrap_or(&mut []);
-                last_len += channel_count;
+            descriptor.channel_count = channel_count;
+            descriptor.latency = 0;
 
-                if descriptor.data32.is_null() {
-                    descriptor.data64 = buffers.as_ptr().cast();
-                } else {
-                    descriptor.data32 = buffers.as_ptr().cast();
-                }
+            let mut constant_mask = ConstantMask::FULLY_DYNAMIC;
+            for channel_index in 0..channel_count {
+                constant_mask.set_channel_constant(channel_index.into(), true);
             }
+            descriptor.constant_mask = constant_mask.to_bits();
+            descriptor.data32 = buffers.as_ptr() as *const *const _;
+            descriptor.data64 = core::ptr::null();
         }
 
         InputAudioBuffers {
-            buffers: &self.buffer_configs[..total],
-            frames_count: if min_channel_buffer_length == usize::MAX {
+            buffers: &self.buffer_configs[..channel_counts.len()],
+            frames_count: if total_channel_count == 0 {
                 None
             } else {
-                Some(min_channel_buffer_length as u32)
+                Some(frames_count)
             },
         }
     }
 
+    /// Prepares the given output buffers to be passed to a plugin's `process` call.
+    ///
+    /// The CLAP specification requires all the channels of a port to hold at least as many
+    /// samples as the number of frames being processed. The number of frames of the returned
+    /// buffers is therefore the length of the shortest channel across all ports.
+    ///
+    /// All the channels of a single port are expected to have the same length. In debug builds,
+    /// this panics if they don't, as this is most likely a bug in the host's buffer management.
+    /// In release builds, the extra samples in the longer channels are simply ignored.
     pub fn with_output_buffers<'a, I, Iter, ChannelIter32, ChannelIter64>(
         &'a mut self,
         iter: I,
@@ -258,7 +344,31 @@ impl AudioPorts {
         ChannelIter32: IntoIterator<Item = &'a mut [f32]>,
         ChannelIter64: IntoIterator<Item = &'a mut [f64]>,
     {
-        let iter = iter.into_iter();
+        let (total, frames_count) = self.write_buffer_configs(iter.into_iter());
+
+        OutputAudioBuffers {
+            buffers: &mut self.buffer_configs[..total],
+            frames_count,
+        }
+    }
+
+    /// Writes the buffer descriptors of all the given ports, for both
+    /// [`with_input_buffers`](Self::with_input_buffers) and
+    /// [`with_output_buffers`](Self::with_output_buffers).
+    ///
+    /// This returns the number of ports that were written, as well as the length of the shortest
+    /// channel across all ports, if there is any channel at all.
+    fn write_buffer_configs<Iter, ChannelIter32, ChannelIter64>(
+        &mut self,
+        iter: Iter,
+    ) -> (usize, Option<u32>)
+    where
+        Iter: ExactSizeIterator<Item = AudioPortBuffer<ChannelIter32, ChannelIter64>>,
+        ChannelIter32: IntoIterator,
+        ChannelIter32::Item: RawChannel,
+        ChannelIter64: IntoIterator,
+        ChannelIter64::Item: RawChannel,
+    {
         self.resize_buffer_configs(iter.len());
         self.buffer_lists.clear();
 
@@ -270,41 +380,31 @@ impl AudioPorts {
             total = i + 1;
 
             let last = self.buffer_lists.len();
+            let mut channel_lengths = ChannelLengths::new();
 
-            let is_f64 = match port.channels {
+            let (constant_mask, is_f64) = match port.channels {
                 AudioPortBufferType::F32(channels) => {
-                    for channel in channels {
-                        min_channel_buffer_length = min_channel_buffer_length.min(channel.len());
-
-                        if self.buffer_lists.len() >= self.buffer_lists.capacity() {
-                            has_reallocated = true;
-                        }
-
-                        self.buffer_lists.push(channel.as_mut_ptr().cast())
-                    }
-                    false
+                    let mask =
+                        self.push_channels(channels, &mut channel_lengths, &mut has_reallocated);
+                    (mask, false)
                 }
                 AudioPortBufferType::F64(channels) => {
-                    for channel in channels {
-                        min_channel_buffer_length = min_channel_buffer_length.min(channel.len());
-
-                        if self.buffer_lists.len() >= self.buffer_lists.capacity() {
-                            has_reallocated = true;
-                        }
-
-                        self.buffer_lists.push(channel.as_mut_ptr().cast())
-                    }
-                    true
+                    let mask =
+                        self.push_channels(channels, &mut channel_lengths, &mut has_reallocated);
+                    (mask, true)
                 }
             };
 
+            channel_lengths.debug_assert_equal(i);
+            min_channel_buffer_length = min_channel_buffer_length.min(channel_lengths.min);
+
             let buffers = self.buffer_lists.get_mut(last..).unwrap_or(&mut []);
 
             // PANIC: this can only panic with an invalid implementation of ExactSizeIterator
             let descriptor = &mut self.buffer_configs[i];
             descriptor.channel_count = buffers.len() as u32;
             descriptor.latency = port.latency;
-            descriptor.constant_mask = 0;
+            descriptor.constant_mask = constant_mask;
 
             if is_f64 {
                 descriptor.data64 = buffers.as_mut_ptr().cast();
@@ -324,7 +424,7 @@ impl AudioPorts {
                 let channel_count = descriptor.channel_count as usize;
                 let buffers = self
                     .buffer_lists
-                    .get_mut(last_len..channel_count)
+                    .get_mut(last_len..last_len + channel_count)
                     .unwrap_or(&mut []);
                 last_len += channel_count;
 
@@ -336,14 +436,45 @@ impl AudioPorts {
             }
         }
 
-        OutputAudioBuffers {
-            buffers: &mut self.buffer_configs[..total],
-            frames_count: if min_channel_buffer_length == usize::MAX {
-                None
-            } else {
-                Some(min_channel_buffer_length as u32)
-            },
+        let frames_count = if min_channel_buffer_length == usize::MAX {
+            None
+        } else {
+            Some(min_channel_buffer_length as u32)
+        };
+
+        (total, frames_count)
+    }
+
+    /// Pushes the pointers of all the given channels of a single port, and returns its constant
+    /// mask.
+    ///
+    /// Constant channels past the 64th can't be represented in the mask, and are therefore
+    /// reported as non-constant.
+    #[inline]
+    fn push_channels<C: RawChannel>(
+        &mut self,
+        channels: impl IntoIterator<Item = C>,
+        channel_lengths: &mut ChannelLengths,
+        has_reallocated: &mut bool,
+    ) -> u64 {
+        let mut constant_mask = ConstantMask::FULLY_DYNAMIC;
+
+        for (channel_index, channel) in channels.into_iter().enumerate() {
+            let (ptr, len, is_constant) = channel.into_raw_parts();
+
+            channel_lengths.push(len);
+            if is_constant {
+                constant_mask.set_channel_constant(channel_index as u64, true);
+            }
+
+            if self.buffer_lists.len() >= self.buffer_lists.capacity() {
+                *has_reallocated = true;
+            }
+
+            self.buffer_lists.push(ptr);
         }
+
+        constant_mask.to_bits()
     }
 
     #[inline]
@@ -352,6 +483,277 @@ impl AudioPorts {
     }
 }
 
+impl Debug for AudioPorts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AudioPorts")
+            .field("port_capacity", &self.port_capacity())
+            .field("channel_capacity", &self.buffer_lists.capacity())
+            .finish()
+    }
+}
+
+impl<T> Debug for SharedScratch<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedScratch")
+            .field("max_channel_count", &self.max_channel_count)
+            .field("max_frames_count", &self.max_frames_count)
+            .finish()
+    }
+}
+
+impl<T> Debug for ScratchChannels<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScratchChannels")
+            .field("channel_count", &self.len())
+            .field("frames_count", &self.frames_count)
+            .finish()
+    }
+}
+
+impl Debug for InputAudioBuffers<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InputAudioBuffers")
+            .field("frames_count", &self.frames_count)
+            .field("channel_counts", &ChannelCounts(self.buffers))
+            .finish()
+    }
+}
+
+impl Debug for OutputAudioBuffers<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OutputAudioBuffers")
+            .field("frames_count", &self.frames_count)
+            .field("channel_counts", &ChannelCounts(self.buffers))
+            .finish()
+    }
+}
+
+/// Displays the channel count of each port of the given buffers, without any pointers.
+struct ChannelCounts<'a>(&'a [clap_audio_buffer]);
+
+impl Debug for ChannelCounts<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|b| b.channel_count))
+            .finish()
+    }
+}
+
+/// A single channel buffer that can be handed to [`AudioPorts`], either as an input or an output.
+trait RawChannel {
+    /// Returns the pointer to the channel's samples (cast to `f32`), its length, and whether it
+    /// is constant.
+    fn into_raw_parts(self) -> (*mut f32, usize, bool);
+}
+
+impl<T> RawChannel for InputChannel<'_, T> {
+    #[inline]
+    fn into_raw_parts(self) -> (*mut f32, usize, bool) {
+        (
+            self.buffer.as_mut_ptr().cast(),
+            self.buffer.len(),
+            self.is_constant,
+        )
+    }
+}
+
+impl<T> RawChannel for &mut [T] {
+    #[inline]
+    fn into_raw_parts(self) -> (*mut f32, usize, bool) {
+        // Output buffers are never marked as constant by the host.
+        (self.as_mut_ptr().cast(), self.len(), false)
+    }
+}
+
+/// Tracks the shortest and longest channels of a single port.
+struct ChannelLengths {
+    min: usize,
+    max: usize,
+}
+
+impl ChannelLengths {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            min: usize::MAX,
+            max: 0,
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, length: usize) {
+        self.min = self.min.min(length);
+        self.max = self.max.max(length);
+    }
+
+    /// Checks all the channels of the port have the same length, in debug builds only.
+    #[inline]
+    fn debug_assert_equal(&self, port_index: usize) {
+        debug_assert!(
+            self.min == usize::MAX || self.min == self.max,
+            "Channels of audio port #{port_index} have mismatched lengths: the shortest has {} samples, but the longest has {}. All channels of a port must have the same length.",
+            self.min,
+            self.max
+        );
+    }
+}
+
+/// A scratch buffer arena, which can be shared across all the plugins of a processing graph.
+///
+/// In a plugin graph, plugins are processed one after another, all with the same block size.
+/// Instead of allocating intermediate audio buffers for every single plugin, a host can allocate a
+/// single [`SharedScratch`] upfront, sized for the maximum channel count and maximum block size
+/// it will ever need, and lend it to each plugin's `process` call in turn.
+///
+/// The channels lent by [`channels`](Self::channels) can directly be fed to
+/// [`AudioPorts::with_input_buffers`] or [`AudioPorts::with_output_buffers`].
+///
+/// # Example
+///
+/// ```
+/// use clack_host::prelude::*;
+///
+/// let mut scratch = SharedScratch::<f32>::new(4, 256);
+/// let mut input_ports = AudioPorts::with_capacity(2, 1);
+/// let mut output_ports = AudioPorts::with_capacity(2, 1);
+///
+/// // Lend the first two channels as inputs, and the next two as outputs, for a 128-frame block.
+/// let (inputs, outputs) = scratch.channels(128).split_at(2);
+///
+/// let input_buffers = input_ports.with_input_buffers([AudioPortBuffer {
+///     latency: 0,
+///     channels: AudioPortBufferType::f32_input_only(inputs.map(InputChannel::variable)),
+/// }]);
+///
+/// let output_buffers = output_ports.with_output_buffers([AudioPortBuffer {
+///     latency: 0,
+///     channels: AudioPortBufferType::f32_output_only(outputs),
+/// }]);
+///
+/// assert_eq!(input_buffers.frames_count(), Some(128));
+/// assert_eq!(output_buffers.frames_count(), Some(128));
+/// ```
+pub struct SharedScratch<T> {
+    data: Vec<T>,
+    max_channel_count: usize,
+    max_frames_count: usize,
+}
+
+impl<T: Copy + Default> SharedScratch<T> {
+    /// Allocates a new scratch arena, able to hold `max_channel_count` channels of
+    /// `max_frames_count` frames each.
+    ///
+    /// All the arena's samples are initialized to their default value (i.e. silence).
+    pub fn new(max_channel_count: usize, max_frames_count: usize) -> Self {
+        Self {
+            data: vec![T::default(); max_channel_count * max_frames_count],
+            max_channel_count,
+            max_frames_count,
+        }
+    }
+
+    /// Resets all of the arena's samples to their default value (i.e. silence).
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.fill(T::default())
+    }
+}
+
+impl<T> SharedScratch<T> {
+    /// Returns the maximum number of channels this arena can lend at once.
+    #[inline]
+    pub fn max_channel_count(&self) -> usize {
+        self.max_channel_count
+    }
+
+    /// Returns the maximum number of frames of each channel this arena can lend.
+    #[inline]
+    pub fn max_frames_count(&self) -> usize {
+        self.max_frames_count
+    }
+
+    /// Lends all the channels of this arena, each containing `frames_count` frames.
+    ///
+    /// If `frames_count` is greater than [`max_frames_count`](Self::max_frames_count), the
+    /// channels are truncated to `max_frames_count` frames.
+    ///
+    /// The arena's contents are not cleared: the channels may still contain data written during a
+    /// previous use.
+    #[inline]
+    pub fn channels(&mut self, frames_count: usize) -> ScratchChannels<'_, T> {
+        ScratchChannels {
+            data: &mut self.data,
+            frames_count: frames_count.min(self.max_frames_count),
+            stride: self.max_frames_count,
+        }
+    }
+}
+
+/// An iterator over channel buffers lent by a [`SharedScratch`] arena.
+///
+/// This is created by [`SharedScratch::channels`].
+pub struct ScratchChannels<'a, T> {
+    data: &'a mut [T],
+    frames_count: usize,
+    stride: usize,
+}
+
+impl<'a, T> ScratchChannels<'a, T> {
+    /// Splits these channels in two at the given channel index.
+    ///
+    /// The first returned set contains the channels `[0, mid)`, and the second contains all the
+    /// remaining channels. This allows to e.g. use some channels as inputs and the others as
+    /// outputs of the same process call.
+    ///
+    /// If `mid` is greater than the number of channels, the second set is empty.
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let mid = mid.min(self.len()) * self.stride;
+        let (first, second) = self.data.split_at_mut(mid);
+
+        (
+            Self {
+                data: first,
+                frames_count: self.frames_count,
+                stride: self.stride,
+            },
+            Self {
+                data: second,
+                frames_count: self.frames_count,
+                stride: self.stride,
+            },
+        )
+    }
+}
+
+impl<'a, T> Iterator for ScratchChannels<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stride == 0 || self.data.is_empty() {
+            return None;
+        }
+
+        let data = core::mem::take(&mut self.data);
+        let (channel, rest) = data.split_at_mut(self.stride.min(data.len()));
+        self.data = rest;
+
+        Some(&mut channel[..self.frames_count])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for ScratchChannels<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.len().checked_div(self.stride).unwrap_or(0)
+    }
+}
+
 pub struct InputAudioBuffers<'a> {
     buffers: &'a [clap_audio_buffer],
     frames_count: Option<u32>,
@@ -395,6 +797,10 @@ impl<'a> InputAudioBuffers<'a> {
         }
     }
 
+    /// Borrows the input buffers of the given `clack-plugin` [`Audio`](clack_plugin::prelude::Audio).
+    ///
+    /// The returned buffers have the same frame count as the `Audio`, and the same channel
+    /// counts, latencies and constant masks for all of their ports.
     #[cfg(feature = "clack-plugin")]
     pub fn from_plugin_audio(audio: &clack_plugin::prelude::Audio<'a>) -> InputAudioBuffers<'a> {
         let frames_count = audio.frames_count();
@@ -404,6 +810,11 @@ impl<'a> InputAudioBuffers<'a> {
         unsafe { InputAudioBuffers::from_raw_buffers(ins, frames_count) }
     }
 
+    /// Produces a `clack-plugin` [`Audio`](clack_plugin::prelude::Audio) with these input
+    /// buffers, and no output buffers.
+    ///
+    /// If these buffers have no ports, the `Audio`'s frame count is zero. Use
+    /// [`OutputAudioBuffers::as_plugin_audio_with_inputs`] to also pass output buffers.
     #[cfg(feature = "clack-plugin")]
     pub fn as_plugin_audio(&self) -> clack_plugin::prelude::Audio<'a> {
         // SAFETY: the validity of the buffers is guaranteed by this type
@@ -509,6 +920,11 @@ impl<'a> OutputAudioBuffers<'a> {
         }
     }
 
+    /// Converts the given `clack-plugin` [`Audio`](clack_plugin::prelude::Audio) into its
+    /// output buffers.
+    ///
+    /// The returned buffers have the same frame count as the `Audio`, and the same channel
+    /// counts, latencies and constant masks for all of their ports.
     #[cfg(feature = "clack-plugin")]
     pub fn from_plugin_audio(audio: clack_plugin::prelude::Audio<'a>) -> OutputAudioBuffers<'a> {
         let frames_count = audio.frames_count();
@@ -518,6 +934,10 @@ impl<'a> OutputAudioBuffers<'a> {
         unsafe { OutputAudioBuffers::from_raw_buffers(outs, frames_count) }
     }
 
+    /// Borrows the output buffers of the given `clack-plugin` [`Audio`](clack_plugin::prelude::Audio).
+    ///
+    /// This is the same as [`from_plugin_audio`](Self::from_plugin_audio), but borrows the
+    /// `Audio` instead of consuming it.
     #[cfg(feature = "clack-plugin")]
     pub fn from_plugin_audio_mut(
         audio: &'a mut clack_plugin::prelude::Audio,
@@ -529,6 +949,12 @@ impl<'a> OutputAudioBuffers<'a> {
         unsafe { OutputAudioBuffers::from_raw_buffers(outs, frames_count) }
     }
 
+    /// Produces a `clack-plugin` [`Audio`](clack_plugin::prelude::Audio) with these output
+    /// buffers, and no input buffers.
+    ///
+    /// If these buffers have no ports, the `Audio`'s frame count is zero. Use
+    /// [`as_plugin_audio_with_inputs`](Self::as_plugin_audio_with_inputs) to also pass input
+    /// buffers.
     #[cfg(feature = "clack-plugin")]
     pub fn as_plugin_audio(&'a mut self) -> clack_plugin::prelude::Audio<'a> {
         // SAFETY: the validity of the buffers is guaranteed by this type
@@ -541,6 +967,11 @@ impl<'a> OutputAudioBuffers<'a> {
         }
     }
 
+    /// Converts these output buffers into a `clack-plugin` [`Audio`](clack_plugin::prelude::Audio),
+    /// with no input buffers.
+    ///
+    /// This is the same as [`as_plugin_audio`](Self::as_plugin_audio), but consumes these buffers
+    /// instead of borrowing them.
     #[cfg(feature = "clack-plugin")]
     pub fn to_plugin_audio(self) -> clack_plugin::prelude::Audio<'a> {
         // SAFETY: the validity of the buffers is guaranteed by this type
@@ -553,6 +984,12 @@ impl<'a> OutputAudioBuffers<'a> {
         }
     }
 
+    /// Produces a `clack-plugin` [`Audio`](clack_plugin::prelude::Audio) with these output
+    /// buffers, and the given input buffers.
+    ///
+    /// The `Audio`'s frame count is the minimum frame count of both the inputs and outputs, as
+    /// returned by [`InputAudioBuffers::min_available_frames_with`]. All ports keep their channel
+    /// counts, latencies and constant masks.
     #[cfg(feature = "clack-plugin")]
     pub fn as_plugin_audio_with_inputs(
         &'a mut self,
@@ -570,6 +1007,11 @@ impl<'a> OutputAudioBuffers<'a> {
         }
     }
 
+    /// Converts these output buffers into a `clack-plugin` [`Audio`](clack_plugin::prelude::Audio),
+    /// with the given input buffers.
+    ///
+    /// This is the same as [`as_plugin_audio_with_inputs`](Self::as_plugin_audio_with_inputs),
+    /// but consumes these buffers instead of borrowing them.
     #[cfg(feature = "clack-plugin")]
     pub fn to_plugin_audio_with_inputs(
         self,
@@ -587,6 +1029,22 @@ impl<'a> OutputAudioBuffers<'a> {
         }
     }
 
+    /// Borrows these output buffers as [`InputAudioBuffers`].
+    ///
+    /// This allows the output of a processing stage to be used as the input of the next one, e.g.
+    /// in a chain of plugins, without copying any sample data.
+    ///
+    /// The returned buffers have the same frame count as these buffers, and the same channel
+    /// counts, latencies and constant masks for all of their ports. This includes any constant
+    /// mask set by the plugin that processed these buffers.
+    #[inline]
+    pub fn as_input_buffers(&self) -> InputAudioBuffers<'_> {
+        InputAudioBuffers {
+            buffers: self.buffers,
+            frames_count: self.frames_count,
+        }
+    }
+
     #[inline]
     pub fn as_raw_buffers(&mut self) -> &mut [clap_audio_buffer] {
         self.buffers
@@ -624,6 +1082,138 @@ impl<'a> OutputAudioBuffers<'a> {
     pub fn port_infos(&self) -> impl Iterator<Item = AudioPortProcessingInfo> + '_ {
         self.buffers.iter().map(AudioPortProcessingInfo::from_raw)
     }
+
+    /// Returns the samples of the given 32-bit output channel, up to [`frames_count`].
+    ///
+    /// This allows hosts to read back what the plugin wrote, e.g. for metering or analysis,
+    /// without going through the original buffers.
+    ///
+    /// This returns [`None`] if the port or channel doesn't exist, or if the port holds 64-bit
+    /// buffers, in which case [`channel_f64`](Self::channel_f64) must be used instead.
+    ///
+    /// [`frames_count`]: self.frames_count
+    #[inline]
+    pub fn channel_f32(&self, port_index: u32, channel_index: u32) -> Option<&[f32]> {
+        let port = self.buffers.get(port_index as usize)?;
+
+        if !port.data64.is_null() {
+            return None;
+        }
+
+        // SAFETY: this type ensures the buffer pointers are valid, and that all channels are at
+        // least frames_count long.
+        unsafe {
+            read_channel(
+                port.data32,
+                port.channel_count,
+                channel_index,
+                self.frames(),
+            )
+        }
+    }
+
+    /// Returns the samples of the given 64-bit output channel, up to [`frames_count`].
+    ///
+    /// This allows hosts to read back what the plugin wrote, e.g. for metering or analysis,
+    /// without going through the original buffers.
+    ///
+    /// This returns [`None`] if the port or channel doesn't exist, or if the port holds 32-bit
+    /// buffers, in which case [`channel_f32`](Self::channel_f32) must be used instead.
+    ///
+    /// [`frames_count`]: self.frames_count
+    #[inline]
+    pub fn channel_f64(&self, port_index: u32, channel_index: u32) -> Option<&[f64]> {
+        let port = self.buffers.get(port_index as usize)?;
+
+        // SAFETY: this type ensures the buffer pointers are valid, and that all channels are at
+        // least frames_count long.
+        unsafe {
+            read_channel(
+                port.data64,
+                port.channel_count,
+                channel_index,
+                self.frames(),
+            )
+        }
+    }
+
+    #[inline]
+    fn frames(&self) -> usize {
+        self.frames_count.unwrap_or(0) as usize
+    }
+
+    /// Fills all the channels of all the port buffers with zeroes, up to [`frames_count`].
+    ///
+    /// Each port is cleared using the sample type it was given, i.e. ports holding 64-bit
+    /// buffers are cleared as `f64`, and all the others as `f32`. The constant mask of each port
+    /// is also reset.
+    ///
+    /// This is useful for hosts to call right before processing, as plugins may only add their
+    /// output to the buffers instead of overwriting them.
+    ///
+    /// This method is realtime-safe: it does not allocate, and only writes to the buffers it has
+    /// been given.
+    ///
+    /// [`frames_count`]: self.frames_count
+    pub fn clear(&mut self) {
+        let frames_count = self.frames_count.unwrap_or(0) as usize;
+
+        for port in self.buffers.iter_mut() {
+            port.constant_mask = 0;
+            let channel_count = port.channel_count as usize;
+
+            // SAFETY: this type ensures the buffer pointers are valid and exclusively borrowed,
+            // and that all channels are at least frames_count long.
+            unsafe {
+                if !port.data64.is_null() {
+                    clear_channels(port.data64, channel_count, frames_count);
+                } else if !port.data32.is_null() {
+                    clear_channels(port.data32, channel_count, frames_count);
+                }
+            }
+        }
+    }
+}
+
+/// # Safety
+///
+/// `channels` must be either null, or point to `channel_count` channel pointers, each of which
+/// must be either null or valid for reads of `frames_count` samples for the lifetime `'a`.
+#[inline]
+unsafe fn read_channel<'a, T>(
+    channels: *const *const T,
+    channel_count: u32,
+    channel_index: u32,
+    frames_count: usize,
+) -> Option<&'a [T]> {
+    if channels.is_null() || channel_index >= channel_count {
+        return None;
+    }
+
+    let channel = *channels.add(channel_index as usize);
+
+    if channel.is_null() {
+        return None;
+    }
+
+    Some(core::slice::from_raw_parts(channel, frames_count))
+}
+
+/// # Safety
+///
+/// `channels` must point to `channel_count` channel pointers, each of which must be either null
+/// or valid for writes of `frames_count` samples.
+#[inline]
+unsafe fn clear_channels<T: Default + Copy>(
+    channels: *const *const T,
+    channel_count: usize,
+    frames_count: usize,
+) {
+    for channel in core::slice::from_raw_parts(channels, channel_count) {
+        if !channel.is_null() {
+            core::slice::from_raw_parts_mut(channel.cast_mut(), frames_count).fill(T::default());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -651,6 +1241,32 @@ mod test {
         assert_eq!(buffers.frames_count, Some(4));
     }
 
+    #[test]
+    pub fn f64_input_audio_buffers_work() {
+        let mut ports = AudioPorts::with_capacity(2, 1);
+        let mut bufs = [[0f64; 4]; 2];
+        let ptrs: Vec<_> = bufs.iter().map(|b| b.as_ptr()).collect();
+
+        let buffers = ports.with_input_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f64_input_only(bufs.iter_mut().map(|b| InputChannel {
+                buffer: b.as_mut_slice(),
+                is_constant: false,
+            })),
+        }]);
+
+        assert_eq!(buffers.buffers.len(), 1);
+        assert_eq!(buffers.frames_count, Some(4));
+
+        let port = &buffers.buffers[0];
+        assert_eq!(port.channel_count, 2);
+        assert!(port.data32.is_null());
+        assert!(!port.data64.is_null());
+        // SAFETY: the port holds 2 valid channel pointers, as checked above.
+        let channels = unsafe { core::slice::from_raw_parts(port.data64, 2) };
+        assert_eq!(channels, ptrs);
+    }
+
     #[test]
     pub fn output_audio_buffers_work() {
         let mut ports = AudioPorts::with_capacity(2, 1);
@@ -670,6 +1286,137 @@ mod test {
         assert_eq!(ports.port_count(), 1);
     }
 
+    #[test]
+    pub fn output_audio_buffers_can_be_read_back() {
+        let mut ports = AudioPorts::with_capacity(3, 2);
+        let mut bufs32 = [[1f32, 2., 3., 4.], [5f32, 6., 7., 8.]];
+        let mut bufs64 = [[9f64; 5]];
+
+        let [left, right] = &mut bufs32;
+        let [mono] = &mut bufs64;
+
+        let mut buffers = ports.with_output_buffers([
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::F32(vec![left.as_mut_slice(), right.as_mut_slice()]),
+            },
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::F64(vec![mono.as_mut_slice()]),
+            },
+        ]);
+
+        assert_eq!(
+            buffers.channel_f32(0, 1),
+            Some([5f32, 6., 7., 8.].as_slice())
+        );
+        assert_eq!(buffers.channel_f64(1, 0), Some([9f64; 4].as_slice()));
+
+        // Wrong sample types
+        assert_eq!(buffers.channel_f64(0, 0), None);
+        assert_eq!(buffers.channel_f32(1, 0), None);
+
+        // Out of bounds
+        assert_eq!(buffers.channel_f32(0, 2), None);
+        assert_eq!(buffers.channel_f32(2, 0), None);
+
+        buffers.truncate(2);
+        assert_eq!(buffers.channel_f32(0, 0), Some([1f32, 2.].as_slice()));
+    }
+
+    #[test]
+    pub fn output_audio_buffers_clear() {
+        let mut ports = AudioPorts::with_capacity(3, 2);
+        let mut bufs32 = [[1f32; 4]; 2];
+        let mut bufs64 = [[1f64; 6]];
+
+        let mut buffers = ports.with_output_buffers([
+            AudioPortBuffer::<Vec<_>, Vec<_>> {
+                latency: 0,
+                channels: AudioPortBufferType::F32(
+                    bufs32.iter_mut().map(|b| b.as_mut_slice()).collect(),
+                ),
+            },
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::F64(
+                    bufs64.iter_mut().map(|b| b.as_mut_slice()).collect(),
+                ),
+            },
+        ]);
+
+        buffers.as_raw_buffers()[0].constant_mask = 0b11;
+        buffers.clear();
+        assert_eq!(buffers.as_raw_buffers()[0].constant_mask, 0);
+
+        assert_eq!(bufs32, [[0f32; 4]; 2]);
+        // Only the first frames_count (4) frames are cleared
+        assert_eq!(bufs64, [[0f64, 0.0, 0.0, 0.0, 1.0, 1.0]]);
+    }
+
+    #[test]
+    pub fn ports_may_have_different_lengths() {
+        let mut ports = AudioPorts::with_capacity(3, 2);
+        let mut long = [[0f32; 8]; 2];
+        let mut short = [0f32; 4];
+
+        let buffers = ports.with_output_buffers([
+            AudioPortBuffer::<Vec<_>, Vec<_>> {
+                latency: 0,
+                channels: AudioPortBufferType::F32(
+                    long.iter_mut().map(|b| b.as_mut_slice()).collect(),
+                ),
+            },
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::F32(vec![short.as_mut_slice()]),
+            },
+        ]);
+
+        assert_eq!(buffers.frames_count, Some(4));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Channels of audio port #0 have mismatched lengths")]
+    pub fn mismatched_input_channel_lengths_panic() {
+        let mut ports = AudioPorts::with_capacity(2, 1);
+        let mut left = [0f32; 4];
+        let mut right = [0f32; 3];
+
+        ports.with_input_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_input_only([
+                InputChannel::variable(&mut left),
+                InputChannel::variable(&mut right),
+            ]),
+        }]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Channels of audio port #1 have mismatched lengths")]
+    pub fn mismatched_output_channel_lengths_panic() {
+        let mut ports = AudioPorts::with_capacity(3, 2);
+        let mut mono = [0f64; 4];
+        let mut left = [0f64; 4];
+        let mut right = [0f64; 5];
+
+        ports.with_output_buffers([
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f64_output_only(vec![mono.as_mut_slice()]),
+            },
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f64_output_only(vec![
+                    left.as_mut_slice(),
+                    right.as_mut_slice(),
+                ]),
+            },
+        ]);
+    }
+
     #[test]
     pub fn input_audio_buffers_work_with_refcell() {
         let mut ports = AudioPorts::with_capacity(2, 1);
@@ -690,6 +1437,28 @@ mod test {
         assert_eq!(buffers.frames_count, Some(4));
     }
 
+    #[test]
+    pub fn f64_input_audio_buffers_work_with_refcell() {
+        let mut ports = AudioPorts::with_capacity(2, 1);
+        let bufs = [RefCell::new([0f64; 4]), RefCell::new([0f64; 4])];
+        let mut borrowed: Vec<_> = bufs.iter().map(|c| c.borrow_mut()).collect();
+
+        let buffers = ports.with_input_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f64_input_only(borrowed.iter_mut().map(|b| {
+                InputChannel {
+                    buffer: b.as_mut_slice(),
+                    is_constant: false,
+                }
+            })),
+        }]);
+
+        assert_eq!(buffers.buffers.len(), 1);
+        assert_eq!(buffers.frames_count, Some(4));
+        assert!(buffers.buffers[0].data32.is_null());
+        assert!(!buffers.buffers[0].data64.is_null());
+    }
+
     #[test]
     pub fn output_audio_buffers_work_with_refcell() {
         let mut ports = AudioPorts::with_capacity(2, 1);
@@ -710,6 +1479,34 @@ mod test {
         assert_eq!(ports.port_count(), 1);
     }
 
+    #[test]
+    pub fn shared_scratch_lends_disjoint_channels() {
+        let mut scratch = SharedScratch::<f32>::new(3, 8);
+        let mut ports = AudioPorts::with_capacity(3, 1);
+
+        let (first, rest) = scratch.channels(4).split_at(1);
+        assert_eq!(first.len(), 1);
+        assert_eq!(rest.len(), 2);
+
+        for (i, channel) in rest.enumerate() {
+            assert_eq!(channel.len(), 4);
+            channel.fill(i as f32 + 1.0);
+        }
+
+        let buffers = ports.with_output_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_output_only(scratch.channels(16)),
+        }]);
+
+        assert_eq!(buffers.port_count(), 1);
+        assert_eq!(buffers.frames_count(), Some(8));
+
+        let channels: Vec<_> = scratch.channels(8).collect();
+        assert_eq!(channels[0], [0.0; 8]);
+        assert_eq!(channels[1], [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(channels[2], [2.0, 2.0, 2.0, 2.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
     #[test]
     pub fn audio_buffers_work_with_wrong_capacity() {
         let mut input_ports = AudioPorts::with_capacity(1, 1);
@@ -777,4 +1574,345 @@ mod test {
             }
         }
     }
+
+    #[test]
+    pub fn constant_mask_matches_constant_channels() {
+        let mut ports = AudioPorts::with_capacity(5, 2);
+        let [mut main_l, mut main_c, mut main_r, mut side_l, mut side_r] = [[0f32; 4]; 5];
+
+        let buffers = ports.with_input_buffers([
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_input_only(vec![
+                    InputChannel::constant(&mut main_l),
+                    InputChannel::variable(&mut main_c),
+                    InputChannel::constant(&mut main_r),
+                ]),
+            },
+            AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_input_only(vec![
+                    InputChannel::variable(&mut side_l),
+                    InputChannel::constant(&mut side_r),
+                ]),
+            },
+        ]);
+
+        let masks: Vec<_> = buffers
+            .port_infos()
+            .map(|info| info.constant_mask().to_bits())
+            .collect();
+        assert_eq!(masks, [0b101, 0b10]);
+    }
+
+    #[test]
+    pub fn mixed_input_ports_are_described_correctly() {
+        // No capacity, to also check the pointers are rewritten after reallocating.
+        let mut ports = AudioPorts::with_capacity(0, 0);
+        let mut bufs32 = [[1f32; 4]; 2];
+        let mut bufs64 = [[2f64; 4]; 3];
+        let ptrs32: Vec<_> = bufs32.iter().map(|b| b.as_ptr()).collect();
+        let ptrs64: Vec<_> = bufs64.iter().map(|b| b.as_ptr()).collect();
+
+        let [left, right] = &mut bufs32;
+        let buffers = ports.with_input_buffers([
+            AudioPortBuffer {
+                latency: 1,
+                channels: AudioPortBufferType::F32(vec![
+                    InputChannel::variable(left),
+                    InputChannel::constant(right),
+                ]),
+            },
+            AudioPortBuffer {
+                latency: 2,
+                channels: AudioPortBufferType::F64(
+                    bufs64
+                        .iter_mut()
+                        .map(InputChannel::constant)
+                        .collect::<Vec<_>>(),
+                ),
+            },
+        ]);
+
+        assert_eq!(buffers.frames_count(), Some(4));
+
+        let [port32, port64] = buffers.buffers else {
+            panic!("Expected two ports")
+        };
+
+        assert_eq!(port32.channel_count, 2);
+        assert_eq!(port32.latency, 1);
+        assert_eq!(port32.constant_mask, 0b10);
+        assert!(port32.data64.is_null());
+        // SAFETY: the port holds 2 valid channel pointers, as checked above.
+        let channels32 = unsafe { core::slice::from_raw_parts(port32.data32, 2) };
+        assert_eq!(channels32, ptrs32);
+
+        assert_eq!(port64.channel_count, 3);
+        assert_eq!(port64.latency, 2);
+        assert_eq!(port64.constant_mask, 0b111);
+        assert!(port64.data32.is_null());
+        // SAFETY: the port holds 3 valid channel pointers, as checked above.
+        let channels64 = unsafe { core::slice::from_raw_parts(port64.data64, 3) };
+        assert_eq!(channels64, ptrs64);
+    }
+
+    #[test]
+    pub fn mixed_output_ports_are_described_correctly() {
+        // No capacity, to also check the pointers are rewritten after reallocating.
+        let mut ports = AudioPorts::with_capacity(0, 0);
+        let mut bufs64 = [[2f64; 4]; 3];
+        let mut bufs32 = [[1f32; 4]; 2];
+        let ptrs64: Vec<_> = bufs64.iter().map(|b| b.as_ptr()).collect();
+        let ptrs32: Vec<_> = bufs32.iter().map(|b| b.as_ptr()).collect();
+
+        let buffers = ports.with_output_buffers([
+            AudioPortBuffer {
+                latency: 3,
+                channels: AudioPortBufferType::F64(
+                    bufs64
+                        .iter_mut()
+                        .map(|b| b.as_mut_slice())
+                        .collect::<Vec<_>>(),
+                ),
+            },
+            AudioPortBuffer {
+                latency: 4,
+                channels: AudioPortBufferType::F32(
+                    bufs32
+                        .iter_mut()
+                        .map(|b| b.as_mut_slice())
+                        .collect::<Vec<_>>(),
+                ),
+            },
+        ]);
+
+        assert_eq!(buffers.frames_count(), Some(4));
+
+        let [port64, port32] = &*buffers.buffers else {
+            panic!("Expected two ports")
+        };
+
+        assert_eq!(port64.channel_count, 3);
+        assert_eq!(port64.latency, 3);
+        assert_eq!(port64.constant_mask, 0);
+        assert!(port64.data32.is_null());
+        // SAFETY: the port holds 3 valid channel pointers, as checked above.
+        let channels64 = unsafe { core::slice::from_raw_parts(port64.data64, 3) };
+        assert_eq!(channels64, ptrs64);
+
+        assert_eq!(port32.channel_count, 2);
+        assert_eq!(port32.latency, 4);
+        assert_eq!(port32.constant_mask, 0);
+        assert!(port32.data64.is_null());
+        // SAFETY: the port holds 2 valid channel pointers, as checked above.
+        let channels32 = unsafe { core::slice::from_raw_parts(port32.data32, 2) };
+        assert_eq!(channels32, ptrs32);
+    }
+
+    #[test]
+    pub fn constant_channels_past_64th_are_not_constant() {
+        let mut ports = AudioPorts::with_capacity(0, 0);
+        let mut bufs = vec![[0f32; 4]; 100];
+
+        let buffers = ports.with_input_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_input_only(
+                bufs.iter_mut().map(InputChannel::constant),
+            ),
+        }]);
+
+        let info = buffers.port_info(0).unwrap();
+        assert_eq!(info.channel_count(), 100);
+        assert_eq!(info.constant_mask(), ConstantMask::FULLY_CONSTANT);
+        assert!(!info.constant_mask().is_channel_constant(64));
+        assert!(!info.constant_mask().is_channel_constant(99));
+    }
+
+    #[test]
+    pub fn silent_input_supports_more_than_64_channels() {
+        let mut ports = AudioPorts::with_capacity(0, 0);
+
+        let buffers = ports.with_silent_input(&[63, 64, 65, 300], 4);
+
+        let masks: Vec<_> = buffers
+            .port_infos()
+            .map(|info| info.constant_mask().to_bits())
+            .collect();
+        assert_eq!(masks, [u64::MAX >> 1, u64::MAX, u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    pub fn all_constant_marks_every_channel() {
+        let mut ports = AudioPorts::with_capacity(3, 1);
+        let mut bufs = [[0f64; 4]; 3];
+
+        let buffers = ports.with_input_buffers([AudioPortBuffer {
+            latency: 7,
+            channels: AudioPortBufferType::f64_input_only(
+                bufs.iter_mut().map(InputChannel::variable),
+            ),
+        }
+        .all_constant()]);
+
+        let info = buffers.port_info(0).unwrap();
+        assert_eq!(info.constant_mask().to_bits(), 0b111);
+        assert_eq!(info.latency(), 7);
+        assert_eq!(buffers.frames_count(), Some(4));
+    }
+
+    #[test]
+    pub fn silent_input_is_zeroed_and_constant() {
+        let mut ports = AudioPorts::with_capacity(0, 0);
+
+        let buffers = ports.with_silent_input(&[2, 1, 0], 16);
+        assert_eq!(buffers.port_count(), 3);
+        assert_eq!(buffers.frames_count(), Some(16));
+
+        let masks: Vec<_> = buffers
+            .port_infos()
+            .map(|info| info.constant_mask().to_bits())
+            .collect();
+        assert_eq!(masks, [0b11, 0b1, 0]);
+
+        // SAFETY: the validity of the buffers is guaranteed by the InputAudioBuffers type
+        let audio = unsafe {
+            Audio::from_raw_buffers(
+                buffers.as_raw_buffers(),
+                &mut [],
+                buffers.frames_count().unwrap(),
+            )
+        };
+        for port in audio.input_ports() {
+            let channels = port.channels().unwrap().into_f32().unwrap();
+            for channel in channels.iter() {
+                assert_eq!(channel, &[0.0; 16]);
+            }
+        }
+
+        assert_eq!(ports.with_silent_input(&[], 16).frames_count(), None);
+    }
+
+    #[test]
+    pub fn input_pointers_are_fixed_after_reallocating_many_ports() {
+        let mut ports = AudioPorts::with_capacity(1, 1);
+        let mut bufs = [[[1f32; 4]; 2], [[2f32; 4]; 2], [[3f32; 4]; 2]];
+
+        let buffers = ports.with_input_buffers(bufs.iter_mut().map(|port| AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_input_only(
+                port.iter_mut().map(InputChannel::variable),
+            ),
+        }));
+
+        // SAFETY: the validity of the buffers is guaranteed by the InputAudioBuffers type
+        let audio = unsafe {
+            Audio::from_raw_buffers(
+                buffers.as_raw_buffers(),
+                &mut [],
+                buffers.frames_count().unwrap(),
+            )
+        };
+        for (index, port) in audio.input_ports().enumerate() {
+            let channels = port.channels().unwrap().into_f32().unwrap();
+            for channel in channels.iter() {
+                assert_eq!(channel, &[(index + 1) as f32; 4]);
+            }
+        }
+    }
+
+    #[test]
+    pub fn debug_output_shows_counts_only() {
+        let mut ports = AudioPorts::with_capacity(3, 2);
+        assert_eq!(
+            format!("{ports:?}"),
+            "AudioPorts { port_capacity: 2, channel_capacity: 3 }"
+        );
+
+        let buffers = ports.with_silent_input(&[2, 1], 16);
+        assert_eq!(
+            format!("{buffers:?}"),
+            "InputAudioBuffers { frames_count: Some(16), channel_counts: [2, 1] }"
+        );
+
+        let mut bufs = [[0f32; 4]; 2];
+        let buffers = ports.with_output_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_output_only(
+                bufs.iter_mut().map(|b| b.as_mut_slice()),
+            ),
+        }]);
+        assert_eq!(
+            format!("{buffers:?}"),
+            "OutputAudioBuffers { frames_count: Some(4), channel_counts: [2] }"
+        );
+    }
+
+    #[test]
+    pub fn outputs_can_be_used_as_inputs() {
+        let mut ports = AudioPorts::with_capacity(2, 1);
+        let mut bufs = [[1f32; 8]; 2];
+
+        let mut outputs = ports.with_output_buffers([AudioPortBuffer {
+            latency: 3,
+            channels: AudioPortBufferType::f32_output_only(
+                bufs.iter_mut().map(|b| b.as_mut_slice()),
+            ),
+        }]);
+        outputs.as_raw_buffers()[0].constant_mask = 0b10;
+
+        let inputs = outputs.as_input_buffers();
+        assert_eq!(inputs.frames_count(), Some(8));
+
+        let info = inputs.port_info(0).unwrap();
+        assert_eq!(info.channel_count(), 2);
+        assert_eq!(info.latency(), 3);
+        assert_eq!(info.constant_mask().to_bits(), 0b10);
+    }
+
+    #[cfg(feature = "clack-plugin")]
+    #[test]
+    pub fn plugin_audio_round_trip_preserves_frames_and_constant_masks() {
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+        let [mut in_l, mut in_r] = [[0f32; 16]; 2];
+        let mut out_bufs = [[0f32; 16]; 2];
+
+        let inputs = input_ports.with_input_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_input_only(vec![
+                InputChannel::constant(&mut in_l),
+                InputChannel::variable(&mut in_r),
+            ]),
+        }]);
+        let mut outputs = output_ports.with_output_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f32_output_only(
+                out_bufs.iter_mut().map(|b| b.as_mut_slice()),
+            ),
+        }]);
+
+        let mut audio = outputs.as_plugin_audio_with_inputs(&inputs);
+        assert_eq!(audio.frames_count(), 16);
+        assert_eq!(audio.input_port(0).unwrap().constant_mask().to_bits(), 0b01);
+
+        // The plugin marks its whole output as constant.
+        audio
+            .output_port(0)
+            .unwrap()
+            .set_constant_mask(clack_common::process::ConstantMask::FULLY_CONSTANT);
+
+        let (inputs, outputs) = AudioPorts::from_plugin_audio_mut(&mut audio);
+        assert_eq!(inputs.frames_count(), Some(16));
+        assert_eq!(outputs.frames_count(), Some(16));
+        assert_eq!(inputs.port_info(0).unwrap().constant_mask().to_bits(), 0b01);
+
+        // The next stage sees the constant output as its input.
+        let next_inputs = outputs.as_input_buffers();
+        assert_eq!(next_inputs.frames_count(), Some(16));
+        assert_eq!(
+            next_inputs.port_info(0).unwrap().constant_mask(),
+            clack_common::process::ConstantMask::FULLY_CONSTANT
+        );
+    }
 }
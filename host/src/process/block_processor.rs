@@ -0,0 +1,342 @@
+use crate::host::HostHandlers;
+use crate::plugin::PluginInstanceError;
+use crate::process::audio_buffers::{InputAudioBuffers, OutputAudioBuffers};
+use crate::process::{ProcessStatus, StartedPluginAudioProcessor, SteadyTimeCounter};
+use clack_common::events::event_types::TransportEvent;
+use clack_common::events::io::EventBuffer;
+use clap_sys::audio_buffer::clap_audio_buffer;
+
+/// A helper to process buffers of any length, by splitting them into blocks that never exceed
+/// the plugin's maximum frame count.
+///
+/// When activated, a plugin is given a
+/// [`max_frames_count`](crate::process::PluginAudioConfiguration::max_frames_count), and
+/// [`process`](StartedPluginAudioProcessor::process) must never be called with more frames than
+/// that. Hosts that need to process larger buffers in one go (e.g. when rendering offline) can
+/// use this type, which calls `process` as many times as needed on consecutive sub-slices of the
+/// given buffers.
+///
+/// For each block, this type takes care of:
+///
+/// * Offsetting all channel buffers to the start of the block, while keeping the ports'
+///   latencies. Their constant masks are cleared, as they describe the whole buffers, and may
+///   not hold for every block;
+/// * Only passing the input events that fall within the block, with their times re-based to the
+///   start of the block;
+/// * Collecting the plugin's output events, with their times re-based to the start of the
+///   whole buffer;
+/// * Advancing the `steady_time` given to the plugin by the block's frame count (see
+///   [`SteadyTimeCounter`]).
+///
+/// # Example
+///
+/// ```
+/// use clack_host::prelude::*;
+/// use clack_host::process::{BlockProcessor, StartedPluginAudioProcessor};
+///
+/// fn render<H: HostHandlers>(
+///     processor: &mut StartedPluginAudioProcessor<H>,
+///     blocks: &mut BlockProcessor,
+///     ports: &mut AudioPorts,
+///     output: &mut [Vec<f32>],
+///     notes: &EventBuffer,
+/// ) -> Result<ProcessStatus, PluginInstanceError> {
+///     let mut output_events = EventBuffer::new();
+///     let mut outputs = ports.with_output_buffers([AudioPortBuffer {
+///         latency: 0,
+///         channels: AudioPortBufferType::f32_output_only(output.iter_mut().map(|c| c.as_mut_slice())),
+///     }]);
+///
+///     // The output buffers can be much longer than the plugin's max_frames_count.
+///     blocks.process(
+///         processor,
+///         &InputAudioBuffers::empty(),
+///         &mut outputs,
+///         notes,
+///         &mut output_events,
+///         None,
+///     )
+/// }
+/// ```
+///
+/// # Realtime Safety
+///
+/// This type keeps internal buffers to describe each block to the plugin, which grow as needed.
+/// Hosts should therefore use [`with_capacity`](Self::with_capacity) to pre-allocate enough space
+/// for their buffers and events, to avoid allocating on the audio thread.
+pub struct BlockProcessor {
+    max_frames_count: u32,
+    steady_time: SteadyTimeCounter,
+    channels: Vec<*mut f32>, // Can be f32 or f64, cast on-demand
+    input_configs: Vec<clap_audio_buffer>,
+    output_configs: Vec<clap_audio_buffer>,
+    output_constant_masks: Vec<u64>,
+    input_events: EventBuffer,
+    output_events: EventBuffer,
+}
+
+// SAFETY: The pointers are only temporary storage, they are not used unless BlockProcessor is exclusively borrowed
+unsafe impl Send for BlockProcessor {}
+// SAFETY: The pointers are only temporary storage, they are not used unless BlockProcessor is exclusively borrowed
+unsafe impl Sync for BlockProcessor {}
+
+impl BlockProcessor {
+    /// Creates a new block processor, which processes at most `max_frames_count` frames at once.
+    ///
+    /// This should be the same `max_frames_count` the plugin was activated with.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `max_frames_count` is zero.
+    pub fn new(max_frames_count: u32) -> Self {
+        Self::with_capacity(max_frames_count, 0, 0, 0)
+    }
+
+    /// Creates a new block processor, which processes at most `max_frames_count` frames at once,
+    /// with enough pre-allocated space for the given number of ports, channels, and events per
+    /// block.
+    ///
+    /// The channel and port counts are the totals of both input and output ports.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `max_frames_count` is zero.
+    pub fn with_capacity(
+        max_frames_count: u32,
+        total_channel_count: usize,
+        port_count: usize,
+        events_count: usize,
+    ) -> Self {
+        assert!(max_frames_count > 0, "max_frames_count must not be zero");
+
+        Self {
+            max_frames_count,
+            steady_time: SteadyTimeCounter::new(),
+            channels: Vec::with_capacity(total_channel_count),
+            input_configs: Vec::with_capacity(port_count),
+            output_configs: Vec::with_capacity(port_count),
+            output_constant_masks: Vec::with_capacity(port_count),
+            input_events: EventBuffer::with_capacity(events_count),
+            output_events: EventBuffer::with_capacity(events_count),
+        }
+    }
+
+    /// Returns the maximum number of frames processed at once.
+    #[inline]
+    pub fn max_frames_count(&self) -> u32 {
+        self.max_frames_count
+    }
+
+    /// Returns the `steady_time` that will be given to the plugin for the next processed block.
+    #[inline]
+    pub fn steady_time(&self) -> SteadyTimeCounter {
+        self.steady_time
+    }
+
+    /// Resets the `steady_time` given to the plugin back to `0`.
+    ///
+    /// This must be called alongside the plugin's
+    /// [`reset`](StartedPluginAudioProcessor::reset) method, if at all.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.steady_time.reset();
+    }
+
+    /// Processes the given buffers and events, in as many blocks as needed for none of them to
+    /// exceed [`max_frames_count`](Self::max_frames_count).
+    ///
+    /// The arguments are the same as [`StartedPluginAudioProcessor::process`], with the following
+    /// differences:
+    ///
+    /// * `input_events` must be sorted by time. If the buffers are split into multiple blocks,
+    ///   events whose time is past the end of the buffers are not passed to the plugin. Otherwise,
+    ///   `input_events` is passed to the plugin as-is.
+    /// * If the buffers are split into multiple blocks, the plugin is given empty constant masks
+    ///   for all ports. The output ports' constant masks are then only set for the channels that
+    ///   the plugin marked as constant in every block.
+    /// * The plugin's output events are appended to `output_events`, with times relative to the
+    ///   start of the buffers.
+    /// * The same `transport` is given to the plugin for every block, as this type does not
+    ///   advance it.
+    ///
+    /// If the buffers have no frames (e.g. if there are no audio ports at all), `process` is
+    /// called exactly once, with all the events.
+    ///
+    /// On success, this returns the [`ProcessStatus`] of the last processed block.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`StartedPluginAudioProcessor::process`]. If the plugin
+    /// returns an error for a block, the remaining blocks are not processed.
+    pub fn process<H: HostHandlers>(
+        &mut self,
+        processor: &mut StartedPluginAudioProcessor<H>,
+        audio_inputs: &InputAudioBuffers,
+        audio_outputs: &mut OutputAudioBuffers,
+        input_events: &EventBuffer,
+        output_events: &mut EventBuffer,
+        transport: Option<&TransportEvent>,
+    ) -> Result<ProcessStatus, PluginInstanceError> {
+        let frames_count = audio_inputs.min_available_frames_with(audio_outputs);
+
+        if frames_count <= self.max_frames_count {
+            let status = processor.process(
+                audio_inputs,
+                audio_outputs,
+                &input_events.as_input(),
+                &mut output_events.as_output(),
+                self.steady_time.get(),
+                transport,
+            )?;
+
+            self.steady_time.advance(frames_count);
+            return Ok(status);
+        }
+
+        let mut start = 0;
+        let mut status = ProcessStatus::Continue;
+
+        self.output_constant_masks.clear();
+        self.output_constant_masks
+            .resize(audio_outputs.port_count(), u64::MAX);
+
+        while start < frames_count {
+            let block_len = (frames_count - start).min(self.max_frames_count);
+            let end = start + block_len;
+
+            self.input_events.clear();
+            self.input_events
+                .push_range_rebased(input_events, start, end);
+            self.output_events.clear();
+
+            self.prepare_configs(
+                audio_inputs.as_raw_buffers(),
+                audio_outputs.as_raw_buffers(),
+                start as usize,
+            );
+
+            // SAFETY: the configs point to the same channel buffers as the given buffers, offset by
+            // `start` frames. Since `end` is lower than or equal to the frame count of the given
+            // buffers, all channels are valid for `block_len` frames.
+            let (inputs, mut outputs) = unsafe {
+                (
+                    InputAudioBuffers::from_raw_buffers(&self.input_configs, block_len),
+                    OutputAudioBuffers::from_raw_buffers(&mut self.output_configs, block_len),
+                )
+            };
+
+            status = processor.process(
+                &inputs,
+                &mut outputs,
+                &self.input_events.as_input(),
+                &mut self.output_events.as_output(),
+                self.steady_time.get(),
+                transport,
+            )?;
+
+            for (mask, config) in self
+                .output_constant_masks
+                .iter_mut()
+                .zip(&self.output_configs)
+            {
+                *mask &= config.constant_mask;
+            }
+
+            output_events.push_all_offset(&self.output_events, start);
+            self.steady_time.advance(block_len);
+            start = end;
+        }
+
+        for (config, mask) in audio_outputs
+            .as_raw_buffers()
+            .iter_mut()
+            .zip(&self.output_constant_masks)
+        {
+            config.constant_mask = *mask;
+        }
+
+        Ok(status)
+    }
+
+    /// Fills the input and output configs with copies of the given ones, but with all channel
+    /// pointers offset by `offset` frames.
+    fn prepare_configs(
+        &mut self,
+        inputs: &[clap_audio_buffer],
+        outputs: &[clap_audio_buffer],
+        offset: usize,
+    ) {
+        self.channels.clear();
+        self.input_configs.clear();
+        self.output_configs.clear();
+
+        // All channel pointers must be pushed first, as pushing may reallocate.
+        for config in inputs.iter().chain(outputs) {
+            push_offset_channels(&mut self.channels, config, offset);
+        }
+
+        let mut channels = self.channels.as_mut_ptr();
+        push_offset_configs(&mut self.input_configs, inputs, &mut channels);
+        push_offset_configs(&mut self.output_configs, outputs, &mut channels);
+    }
+}
+
+/// Pushes copies of the given buffer configs, pointing to the channel pointers starting at
+/// `channels`, which must have been pushed by [`push_offset_channels`] in the same order.
+///
+/// The copies' constant masks are cleared.
+fn push_offset_configs(
+    configs: &mut Vec<clap_audio_buffer>,
+    sources: &[clap_audio_buffer],
+    channels: &mut *mut *mut f32,
+) {
+    for source in sources {
+        let channel_count = source.channel_count as usize;
+        let mut config = *source;
+
+        // The given mask applies to the whole buffer, and we don't know if it holds for this block.
+        config.constant_mask = 0;
+
+        if !source.data32.is_null() {
+            config.data32 = *channels as *const *const f32;
+            *channels = channels.wrapping_add(channel_count);
+        }
+
+        if !source.data64.is_null() {
+            config.data64 = *channels as *const *const f64;
+            *channels = channels.wrapping_add(channel_count);
+        }
+
+        configs.push(config);
+    }
+}
+
+/// Pushes the channel pointers of the given buffer config, offset by `offset` frames.
+fn push_offset_channels(channels: &mut Vec<*mut f32>, config: &clap_audio_buffer, offset: usize) {
+    let channel_count = config.channel_count as usize;
+
+    if !config.data32.is_null() {
+        // SAFETY: the config is valid, therefore data32 points to channel_count channels.
+        let data32 = unsafe { core::slice::from_raw_parts(config.data32, channel_count) };
+        channels.extend(data32.iter().map(|c| c.wrapping_add(offset).cast_mut()));
+    }
+
+    if !config.data64.is_null() {
+        // SAFETY: the config is valid, therefore data64 points to channel_count channels.
+        let data64 = unsafe { core::slice::from_raw_parts(config.data64, channel_count) };
+        channels.extend(
+            data64
+                .iter()
+                .map(|c| c.wrapping_add(offset).cast_mut() as *mut f32),
+        );
+    }
+}
+
+impl core::fmt::Debug for BlockProcessor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlockProcessor")
+            .field("max_frames_count", &self.max_frames_count)
+            .field("steady_time", &self.steady_time)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,217 @@
+use crate::host::HostHandlers;
+use crate::process::{
+    ProcessingStartError, StartedPluginAudioProcessor, StoppedPluginAudioProcessor,
+};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::Duration;
+
+type StartResult<H> = Result<StartedPluginAudioProcessor<H>, ProcessingStartError<H>>;
+
+struct PendingState<H: HostHandlers> {
+    result: Mutex<Option<StartResult<H>>>,
+    finished: Condvar,
+}
+
+impl<H: HostHandlers> PendingState<H> {
+    fn complete(&self, result: StartResult<H>) {
+        *self.result.lock().unwrap_or_else(PoisonError::into_inner) = Some(result);
+        self.finished.notify_all();
+    }
+}
+
+/// A task that starts a plugin's audio processor, created by
+/// [`StoppedPluginAudioProcessor::start_processing_on`].
+///
+/// This task must be [run](Self::run) by the executor it was given to, e.g. on a worker thread.
+///
+/// If this task is dropped without being run, the start is considered to have failed, and the
+/// [`StoppedPluginAudioProcessor`] can be recovered from the resulting [`ProcessingStartError`].
+pub struct ProcessingStartTask<H: HostHandlers> {
+    processor: Option<StoppedPluginAudioProcessor<H>>,
+    state: Arc<PendingState<H>>,
+}
+
+impl<H: HostHandlers> ProcessingStartTask<H> {
+    /// Starts the plugin's audio processor, blocking until the plugin's `start_processing`
+    /// function returns.
+    ///
+    /// The result is then made available to the matching [`PendingProcessingStart`] handle.
+    pub fn run(mut self) {
+        if let Some(processor) = self.processor.take() {
+            self.state.complete(processor.start_processing());
+        }
+    }
+}
+
+impl<H: HostHandlers> Drop for ProcessingStartTask<H> {
+    fn drop(&mut self) {
+        if let Some(processor) = self.processor.take() {
+            self.state.complete(Err(ProcessingStartError { processor }));
+        }
+    }
+}
+
+/// A handle to a plugin audio processor that is being started by a
+/// [`ProcessingStartTask`], created by [`StoppedPluginAudioProcessor::start_processing_on`].
+///
+/// This handle can be polled (see [`try_take`](Self::try_take)) or waited on (see
+/// [`wait`](Self::wait) and [`wait_timeout`](Self::wait_timeout)) to retrieve the started audio
+/// processor once the plugin is done starting.
+///
+/// Note that the plugin's `start_processing` call itself cannot be cancelled: it always runs to
+/// completion, even if the host stops waiting for it. The audio processor cannot be used to
+/// [`process`](StartedPluginAudioProcessor::process) audio before then.
+pub struct PendingProcessingStart<H: HostHandlers> {
+    state: Arc<PendingState<H>>,
+}
+
+impl<H: HostHandlers> PendingProcessingStart<H> {
+    /// Returns `true` if the plugin is done starting, and the result can be retrieved without
+    /// blocking.
+    pub fn is_finished(&self) -> bool {
+        self.state
+            .result
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+
+    /// Returns the result of the start operation if it has finished, or `None` otherwise.
+    ///
+    /// This never blocks. Once this returns the result, this handle cannot be used again: all
+    /// subsequent calls return `None`.
+    pub fn try_take(&mut self) -> Option<StartResult<H>> {
+        self.state
+            .result
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// Blocks until the start operation has finished, for at most the given `timeout`.
+    ///
+    /// This returns the result of the start operation, or `None` if it didn't finish in time. In
+    /// that case, this method (or any other) can be called again to keep waiting.
+    ///
+    /// Once this returns the result, this handle cannot be used again: all subsequent calls
+    /// return `None`.
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Option<StartResult<H>> {
+        let result = self
+            .state
+            .result
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let (mut result, _) = self
+            .state
+            .finished
+            .wait_timeout_while(result, timeout, |r| r.is_none())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        result.take()
+    }
+
+    /// Blocks until the start operation has finished, and returns its result.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the result was already retrieved through [`try_take`](Self::try_take) or
+    /// [`wait_timeout`](Self::wait_timeout).
+    pub fn wait(self) -> StartResult<H> {
+        let result = self
+            .state
+            .result
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let mut result = self
+            .state
+            .finished
+            .wait_while(result, |r| r.is_none())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        result
+            .take()
+            .expect("The result of this start operation was already retrieved")
+    }
+}
+
+impl<H: HostHandlers> StoppedPluginAudioProcessor<H> {
+    /// Indicates to the plugin that continuous processing is about to start, using the given
+    /// `executor` to run the plugin's (blocking) `start_processing` function.
+    ///
+    /// Some plugins perform expensive operations when starting to process (e.g. allocating large
+    /// buffers). This method allows hosts to offload them, e.g. to a worker thread, while keeping
+    /// the ability to e.g. display a progress indicator or to stop waiting after a while.
+    ///
+    /// The given `executor` is called right away with a [`ProcessingStartTask`], which it must
+    /// [run](ProcessingStartTask::run) (or send somewhere to be run). The returned
+    /// [`PendingProcessingStart`] handle can then be used to retrieve the resulting
+    /// [`StartedPluginAudioProcessor`].
+    ///
+    /// Note that, as per the CLAP specification, the plugin's `start_processing` function must
+    /// always run to completion before it can [`process`](StartedPluginAudioProcessor::process)
+    /// audio. This cannot be cancelled once the task is running.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_host::prelude::*;
+    /// use clack_host::process::{
+    ///     ProcessingStartTask, StartedPluginAudioProcessor, StoppedPluginAudioProcessor,
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// fn start_in_background<H: HostHandlers>(
+    ///     processor: StoppedPluginAudioProcessor<H>,
+    /// ) -> Option<StartedPluginAudioProcessor<H>>
+    /// where
+    ///     ProcessingStartTask<H>: Send + 'static,
+    /// {
+    ///     let mut pending = processor.start_processing_on(|task| {
+    ///         std::thread::spawn(move || task.run());
+    ///     });
+    ///
+    ///     loop {
+    ///         match pending.wait_timeout(Duration::from_millis(100)) {
+    ///             Some(Ok(started)) => return Some(started),
+    ///             Some(Err(_)) => return None,
+    ///             None => println!("The plugin is still starting..."),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn start_processing_on<E>(self, executor: E) -> PendingProcessingStart<H>
+    where
+        E: FnOnce(ProcessingStartTask<H>),
+    {
+        let state = Arc::new(PendingState {
+            result: Mutex::new(None),
+            finished: Condvar::new(),
+        });
+
+        executor(ProcessingStartTask {
+            processor: Some(self),
+            state: state.clone(),
+        });
+
+        PendingProcessingStart { state }
+    }
+}
+
+impl<H: HostHandlers> Debug for ProcessingStartTask<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessingStartTask")
+            .field("processor", &self.processor)
+            .finish()
+    }
+}
+
+impl<H: HostHandlers> Debug for PendingProcessingStart<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingProcessingStart")
+            .field("is_finished", &self.is_finished())
+            .finish()
+    }
+}
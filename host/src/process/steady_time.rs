@@ -0,0 +1,137 @@
+/// A steady sample time counter, to be passed to a plugin's
+/// [`process`](super::StartedPluginAudioProcessor::process) calls.
+///
+/// CLAP requires the `steady_time` given to a plugin to increase by at least the number of
+/// processed frames between two calls to `process`, and to never decrease unless the plugin is
+/// [reset](super::StartedPluginAudioProcessor::reset). This type keeps track of it for a single
+/// plugin instance: the host only has to [`advance`](Self::advance) it after each `process` call,
+/// and to [`reset`](Self::reset) it whenever it resets the plugin.
+///
+/// # Wraparound
+///
+/// CLAP transmits `steady_time` as a signed 64-bit integer, where negative values mean the time
+/// is unavailable. This counter therefore wraps around to `0` once it goes past [`i64::MAX`]
+/// instead of [`u64::MAX`], and never panics on overflow. This is the only case (besides
+/// [`reset`](Self::reset)) where the counter goes backwards. At a sample rate of 192kHz, this
+/// happens after more than a million years of continuous processing.
+///
+/// # Example
+///
+/// ```
+/// use clack_host::process::SteadyTimeCounter;
+///
+/// let mut steady_time = SteadyTimeCounter::new();
+/// assert_eq!(steady_time.get(), Some(0));
+///
+/// // Pass steady_time.get() to process(), then:
+/// steady_time.advance(256);
+/// assert_eq!(steady_time.get(), Some(256));
+///
+/// // When resetting the plugin (e.g. when the playhead jumps), the counter can go back to 0.
+/// steady_time.reset();
+/// assert_eq!(steady_time.get(), Some(0));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SteadyTimeCounter {
+    current: u64,
+}
+
+impl SteadyTimeCounter {
+    /// The largest value this counter can hold before wrapping around to `0`.
+    pub const MAX: u64 = i64::MAX as u64;
+
+    /// Creates a new counter, starting at `0`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { current: 0 }
+    }
+
+    /// Creates a new counter, starting at the given sample time.
+    ///
+    /// If `start` is greater than [`MAX`](Self::MAX), it is wrapped around the same way
+    /// [`advance`](Self::advance) does.
+    #[inline]
+    pub const fn starting_at(start: u64) -> Self {
+        Self {
+            current: start & Self::MAX,
+        }
+    }
+
+    /// Returns the current sample time, to be passed as the `steady_time` argument of the next
+    /// [`process`](super::StartedPluginAudioProcessor::process) call.
+    ///
+    /// This always returns `Some`, but matches the type expected by `process`.
+    #[inline]
+    pub const fn get(&self) -> Option<u64> {
+        Some(self.current)
+    }
+
+    /// Advances the counter by the given number of frames.
+    ///
+    /// This must be called after each [`process`](super::StartedPluginAudioProcessor::process)
+    /// call, with the number of frames that were processed.
+    ///
+    /// This returns `true` if the counter wrapped around to `0` (see the
+    /// [type documentation](Self#wraparound)), or `false` otherwise.
+    #[inline]
+    pub fn advance(&mut self, frames_count: u32) -> bool {
+        let next = self.current + frames_count as u64;
+        self.current = next & Self::MAX;
+
+        next > Self::MAX
+    }
+
+    /// Resets the counter back to `0`.
+    ///
+    /// This is a discontinuity, which plugins only allow after they have been
+    /// [reset](super::StartedPluginAudioProcessor::reset) themselves. This should therefore always
+    /// be called alongside the plugin's `reset` method.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.current = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn advances_by_frame_count() {
+        let mut counter = SteadyTimeCounter::new();
+
+        assert!(!counter.advance(64));
+        assert!(!counter.advance(32));
+        assert_eq!(counter.get(), Some(96));
+
+        counter.reset();
+        assert_eq!(counter.get(), Some(0));
+    }
+
+    #[test]
+    fn wraps_around_past_i64_max() {
+        let mut counter = SteadyTimeCounter::starting_at(SteadyTimeCounter::MAX - 10);
+
+        assert!(!counter.advance(10));
+        assert_eq!(counter.get(), Some(i64::MAX as u64));
+
+        assert!(counter.advance(1));
+        assert_eq!(counter.get(), Some(0));
+
+        let mut counter = SteadyTimeCounter::starting_at(SteadyTimeCounter::MAX - 1);
+        assert!(counter.advance(u32::MAX));
+        assert_eq!(counter.get(), Some(u32::MAX as u64 - 2));
+    }
+
+    #[test]
+    fn start_is_wrapped_around() {
+        assert_eq!(
+            SteadyTimeCounter::starting_at(u64::MAX).get(),
+            Some(i64::MAX as u64)
+        );
+        assert_eq!(
+            SteadyTimeCounter::starting_at(SteadyTimeCounter::MAX + 5).get(),
+            Some(4)
+        );
+    }
+}
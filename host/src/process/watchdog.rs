@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::Thread;
+use std::time::{Duration, Instant};
+
+/// The state shared between the audio thread and the watchdog's monitor thread.
+struct WatchdogState {
+    epoch: Instant,
+    /// The time the current process call started at, in nanoseconds since `epoch`, plus one.
+    /// This is 0 if no process call is currently running.
+    call_started_at: AtomicU64,
+    is_stopped: AtomicBool,
+}
+
+impl WatchdogState {
+    #[inline]
+    fn now(&self) -> u64 {
+        // Plus one, so that 0 is never a valid timestamp.
+        self.epoch.elapsed().as_nanos() as u64 + 1
+    }
+}
+
+/// A watchdog monitoring the duration of `process` calls from a separate thread.
+///
+/// The monitor thread is stopped when this is dropped. This never blocks: the monitor thread
+/// is only signaled, and exits on its own.
+pub(crate) struct ProcessWatchdog {
+    state: Arc<WatchdogState>,
+    monitor_thread: Thread,
+}
+
+impl ProcessWatchdog {
+    /// Spawns a new monitor thread, which calls `on_timeout` whenever a process call exceeds the
+    /// given `timeout`.
+    pub(crate) fn spawn(
+        timeout: Duration,
+        on_timeout: impl FnMut(Duration) + Send + 'static,
+    ) -> std::io::Result<Self> {
+        let state = Arc::new(WatchdogState {
+            epoch: Instant::now(),
+            call_started_at: AtomicU64::new(0),
+            is_stopped: AtomicBool::new(false),
+        });
+
+        let monitor_state = Arc::clone(&state);
+        let monitor_thread = std::thread::Builder::new()
+            .name("clack-process-watchdog".into())
+            .spawn(move || monitor(&monitor_state, timeout, on_timeout))?
+            .thread()
+            .clone();
+
+        Ok(Self {
+            state,
+            monitor_thread,
+        })
+    }
+
+    /// Records the start of a process call.
+    ///
+    /// This is realtime-safe.
+    #[inline]
+    pub(crate) fn start_call(&self) {
+        let now = self.state.now();
+        self.state.call_started_at.store(now, Ordering::Release);
+    }
+
+    /// Records the end of the current process call.
+    ///
+    /// This is realtime-safe.
+    #[inline]
+    pub(crate) fn end_call(&self) {
+        self.state.call_started_at.store(0, Ordering::Release);
+    }
+}
+
+impl Drop for ProcessWatchdog {
+    fn drop(&mut self) {
+        self.state.is_stopped.store(true, Ordering::Release);
+        self.monitor_thread.unpark();
+    }
+}
+
+fn monitor(state: &WatchdogState, timeout: Duration, mut on_timeout: impl FnMut(Duration)) {
+    let poll_interval = (timeout / 4).max(Duration::from_millis(1));
+    let timeout_nanos = timeout.as_nanos() as u64;
+    let mut last_reported_call = 0;
+
+    while !state.is_stopped.load(Ordering::Acquire) {
+        std::thread::park_timeout(poll_interval);
+
+        let call_started_at = state.call_started_at.load(Ordering::Acquire);
+
+        // Only report each stuck call once.
+        if call_started_at == 0 || call_started_at == last_reported_call {
+            continue;
+        }
+
+        let elapsed = state.now().saturating_sub(call_started_at);
+        if elapsed > timeout_nanos {
+            last_reported_call = call_started_at;
+            on_timeout(Duration::from_nanos(elapsed));
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! A ready-made, minimal host implementation, for simple use cases such as scripts, tests, or
+//! headless rendering.
+//!
+//! This module is only available when the `simple` feature is enabled.
+//!
+//! The [`BasicHost`] type is a [`HostHandlers`] implementation that does not implement any host
+//! extension. The plugin's restart and process requests are recorded as flags in the
+//! [`BasicHostShared`] handler, which the host can then poll and handle at its own pace.
+//!
+//! Main-thread callback requests are already tracked by the [`PluginInstance`] itself, and can
+//! be serviced using [`PluginInstance::call_on_main_thread`].
+//!
+//! # Example
+//!
+//! ```
+//! use clack_host::prelude::*;
+//! use clack_host::simple::{BasicHost, BasicHostShared};
+//! use std::ffi::CStr;
+//!
+//! # fn x(bundle: &PluginBundle) -> Result<(), PluginInstanceError> {
+//! let bundle: &PluginBundle = /* ... */
+//! # unreachable!();
+//! let host_info = HostInfo::new("Legit Studio", "Legit Ltd.", "https://example.com", "4.3.2")
+//!     .unwrap();
+//! let plugin_id = CStr::from_bytes_with_nul(b"com.u-he.diva\0").unwrap();
+//!
+//! let mut instance = PluginInstance::<BasicHost>::new(
+//!     |_| BasicHostShared::new(),
+//!     |_| (),
+//!     bundle,
+//!     plugin_id,
+//!     &host_info,
+//! )?;
+//!
+//! // Later, e.g. in the host's main loop:
+//! if instance.access_shared_handler(|s| s.take_restart_request()) {
+//!     // Deactivate and reactivate the plugin...
+//! }
+//!
+//! // Only calls the plugin's callback if it requested it.
+//! instance.call_on_main_thread();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A minimal [`HostHandlers`] implementation, which records all requests made by the plugin.
+///
+/// See the [module documentation](self) for more information.
+pub struct BasicHost;
+
+impl HostHandlers for BasicHost {
+    type Shared<'a> = BasicHostShared;
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = ();
+}
+
+/// The [`SharedHandler`] of the [`BasicHost`].
+///
+/// This holds a flag for the plugin's restart and process requests, which is set when the plugin
+/// makes the matching request. The flags can be checked using e.g.
+/// [`is_restart_requested`](Self::is_restart_requested), or checked and cleared at the same time
+/// using e.g. [`take_restart_request`](Self::take_restart_request).
+///
+/// All flags are atomic, and can therefore be polled from any thread.
+///
+/// Callback requests aren't recorded here, as the [`PluginInstance`] already tracks them: see
+/// [`PluginInstance::needs_main_thread_callback`] and [`PluginInstance::call_on_main_thread`].
+#[derive(Default)]
+pub struct BasicHostShared {
+    restart_requested: AtomicBool,
+    process_requested: AtomicBool,
+}
+
+impl BasicHostShared {
+    /// Creates a new shared handler, with no pending requests.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the plugin requested to be restarted.
+    #[inline]
+    pub fn is_restart_requested(&self) -> bool {
+        self.restart_requested.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if the plugin requested to be processed.
+    #[inline]
+    pub fn is_process_requested(&self) -> bool {
+        self.process_requested.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if the plugin requested to be restarted, and clears the request.
+    #[inline]
+    pub fn take_restart_request(&self) -> bool {
+        self.restart_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// Returns `true` if the plugin requested to be processed, and clears the request.
+    #[inline]
+    pub fn take_process_request(&self) -> bool {
+        self.process_requested.swap(false, Ordering::SeqCst)
+    }
+}
+
+impl SharedHandler<'_> for BasicHostShared {
+    fn request_restart(&self) {
+        self.restart_requested.store(true, Ordering::SeqCst);
+    }
+
+    fn request_process(&self) {
+        self.process_requested.store(true, Ordering::SeqCst);
+    }
+
+    fn request_callback(&self) {
+        // Already tracked by the PluginInstance, see PluginInstance::call_on_main_thread.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_recorded_until_taken() {
+        let shared = BasicHostShared::new();
+        assert!(!shared.is_restart_requested());
+        assert!(!shared.take_restart_request());
+
+        shared.request_restart();
+        assert!(shared.is_restart_requested());
+        assert!(!shared.is_process_requested());
+
+        assert!(shared.take_restart_request());
+        assert!(!shared.is_restart_requested());
+        assert!(!shared.take_restart_request());
+
+        shared.request_process();
+        assert!(shared.take_process_request());
+        assert!(!shared.take_process_request());
+    }
+}
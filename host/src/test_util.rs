@@ -0,0 +1,319 @@
+//! Utilities to test plugin implementations in-process, without implementing a full host.
+//!
+//! This module is only available when the `test-util` feature is enabled, and is meant to be used
+//! from a plugin's tests, e.g. as a `dev-dependency`.
+//!
+//! The [`MockHost`] type is a minimal [`HostHandlers`] implementation, which records the requests
+//! the plugin makes to the host. The [`MockPlugin`] type wraps a plugin instance using this host,
+//! and provides helpers to activate it and drive its `process` method using simple buffers.
+//!
+//! # Example
+//!
+//! ```
+//! use clack_host::prelude::*;
+//! use clack_host::test_util::MockPlugin;
+//! use std::ffi::CStr;
+//!
+//! # fn x(bundle: &PluginBundle) -> Result<(), PluginInstanceError> {
+//! let bundle: &PluginBundle = /* ... */
+//! # unreachable!();
+//! let plugin_id = CStr::from_bytes_with_nul(b"org.rust-audio.clack.gain\0").unwrap();
+//!
+//! let mut plugin = MockPlugin::new(bundle, plugin_id)?;
+//! plugin.activate(PluginAudioConfiguration {
+//!     sample_rate: 44_100.0,
+//!     min_frames_count: 32,
+//!     max_frames_count: 32,
+//! })?;
+//!
+//! let mut input = [[1.0f32; 32], [1.0f32; 32]];
+//! let mut output = [[0.0f32; 32], [0.0f32; 32]];
+//!
+//! plugin.process(&mut input, &mut output, &InputEvents::empty())?;
+//!
+//! assert_eq!(plugin.shared().restart_requests(), 0);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::prelude::*;
+use crate::process::{StartedPluginAudioProcessor, SteadyTimeCounter};
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A minimal [`HostHandlers`] implementation, which records all requests made by the plugin.
+///
+/// See the [module documentation](self) for more information.
+pub struct MockHost;
+
+impl HostHandlers for MockHost {
+    type Shared<'a> = MockHostShared;
+    type MainThread<'a> = MockHostMainThread;
+    type AudioProcessor<'a> = ();
+}
+
+/// The [`SharedHandler`] of the [`MockHost`].
+///
+/// This counts how many times the plugin called each of the host's request callbacks.
+#[derive(Default)]
+pub struct MockHostShared {
+    restart_requests: AtomicU32,
+    process_requests: AtomicU32,
+    callback_requests: AtomicU32,
+}
+
+impl MockHostShared {
+    /// Returns how many times the plugin requested to be restarted.
+    #[inline]
+    pub fn restart_requests(&self) -> u32 {
+        self.restart_requests.load(Ordering::SeqCst)
+    }
+
+    /// Returns how many times the plugin requested to be processed.
+    #[inline]
+    pub fn process_requests(&self) -> u32 {
+        self.process_requests.load(Ordering::SeqCst)
+    }
+
+    /// Returns how many times the plugin requested a main-thread callback.
+    #[inline]
+    pub fn callback_requests(&self) -> u32 {
+        self.callback_requests.load(Ordering::SeqCst)
+    }
+
+    /// Resets all request counters to zero.
+    pub fn reset_requests(&self) {
+        self.restart_requests.store(0, Ordering::SeqCst);
+        self.process_requests.store(0, Ordering::SeqCst);
+        self.callback_requests.store(0, Ordering::SeqCst);
+    }
+}
+
+impl SharedHandler<'_> for MockHostShared {
+    fn request_restart(&self) {
+        self.restart_requests.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn request_process(&self) {
+        self.process_requests.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn request_callback(&self) {
+        self.callback_requests.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// The [`MainThreadHandler`] of the [`MockHost`].
+#[derive(Default)]
+pub struct MockHostMainThread {
+    is_initialized: bool,
+}
+
+impl MockHostMainThread {
+    /// Returns `true` if the plugin instance finished its initialization.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl<'a> MainThreadHandler<'a> for MockHostMainThread {
+    fn initialized(&mut self, _instance: InitializedPluginHandle<'a>) {
+        self.is_initialized = true;
+    }
+}
+
+/// A plugin instance running on a [`MockHost`].
+///
+/// See the [module documentation](self) for more information.
+///
+/// If the plugin is still active when this is dropped, it is deactivated first.
+pub struct MockPlugin {
+    instance: PluginInstance<MockHost>,
+    processor: Option<StartedPluginAudioProcessor<MockHost>>,
+    input_ports: AudioPorts,
+    output_ports: AudioPorts,
+    output_events: EventBuffer,
+    steady_time: SteadyTimeCounter,
+}
+
+impl MockPlugin {
+    /// Instantiates the plugin matching the given `plugin_id` from the given bundle.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`PluginInstance::new`].
+    pub fn new(bundle: &PluginBundle, plugin_id: &CStr) -> Result<Self, PluginInstanceError> {
+        let host_info = HostInfo::new(
+            "Clack Mock Host",
+            "Clack",
+            "https://github.com/prokopyl/clack",
+            "0.1.0",
+        )
+        .expect("Mock host info should be valid");
+
+        let instance = PluginInstance::<MockHost>::new(
+            |_| MockHostShared::default(),
+            |_| MockHostMainThread::default(),
+            bundle,
+            plugin_id,
+            &host_info,
+        )?;
+
+        Ok(Self {
+            instance,
+            processor: None,
+            input_ports: AudioPorts::with_capacity(0, 0),
+            output_ports: AudioPorts::with_capacity(0, 0),
+            output_events: EventBuffer::new(),
+            steady_time: SteadyTimeCounter::new(),
+        })
+    }
+
+    /// Returns the underlying plugin instance.
+    #[inline]
+    pub fn instance(&self) -> &PluginInstance<MockHost> {
+        &self.instance
+    }
+
+    /// Returns the underlying plugin instance, mutably.
+    ///
+    /// This can be used e.g. to query and use the plugin's extensions.
+    #[inline]
+    pub fn instance_mut(&mut self) -> &mut PluginInstance<MockHost> {
+        &mut self.instance
+    }
+
+    /// Returns the [`MockHostShared`] handler, to inspect the requests made by the plugin.
+    #[inline]
+    pub fn shared(&self) -> &MockHostShared {
+        self.instance.access_shared_handler(|s| s)
+    }
+
+    /// Returns the [`MockHostMainThread`] handler.
+    #[inline]
+    pub fn main_thread(&self) -> &MockHostMainThread {
+        self.instance.access_handler(|h| h)
+    }
+
+    /// Returns `true` if the plugin is currently active.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.processor.is_some()
+    }
+
+    /// Activates the plugin with the given audio configuration, and starts processing.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`PluginInstance::activate`], as well as
+    /// [`PluginInstanceError::StartProcessingFailed`] if the plugin failed to start processing,
+    /// in which case it is deactivated again.
+    pub fn activate(
+        &mut self,
+        configuration: PluginAudioConfiguration,
+    ) -> Result<(), PluginInstanceError> {
+        let processor = self.instance.activate(|_, _| (), configuration)?;
+
+        match processor.start_processing() {
+            Ok(processor) => {
+                self.processor = Some(processor);
+                Ok(())
+            }
+            Err(e) => {
+                self.instance.deactivate(e.into_stopped_processor());
+                Err(PluginInstanceError::StartProcessingFailed)
+            }
+        }
+    }
+
+    /// Stops processing and deactivates the plugin.
+    ///
+    /// This does nothing if the plugin isn't active.
+    pub fn deactivate(&mut self) {
+        if let Some(processor) = self.processor.take() {
+            self.instance.deactivate(processor.stop_processing());
+        }
+    }
+
+    /// Processes a single block of audio, using a single 32-bit input port and a single 32-bit
+    /// output port.
+    ///
+    /// Each item of `inputs` and `outputs` is a channel of the matching port. If either of them
+    /// is empty, no port is passed to the plugin for that side. The number of processed frames is
+    /// the length of the shortest channel.
+    ///
+    /// The events produced by the plugin during this call can then be retrieved using
+    /// [`output_events`](Self::output_events).
+    ///
+    /// For more complex port configurations, the plugin's audio processor can be driven
+    /// directly using [`PluginInstance::activate`] on [`instance_mut`](Self::instance_mut)
+    /// instead.
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PluginInstanceError::DeactivatedPlugin`] if the plugin hasn't been
+    /// [activated](Self::activate), as well as the same errors as
+    /// [`StartedPluginAudioProcessor::process`].
+    pub fn process<I: AsMut<[f32]>, O: AsMut<[f32]>>(
+        &mut self,
+        inputs: &mut [I],
+        outputs: &mut [O],
+        input_events: &InputEvents,
+    ) -> Result<ProcessStatus, PluginInstanceError> {
+        let processor = self
+            .processor
+            .as_mut()
+            .ok_or(PluginInstanceError::DeactivatedPlugin)?;
+
+        let input_buffers = self
+            .input_ports
+            .with_input_buffers((!inputs.is_empty()).then(|| {
+                AudioPortBuffer {
+                    channels: AudioPortBufferType::f32_input_only(
+                        inputs
+                            .iter_mut()
+                            .map(|channel| InputChannel::variable(channel.as_mut())),
+                    ),
+                    latency: 0,
+                }
+            }));
+
+        let mut output_buffers = self
+            .output_ports
+            .with_output_buffers((!outputs.is_empty()).then(|| AudioPortBuffer {
+                channels: AudioPortBufferType::f32_output_only(
+                    outputs.iter_mut().map(|channel| channel.as_mut()),
+                ),
+                latency: 0,
+            }));
+
+        self.output_events.clear();
+
+        let status = processor.process(
+            &input_buffers,
+            &mut output_buffers,
+            input_events,
+            &mut self.output_events.as_output(),
+            self.steady_time.get(),
+            None,
+        )?;
+
+        let frames_count = input_buffers.min_available_frames_with(&output_buffers);
+        self.steady_time.advance(frames_count);
+
+        Ok(status)
+    }
+
+    /// Returns the events the plugin produced during the last call to [`process`](Self::process).
+    #[inline]
+    pub fn output_events(&self) -> &EventBuffer {
+        &self.output_events
+    }
+}
+
+impl Drop for MockPlugin {
+    fn drop(&mut self) {
+        self.deactivate();
+    }
+}
@@ -0,0 +1,364 @@
+mod common;
+
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.activation";
+
+pub struct ActivationPluginAudioProcessor;
+pub struct ActivationPlugin;
+pub struct ActivationPluginMainThread;
+
+impl PluginMainThread<'_, ()> for ActivationPluginMainThread {}
+
+impl Plugin for ActivationPlugin {
+    type AudioProcessor<'a> = ActivationPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = ActivationPluginMainThread;
+}
+
+impl DefaultPluginFactory for ActivationPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Activation Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ActivationPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), ActivationPluginMainThread>
+    for ActivationPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut ActivationPluginMainThread,
+        _shared: &'a (),
+        audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        if audio_config.sample_rate != 48_000.0 {
+            return Err(PluginError::Message("Unsupported sample rate"));
+        }
+
+        Ok(ActivationPluginAudioProcessor)
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+pub static ACTIVATION_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<ActivationPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostAudioProcessor;
+impl AudioProcessorHandler<'_> for MyHostAudioProcessor {}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = MyHostAudioProcessor;
+}
+
+#[test]
+pub fn can_reactivate_after_activation_failure() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    let unsupported_config = PluginAudioConfiguration {
+        sample_rate: 44_100.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let error = instance
+        .activate(|_, _| MyHostAudioProcessor, unsupported_config)
+        .err()
+        .unwrap();
+
+    assert_eq!(error, PluginInstanceError::ActivationFailed);
+    assert!(!instance.is_active());
+
+    let supported_config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        ..unsupported_config
+    };
+
+    let processor = instance
+        .activate(|_, _| MyHostAudioProcessor, supported_config)
+        .unwrap();
+
+    assert!(instance.is_active());
+
+    instance.deactivate(processor);
+    assert!(!instance.is_active());
+}
+
+#[test]
+pub fn can_probe_supported_sample_rates() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 44_100.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let supported = instance
+        .probe_sample_rates(&[44_100.0, 48_000.0, 96_000.0], config, |_, _| {
+            MyHostAudioProcessor
+        })
+        .unwrap();
+
+    assert_eq!(supported, [48_000.0]);
+    assert!(!instance.is_active());
+
+    let processor = instance
+        .activate(
+            |_, _| MyHostAudioProcessor,
+            PluginAudioConfiguration {
+                sample_rate: 48_000.0,
+                ..config
+            },
+        )
+        .unwrap();
+
+    let error = instance
+        .probe_sample_rates(&[48_000.0], config, |_, _| MyHostAudioProcessor)
+        .unwrap_err();
+    assert_eq!(error, PluginInstanceError::AlreadyActivatedPlugin);
+
+    instance.deactivate(processor);
+}
+
+#[test]
+pub fn activation_with_ports_falls_back_to_stereo() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let activation = instance
+        .activate_with_config_and_ports(|_, _| MyHostAudioProcessor, config)
+        .unwrap();
+
+    assert_eq!(activation.layout, AudioPortsLayout::stereo());
+    assert_eq!(activation.input_ports.port_capacity(), 1);
+    assert_eq!(activation.output_ports.port_capacity(), 1);
+
+    let error = instance
+        .activate_with_config_and_ports(|_, _| MyHostAudioProcessor, config)
+        .err()
+        .unwrap();
+    assert_eq!(error, PluginInstanceError::AlreadyActivatedPlugin);
+
+    instance.deactivate(activation.audio_processor);
+}
+
+#[test]
+pub fn can_inspect_instance_lifecycle_state() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    assert!(instance.is_initialized());
+    assert!(!instance.is_active());
+    assert!(!instance.is_processing());
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap();
+
+    assert!(instance.is_active());
+    assert!(!instance.is_processing());
+
+    let processor = processor.start_processing().unwrap();
+    assert!(instance.is_processing());
+
+    let processor = processor.stop_processing();
+    assert!(instance.is_active());
+    assert!(!instance.is_processing());
+
+    instance.deactivate(processor);
+    assert!(instance.is_initialized());
+    assert!(!instance.is_active());
+}
+
+#[test]
+pub fn debug_output_shows_plugin_id_and_state() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    assert_eq!(
+        format!("{instance:?}"),
+        format!(
+            r#"PluginInstance {{ plugin_id: Some("{PLUGIN_ID}"), is_active: false, is_processing: false }}"#
+        )
+    );
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let processor: clack_host::process::PluginAudioProcessor<_> = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap()
+        .into();
+
+    assert_eq!(
+        format!("{processor:?}"),
+        format!(r#"Stopped(StoppedPluginAudioProcessor {{ plugin_id: Some("{PLUGIN_ID}") }})"#)
+    );
+
+    let processor = processor.into_started().unwrap();
+    assert_eq!(
+        format!("{processor:?}"),
+        format!(
+            r#"StartedPluginAudioProcessor {{ plugin_id: Some("{PLUGIN_ID}"), has_watchdog: false }}"#
+        )
+    );
+
+    instance.deactivate(processor.stop_processing());
+}
+
+#[test]
+pub fn can_restart_processing_instance() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap()
+        .start_processing()
+        .unwrap();
+    assert!(instance.is_processing());
+
+    // Restarting a started processor stops it first.
+    let processor = instance
+        .restart(processor.into(), |_, _| MyHostAudioProcessor, config)
+        .unwrap();
+
+    assert!(instance.is_active());
+    assert!(!instance.is_processing());
+
+    // If re-activation fails, the instance is left deactivated.
+    let unsupported_config = PluginAudioConfiguration {
+        sample_rate: 44_100.0,
+        ..config
+    };
+
+    let error = instance
+        .restart(
+            processor.into(),
+            |_, _| MyHostAudioProcessor,
+            unsupported_config,
+        )
+        .err()
+        .unwrap();
+
+    assert_eq!(error, PluginInstanceError::ActivationFailed);
+    assert!(!instance.is_active());
+
+    let processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap();
+    instance.deactivate(processor);
+}
+
+#[test]
+pub fn can_start_processing_on_another_thread() {
+    let bundle = common::load_bundle(&ACTIVATION_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap();
+
+    let mut worker = None;
+    let mut pending = processor.start_processing_on(|task| {
+        worker = Some(std::thread::spawn(move || task.run()));
+    });
+
+    worker.unwrap().join().unwrap();
+    assert!(pending.is_finished());
+
+    let processor = pending.try_take().unwrap().unwrap();
+    assert!(instance.is_processing());
+    assert!(pending.try_take().is_none());
+
+    // If the task is never run, the stopped processor can be recovered.
+    let mut pending = processor.stop_processing().start_processing_on(drop);
+    let error = pending
+        .wait_timeout(std::time::Duration::from_secs(1))
+        .unwrap()
+        .err()
+        .unwrap();
+
+    assert!(!instance.is_processing());
+    instance.deactivate(error.into_stopped_processor());
+}
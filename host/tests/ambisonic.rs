@@ -0,0 +1,158 @@
+mod common;
+
+use clack_extensions::ambisonic::{
+    AmbisonicConfig, AmbisonicNormalization, AmbisonicOrdering, HostAmbisonic, HostAmbisonicImpl,
+    PluginAmbisonic, PluginAmbisonicImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const ACN_SN3D: AmbisonicConfig = AmbisonicConfig {
+    ordering: AmbisonicOrdering::Acn,
+    normalization: AmbisonicNormalization::Sn3d,
+};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.ambisonic";
+
+pub struct AmbisonicPlugin;
+pub struct AmbisonicPluginMainThread;
+
+impl PluginMainThread<'_, ()> for AmbisonicPluginMainThread {}
+
+impl Plugin for AmbisonicPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = AmbisonicPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginAmbisonic>();
+    }
+}
+
+impl DefaultPluginFactory for AmbisonicPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        use clack_plugin::plugin::features::*;
+
+        PluginDescriptor::new(PLUGIN_ID, "Ambisonic Test Plugin").with_features([AMBISONIC])
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        mut host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        let ambisonic: HostAmbisonic = host.get_extension().unwrap();
+        ambisonic.changed(&mut host);
+
+        Ok(AmbisonicPluginMainThread)
+    }
+}
+
+impl PluginAmbisonicImpl for AmbisonicPluginMainThread {
+    fn is_config_supported(&mut self, config: AmbisonicConfig) -> bool {
+        config == ACN_SN3D
+    }
+
+    fn get_config(&mut self, is_input: bool, port_index: u32) -> Option<AmbisonicConfig> {
+        if !is_input && port_index == 0 {
+            Some(ACN_SN3D)
+        } else {
+            None
+        }
+    }
+}
+
+pub static AMBISONIC_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<AmbisonicPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostMainThread {
+    changed_count: u32,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostAmbisonicImpl for MyHostMainThread {
+    fn changed(&mut self) {
+        self.changed_count += 1;
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostAmbisonic>();
+    }
+}
+
+#[test]
+pub fn can_negotiate_ambisonic_config() {
+    let bundle = common::load_bundle(&AMBISONIC_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread { changed_count: 0 },
+    );
+
+    assert_eq!(instance.access_handler(|h| h.changed_count), 1);
+
+    let ambisonic: PluginAmbisonic = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Ambisonic extension");
+
+    let mut plugin = instance.plugin_handle();
+
+    assert_eq!(ambisonic.get_config(&mut plugin, 0, false), Some(ACN_SN3D));
+    assert_eq!(ambisonic.get_config(&mut plugin, 0, true), None);
+
+    assert!(ambisonic.is_config_supported(&mut plugin, &ACN_SN3D));
+    assert!(!ambisonic.is_config_supported(
+        &mut plugin,
+        &AmbisonicConfig {
+            ordering: AmbisonicOrdering::FuMa,
+            normalization: AmbisonicNormalization::MaxN,
+        }
+    ));
+}
+
+#[test]
+pub fn rejects_unknown_raw_values() {
+    for raw in 0..2 {
+        assert_eq!(AmbisonicOrdering::from_raw(raw).unwrap().to_raw(), raw);
+    }
+    assert_eq!(AmbisonicOrdering::from_raw(2), None);
+
+    for raw in 0..5 {
+        assert_eq!(AmbisonicNormalization::from_raw(raw).unwrap().to_raw(), raw);
+    }
+    assert_eq!(AmbisonicNormalization::from_raw(5), None);
+
+    let mut raw = ACN_SN3D.to_raw();
+    assert_eq!(AmbisonicConfig::from_raw(&raw), Some(ACN_SN3D));
+
+    raw.normalization = 42;
+    assert_eq!(AmbisonicConfig::from_raw(&raw), None);
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use clack_extensions::audio_ports_activation::{
+    PluginAudioPortsActivation, PluginAudioPortsActivationImpl,
+    PluginAudioProcessorAudioPortsActivationImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.audio-ports-activation";
+
+pub struct AudioPortsActivationPlugin;
+
+/// The plugin has a stereo main input and output, and a sidechain input at index 1.
+fn port_exists(is_input: bool, port_index: u32) -> bool {
+    port_index == 0 || (is_input && port_index == 1)
+}
+
+pub struct AudioPortsActivationPluginMainThread {
+    sidechain_active: bool,
+}
+
+impl<'a> PluginMainThread<'a, ()> for AudioPortsActivationPluginMainThread {}
+
+pub struct AudioPortsActivationPluginAudioProcessor {
+    sidechain_active: bool,
+}
+
+impl Plugin for AudioPortsActivationPlugin {
+    type AudioProcessor<'a> = AudioPortsActivationPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = AudioPortsActivationPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginAudioPortsActivation>();
+    }
+}
+
+impl DefaultPluginFactory for AudioPortsActivationPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Audio Ports Activation Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(AudioPortsActivationPluginMainThread {
+            sidechain_active: true,
+        })
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), AudioPortsActivationPluginMainThread>
+    for AudioPortsActivationPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        main_thread: &mut AudioPortsActivationPluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        Ok(Self {
+            sidechain_active: main_thread.sidechain_active,
+        })
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+impl PluginAudioPortsActivationImpl for AudioPortsActivationPluginMainThread {
+    fn can_activate_while_processing(&mut self) -> bool {
+        true
+    }
+
+    fn set_active(
+        &mut self,
+        is_input: bool,
+        port_index: u32,
+        is_active: bool,
+        sample_size: u32,
+    ) -> bool {
+        if !port_exists(is_input, port_index) || ![0, 32].contains(&sample_size) {
+            return false;
+        }
+
+        if is_input && port_index == 1 {
+            self.sidechain_active = is_active;
+        }
+
+        true
+    }
+}
+
+impl PluginAudioProcessorAudioPortsActivationImpl for AudioPortsActivationPluginAudioProcessor {
+    fn set_active(
+        &mut self,
+        is_input: bool,
+        port_index: u32,
+        is_active: bool,
+        _sample_size: u32,
+    ) -> bool {
+        // Only the sidechain can be toggled while processing.
+        if !is_input || port_index != 1 {
+            return false;
+        }
+
+        self.sidechain_active = is_active;
+        true
+    }
+}
+
+pub static AUDIO_PORTS_ACTIVATION_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<AudioPortsActivationPlugin>);
+
+#[test]
+pub fn can_activate_ports() {
+    let bundle = common::load_bundle(&AUDIO_PORTS_ACTIVATION_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let activation: PluginAudioPortsActivation = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Audio Ports Activation extension");
+
+    let mut plugin = instance.plugin_handle();
+
+    assert!(activation.can_activate_while_processing(&mut plugin));
+
+    // SAFETY: the plugin isn't activated yet.
+    unsafe {
+        assert!(activation.set_active(&mut plugin, 1, true, 32, false));
+        assert!(!activation.set_active(&mut plugin, 1, false, 32, false));
+        assert!(!activation.set_active(&mut plugin, 1, true, 64, true));
+    }
+
+    // SAFETY: the plugin isn't activated yet.
+    let failed = unsafe {
+        activation.set_many(
+            &mut plugin,
+            &[
+                (0, true, true),
+                (1, true, true),
+                (2, true, false),
+                (0, false, true),
+                (1, false, true),
+            ],
+            0,
+        )
+    };
+
+    assert_eq!(failed, [(2, true), (1, false)]);
+
+    let mut processor = instance
+        .activate(
+            |_, _| (),
+            PluginAudioConfiguration {
+                sample_rate: 44_100.0,
+                min_frames_count: 1,
+                max_frames_count: 256,
+            },
+        )
+        .unwrap()
+        .start_processing()
+        .unwrap();
+
+    let mut plugin = processor.plugin_handle();
+
+    assert!(activation.set_active_while_processing(&mut plugin, 1, true, 32, false));
+    assert!(!activation.set_active_while_processing(&mut plugin, 0, true, 32, false));
+
+    instance.deactivate(processor.stop_processing());
+}
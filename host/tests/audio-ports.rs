@@ -0,0 +1,124 @@
+mod common;
+
+use clack_extensions::audio_ports::{
+    AudioPortFlags, AudioPortInfo, AudioPortInfoBuffer, AudioPortInfoWriter, AudioPortType,
+    PluginAudioPorts, PluginAudioPortsImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.audio-ports";
+
+pub struct AudioPortsPlugin;
+pub struct AudioPortsPluginMainThread;
+
+impl PluginMainThread<'_, ()> for AudioPortsPluginMainThread {}
+
+impl Plugin for AudioPortsPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = AudioPortsPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginAudioPorts>();
+    }
+}
+
+impl DefaultPluginFactory for AudioPortsPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Audio Ports Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(AudioPortsPluginMainThread)
+    }
+}
+
+impl PluginAudioPortsImpl for AudioPortsPluginMainThread {
+    fn count(&mut self, is_input: bool) -> u32 {
+        if is_input {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn get(&mut self, index: u32, is_input: bool, writer: &mut AudioPortInfoWriter) {
+        // Only the second input port is the main one, and no output port is.
+        let (name, flags): (&[u8], _) = match (is_input, index) {
+            (true, 0) => (b"Sidechain", AudioPortFlags::empty()),
+            (true, 1) => (b"Main In", AudioPortFlags::IS_MAIN),
+            (false, 0) => (b"Aux Out", AudioPortFlags::empty()),
+            _ => return,
+        };
+
+        writer.set(
+            &AudioPortInfo::new(ClapId::new(index), name)
+                .with_channels(2)
+                .with_flags(flags)
+                .with_type(AudioPortType::STEREO),
+        );
+    }
+}
+
+pub static AUDIO_PORTS_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<AudioPortsPlugin>);
+
+#[test]
+pub fn can_find_main_ports() {
+    let bundle = common::load_bundle(&AUDIO_PORTS_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let audio_ports: PluginAudioPorts = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Audio Ports extension");
+
+    let mut buffer = AudioPortInfoBuffer::new();
+    let main_input = audio_ports
+        .main_input_port(&mut instance.plugin_handle(), &mut buffer)
+        .expect("Plugin should have a main input port");
+
+    assert_eq!(main_input.id, ClapId::new(1));
+    assert_eq!(main_input.name, b"Main In");
+    assert!(main_input.flags.contains(AudioPortFlags::IS_MAIN));
+
+    let mut buffer = AudioPortInfoBuffer::new();
+    assert!(audio_ports
+        .main_output_port(&mut instance.plugin_handle(), &mut buffer)
+        .is_none());
+}
+
+#[test]
+pub fn can_query_audio_ports_layout() {
+    let bundle = common::load_bundle(&AUDIO_PORTS_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let activation = instance
+        .activate_with_config_and_ports(|_, _| (), config)
+        .unwrap();
+
+    assert_eq!(
+        activation.layout,
+        AudioPortsLayout::new(vec![2, 2], vec![2])
+    );
+    assert_eq!(activation.input_ports.port_capacity(), 2);
+    assert_eq!(activation.output_ports.port_capacity(), 1);
+
+    instance.deactivate(activation.audio_processor);
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use clack_host::prelude::*;
+use clack_host::process::{BlockProcessor, ConstantMask};
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::Mutex;
+
+#[derive(Debug, Eq, PartialEq)]
+struct ProcessCall {
+    frames_count: u32,
+    steady_time: Option<u64>,
+    event_times: Vec<u32>,
+    input_constant_mask: u64,
+}
+
+static CALLS: Mutex<Vec<ProcessCall>> = Mutex::new(Vec::new());
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.block-processing";
+
+pub struct BlockProcessingPluginAudioProcessor;
+pub struct BlockProcessingPlugin;
+pub struct BlockProcessingPluginMainThread;
+
+impl PluginMainThread<'_, ()> for BlockProcessingPluginMainThread {}
+
+impl Plugin for BlockProcessingPlugin {
+    type AudioProcessor<'a> = BlockProcessingPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = BlockProcessingPluginMainThread;
+}
+
+impl DefaultPluginFactory for BlockProcessingPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Block Processing Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(BlockProcessingPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), BlockProcessingPluginMainThread>
+    for BlockProcessingPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut BlockProcessingPluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        Ok(BlockProcessingPluginAudioProcessor)
+    }
+
+    fn process(
+        &mut self,
+        process: Process,
+        mut audio: Audio,
+        events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        CALLS.lock().unwrap().push(ProcessCall {
+            frames_count: audio.frames_count(),
+            steady_time: process.steady_time,
+            event_times: events.input.iter().map(|e| e.header().time()).collect(),
+            input_constant_mask: audio.input_port(0).unwrap().constant_mask().to_bits(),
+        });
+
+        // Echo all input events back to the host.
+        for event in events.input {
+            events.output.try_push(event)?;
+        }
+
+        // Copy the input to the output, with an offset for checking the blocks' boundaries.
+        let input = audio.input_port(0).unwrap().channels()?.into_f32().unwrap();
+        let input = input.channel(0).unwrap().to_vec();
+
+        let mut output = audio.output_port(0).unwrap();
+        let mut output = output.channels()?.into_f32().unwrap();
+        let output = output.channel_mut(0).unwrap();
+
+        for (output, input) in output.iter_mut().zip(&input) {
+            *output = *input + 1.0;
+        }
+
+        // Only the first two bits are reported constant in every block.
+        let mask = if process.steady_time == Some(64) {
+            0b0111
+        } else {
+            0b1011
+        };
+
+        audio
+            .output_port(0)
+            .unwrap()
+            .set_constant_mask(ConstantMask::from_bits(mask));
+
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+pub static BLOCK_PROCESSING_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<BlockProcessingPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostAudioProcessor;
+impl AudioProcessorHandler<'_> for MyHostAudioProcessor {}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = MyHostAudioProcessor;
+}
+
+#[test]
+pub fn long_buffers_are_processed_in_blocks() {
+    let bundle = common::load_bundle(&BLOCK_PROCESSING_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 64,
+    };
+
+    let mut processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap()
+        .start_processing()
+        .unwrap();
+
+    let mut input: Vec<f32> = (0..150).map(|i| i as f32).collect();
+    let mut silence = vec![0.0f32; 150];
+    let mut output = vec![0.0f32; 150];
+
+    let mut input_events = EventBuffer::new();
+    input_events.push_note_on(0, Pckn::new(0u16, 0u16, 60u16, 0u32), 1.0);
+    input_events.push_note_on(63, Pckn::new(0u16, 0u16, 62u16, 1u32), 1.0);
+    input_events.push_note_off(64, Pckn::new(0u16, 0u16, 60u16, 0u32), 1.0);
+    input_events.push_note_off(140, Pckn::new(0u16, 0u16, 62u16, 1u32), 1.0);
+    let mut output_events = EventBuffer::new();
+
+    let mut input_ports = AudioPorts::with_capacity(2, 1);
+    let mut output_ports = AudioPorts::with_capacity(1, 1);
+    let mut blocks = BlockProcessor::new(config.max_frames_count);
+
+    let mut output_buffers = output_ports.with_output_buffers([AudioPortBuffer {
+        latency: 0,
+        channels: AudioPortBufferType::f32_output_only([output.as_mut_slice()]),
+    }]);
+
+    let status = blocks
+        .process(
+            &mut processor,
+            &input_ports.with_input_buffers([AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_input_only([
+                    InputChannel::variable(&mut input),
+                    InputChannel::constant(&mut silence),
+                ]),
+            }]),
+            &mut output_buffers,
+            &input_events,
+            &mut output_events,
+            None,
+        )
+        .unwrap();
+
+    // The constant masks of all blocks are merged back into the given output buffers.
+    assert_eq!(output_buffers.as_raw_buffers()[0].constant_mask, 0b0011);
+
+    assert_eq!(status, ProcessStatus::Continue);
+
+    // The silent input channel is constant over the whole buffer, but blocks don't inherit that.
+    assert_eq!(
+        *CALLS.lock().unwrap(),
+        [
+            ProcessCall {
+                frames_count: 64,
+                steady_time: Some(0),
+                event_times: vec![0, 63],
+                input_constant_mask: 0,
+            },
+            ProcessCall {
+                frames_count: 64,
+                steady_time: Some(64),
+                event_times: vec![0],
+                input_constant_mask: 0,
+            },
+            ProcessCall {
+                frames_count: 22,
+                steady_time: Some(128),
+                event_times: vec![12],
+                input_constant_mask: 0,
+            },
+        ]
+    );
+
+    // All frames were processed, at the right offsets.
+    let expected: Vec<f32> = (0..150).map(|i| i as f32 + 1.0).collect();
+    assert_eq!(output, expected);
+
+    // The output events are relative to the start of the whole buffer.
+    let output_times: Vec<u32> = output_events.iter().map(|e| e.header().time()).collect();
+    assert_eq!(output_times, [0, 63, 64, 140]);
+    assert_eq!(blocks.steady_time().get(), Some(150));
+
+    instance.deactivate(processor.stop_processing());
+}
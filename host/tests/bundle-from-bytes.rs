@@ -0,0 +1,28 @@
+use clack_host::bundle::PluginBundleError;
+use clack_host::prelude::*;
+use std::path::Path;
+
+#[test]
+pub fn loading_invalid_bytes_fails() {
+    let path = Path::new("/home/user/.clap/u-he/libdiva.so");
+
+    // SAFETY: this is not a valid library, loading it should fail before running any code.
+    let result = unsafe { PluginBundle::load_from_bytes(b"definitely not a CLAP bundle", path) };
+
+    assert!(matches!(
+        result,
+        Err(PluginBundleError::LibraryLoadingError(_))
+    ));
+}
+
+#[test]
+#[cfg(unix)]
+pub fn loading_from_bytes_requires_utf8_path() {
+    use std::os::unix::ffi::OsStrExt;
+    let path = Path::new(std::ffi::OsStr::from_bytes(b"/home/user/\xFF.clap"));
+
+    // SAFETY: the path is checked before the bytes are ever loaded.
+    let result = unsafe { PluginBundle::load_from_bytes(&[], path) };
+
+    assert!(matches!(result, Err(PluginBundleError::InvalidUtf8Path)));
+}
@@ -0,0 +1,79 @@
+//! Fixtures shared by the host integration tests.
+//!
+//! Each test file is compiled as its own crate, and only uses some of these.
+#![allow(dead_code)]
+
+use clack_host::prelude::*;
+use clack_plugin::entry::EntryDescriptor;
+use std::ffi::CString;
+
+/// The path all test bundles pretend to be loaded from.
+pub const BUNDLE_PATH: &str = "/home/user/.clap/clack-tests.clap";
+
+/// Loads the given in-process plugin entry as a bundle.
+pub fn load_bundle(entry: &'static EntryDescriptor) -> PluginBundle {
+    // SAFETY: the entry is a valid Clack plugin entry.
+    unsafe { PluginBundle::load_from_raw(entry, BUNDLE_PATH) }.unwrap()
+}
+
+/// The host information all test hosts use.
+pub fn host_info() -> HostInfo {
+    HostInfo::new(
+        "Clack Test Host",
+        "Clack",
+        "https://github.com/prokopyl/clack",
+        "0.0.0",
+    )
+    .unwrap()
+}
+
+/// Instantiates the plugin with the given ID from the given bundle, on a [`TestHost`].
+pub fn instantiate(bundle: &PluginBundle, plugin_id: &str) -> PluginInstance<TestHost> {
+    instantiate_with::<TestHost, _, _>(bundle, plugin_id, |_| TestHostShared, |_| ())
+}
+
+/// Instantiates the plugin with the given ID from the given bundle, using the given host
+/// handlers.
+pub fn instantiate_with<H, FS, FH>(
+    bundle: &PluginBundle,
+    plugin_id: &str,
+    shared: FS,
+    main_thread: FH,
+) -> PluginInstance<H>
+where
+    H: HostHandlers,
+    FS: for<'b> FnOnce(&'b ()) -> <H as HostHandlers>::Shared<'b>,
+    FH: for<'b> FnOnce(&'b <H as HostHandlers>::Shared<'b>) -> <H as HostHandlers>::MainThread<'b>,
+{
+    let plugin_id = CString::new(plugin_id).unwrap();
+
+    PluginInstance::<H>::new(shared, main_thread, bundle, &plugin_id, &host_info()).unwrap()
+}
+
+/// A [`SharedHandler`] for tests in which the plugin isn't expected to make any request to the
+/// host.
+pub struct TestHostShared;
+
+impl SharedHandler<'_> for TestHostShared {
+    fn request_restart(&self) {
+        panic!("The plugin unexpectedly requested a restart")
+    }
+
+    fn request_process(&self) {
+        panic!("The plugin unexpectedly requested processing")
+    }
+
+    fn request_callback(&self) {
+        panic!("The plugin unexpectedly requested a main-thread callback")
+    }
+}
+
+/// A host that doesn't implement any extension, and doesn't expect the plugin to make any
+/// request.
+pub struct TestHost;
+
+impl HostHandlers for TestHost {
+    type Shared<'a> = TestHostShared;
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = ();
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use clack_extensions::audio_ports::AudioPortType;
+use clack_extensions::configurable_audio_ports::{
+    AudioPortConfigRequest, AudioPortConfigRequests, PluginConfigurableAudioPorts,
+    PluginConfigurableAudioPortsImpl,
+};
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.configurable-audio-ports";
+
+pub struct ConfigurableAudioPortsPlugin;
+
+static MAIN_OUTPUT_CHANNELS: AtomicU32 = AtomicU32::new(2);
+static APPLY_COUNT: AtomicU32 = AtomicU32::new(0);
+
+pub struct ConfigurableAudioPortsPluginMainThread;
+
+impl<'a> PluginMainThread<'a, ()> for ConfigurableAudioPortsPluginMainThread {}
+
+impl Plugin for ConfigurableAudioPortsPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ConfigurableAudioPortsPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginConfigurableAudioPorts>();
+    }
+}
+
+impl DefaultPluginFactory for ConfigurableAudioPortsPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Configurable Audio Ports Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ConfigurableAudioPortsPluginMainThread)
+    }
+}
+
+/// The plugin only supports configuring its main output, in either mono or stereo.
+fn is_supported(request: &AudioPortConfigRequest) -> bool {
+    if request.is_input || request.port_index != 0 {
+        return false;
+    }
+
+    match request.port_type {
+        None => (1..=2).contains(&request.channel_count),
+        Some(port_type) => {
+            AudioPortType::from_channel_count(request.channel_count) == Some(port_type)
+        }
+    }
+}
+
+impl PluginConfigurableAudioPortsImpl for ConfigurableAudioPortsPluginMainThread {
+    fn can_apply_configuration(&mut self, requests: AudioPortConfigRequests) -> bool {
+        requests.iter().all(|r| is_supported(&r))
+    }
+
+    fn apply_configuration(&mut self, requests: AudioPortConfigRequests) -> bool {
+        APPLY_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        if !requests.iter().all(|r| is_supported(&r)) {
+            return false;
+        }
+
+        for request in requests.iter() {
+            MAIN_OUTPUT_CHANNELS.store(request.channel_count, Ordering::SeqCst);
+        }
+
+        true
+    }
+}
+
+pub static CONFIGURABLE_AUDIO_PORTS_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<ConfigurableAudioPortsPlugin>);
+
+#[test]
+pub fn can_apply_port_configuration() {
+    let bundle = common::load_bundle(&CONFIGURABLE_AUDIO_PORTS_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let configurable_ports: PluginConfigurableAudioPorts = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Configurable Audio Ports extension");
+
+    let mut plugin = instance.plugin_handle();
+
+    let mono = [AudioPortConfigRequest {
+        is_input: false,
+        port_index: 0,
+        channel_count: 1,
+        port_type: Some(AudioPortType::MONO),
+    }];
+
+    let invalid = [
+        AudioPortConfigRequest {
+            is_input: false,
+            port_index: 0,
+            channel_count: 2,
+            port_type: Some(AudioPortType::STEREO),
+        },
+        AudioPortConfigRequest {
+            is_input: true,
+            port_index: 1,
+            channel_count: 1,
+            port_type: None,
+        },
+    ];
+
+    assert!(configurable_ports.can_apply_configuration(&mut plugin, &mono));
+    assert!(!configurable_ports.can_apply_configuration(&mut plugin, &invalid));
+
+    // Invalid configurations are never applied.
+    assert!(!configurable_ports.apply(&mut plugin, &invalid));
+    assert_eq!(APPLY_COUNT.load(Ordering::SeqCst), 0);
+    assert_eq!(MAIN_OUTPUT_CHANNELS.load(Ordering::SeqCst), 2);
+
+    assert!(configurable_ports.apply(&mut plugin, &mono));
+    assert_eq!(APPLY_COUNT.load(Ordering::SeqCst), 1);
+    assert_eq!(MAIN_OUTPUT_CHANNELS.load(Ordering::SeqCst), 1);
+}
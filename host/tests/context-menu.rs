@@ -0,0 +1,210 @@
+mod common;
+
+use clack_extensions::context_menu::{
+    ContextMenu, ContextMenuBuilder, ContextMenuError, ContextMenuItem, ContextMenuTarget,
+    PluginContextMenu, PluginContextMenuImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::ffi::{CStr, CString};
+use std::sync::Mutex;
+
+static PERFORMED_ACTIONS: Mutex<Vec<(ContextMenuTarget, ClapId)>> = Mutex::new(Vec::new());
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.context-menu";
+
+pub struct ContextMenuPluginAudioProcessor;
+pub struct ContextMenuPlugin;
+pub struct ContextMenuPluginMainThread;
+
+impl PluginMainThread<'_, ()> for ContextMenuPluginMainThread {}
+
+impl Plugin for ContextMenuPlugin {
+    type AudioProcessor<'a> = ContextMenuPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = ContextMenuPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginContextMenu>();
+    }
+}
+
+impl DefaultPluginFactory for ContextMenuPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Context Menu Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ContextMenuPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), ContextMenuPluginMainThread>
+    for ContextMenuPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut ContextMenuPluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        unimplemented!()
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        unimplemented!()
+    }
+}
+
+impl PluginContextMenuImpl for ContextMenuPluginMainThread {
+    fn populate(
+        &mut self,
+        target: ContextMenuTarget,
+        builder: &mut ContextMenuBuilder,
+    ) -> Result<(), PluginError> {
+        match target {
+            ContextMenuTarget::Param(_) => {
+                builder
+                    .title(cstr(b"Cutoff\0"), true)
+                    .entry(cstr(b"Reset\0"), ClapId::new(1), true)
+                    .separator()
+                    .submenu_begin(cstr(b"Mode\0"), true)
+                    .check_item(cstr(b"Fast\0"), ClapId::new(2), true, true)
+                    .check_item(cstr(b"Slow\0"), ClapId::new(3), true, false)
+                    .submenu_end();
+            }
+            ContextMenuTarget::Global => {
+                // Oops, forgot to end the submenu.
+                builder.submenu_begin(cstr(b"Presets\0"), true).entry(
+                    cstr(b"Init\0"),
+                    ClapId::new(4),
+                    true,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn perform(&mut self, target: ContextMenuTarget, action_id: ClapId) -> Result<(), PluginError> {
+        PERFORMED_ACTIONS.lock().unwrap().push((target, action_id));
+        Ok(())
+    }
+}
+
+pub static CONTEXT_MENU_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<ContextMenuPlugin>);
+
+fn cstr(bytes: &[u8]) -> &CStr {
+    CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+fn label(label: &CStr) -> CString {
+    label.to_owned()
+}
+
+#[test]
+pub fn can_populate_context_menu() {
+    let bundle = common::load_bundle(&CONTEXT_MENU_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let context_menu: PluginContextMenu = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Context Menu extension");
+
+    let target = ContextMenuTarget::Param(ClapId::new(42));
+    let mut menu = ContextMenu::new();
+    context_menu
+        .populate(&mut instance.plugin_handle(), target, &mut menu)
+        .unwrap();
+
+    assert!(menu.is_complete());
+    assert_eq!(
+        menu.items(),
+        [
+            ContextMenuItem::Title {
+                title: label(cstr(b"Cutoff\0")),
+                is_enabled: true
+            },
+            ContextMenuItem::Entry {
+                label: label(cstr(b"Reset\0")),
+                is_enabled: true,
+                action_id: ClapId::new(1)
+            },
+            ContextMenuItem::Separator,
+            ContextMenuItem::BeginSubmenu {
+                label: label(cstr(b"Mode\0")),
+                is_enabled: true
+            },
+            ContextMenuItem::CheckEntry {
+                label: label(cstr(b"Fast\0")),
+                is_enabled: true,
+                is_checked: true,
+                action_id: ClapId::new(2)
+            },
+            ContextMenuItem::CheckEntry {
+                label: label(cstr(b"Slow\0")),
+                is_enabled: true,
+                is_checked: false,
+                action_id: ClapId::new(3)
+            },
+            ContextMenuItem::EndSubmenu,
+        ]
+    );
+
+    context_menu
+        .perform(&mut instance.plugin_handle(), target, ClapId::new(3))
+        .unwrap();
+
+    assert_eq!(
+        *PERFORMED_ACTIONS.lock().unwrap(),
+        [(target, ClapId::new(3))]
+    );
+
+    // The plugin leaves a submenu open for this target.
+    let mut menu = ContextMenu::new();
+    let result = context_menu.populate(
+        &mut instance.plugin_handle(),
+        ContextMenuTarget::Global,
+        &mut menu,
+    );
+
+    assert_eq!(result, Err(ContextMenuError::Failed));
+    assert!(menu.is_empty());
+}
+
+#[test]
+pub fn rejects_mismatched_submenu_end() {
+    let mut menu = ContextMenu::new();
+
+    assert_eq!(
+        menu.push(ContextMenuItem::EndSubmenu),
+        Err(ContextMenuError::UnexpectedSubmenuEnd)
+    );
+    assert!(menu.is_empty());
+
+    menu.push(ContextMenuItem::BeginSubmenu {
+        label: label(cstr(b"Sub\0")),
+        is_enabled: true,
+    })
+    .unwrap();
+    assert_eq!(menu.depth(), 1);
+    assert!(!menu.is_complete());
+
+    menu.push(ContextMenuItem::EndSubmenu).unwrap();
+    assert!(menu.is_complete());
+}
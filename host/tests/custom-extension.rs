@@ -0,0 +1,113 @@
+mod common;
+
+use clack_host::extensions::{clack_extension, PluginExtensionSide};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::extensions::wrapper::PluginWrapper;
+use clack_plugin::prelude::*;
+use clap_sys::plugin::clap_plugin;
+use std::ffi::CStr;
+
+const CLAP_EXT_ANSWER: &CStr = match CStr::from_bytes_with_nul(b"com.example.answer\0") {
+    Ok(id) => id,
+    Err(_) => unreachable!(),
+};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.custom-extension";
+
+#[repr(C)]
+pub struct clap_plugin_answer {
+    pub get: Option<unsafe extern "C" fn(plugin: *const clap_plugin) -> u32>,
+}
+
+clack_extension! {
+    /// A custom extension that allows the host to ask the plugin for an answer.
+    // SAFETY: CLAP_EXT_ANSWER is the identifier of the clap_plugin_answer extension.
+    pub unsafe struct PluginAnswer(clap_plugin_answer): PluginExtensionSide = CLAP_EXT_ANSWER;
+}
+
+impl PluginAnswer {
+    pub fn get(&self, plugin: &mut PluginMainThreadHandle) -> u32 {
+        match plugin.use_extension(&self.0).get {
+            None => 0,
+            // SAFETY: This type ensures the function pointer is valid.
+            Some(get) => unsafe { get(plugin.as_raw()) },
+        }
+    }
+}
+
+pub trait PluginAnswerImpl {
+    fn get(&mut self) -> u32;
+}
+
+clack_extension! {
+    // SAFETY: the implementation is a clap_plugin_answer, matching the extension type.
+    unsafe impl<P: Plugin> ExtensionImplementation<P> for PluginAnswer
+    where
+        for<'a> P::MainThread<'a>: PluginAnswerImpl,
+    = clap_plugin_answer {
+        get: Some(get::<P>),
+    };
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get<P: Plugin>(plugin: *const clap_plugin) -> u32
+where
+    for<'a> P::MainThread<'a>: PluginAnswerImpl,
+{
+    PluginWrapper::<P>::handle(plugin, |plugin| Ok(plugin.main_thread().as_mut().get()))
+        .unwrap_or(0)
+}
+
+pub struct AnswerPlugin;
+
+pub struct AnswerPluginMainThread;
+
+impl<'a> PluginMainThread<'a, ()> for AnswerPluginMainThread {}
+
+impl PluginAnswerImpl for AnswerPluginMainThread {
+    fn get(&mut self) -> u32 {
+        42
+    }
+}
+
+impl Plugin for AnswerPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = AnswerPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginAnswer>();
+    }
+}
+
+impl DefaultPluginFactory for AnswerPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Custom Extension Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(AnswerPluginMainThread)
+    }
+}
+
+pub static ANSWER_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<AnswerPlugin>);
+
+#[test]
+pub fn host_can_call_custom_plugin_extension() {
+    let bundle = common::load_bundle(&ANSWER_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let mut plugin = instance.plugin_handle();
+    let answer = plugin.get_extension::<PluginAnswer>().unwrap();
+
+    assert_eq!(answer.get(&mut plugin), 42);
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use clack_extensions::log::{HostLog, HostLogImpl, LogSeverity};
+use clack_extensions::note_name::{
+    HostNoteName, HostNoteNameImpl, NoteNameWriter, PluginNoteName, PluginNoteNameImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.duplicate-extensions";
+
+pub struct DuplicateExtensionsPlugin;
+
+pub struct DuplicateExtensionsPluginMainThread;
+
+impl PluginMainThread<'_, ()> for DuplicateExtensionsPluginMainThread {}
+
+impl Plugin for DuplicateExtensionsPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = DuplicateExtensionsPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder
+            .register::<PluginNoteName>()
+            .register::<PluginNoteName>();
+    }
+}
+
+impl DefaultPluginFactory for DuplicateExtensionsPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Duplicate Extensions Test Plugin")
+    }
+
+    fn new_shared(host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        // The first registration must still be returned.
+        host.get_extension::<HostNoteName>()
+            .ok_or(PluginError::Message("Host Note Name extension not found"))?;
+
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(DuplicateExtensionsPluginMainThread)
+    }
+}
+
+impl PluginNoteNameImpl for DuplicateExtensionsPluginMainThread {
+    fn count(&mut self) -> usize {
+        0
+    }
+
+    fn get(&mut self, _index: usize, _writer: &mut NoteNameWriter) {}
+}
+
+pub static DUPLICATE_EXTENSIONS_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<DuplicateExtensionsPlugin>);
+
+#[derive(Default)]
+struct MyHostShared {
+    logs: Mutex<Vec<(LogSeverity, String)>>,
+}
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+impl HostLogImpl for MyHostShared {
+    fn log(&self, severity: LogSeverity, message: &str) {
+        self.logs
+            .lock()
+            .unwrap()
+            .push((severity, message.to_owned()));
+    }
+}
+
+struct MyHostMainThread;
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostNoteNameImpl for MyHostMainThread {
+    fn changed(&mut self) {}
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder
+            .register::<HostLog>()
+            .register::<HostNoteName>()
+            .register::<HostNoteName>();
+    }
+}
+
+#[test]
+pub fn duplicate_extensions_are_reported_and_first_is_used() {
+    let bundle = common::load_bundle(&DUPLICATE_EXTENSIONS_ENTRY);
+
+    // The plugin queries the host's duplicated extension during instantiation.
+    let instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared::default(),
+        |_| MyHostMainThread,
+    );
+
+    assert_eq!(
+        instance.access_shared_handler(|h| h.logs.lock().unwrap().len()),
+        1
+    );
+
+    let note_name: Option<PluginNoteName> = instance.plugin_shared_handle().get_extension();
+    assert!(note_name.is_some());
+
+    let logs = instance.access_shared_handler(|h| h.logs.lock().unwrap().clone());
+    assert_eq!(logs.len(), 2);
+
+    for (severity, _) in &logs {
+        // Duplicates are only warned about in release builds, they panic in debug builds.
+        if cfg!(debug_assertions) {
+            assert_ne!(*severity, LogSeverity::Warning);
+        } else {
+            assert_eq!(*severity, LogSeverity::Warning);
+        }
+    }
+}
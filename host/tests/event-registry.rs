@@ -0,0 +1,151 @@
+mod common;
+
+use clack_common::events::io::EventBuffer;
+use clack_common::events::spaces::{CustomEventSpace, EventSpaceId};
+use clack_common::events::{Event, EventFlags, EventHeader, UnknownEvent};
+use clack_extensions::event_registry::{HostEventRegistry, HostEventRegistryImpl};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::ffi::CStr;
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.event-registry";
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VendorEvent {
+    header: EventHeader<Self>,
+    value: f64,
+}
+
+impl AsRef<UnknownEvent> for VendorEvent {
+    fn as_ref(&self) -> &UnknownEvent {
+        self.as_unknown()
+    }
+}
+
+// SAFETY: VendorEvent starts with an event header, and its event space is its own.
+unsafe impl Event for VendorEvent {
+    const TYPE_ID: u16 = 0;
+    type EventSpace<'a> = &'a VendorEvent;
+}
+
+// SAFETY: This space name is unique to this event type.
+unsafe impl CustomEventSpace for VendorEvent {
+    const SPACE_NAME: &'static CStr =
+        match CStr::from_bytes_with_nul(b"org.rust-audio.clack.tests.vendor-event\0") {
+            Ok(name) => name,
+            Err(_) => panic!(),
+        };
+}
+
+static RECEIVED: Mutex<Vec<(Option<u16>, Option<f64>)>> = Mutex::new(Vec::new());
+
+pub struct EventRegistryPlugin;
+
+impl Plugin for EventRegistryPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ();
+}
+
+impl DefaultPluginFactory for EventRegistryPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Event Registry Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        let registry: HostEventRegistry = host.get_extension().unwrap();
+        let vendor_space_id = registry.query_custom::<VendorEvent>(&host);
+
+        let unknown_space = CStr::from_bytes_with_nul(b"com.example.unknown\0").unwrap();
+        assert!(registry.query_space(&host, unknown_space).is_none());
+
+        let mut decoded = None;
+        if let Some(space_id) = vendor_space_id {
+            let event = VendorEvent {
+                header: VendorEvent::new_header(space_id, 0, EventFlags::empty()),
+                value: 0.5,
+            };
+
+            let mut buffer = EventBuffer::new();
+            buffer.push(&event);
+
+            let received = buffer.iter().next().unwrap();
+            decoded = VendorEvent::from_unknown(received, space_id).map(|e| e.value);
+        }
+
+        RECEIVED
+            .lock()
+            .unwrap()
+            .push((vendor_space_id.map(|id| id.id()), decoded));
+
+        Ok(())
+    }
+}
+
+pub static EVENT_REGISTRY_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<EventRegistryPlugin>);
+
+struct MyHostShared;
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostMainThread;
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+// SAFETY: The returned IDs are stable and unique.
+unsafe impl HostEventRegistryImpl for MyHostMainThread {
+    fn query(&self, space_name: &CStr) -> Option<EventSpaceId> {
+        if space_name == VendorEvent::SPACE_NAME {
+            EventSpaceId::new(4242)
+        } else {
+            None
+        }
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostEventRegistry>();
+    }
+}
+
+#[test]
+pub fn can_query_and_use_custom_event_spaces() {
+    let bundle = common::load_bundle(&EVENT_REGISTRY_ENTRY);
+
+    let _instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread,
+    );
+
+    assert_eq!(*RECEIVED.lock().unwrap(), [(Some(4242), Some(0.5))]);
+}
@@ -0,0 +1,98 @@
+use clack_host::bundle::PluginBundle;
+use clack_host::factory::PluginFactory as HostPluginFactory;
+use clack_plugin::clack_entry;
+use clack_plugin::entry::prelude::*;
+use clack_plugin::plugin::features::*;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static DESCRIPTOR_FETCHES: AtomicU32 = AtomicU32::new(0);
+
+pub struct StubEntry {
+    plugin_factory: PluginFactoryWrapper<StubPluginFactory>,
+}
+
+impl Entry for StubEntry {
+    fn new(_bundle_path: &CStr) -> Result<Self, EntryLoadError> {
+        Ok(Self {
+            plugin_factory: PluginFactoryWrapper::new(StubPluginFactory {
+                descriptors: [
+                    PluginDescriptor::new("com.u-he.diva", "Diva")
+                        .with_vendor("u-he")
+                        .with_features([INSTRUMENT, SYNTHESIZER]),
+                    PluginDescriptor::new("com.u-he.presswerk", "Presswerk")
+                        .with_vendor("u-he")
+                        .with_features([AUDIO_EFFECT, COMPRESSOR]),
+                    PluginDescriptor::new("com.example.synth", "Example Synth")
+                        .with_vendor("Example")
+                        .with_features([INSTRUMENT, SYNTHESIZER]),
+                    PluginDescriptor::new("com.example.unknown", "Unknown"),
+                ],
+            }),
+        })
+    }
+
+    fn declare_factories<'a>(&'a self, builder: &mut EntryFactories<'a>) {
+        builder.register_factory(&self.plugin_factory);
+    }
+}
+
+pub struct StubPluginFactory {
+    descriptors: [PluginDescriptor; 4],
+}
+
+impl PluginFactory for StubPluginFactory {
+    fn plugin_count(&self) -> u32 {
+        self.descriptors.len() as u32
+    }
+
+    fn plugin_descriptor(&self, index: u32) -> Option<&PluginDescriptor> {
+        DESCRIPTOR_FETCHES.fetch_add(1, Ordering::SeqCst);
+        self.descriptors.get(index as usize)
+    }
+
+    fn create_plugin<'a>(
+        &'a self,
+        _host_info: HostInfo<'a>,
+        _plugin_id: &CStr,
+    ) -> Option<PluginInstance<'a>> {
+        None
+    }
+}
+
+pub static STUB_ENTRY: EntryDescriptor = clack_entry!(StubEntry);
+
+fn ids<'a>(
+    descriptors: impl Iterator<Item = clack_host::factory::PluginDescriptor<'a>>,
+) -> Vec<&'a [u8]> {
+    descriptors.map(|d| d.id().unwrap().to_bytes()).collect()
+}
+
+#[test]
+pub fn can_filter_descriptors() {
+    let bundle =
+        unsafe { PluginBundle::load_from_raw(&STUB_ENTRY, "/home/user/.clap/stubs.so") }.unwrap();
+    let factory = bundle.get_factory::<HostPluginFactory>().unwrap();
+
+    DESCRIPTOR_FETCHES.store(0, Ordering::SeqCst);
+    assert_eq!(
+        ids(factory.plugin_descriptors_with_feature(SYNTHESIZER)),
+        [b"com.u-he.diva".as_slice(), b"com.example.synth"]
+    );
+    assert_eq!(DESCRIPTOR_FETCHES.load(Ordering::SeqCst), 4);
+
+    assert_eq!(
+        ids(factory.plugin_descriptors_with_feature(AUDIO_EFFECT)),
+        [b"com.u-he.presswerk".as_slice()]
+    );
+
+    DESCRIPTOR_FETCHES.store(0, Ordering::SeqCst);
+    assert_eq!(
+        ids(factory.plugin_descriptors_by_vendor("u-he")),
+        [b"com.u-he.diva".as_slice(), b"com.u-he.presswerk"]
+    );
+    assert_eq!(DESCRIPTOR_FETCHES.load(Ordering::SeqCst), 4);
+
+    assert_eq!(factory.plugin_descriptors_by_vendor("").count(), 0);
+    assert_eq!(factory.plugin_descriptors_by_vendor("U-HE").count(), 0);
+}
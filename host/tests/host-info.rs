@@ -0,0 +1,60 @@
+use clack_host::host::HostInfoError;
+use clack_host::prelude::*;
+
+#[test]
+pub fn exposes_fields() {
+    let info =
+        HostInfo::try_new("Legit Studio", "Legit Ltd.", "https://example.com", "4.3.2").unwrap();
+
+    assert_eq!(info.name().unwrap().to_bytes(), b"Legit Studio");
+    assert_eq!(info.vendor().unwrap().to_bytes(), b"Legit Ltd.");
+    assert_eq!(info.url().unwrap().to_bytes(), b"https://example.com");
+    assert_eq!(info.version().unwrap().to_bytes(), b"4.3.2");
+}
+
+#[test]
+pub fn empty_fields_are_none() {
+    let info = HostInfo::new("Legit Studio", "", "", "").unwrap();
+
+    assert_eq!(info.name().unwrap().to_bytes(), b"Legit Studio");
+    assert!(info.vendor().is_none());
+    assert!(info.url().is_none());
+    assert!(info.version().is_none());
+
+    // The URL is optional.
+    let info = HostInfo::try_new("Legit Studio", "Legit Ltd.", "", "4.3.2").unwrap();
+    assert!(info.url().is_none());
+}
+
+#[test]
+pub fn rejects_empty_required_fields() {
+    assert!(matches!(
+        HostInfo::try_new("", "Legit Ltd.", "", "4.3.2"),
+        Err(HostInfoError::EmptyName)
+    ));
+    assert!(matches!(
+        HostInfo::try_new("Legit Studio", "", "", "4.3.2"),
+        Err(HostInfoError::EmptyVendor)
+    ));
+    assert!(matches!(
+        HostInfo::try_new("Legit Studio", "Legit Ltd.", "", ""),
+        Err(HostInfoError::EmptyVersion)
+    ));
+}
+
+#[test]
+pub fn rejects_nul_bytes() {
+    let fields = ["Legit Studio", "Legit Ltd.", "https://example.com", "4.3.2"];
+
+    for i in 0..fields.len() {
+        let invalid = format!("{}\0", fields[i]);
+        let mut args = fields;
+        args[i] = &invalid;
+
+        assert!(HostInfo::new(args[0], args[1], args[2], args[3]).is_err());
+        assert!(matches!(
+            HostInfo::try_new(args[0], args[1], args[2], args[3]),
+            Err(HostInfoError::InvalidNul(_))
+        ));
+    }
+}
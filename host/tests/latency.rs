@@ -0,0 +1,187 @@
+mod common;
+
+use clack_extensions::latency::{
+    HostLatency, HostLatencyImpl, LatencyReporter, PluginLatency, PluginLatencyImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.latency";
+
+pub struct LatencyPlugin;
+
+pub struct LatencyPluginShared<'a> {
+    latency: LatencyReporter<'a>,
+}
+
+impl<'a> PluginShared<'a> for LatencyPluginShared<'a> {}
+
+pub struct LatencyPluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+    shared: &'a LatencyPluginShared<'a>,
+}
+
+impl<'a> PluginMainThread<'a, LatencyPluginShared<'a>> for LatencyPluginMainThread<'a> {
+    fn on_main_thread(&mut self) {
+        // Simulates a change to a "lookahead" parameter.
+        self.shared.latency.set(128);
+    }
+}
+
+impl PluginLatencyImpl for LatencyPluginMainThread<'_> {
+    fn get(&mut self) -> u32 {
+        self.shared.latency.get()
+    }
+}
+
+pub struct LatencyPluginAudioProcessor;
+
+impl<'a> PluginAudioProcessor<'a, LatencyPluginShared<'a>, LatencyPluginMainThread<'a>>
+    for LatencyPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        main_thread: &mut LatencyPluginMainThread<'a>,
+        shared: &'a LatencyPluginShared<'a>,
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        shared.latency.apply_pending(&mut main_thread.host);
+        Ok(LatencyPluginAudioProcessor)
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+impl Plugin for LatencyPlugin {
+    type AudioProcessor<'a> = LatencyPluginAudioProcessor;
+    type Shared<'a> = LatencyPluginShared<'a>;
+    type MainThread<'a> = LatencyPluginMainThread<'a>;
+
+    fn declare_extensions(
+        builder: &mut PluginExtensions<Self>,
+        _shared: Option<&LatencyPluginShared>,
+    ) {
+        builder.register::<PluginLatency>();
+    }
+}
+
+impl DefaultPluginFactory for LatencyPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Latency Test Plugin")
+    }
+
+    fn new_shared(host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(LatencyPluginShared {
+            latency: LatencyReporter::new(host, 64),
+        })
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(LatencyPluginMainThread { host, shared })
+    }
+}
+
+pub static LATENCY_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<LatencyPlugin>);
+
+#[derive(Default)]
+struct MyHostShared {
+    restart_requests: AtomicU32,
+}
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        self.restart_requests.fetch_add(1, Ordering::Relaxed);
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+#[derive(Default)]
+struct MyHostMainThread {
+    latency_changes: u32,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostLatencyImpl for MyHostMainThread {
+    fn changed(&mut self) {
+        self.latency_changes += 1;
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostLatency>();
+    }
+}
+
+#[test]
+pub fn latency_changes_are_applied_on_restart() {
+    let bundle = common::load_bundle(&LATENCY_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared::default(),
+        |_| MyHostMainThread::default(),
+    );
+
+    let latency: PluginLatency = instance.plugin_handle().get_extension().unwrap();
+    let config = PluginAudioConfiguration {
+        sample_rate: 44_100.0,
+        min_frames_count: 1,
+        max_frames_count: 32,
+    };
+
+    let processor = instance.activate(|_, _| (), config).unwrap();
+    assert_eq!(latency.get(&mut instance.plugin_handle()), 64);
+
+    // Changing the latency requests a restart, but doesn't change it right away.
+    instance.call_on_main_thread_callback();
+    assert_eq!(
+        instance.access_shared_handler(|h| h.restart_requests.load(Ordering::Relaxed)),
+        1
+    );
+    assert_eq!(latency.get(&mut instance.plugin_handle()), 64);
+
+    // Setting the same latency again doesn't request another restart.
+    instance.call_on_main_thread_callback();
+    assert_eq!(
+        instance.access_shared_handler(|h| h.restart_requests.load(Ordering::Relaxed)),
+        1
+    );
+
+    // The new latency is applied when the plugin is re-activated.
+    instance.deactivate(processor);
+    let processor = instance.activate(|_, _| (), config).unwrap();
+
+    assert_eq!(latency.get(&mut instance.plugin_handle()), 128);
+    assert_eq!(instance.access_handler(|h| h.latency_changes), 1);
+
+    // Re-activating without any change doesn't notify the host.
+    instance.deactivate(processor);
+    let _processor = instance.activate(|_, _| (), config).unwrap();
+    assert_eq!(instance.access_handler(|h| h.latency_changes), 1);
+}
@@ -1,4 +1,4 @@
-use clack_host::bundle::PluginBundle;
+use clack_host::bundle::{LoadOptions, PluginBundle, PluginBundleError};
 use clack_host::factory::PluginFactory;
 
 #[test]
@@ -45,3 +45,42 @@ pub fn it_works_concurrently() {
         }
     })
 }
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri does not support calling foreign function (dlopen)
+pub fn missing_file_is_an_access_error() {
+    let bundle_path = format!(
+        "{}/../target/debug/does-not-exist{}",
+        env!("CARGO_MANIFEST_DIR"),
+        std::env::consts::DLL_SUFFIX
+    );
+
+    let options = LoadOptions::new().with_retry_count(3);
+    let error = unsafe { PluginBundle::load_with_options(bundle_path, options) }.err();
+
+    let Some(PluginBundleError::FileAccessError(e)) = &error else {
+        panic!("Expected a file access error, got {error:?}");
+    };
+
+    assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+    assert!(!error.unwrap().is_transient());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri does not support calling foreign function (dlopen)
+pub fn invalid_library_is_not_retried() {
+    // This file exists, but it isn't a valid library.
+    let bundle_path = format!("{}/Cargo.toml", env!("CARGO_MANIFEST_DIR"));
+
+    // If this was retried, this test would take over 10 minutes to complete.
+    let options = LoadOptions::new()
+        .with_retry_count(10)
+        .with_retry_delay(std::time::Duration::from_secs(60));
+    let error = unsafe { PluginBundle::load_with_options(bundle_path, options) }.err();
+
+    assert!(matches!(
+        error,
+        Some(PluginBundleError::LibraryLoadingError(_))
+    ));
+    assert!(!error.unwrap().is_transient());
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use clack_extensions::log::{HostLog, HostLogImpl, LogForwarder, LogSeverity};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use log::{Level, Log, Metadata, Record};
+use std::ffi::CStr;
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.log-forwarder";
+
+pub struct LogForwarderPlugin;
+
+impl Plugin for LogForwarderPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ();
+}
+
+impl DefaultPluginFactory for LogForwarderPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Log Forwarder Test Plugin")
+    }
+
+    fn new_shared(host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        let log: HostLog = host.get_extension().unwrap();
+
+        let messages: [(LogSeverity, &[u8]); 4] = [
+            (LogSeverity::Debug, b"Loading wavetables\0"),
+            (LogSeverity::Warning, b"Sample rate is very low\0"),
+            (LogSeverity::Fatal, b"Out of voices\0"),
+            (
+                LogSeverity::HostMisbehaving,
+                b"Host called process before activate\0",
+            ),
+        ];
+
+        for (severity, message) in messages {
+            log.log(&host, severity, CStr::from_bytes_with_nul(message).unwrap());
+        }
+
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(())
+    }
+}
+
+pub static LOG_FORWARDER_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<LogForwarderPlugin>);
+
+struct MyHostShared {
+    logger: LogForwarder,
+}
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+impl HostLogImpl for MyHostShared {
+    fn log(&self, severity: LogSeverity, message: &str) {
+        self.logger.log(severity, message)
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostLog>();
+    }
+}
+
+struct CapturingLogger {
+    records: Mutex<Vec<(Level, String, String)>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push((
+            record.level(),
+            record.target().to_string(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+pub fn forwards_plugin_logs_to_log_facade() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let bundle = common::load_bundle(&LOG_FORWARDER_ENTRY);
+
+    let _instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared {
+            logger: LogForwarder::new(PLUGIN_ID),
+        },
+        |_| (),
+    );
+
+    let target = format!("clap::{PLUGIN_ID}");
+
+    assert_eq!(
+        *LOGGER.records.lock().unwrap(),
+        [
+            (Level::Debug, target.clone(), "Loading wavetables".into()),
+            (
+                Level::Warn,
+                target.clone(),
+                "Sample rate is very low".into()
+            ),
+            (Level::Error, target.clone(), "Out of voices".into()),
+            (
+                Level::Error,
+                target,
+                "[host misbehaving] Host called process before activate".into()
+            ),
+        ]
+    );
+}
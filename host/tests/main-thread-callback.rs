@@ -0,0 +1,99 @@
+mod common;
+
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.main-thread-callback";
+
+pub struct MainThreadCallbackPlugin;
+
+pub struct MainThreadCallbackPluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+    callback_count: u32,
+}
+
+impl<'a> PluginMainThread<'a, ()> for MainThreadCallbackPluginMainThread<'a> {
+    fn on_main_thread(&mut self) {
+        self.callback_count += 1;
+
+        // Request one more callback from within the first one.
+        if self.callback_count == 1 {
+            self.host.request_callback();
+        }
+    }
+}
+
+impl Plugin for MainThreadCallbackPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = MainThreadCallbackPluginMainThread<'a>;
+}
+
+impl DefaultPluginFactory for MainThreadCallbackPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Main Thread Callback Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        host.request_callback();
+
+        Ok(MainThreadCallbackPluginMainThread {
+            host,
+            callback_count: 0,
+        })
+    }
+}
+
+pub static MAIN_THREAD_CALLBACK_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<MainThreadCallbackPlugin>);
+
+struct MyHostShared;
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {}
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = ();
+}
+
+#[test]
+pub fn only_calls_on_main_thread_when_requested() {
+    let bundle = common::load_bundle(&MAIN_THREAD_CALLBACK_ENTRY);
+
+    let mut instance =
+        common::instantiate_with::<MyHost, _, _>(&bundle, PLUGIN_ID, |_| MyHostShared, |_| ());
+
+    // The plugin requested a callback during its initialization.
+    assert!(instance.needs_main_thread_callback());
+    assert!(instance.call_on_main_thread());
+
+    // The plugin requested another callback during the first one.
+    assert!(instance.needs_main_thread_callback());
+    assert!(instance.call_on_main_thread());
+
+    assert!(!instance.needs_main_thread_callback());
+    assert!(!instance.call_on_main_thread());
+
+    // Unconditional calls are still possible.
+    instance.call_on_main_thread_callback();
+    assert!(!instance.needs_main_thread_callback());
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use clack_extensions::note_name::{
+    HostNoteName, HostNoteNameImpl, NoteName, NoteNameEntry, NoteNameWriter, PluginNoteName,
+    PluginNoteNameImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::events::Match;
+use clack_plugin::prelude::*;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.note-name";
+
+pub struct NoteNamePlugin;
+
+pub struct NoteNamePluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+}
+
+impl<'a> PluginMainThread<'a, ()> for NoteNamePluginMainThread<'a> {}
+
+impl Plugin for NoteNamePlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = NoteNamePluginMainThread<'a>;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginNoteName>();
+    }
+}
+
+impl DefaultPluginFactory for NoteNamePlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Note Name Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(NoteNamePluginMainThread { host })
+    }
+}
+
+impl PluginNoteNameImpl for NoteNamePluginMainThread<'_> {
+    fn count(&mut self) -> usize {
+        3
+    }
+
+    fn get(&mut self, index: usize, writer: &mut NoteNameWriter) {
+        match index {
+            0 => writer.write(&NoteName {
+                name: b"Kick",
+                port: Match::All,
+                channel: Match::All,
+                key: Match::Specific(36),
+            }),
+            1 => writer.write(&NoteName {
+                name: b"Snare",
+                port: Match::Specific(0),
+                channel: Match::Specific(9),
+                key: Match::Specific(38),
+            }),
+            // The third entry fails to be written, and should be skipped.
+            _ => {}
+        }
+
+        if let Some(note_name) = self.host.get_extension::<HostNoteName>() {
+            note_name.changed(&mut self.host);
+        }
+    }
+}
+
+pub static NOTE_NAME_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<NoteNamePlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+#[derive(Default)]
+struct MyHostMainThread {
+    changed_count: usize,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostNoteNameImpl for MyHostMainThread {
+    fn changed(&mut self) {
+        self.changed_count += 1;
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostNoteName>();
+    }
+}
+
+#[test]
+pub fn can_fetch_all_note_names() {
+    let bundle = common::load_bundle(&NOTE_NAME_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread::default(),
+    );
+
+    let note_name: PluginNoteName = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Note Name extension");
+
+    let names = note_name.names(&mut instance.plugin_handle());
+
+    assert_eq!(
+        names,
+        [
+            NoteNameEntry {
+                name: b"Kick".to_vec(),
+                port: Match::All,
+                channel: Match::All,
+                key: Match::Specific(36),
+            },
+            NoteNameEntry {
+                name: b"Snare".to_vec(),
+                port: Match::Specific(0),
+                channel: Match::Specific(9),
+                key: Match::Specific(38),
+            },
+        ]
+    );
+
+    assert!(names[0].matches(1, 3, 36));
+    assert!(!names[1].matches(1, 9, 38));
+    assert_eq!(names[0].wildcard_count(), 2);
+    assert_eq!(names[1].wildcard_count(), 0);
+
+    instance.access_handler(|h| assert_eq!(h.changed_count, 3));
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use clack_extensions::note_ports::{
+    NoteDialect, NoteDialects, NotePortInfo, NotePortInfoWriter, NotePortRescanFlags,
+    PluginNotePorts, PluginNotePortsImpl,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.note-ports";
+
+static RENAMED: AtomicBool = AtomicBool::new(false);
+
+pub struct NotePortsPlugin;
+pub struct NotePortsPluginMainThread;
+
+impl PluginMainThread<'_, ()> for NotePortsPluginMainThread {}
+
+impl Plugin for NotePortsPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = NotePortsPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginNotePorts>();
+    }
+}
+
+impl DefaultPluginFactory for NotePortsPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        use clack_plugin::plugin::features::*;
+
+        PluginDescriptor::new(PLUGIN_ID, "Note Ports Test Plugin").with_features([NOTE_EFFECT])
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(NotePortsPluginMainThread)
+    }
+}
+
+impl PluginNotePortsImpl for NotePortsPluginMainThread {
+    fn count(&mut self, is_input: bool) -> u32 {
+        if is_input {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn get(&mut self, index: u32, is_input: bool, writer: &mut NotePortInfoWriter) {
+        let renamed = RENAMED.load(Ordering::SeqCst);
+
+        let (name, supported_dialects, preferred_dialect): (&[u8], _, _) = match (is_input, index) {
+            (true, 0) => (
+                if renamed {
+                    b"Notes (renamed)"
+                } else {
+                    b"Notes"
+                },
+                NoteDialects::CLAP | NoteDialects::MIDI,
+                Some(NoteDialect::Clap),
+            ),
+            (true, 1) => (b"MIDI In", NoteDialects::MIDI, None),
+            (false, 0) => (b"MIDI Out", NoteDialects::MIDI, Some(NoteDialect::Midi)),
+            _ => return,
+        };
+
+        writer.set(&NotePortInfo {
+            id: ClapId::new(index + 10),
+            name,
+            supported_dialects,
+            preferred_dialect,
+        });
+    }
+}
+
+pub static NOTE_PORTS_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<NotePortsPlugin>);
+
+#[test]
+pub fn can_query_note_ports_layout() {
+    let bundle = common::load_bundle(&NOTE_PORTS_ENTRY);
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let note_ports: PluginNotePorts = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Note Ports extension");
+
+    let mut layout = note_ports.layout(&mut instance.plugin_handle());
+
+    assert_eq!(layout.inputs.len(), 2);
+    assert_eq!(layout.outputs.len(), 1);
+
+    let notes = &layout.inputs[0];
+    assert_eq!(notes.id, ClapId::new(10));
+    assert_eq!(notes.name, "Notes");
+    assert_eq!(notes.preferred_dialect, Some(NoteDialect::Clap));
+    assert_eq!(
+        notes.dialect_for(NoteDialects::all()),
+        Some(NoteDialect::Clap)
+    );
+    assert_eq!(
+        notes.dialect_for(NoteDialects::MIDI),
+        Some(NoteDialect::Midi)
+    );
+    assert_eq!(notes.dialect_for(NoteDialects::MIDI2), None);
+
+    let midi_in = &layout.inputs[1];
+    assert_eq!(midi_in.name, "MIDI In");
+    assert_eq!(midi_in.preferred_dialect, None);
+    assert_eq!(
+        midi_in.dialect_for(NoteDialects::all()),
+        Some(NoteDialect::Midi)
+    );
+
+    assert_eq!(layout.outputs[0].name, "MIDI Out");
+
+    // The plugin renamed its ports, and requested a rescan.
+    RENAMED.store(true, Ordering::SeqCst);
+    note_ports.update_layout(
+        &mut instance.plugin_handle(),
+        &mut layout,
+        NotePortRescanFlags::NAMES,
+    );
+
+    assert_eq!(layout.inputs[0].name, "Notes (renamed)");
+    assert_eq!(layout, note_ports.layout(&mut instance.plugin_handle()));
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use clack_extensions::param_indication::{
+    AutomationState, PluginParamIndication, PluginParamIndicationImpl,
+};
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use clack_plugin::utils::{ClapId, Color};
+use std::ffi::{CStr, CString};
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.param-indication";
+
+pub struct ParamIndicationPlugin;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Indication {
+    Mapping {
+        param_id: ClapId,
+        has_mapping: bool,
+        color: Option<Color>,
+        label: Option<CString>,
+        description: Option<CString>,
+    },
+    Automation {
+        param_id: ClapId,
+        state: AutomationState,
+        color: Option<Color>,
+    },
+}
+
+static INDICATIONS: Mutex<Vec<Indication>> = Mutex::new(Vec::new());
+
+pub struct ParamIndicationPluginMainThread;
+
+impl<'a> PluginMainThread<'a, ()> for ParamIndicationPluginMainThread {}
+
+impl Plugin for ParamIndicationPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ParamIndicationPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginParamIndication>();
+    }
+}
+
+impl DefaultPluginFactory for ParamIndicationPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Param Indication Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ParamIndicationPluginMainThread)
+    }
+}
+
+impl PluginParamIndicationImpl for ParamIndicationPluginMainThread {
+    fn set_mapping(
+        &mut self,
+        param_id: ClapId,
+        has_mapping: bool,
+        color: Option<Color>,
+        label: Option<&CStr>,
+        description: Option<&CStr>,
+    ) {
+        INDICATIONS.lock().unwrap().push(Indication::Mapping {
+            param_id,
+            has_mapping,
+            color,
+            label: label.map(CStr::to_owned),
+            description: description.map(CStr::to_owned),
+        });
+    }
+
+    fn set_automation(&mut self, param_id: ClapId, state: AutomationState, color: Option<Color>) {
+        INDICATIONS.lock().unwrap().push(Indication::Automation {
+            param_id,
+            state,
+            color,
+        });
+    }
+}
+
+pub static PARAM_INDICATION_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<ParamIndicationPlugin>);
+
+#[test]
+pub fn can_send_param_indications() {
+    let bundle = common::load_bundle(&PARAM_INDICATION_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let param_indication: PluginParamIndication = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Param Indication extension");
+
+    let mut plugin = instance.plugin_handle();
+
+    param_indication.set_mapping(
+        &mut plugin,
+        ClapId::new(42),
+        true,
+        Some(Color::rgb(0xff, 0x80, 0x00)),
+        Some(CStr::from_bytes_with_nul(b"Knob 3\0").unwrap()),
+        Some(CStr::from_bytes_with_nul(b"Pad Controller\0").unwrap()),
+    );
+    param_indication.set_automation(
+        &mut plugin,
+        ClapId::new(42),
+        AutomationState::Recording,
+        None,
+    );
+    param_indication.set_automation(
+        &mut plugin,
+        ClapId::new(7),
+        AutomationState::Overriding,
+        Some(Color {
+            alpha: 0x80,
+            red: 1,
+            green: 2,
+            blue: 3,
+        }),
+    );
+    param_indication.set_mapping(&mut plugin, ClapId::new(42), false, None, None, None);
+
+    assert_eq!(
+        *INDICATIONS.lock().unwrap(),
+        [
+            Indication::Mapping {
+                param_id: ClapId::new(42),
+                has_mapping: true,
+                color: Some(Color::rgb(0xff, 0x80, 0x00)),
+                label: Some(CString::new("Knob 3").unwrap()),
+                description: Some(CString::new("Pad Controller").unwrap()),
+            },
+            Indication::Automation {
+                param_id: ClapId::new(42),
+                state: AutomationState::Recording,
+                color: None,
+            },
+            Indication::Automation {
+                param_id: ClapId::new(7),
+                state: AutomationState::Overriding,
+                color: Some(Color {
+                    alpha: 0x80,
+                    red: 1,
+                    green: 2,
+                    blue: 3
+                }),
+            },
+            Indication::Mapping {
+                param_id: ClapId::new(42),
+                has_mapping: false,
+                color: None,
+                label: None,
+                description: None,
+            },
+        ]
+    );
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use clack_extensions::params::{
+    ParamDisplayWriter, ParamInfo, ParamInfoFlags, ParamInfoWriter, ParamSnapshot,
+    PluginAudioProcessorParams, PluginMainThreadParams, PluginParams,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use clack_plugin::utils::Cookie;
+use std::ffi::CStr;
+use std::fmt::Write;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.param-snapshot";
+
+pub struct ParamSnapshotPlugin;
+pub struct ParamSnapshotPluginMainThread;
+pub struct ParamSnapshotPluginAudioProcessor;
+
+impl PluginMainThread<'_, ()> for ParamSnapshotPluginMainThread {}
+
+impl Plugin for ParamSnapshotPlugin {
+    type AudioProcessor<'a> = ParamSnapshotPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = ParamSnapshotPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginParams>();
+    }
+}
+
+impl DefaultPluginFactory for ParamSnapshotPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Param Snapshot Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ParamSnapshotPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), ParamSnapshotPluginMainThread>
+    for ParamSnapshotPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut ParamSnapshotPluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        Ok(Self)
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+impl PluginMainThreadParams for ParamSnapshotPluginMainThread {
+    fn count(&mut self) -> u32 {
+        3
+    }
+
+    fn get_info(&mut self, param_index: u32, info: &mut ParamInfoWriter) {
+        info.set(&ParamInfo {
+            id: ClapId::new(param_index + 1),
+            flags: ParamInfoFlags::IS_AUTOMATABLE,
+            cookie: Cookie::empty(),
+            name: b"Param",
+            module: b"",
+            min_value: 0.0,
+            max_value: 1.0,
+            default_value: 0.5,
+        })
+    }
+
+    fn get_value(&mut self, param_id: ClapId) -> Option<f64> {
+        match param_id.get() {
+            1 => Some(0.25),
+            3 => Some(0.75),
+            // Parameter 2 fails to report its value.
+            _ => None,
+        }
+    }
+
+    fn value_to_text(
+        &mut self,
+        param_id: ClapId,
+        value: f64,
+        writer: &mut ParamDisplayWriter,
+    ) -> std::fmt::Result {
+        match param_id.get() {
+            1 => write!(writer, "{}%", value * 100.0),
+            // Parameter 3 fails to format its value.
+            _ => Err(std::fmt::Error),
+        }
+    }
+
+    fn text_to_value(&mut self, _param_id: ClapId, _text: &CStr) -> Option<f64> {
+        None
+    }
+
+    fn flush(
+        &mut self,
+        _input_parameter_changes: &InputEvents,
+        _output_parameter_changes: &mut OutputEvents,
+    ) {
+    }
+}
+
+impl PluginAudioProcessorParams for ParamSnapshotPluginAudioProcessor {
+    fn flush(
+        &mut self,
+        _input_parameter_changes: &InputEvents,
+        _output_parameter_changes: &mut OutputEvents,
+    ) {
+    }
+}
+
+pub static PARAM_SNAPSHOT_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<ParamSnapshotPlugin>);
+
+#[test]
+pub fn can_snapshot_param_values() {
+    let bundle = common::load_bundle(&PARAM_SNAPSHOT_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let snapshot = ParamSnapshot::capture(&mut instance.plugin_handle());
+
+    // The snapshot can be inspected from another thread.
+    let snapshot = std::thread::spawn(move || snapshot.clone()).join().unwrap();
+
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(snapshot.get(ClapId::new(1)), Some(0.25));
+    assert_eq!(snapshot.get(ClapId::new(2)), None);
+    assert_eq!(snapshot.get(ClapId::new(3)), Some(0.75));
+}
+
+#[test]
+pub fn can_read_formatted_param_values() {
+    let bundle = common::load_bundle(&PARAM_SNAPSHOT_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let mut plugin = instance.plugin_handle();
+    let params = plugin.get_extension::<PluginParams>().unwrap();
+
+    assert_eq!(
+        params.formatted_values(&mut plugin),
+        [
+            (ClapId::new(1), 0.25, "25%".to_owned()),
+            (ClapId::new(3), 0.75, "0.75".to_owned()),
+        ]
+    );
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use clack_extensions::params::{
+    HostParams, HostParamsImplMainThread, HostParamsImplShared, ParamClearFlags, ParamRescanFlags,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.params-rescan";
+
+pub struct ParamsRescanPlugin;
+
+pub struct ParamsRescanPluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+}
+
+impl<'a> PluginMainThread<'a, ()> for ParamsRescanPluginMainThread<'a> {
+    fn on_main_thread(&mut self) {
+        let Some(params) = self.host.get_extension::<HostParams>() else {
+            return;
+        };
+
+        params.rescan(
+            &mut self.host,
+            ParamRescanFlags::INFO | ParamRescanFlags::TEXT,
+        );
+        params.clear(
+            &mut self.host,
+            ClapId::new(42),
+            ParamClearFlags::AUTOMATIONS,
+        );
+        params.request_flush(&self.host.shared());
+    }
+}
+
+impl Plugin for ParamsRescanPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ParamsRescanPluginMainThread<'a>;
+
+    fn declare_extensions(_builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {}
+}
+
+impl DefaultPluginFactory for ParamsRescanPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Params Rescan Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ParamsRescanPluginMainThread { host })
+    }
+}
+
+pub static PARAMS_RESCAN_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<ParamsRescanPlugin>);
+
+#[derive(Default)]
+struct MyHostShared {
+    flush_requested: AtomicBool,
+}
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+impl HostParamsImplShared for MyHostShared {
+    fn request_flush(&self) {
+        self.flush_requested.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct MyHostMainThread {
+    rescans: Vec<ParamRescanFlags>,
+    clears: Vec<(ClapId, ParamClearFlags)>,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostParamsImplMainThread for MyHostMainThread {
+    fn rescan(&mut self, flags: ParamRescanFlags) {
+        self.rescans.push(flags);
+    }
+
+    fn clear(&mut self, param_id: ClapId, flags: ParamClearFlags) {
+        self.clears.push((param_id, flags));
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostParams>();
+    }
+}
+
+#[test]
+pub fn host_receives_param_rescan_requests() {
+    let bundle = common::load_bundle(&PARAMS_RESCAN_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared::default(),
+        |_| MyHostMainThread::default(),
+    );
+
+    assert!(instance.access_handler(|h| h.rescans.is_empty()));
+
+    instance.call_on_main_thread_callback();
+
+    let rescans = instance.access_handler(|h| h.rescans.clone());
+    assert_eq!(rescans, [ParamRescanFlags::INFO | ParamRescanFlags::TEXT]);
+    assert!(!rescans[0].requires_restart());
+
+    assert_eq!(
+        instance.access_handler(|h| h.clears.clone()),
+        [(ClapId::new(42), ParamClearFlags::AUTOMATIONS)]
+    );
+
+    assert!(instance.access_shared_handler(|h| h.flush_requested.load(Ordering::Relaxed)));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use clack_host::factory::PluginFactory;
+use clack_host::plugin::PluginCategory;
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use common::{TestHost, TestHostShared};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.plugin-descriptor";
+
+pub struct DescriptorPlugin;
+
+impl Plugin for DescriptorPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ();
+}
+
+impl DefaultPluginFactory for DescriptorPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        use clack_plugin::plugin::features::*;
+
+        PluginDescriptor::new(PLUGIN_ID, "Descriptor Test Plugin")
+            .with_version("1.4.4-beta")
+            .with_features([STEREO, AUDIO_EFFECT, INSTRUMENT, SYNTHESIZER])
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(())
+    }
+}
+
+pub static DESCRIPTOR_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<DescriptorPlugin>);
+
+#[test]
+pub fn can_query_features() {
+    use clack_host::plugin::features::*;
+
+    let bundle = common::load_bundle(&DESCRIPTOR_ENTRY);
+
+    let descriptor = bundle
+        .get_factory::<PluginFactory>()
+        .unwrap()
+        .plugin_descriptor(0)
+        .unwrap();
+
+    assert!(descriptor.has_feature(SYNTHESIZER));
+    assert!(!descriptor.has_feature(MONO));
+
+    assert!(descriptor.is_instrument());
+    assert!(descriptor.is_audio_effect());
+    assert!(!descriptor.is_note_effect());
+    assert!(!descriptor.is_analyzer());
+
+    // Both instrument and audio-effect are listed: the first one wins.
+    assert_eq!(
+        descriptor.primary_category(),
+        Some(PluginCategory::AudioEffect)
+    );
+
+    assert_eq!(descriptor.semver(), Some((1, 4, 4)));
+}
+
+#[test]
+pub fn can_instantiate_by_index() {
+    let bundle = common::load_bundle(&DESCRIPTOR_ENTRY);
+    let host_info = common::host_info();
+
+    let instance = PluginInstance::<TestHost>::new_by_index(
+        |_| TestHostShared,
+        |_| (),
+        &bundle,
+        0,
+        &host_info,
+    )
+    .unwrap();
+
+    assert!(format!("{instance:?}").contains(PLUGIN_ID));
+
+    let error = PluginInstance::<TestHost>::new_by_index(
+        |_| TestHostShared,
+        |_| (),
+        &bundle,
+        1,
+        &host_info,
+    )
+    .err()
+    .unwrap();
+
+    assert_eq!(error, PluginInstanceError::InvalidPluginIndex);
+}
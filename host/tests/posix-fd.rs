@@ -0,0 +1,187 @@
+#![cfg(unix)]
+
+mod common;
+
+use clack_extensions::posix_fd::{
+    FdFlags, HostPosixFd, HostPosixFdImpl, PluginPosixFd, PluginPosixFdImpl, PosixFdRegistry,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::io::{Read, Write};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.posix-fd";
+
+pub struct PosixFdPlugin;
+
+static RECEIVED: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+pub struct PosixFdPluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+    socket: UnixStream,
+}
+
+impl<'a> PluginMainThread<'a, ()> for PosixFdPluginMainThread<'a> {}
+
+impl Plugin for PosixFdPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = PosixFdPluginMainThread<'a>;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginPosixFd>();
+    }
+}
+
+impl DefaultPluginFactory for PosixFdPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "POSIX FD Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        mut host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        let socket = SOCKET.lock().unwrap().take().unwrap();
+
+        let posix_fd: HostPosixFd = host.get_extension().unwrap();
+        posix_fd
+            .register_fd(
+                &mut host,
+                socket.as_raw_fd(),
+                FdFlags::READ | FdFlags::ERROR,
+            )
+            .unwrap();
+
+        Ok(PosixFdPluginMainThread { host, socket })
+    }
+}
+
+static SOCKET: Mutex<Option<UnixStream>> = Mutex::new(None);
+
+impl PluginPosixFdImpl for PosixFdPluginMainThread<'_> {
+    fn on_fd(&mut self, fd: RawFd, flags: FdFlags) {
+        assert_eq!(fd, self.socket.as_raw_fd());
+        assert_eq!(flags, FdFlags::READ);
+
+        let mut buf = [0; 5];
+        self.socket.read_exact(&mut buf).unwrap();
+        RECEIVED.lock().unwrap().extend_from_slice(&buf);
+
+        // We only wanted to read a single message.
+        let posix_fd: HostPosixFd = self.host.get_extension().unwrap();
+        posix_fd.unregister_fd(&mut self.host, fd).unwrap();
+    }
+}
+
+pub static POSIX_FD_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<PosixFdPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostMainThread {
+    fds: PosixFdRegistry,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostPosixFdImpl for MyHostMainThread {
+    fn register_fd(&mut self, fd: RawFd, flags: FdFlags) -> Result<(), HostError> {
+        self.fds.register_fd(fd, flags)
+    }
+
+    fn modify_fd(&mut self, fd: RawFd, flags: FdFlags) -> Result<(), HostError> {
+        self.fds.modify_fd(fd, flags)
+    }
+
+    fn unregister_fd(&mut self, fd: RawFd) -> Result<(), HostError> {
+        self.fds.unregister_fd(fd)
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostPosixFd>();
+    }
+}
+
+#[test]
+pub fn registry_forwards_fd_events() {
+    let (mut host_socket, plugin_socket) = UnixStream::pair().unwrap();
+    let plugin_fd = plugin_socket.as_raw_fd();
+    *SOCKET.lock().unwrap() = Some(plugin_socket);
+
+    let bundle = common::load_bundle(&POSIX_FD_ENTRY);
+
+    let registry = PosixFdRegistry::new();
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread {
+            fds: registry.clone(),
+        },
+    );
+
+    let posix_fd: PluginPosixFd = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the POSIX FD extension");
+
+    assert_eq!(
+        registry.fds(),
+        [(plugin_fd, FdFlags::READ | FdFlags::ERROR)]
+    );
+
+    host_socket.write_all(b"hello").unwrap();
+
+    // Simulate the results of the host's event loop: the plugin didn't ask for write events,
+    // and the other FD isn't registered.
+    let events = [
+        (plugin_fd, FdFlags::READ | FdFlags::WRITE),
+        (host_socket.as_raw_fd(), FdFlags::READ),
+    ];
+    let ready: Vec<_> = registry.ready_fds(events).collect();
+    assert_eq!(ready, [(plugin_fd, FdFlags::READ)]);
+
+    for (fd, flags) in ready {
+        registry.notify(&mut instance.plugin_handle(), &posix_fd, fd, flags);
+    }
+
+    assert_eq!(&*RECEIVED.lock().unwrap(), b"hello");
+    assert!(registry.is_empty());
+
+    // Unregistered FDs are not forwarded to the plugin anymore.
+    assert_eq!(registry.ready_fds(events).count(), 0);
+    registry.notify(
+        &mut instance.plugin_handle(),
+        &posix_fd,
+        plugin_fd,
+        FdFlags::READ,
+    );
+    assert_eq!(RECEIVED.lock().unwrap().len(), 5);
+}
@@ -0,0 +1,367 @@
+use clack_host::factory::preset_discovery::*;
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::entry::prelude::*;
+use clack_plugin::factory::Factory;
+use clap_sys::factory::draft::preset_discovery::*;
+use clap_sys::version::CLAP_VERSION;
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+static DESTROYED_PROVIDERS: AtomicU32 = AtomicU32::new(0);
+static LATE_DECLARATION_ACCEPTED: AtomicBool = AtomicBool::new(false);
+
+fn cstr(bytes: &'static [u8]) -> &'static CStr {
+    CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+struct SyncDescriptor(clap_preset_discovery_provider_descriptor);
+// SAFETY: the descriptor only points to static strings.
+unsafe impl Sync for SyncDescriptor {}
+
+static PROVIDER_DESCRIPTOR: SyncDescriptor =
+    SyncDescriptor(clap_preset_discovery_provider_descriptor {
+        clap_version: CLAP_VERSION,
+        id: b"com.u-he.diva.presets\0".as_ptr() as *const _,
+        name: b"Diva Presets\0".as_ptr() as *const _,
+        vendor: b"u-he\0".as_ptr() as *const _,
+    });
+
+#[repr(C)]
+pub struct StubPresetDiscoveryFactory(clap_preset_discovery_factory);
+
+// SAFETY: This type is repr(C) and wraps the matching CLAP factory struct.
+unsafe impl Factory for StubPresetDiscoveryFactory {
+    const IDENTIFIER: &'static CStr = CLAP_PRESET_DISCOVERY_FACTORY_ID;
+}
+
+impl Default for StubPresetDiscoveryFactory {
+    fn default() -> Self {
+        Self(clap_preset_discovery_factory {
+            count: Some(count),
+            get_descriptor: Some(get_descriptor),
+            create: Some(create),
+        })
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn count(_factory: *const clap_preset_discovery_factory) -> u32 {
+    2
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get_descriptor(
+    _factory: *const clap_preset_discovery_factory,
+    index: u32,
+) -> *const clap_preset_discovery_provider_descriptor {
+    // The second provider is advertised, but has no descriptor.
+    match index {
+        0 => &PROVIDER_DESCRIPTOR.0,
+        _ => core::ptr::null(),
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn create(
+    _factory: *const clap_preset_discovery_factory,
+    indexer: *const clap_preset_discovery_indexer,
+    provider_id: *const c_char,
+) -> *const clap_preset_discovery_provider {
+    if CStr::from_ptr(provider_id) != CStr::from_ptr(PROVIDER_DESCRIPTOR.0.id) {
+        return core::ptr::null();
+    }
+
+    Box::into_raw(Box::new(clap_preset_discovery_provider {
+        desc: &PROVIDER_DESCRIPTOR.0,
+        provider_data: indexer as *mut c_void,
+        init: Some(init),
+        destroy: Some(destroy),
+        get_metadata: Some(get_metadata),
+        get_extension: None,
+    }))
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn init(provider: *const clap_preset_discovery_provider) -> bool {
+    let indexer = &*((*provider).provider_data as *const clap_preset_discovery_indexer);
+    assert_eq!(CStr::from_ptr(indexer.name), cstr(b"Preset browser\0"));
+    // Empty fields are passed as empty strings, not null pointers.
+    assert!(!indexer.url.is_null());
+    assert_eq!(CStr::from_ptr(indexer.url), cstr(b"\0"));
+
+    let filetype = clap_preset_discovery_filetype {
+        name: b"Diva preset\0".as_ptr() as *const _,
+        description: core::ptr::null(),
+        file_extension: b"h2p\0".as_ptr() as *const _,
+    };
+
+    let file_location = clap_preset_discovery_location {
+        flags: CLAP_PRESET_DISCOVERY_IS_USER_CONTENT,
+        name: b"User presets\0".as_ptr() as *const _,
+        kind: CLAP_PRESET_DISCOVERY_LOCATION_FILE,
+        location: b"/home/user/presets\0".as_ptr() as *const _,
+    };
+
+    let plugin_location = clap_preset_discovery_location {
+        flags: CLAP_PRESET_DISCOVERY_IS_FACTORY_CONTENT,
+        name: b"Factory presets\0".as_ptr() as *const _,
+        kind: CLAP_PRESET_DISCOVERY_LOCATION_PLUGIN,
+        location: core::ptr::null(),
+    };
+
+    let soundpack = clap_preset_discovery_soundpack {
+        flags: 0,
+        id: b"factory\0".as_ptr() as *const _,
+        name: b"Factory Library\0".as_ptr() as *const _,
+        description: core::ptr::null(),
+        homepage_url: core::ptr::null(),
+        vendor: b"u-he\0".as_ptr() as *const _,
+        image_path: core::ptr::null(),
+        release_timestamp: CLAP_TIMESTAMP_UNKNOWN,
+    };
+
+    indexer.declare_filetype.unwrap()(indexer, &filetype)
+        && indexer.declare_location.unwrap()(indexer, &file_location)
+        && indexer.declare_location.unwrap()(indexer, &plugin_location)
+        && indexer.declare_soundpack.unwrap()(indexer, &soundpack)
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn destroy(provider: *const clap_preset_discovery_provider) {
+    drop(Box::from_raw(
+        provider as *mut clap_preset_discovery_provider,
+    ));
+    DESTROYED_PROVIDERS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn get_metadata(
+    provider: *const clap_preset_discovery_provider,
+    location_kind: clap_preset_discovery_location_kind,
+    _location: *const c_char,
+    receiver: *const clap_preset_discovery_metadata_receiver,
+) -> bool {
+    let indexer = &*((*provider).provider_data as *const clap_preset_discovery_indexer);
+    let receiver_ref = &*receiver;
+
+    let late_location = clap_preset_discovery_location {
+        flags: 0,
+        name: b"Late\0".as_ptr() as *const _,
+        kind: CLAP_PRESET_DISCOVERY_LOCATION_PLUGIN,
+        location: core::ptr::null(),
+    };
+    if indexer.declare_location.unwrap()(indexer, &late_location) {
+        LATE_DECLARATION_ACCEPTED.store(true, Ordering::SeqCst);
+    }
+
+    if location_kind == CLAP_PRESET_DISCOVERY_LOCATION_FILE {
+        receiver_ref.on_error.unwrap()(receiver, 2, b"No such file\0".as_ptr() as *const _);
+        return false;
+    }
+
+    let plugin_id = clap_plugin_id {
+        abi: b"clap\0".as_ptr() as *const _,
+        id: b"com.u-he.diva\0".as_ptr() as *const _,
+    };
+
+    receiver_ref.begin_preset.unwrap()(
+        receiver,
+        b"Bread and Butter\0".as_ptr() as *const _,
+        b"0\0".as_ptr() as *const _,
+    );
+    receiver_ref.add_plugin_id.unwrap()(receiver, &plugin_id);
+    receiver_ref.set_soundpack_id.unwrap()(receiver, b"factory\0".as_ptr() as *const _);
+    receiver_ref.add_creator.unwrap()(receiver, b"Howard\0".as_ptr() as *const _);
+    receiver_ref.set_timestamps.unwrap()(receiver, 1_700_000_000, CLAP_TIMESTAMP_UNKNOWN);
+
+    receiver_ref.begin_preset.unwrap()(
+        receiver,
+        b"Big Pad\0".as_ptr() as *const _,
+        b"1\0".as_ptr() as *const _,
+    );
+    receiver_ref.add_plugin_id.unwrap()(receiver, &plugin_id);
+    receiver_ref.set_flags.unwrap()(receiver, CLAP_PRESET_DISCOVERY_IS_FAVORITE);
+    receiver_ref.add_feature.unwrap()(receiver, b"pad\0".as_ptr() as *const _);
+    receiver_ref.add_extra_info.unwrap()(
+        receiver,
+        b"bank\0".as_ptr() as *const _,
+        b"A\0".as_ptr() as *const _,
+    );
+
+    true
+}
+
+pub struct StubEntry {
+    preset_discovery_factory: StubPresetDiscoveryFactory,
+}
+
+impl Entry for StubEntry {
+    fn new(_bundle_path: &CStr) -> Result<Self, EntryLoadError> {
+        Ok(Self {
+            preset_discovery_factory: StubPresetDiscoveryFactory::default(),
+        })
+    }
+
+    fn declare_factories<'a>(&'a self, builder: &mut EntryFactories<'a>) {
+        builder.register_factory(&self.preset_discovery_factory);
+    }
+}
+
+pub static STUB_ENTRY: EntryDescriptor = clack_entry!(StubEntry);
+
+fn host_info() -> clack_host::host::HostInfo {
+    clack_host::host::HostInfo::new("Preset browser", "Clack", "", "1.0").unwrap()
+}
+
+#[test]
+pub fn lists_provider_descriptors() {
+    let bundle =
+        unsafe { PluginBundle::load_from_raw(&STUB_ENTRY, "/home/user/.clap/u-he/libdiva.so") }
+            .unwrap();
+
+    assert!(bundle.get_plugin_factory().is_none());
+    let factory = bundle.get_preset_discovery_factory().unwrap();
+
+    assert_eq!(factory.provider_count(), 2);
+    assert!(factory.provider_descriptor(1).is_none());
+
+    let descriptors: Vec<_> = factory.provider_descriptors().collect();
+    assert_eq!(descriptors.len(), 1);
+    assert_eq!(descriptors[0].id(), Some(cstr(b"com.u-he.diva.presets\0")));
+    assert_eq!(descriptors[0].name(), Some(cstr(b"Diva Presets\0")));
+    assert_eq!(descriptors[0].vendor(), Some(cstr(b"u-he\0")));
+}
+
+#[test]
+pub fn enumerates_presets() {
+    let bundle =
+        unsafe { PluginBundle::load_from_raw(&STUB_ENTRY, "/home/user/.clap/u-he/libdiva.so") }
+            .unwrap();
+    let factory = bundle.get_preset_discovery_factory().unwrap();
+
+    let destroyed_before = DESTROYED_PROVIDERS.load(Ordering::SeqCst);
+    let provider = factory
+        .create_provider(&host_info(), cstr(b"com.u-he.diva.presets\0"))
+        .unwrap();
+
+    assert_eq!(
+        provider.descriptor().unwrap().id(),
+        Some(cstr(b"com.u-he.diva.presets\0"))
+    );
+
+    assert_eq!(
+        provider.filetypes(),
+        [PresetFiletype {
+            name: cstr(b"Diva preset\0").into(),
+            description: None,
+            file_extension: Some(cstr(b"h2p\0").into()),
+        }]
+    );
+
+    assert_eq!(provider.locations().len(), 2);
+    assert_eq!(
+        provider.locations()[0].kind,
+        PresetDiscoveryLocationKind::File(cstr(b"/home/user/presets\0").into())
+    );
+    assert_eq!(
+        provider.locations()[1].kind,
+        PresetDiscoveryLocationKind::Plugin
+    );
+
+    assert_eq!(provider.soundpacks().len(), 1);
+    assert_eq!(provider.soundpacks()[0].id.as_c_str(), cstr(b"factory\0"));
+    assert_eq!(provider.soundpacks()[0].release_timestamp, None);
+
+    let presets = provider.presets_at(&provider.locations()[1]).unwrap();
+    assert!(!LATE_DECLARATION_ACCEPTED.load(Ordering::SeqCst));
+    assert_eq!(provider.locations().len(), 2);
+
+    assert_eq!(presets.len(), 2);
+    assert_eq!(
+        presets[0].name,
+        Some(CString::from(cstr(b"Bread and Butter\0")))
+    );
+    assert_eq!(presets[0].load_key, Some(CString::from(cstr(b"0\0"))));
+    assert_eq!(
+        presets[0].plugin_ids,
+        [PresetPluginId {
+            abi: cstr(b"clap\0").into(),
+            id: cstr(b"com.u-he.diva\0").into(),
+        }]
+    );
+    assert_eq!(presets[0].soundpack_id, Some(cstr(b"factory\0").into()));
+    assert_eq!(presets[0].creators, [CString::from(cstr(b"Howard\0"))]);
+    assert_eq!(presets[0].creation_time, Some(1_700_000_000));
+    assert_eq!(presets[0].modification_time, None);
+
+    assert_eq!(presets[1].name, Some(CString::from(cstr(b"Big Pad\0"))));
+    assert_eq!(presets[1].flags, CLAP_PRESET_DISCOVERY_IS_FAVORITE);
+    assert_eq!(presets[1].features, [CString::from(cstr(b"pad\0"))]);
+    assert_eq!(
+        presets[1].extra_info,
+        [(cstr(b"bank\0").into(), cstr(b"A\0").into())]
+    );
+
+    let error = provider.presets_at(&provider.locations()[0]).unwrap_err();
+    assert_eq!(
+        error,
+        PresetDiscoveryError::GetMetadataFailed {
+            os_error: 2,
+            message: Some(cstr(b"No such file\0").into())
+        }
+    );
+
+    drop(provider);
+    assert_eq!(
+        DESTROYED_PROVIDERS.load(Ordering::SeqCst),
+        destroyed_before + 1
+    );
+}
+
+#[test]
+pub fn unknown_provider_is_not_found() {
+    let bundle =
+        unsafe { PluginBundle::load_from_raw(&STUB_ENTRY, "/home/user/.clap/u-he/libdiva.so") }
+            .unwrap();
+    let factory = bundle.get_preset_discovery_factory().unwrap();
+
+    let result = factory.create_provider(&host_info(), cstr(b"com.example.unknown\0"));
+    assert!(matches!(
+        result,
+        Err(PresetDiscoveryError::ProviderNotFound)
+    ));
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn null_entry_init(_plugin_path: *const c_char) -> bool {
+    true
+}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn null_entry_deinit() {}
+
+#[allow(clippy::missing_safety_doc)]
+unsafe extern "C" fn null_entry_get_factory(_factory_id: *const c_char) -> *const c_void {
+    core::ptr::null()
+}
+
+/// An entry that claims to support every factory, but only ever returns null pointers.
+pub static NULL_FACTORY_ENTRY: EntryDescriptor = EntryDescriptor {
+    clap_version: CLAP_VERSION,
+    init: Some(null_entry_init),
+    deinit: Some(null_entry_deinit),
+    get_factory: Some(null_entry_get_factory),
+};
+
+#[test]
+pub fn null_factory_pointers_are_ignored() {
+    let bundle =
+        unsafe { PluginBundle::load_from_raw(&NULL_FACTORY_ENTRY, "/home/user/.clap/null.so") }
+            .unwrap();
+
+    assert!(bundle.get_preset_discovery_factory().is_none());
+    assert!(bundle.get_factory::<PresetDiscoveryFactory>().is_none());
+    assert!(bundle.get_plugin_factory().is_none());
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use clack_extensions::preset_load::{
+    HostPresetLoad, HostPresetLoadImpl, PluginPresetLoad, PluginPresetLoadImpl, PresetLoadError,
+    PresetLocation,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::ffi::{CStr, CString};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.preset-load";
+
+pub struct PresetLoadPlugin;
+
+pub struct PresetLoadPluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+    preset_load: Option<HostPresetLoad>,
+}
+
+impl<'a> PluginMainThread<'a, ()> for PresetLoadPluginMainThread<'a> {
+    fn on_main_thread(&mut self) {
+        // Simulates a preset that failed to load asynchronously.
+        let location = PresetLocation::File(PathBuf::from("/presets/Pad.h2p"));
+        self.preset_load
+            .unwrap()
+            .on_error(&mut self.host, &location, None, None);
+    }
+}
+
+impl Plugin for PresetLoadPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = PresetLoadPluginMainThread<'a>;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginPresetLoad>();
+    }
+}
+
+impl DefaultPluginFactory for PresetLoadPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Preset Load Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(PresetLoadPluginMainThread {
+            preset_load: host.get_extension(),
+            host,
+        })
+    }
+}
+
+impl PluginPresetLoadImpl for PresetLoadPluginMainThread<'_> {
+    fn load(&mut self, location: PresetLocation) -> Result<(), PluginError> {
+        let preset_load = self.preset_load.unwrap();
+
+        if let PresetLocation::Container { .. } = &location {
+            preset_load.on_error(
+                &mut self.host,
+                &location,
+                Some(2),
+                Some(CStr::from_bytes_with_nul(b"Bank not found\0").unwrap()),
+            );
+
+            return Err(std::io::Error::from(ErrorKind::NotFound).into());
+        }
+
+        if let PresetLocation::Plugin { .. } = &location {
+            // Simulates a preset that loaded, but without one of its samples.
+            let sample = PresetLocation::File(PathBuf::from("/samples/Lead.wav"));
+            preset_load.on_error(&mut self.host, &sample, None, None);
+        }
+
+        preset_load.loaded(&mut self.host, &location);
+        Ok(())
+    }
+}
+
+pub static PRESET_LOAD_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<PresetLoadPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+#[derive(Default)]
+struct MyHostMainThread {
+    loaded: Vec<PresetLocation>,
+    errors: Vec<(PresetLocation, PresetLoadError)>,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostPresetLoadImpl for MyHostMainThread {
+    fn on_error(&mut self, location: PresetLocation, error: PresetLoadError) {
+        self.errors.push((location, error));
+    }
+
+    fn loaded(&mut self, location: PresetLocation) {
+        self.loaded.push(location);
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostPresetLoad>();
+    }
+}
+
+#[test]
+pub fn can_load_presets_from_locations() {
+    let bundle = common::load_bundle(&PRESET_LOAD_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread::default(),
+    );
+
+    let preset_load: PluginPresetLoad = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Preset Load extension");
+
+    let file = PresetLocation::File(PathBuf::from("/presets/Bass.h2p"));
+    let plugin = PresetLocation::Plugin {
+        load_key: CString::new("factory/lead").unwrap(),
+    };
+    let container = PresetLocation::Container {
+        path: PathBuf::from("/presets/Bank.fxb"),
+        load_key: CString::new("12").unwrap(),
+    };
+
+    preset_load
+        .load(&mut instance.plugin_handle(), &file)
+        .unwrap();
+    preset_load
+        .load(&mut instance.plugin_handle(), &plugin)
+        .unwrap();
+
+    // The error reported during the call is returned, instead of being sent to on_error.
+    assert_eq!(
+        preset_load.load(&mut instance.plugin_handle(), &container),
+        Err(PresetLoadError::Reported {
+            os_error: Some(2),
+            message: Some(CString::new("Bank not found").unwrap()),
+        })
+    );
+
+    let no_details = PresetLoadError::Reported {
+        os_error: None,
+        message: None,
+    };
+
+    // Errors reported for other locations during a load call are still sent to on_error.
+    instance.access_handler(|h| {
+        assert_eq!(h.loaded, [file.clone(), plugin.clone()]);
+        assert_eq!(
+            h.errors,
+            [(
+                PresetLocation::File(PathBuf::from("/samples/Lead.wav")),
+                no_details.clone()
+            )]
+        );
+    });
+
+    // Errors reported outside of a load call are sent to on_error.
+    instance.call_on_main_thread_callback();
+
+    instance.access_handler(|h| {
+        assert_eq!(
+            h.errors[1..],
+            [(
+                PresetLocation::File(PathBuf::from("/presets/Pad.h2p")),
+                no_details.clone()
+            )]
+        );
+    });
+
+    let invalid = PresetLocation::File(PathBuf::from("/presets/Bad\0Name.h2p"));
+    assert_eq!(
+        preset_load.load(&mut instance.plugin_handle(), &invalid),
+        Err(PresetLoadError::InvalidLocation)
+    );
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use clack_extensions::log::{HostLog, HostLogImpl, LogSeverity};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.process-error";
+
+pub struct ProcessErrorPluginAudioProcessor;
+pub struct ProcessErrorPlugin;
+pub struct ProcessErrorPluginMainThread;
+
+impl PluginMainThread<'_, ()> for ProcessErrorPluginMainThread {}
+
+impl Plugin for ProcessErrorPlugin {
+    type AudioProcessor<'a> = ProcessErrorPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = ProcessErrorPluginMainThread;
+}
+
+impl DefaultPluginFactory for ProcessErrorPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Process Error Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ProcessErrorPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), ProcessErrorPluginMainThread>
+    for ProcessErrorPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut ProcessErrorPluginMainThread,
+        _shared: &'a (),
+        audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        if audio_config.sample_rate != 48_000.0 {
+            return Err(PluginError::Owned(format!(
+                "Unsupported sample rate: {}",
+                audio_config.sample_rate
+            )));
+        }
+
+        Ok(ProcessErrorPluginAudioProcessor)
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        Err(PluginError::Message("Sidechain buffer is missing"))
+    }
+}
+
+pub static PROCESS_ERROR_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<ProcessErrorPlugin>);
+
+#[derive(Default)]
+struct MyHostShared {
+    logs: Mutex<Vec<(LogSeverity, String)>>,
+}
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+impl HostLogImpl for MyHostShared {
+    fn log(&self, severity: LogSeverity, message: &str) {
+        self.logs
+            .lock()
+            .unwrap()
+            .push((severity, message.to_owned()));
+    }
+}
+
+struct MyHostAudioProcessor;
+impl AudioProcessorHandler<'_> for MyHostAudioProcessor {}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = MyHostAudioProcessor;
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostLog>();
+    }
+}
+
+#[test]
+pub fn process_error_status_is_returned_to_host() {
+    let bundle = common::load_bundle(&PROCESS_ERROR_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared::default(),
+        |_| (),
+    );
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let mut processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap()
+        .start_processing()
+        .unwrap();
+
+    let mut output_events = EventBuffer::new();
+    let result = processor.process(
+        &InputAudioBuffers::empty(),
+        &mut OutputAudioBuffers::empty(),
+        &InputEvents::empty(),
+        &mut output_events.as_output(),
+        None,
+        None,
+    );
+
+    assert_eq!(result, Err(PluginInstanceError::ProcessingReturnedError));
+
+    let logs = instance.access_shared_handler(|h| h.logs.lock().unwrap().clone());
+    assert_eq!(
+        logs,
+        [(LogSeverity::Error, "Sidechain buffer is missing".to_owned())]
+    );
+
+    instance.deactivate(processor.stop_processing());
+}
+
+#[test]
+pub fn owned_error_messages_are_logged() {
+    let bundle = common::load_bundle(&PROCESS_ERROR_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared::default(),
+        |_| (),
+    );
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 44_100.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let error = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .err()
+        .unwrap();
+    assert_eq!(error, PluginInstanceError::ActivationFailed);
+    assert!(error.to_string().contains("log extension"));
+
+    let logs = instance.access_shared_handler(|h| h.logs.lock().unwrap().clone());
+    assert_eq!(
+        logs,
+        [(
+            LogSeverity::Error,
+            "Unsupported sample rate: 44100".to_owned()
+        )]
+    );
+}
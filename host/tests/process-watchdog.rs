@@ -0,0 +1,130 @@
+mod common;
+
+use clack_host::events::io::{EventBuffer, InputEvents};
+use clack_host::prelude::*;
+use clack_host::process::StartedPluginAudioProcessor;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use common::TestHost;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// How long the plugin's next process call will take, in milliseconds.
+static PROCESS_DURATION_MS: AtomicU64 = AtomicU64::new(0);
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.process-watchdog";
+
+pub struct ProcessWatchdogPlugin;
+
+pub struct ProcessWatchdogPluginAudioProcessor;
+
+impl Plugin for ProcessWatchdogPlugin {
+    type AudioProcessor<'a> = ProcessWatchdogPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = ();
+}
+
+impl DefaultPluginFactory for ProcessWatchdogPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Process Watchdog Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(())
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), ()> for ProcessWatchdogPluginAudioProcessor {
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut (),
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        Ok(Self)
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        let duration = PROCESS_DURATION_MS.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(duration));
+
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+pub static PROCESS_WATCHDOG_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<ProcessWatchdogPlugin>);
+
+#[test]
+pub fn watchdog_reports_slow_process_calls() {
+    let bundle = common::load_bundle(&PROCESS_WATCHDOG_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let processor = instance
+        .activate(
+            |_, _| (),
+            PluginAudioConfiguration {
+                sample_rate: 44_100.0,
+                min_frames_count: 1,
+                max_frames_count: 256,
+            },
+        )
+        .unwrap();
+
+    let mut processor = processor.start_processing().unwrap();
+    assert!(!processor.has_watchdog());
+
+    let (sender, receiver) = channel();
+    processor
+        .enable_watchdog(Duration::from_millis(20), move |elapsed| {
+            sender.send(elapsed).unwrap();
+        })
+        .unwrap();
+    assert!(processor.has_watchdog());
+
+    let mut output_events = EventBuffer::new();
+    let mut process = |processor: &mut StartedPluginAudioProcessor<TestHost>| {
+        processor
+            .process(
+                &InputAudioBuffers::empty(),
+                &mut OutputAudioBuffers::empty(),
+                &InputEvents::empty(),
+                &mut output_events.as_output(),
+                None,
+                None,
+            )
+            .unwrap()
+    };
+
+    // A fast call is not reported
+    PROCESS_DURATION_MS.store(0, Ordering::SeqCst);
+    process(&mut processor);
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+    // A slow call is reported exactly once
+    PROCESS_DURATION_MS.store(200, Ordering::SeqCst);
+    process(&mut processor);
+
+    let elapsed = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(elapsed > Duration::from_millis(20));
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+    processor.disable_watchdog();
+    assert!(!processor.has_watchdog());
+
+    instance.deactivate(processor.stop_processing());
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use clack_extensions::remote_controls::{
+    HostRemoteControls, HostRemoteControlsImpl, PluginRemoteControls, PluginRemoteControlsImpl,
+    RemoteControlsPage, RemoteControlsPageWriter,
+};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.remote-controls";
+
+pub struct RemoteControlsPlugin;
+
+pub struct RemoteControlsPluginMainThread<'a> {
+    host: HostMainThreadHandle<'a>,
+}
+
+impl<'a> PluginMainThread<'a, ()> for RemoteControlsPluginMainThread<'a> {}
+
+impl Plugin for RemoteControlsPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = RemoteControlsPluginMainThread<'a>;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginRemoteControls>();
+    }
+}
+
+impl DefaultPluginFactory for RemoteControlsPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Remote Controls Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(RemoteControlsPluginMainThread { host })
+    }
+}
+
+fn page(page_id: u32) -> RemoteControlsPage {
+    let mut param_ids = [None; 8];
+    param_ids[0] = Some(ClapId::new(page_id * 10));
+    param_ids[3] = Some(ClapId::new(page_id * 10 + 3));
+
+    RemoteControlsPage {
+        section_name: b"Oscillators".to_vec(),
+        page_id: ClapId::new(page_id),
+        page_name: format!("OSC {page_id}").into_bytes(),
+        param_ids,
+        is_for_preset: false,
+    }
+}
+
+impl PluginRemoteControlsImpl for RemoteControlsPluginMainThread<'_> {
+    fn count(&mut self) -> u32 {
+        3
+    }
+
+    fn get(&mut self, page_index: u32, writer: &mut RemoteControlsPageWriter) {
+        // The last page is never written.
+        if page_index < 2 {
+            writer.write(&page(page_index + 1));
+        }
+
+        if let Some(remote_controls) = self.host.get_extension::<HostRemoteControls>() {
+            remote_controls.suggest_page(&mut self.host, ClapId::new(2));
+        }
+    }
+}
+
+pub static REMOTE_CONTROLS_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<RemoteControlsPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+#[derive(Default)]
+struct MyHostMainThread {
+    suggested_page: Option<ClapId>,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostRemoteControlsImpl for MyHostMainThread {
+    fn changed(&mut self) {
+        unimplemented!()
+    }
+
+    fn suggest_page(&mut self, page_id: ClapId) {
+        self.suggested_page = Some(page_id);
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostRemoteControls>();
+    }
+}
+
+#[test]
+pub fn can_enumerate_remote_controls_pages() {
+    let bundle = common::load_bundle(&REMOTE_CONTROLS_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread::default(),
+    );
+
+    let remote_controls: PluginRemoteControls = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Remote Controls extension");
+
+    assert_eq!(remote_controls.count(&mut instance.plugin_handle()), 3);
+
+    let pages = remote_controls.pages(&mut instance.plugin_handle());
+    assert_eq!(pages, [page(1), page(2)]);
+
+    let first = &pages[0];
+    assert_eq!(first.param_ids[0], Some(ClapId::new(10)));
+    assert_eq!(first.param_ids[1], None);
+    assert_eq!(first.param_ids[3], Some(ClapId::new(13)));
+
+    assert!(remote_controls
+        .get(&mut instance.plugin_handle(), 2)
+        .is_none());
+
+    assert_eq!(
+        instance.access_handler(|h| h.suggested_page),
+        Some(ClapId::new(2))
+    );
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use clack_extensions::log::{HostLog, HostLogImpl, LogSeverity};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::Mutex;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.start-processing-error";
+
+pub struct StartProcessingErrorPluginAudioProcessor;
+pub struct StartProcessingErrorPlugin;
+pub struct StartProcessingErrorPluginMainThread;
+
+impl PluginMainThread<'_, ()> for StartProcessingErrorPluginMainThread {}
+
+impl Plugin for StartProcessingErrorPlugin {
+    type AudioProcessor<'a> = StartProcessingErrorPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = StartProcessingErrorPluginMainThread;
+}
+
+impl DefaultPluginFactory for StartProcessingErrorPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Start Processing Error Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(StartProcessingErrorPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), StartProcessingErrorPluginMainThread>
+    for StartProcessingErrorPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut StartProcessingErrorPluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        Ok(StartProcessingErrorPluginAudioProcessor)
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        Ok(ProcessStatus::Continue)
+    }
+
+    fn start_processing(&mut self) -> Result<(), PluginError> {
+        Err(PluginError::Message("Audio device is not ready"))
+    }
+}
+
+pub static START_PROCESSING_ERROR_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<StartProcessingErrorPlugin>);
+
+#[derive(Default)]
+struct MyHostShared {
+    logs: Mutex<Vec<(LogSeverity, String)>>,
+}
+
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+impl HostLogImpl for MyHostShared {
+    fn log(&self, severity: LogSeverity, message: &str) {
+        self.logs
+            .lock()
+            .unwrap()
+            .push((severity, message.to_owned()));
+    }
+}
+
+struct MyHostAudioProcessor;
+impl AudioProcessorHandler<'_> for MyHostAudioProcessor {}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = ();
+    type AudioProcessor<'a> = MyHostAudioProcessor;
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostLog>();
+    }
+}
+
+#[test]
+pub fn start_processing_failure_reason_is_logged() {
+    let bundle = common::load_bundle(&START_PROCESSING_ERROR_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared::default(),
+        |_| (),
+    );
+
+    let config = PluginAudioConfiguration {
+        sample_rate: 48_000.0,
+        min_frames_count: 1,
+        max_frames_count: 256,
+    };
+
+    let processor = instance
+        .activate(|_, _| MyHostAudioProcessor, config)
+        .unwrap();
+
+    let error = processor.start_processing().err().unwrap();
+    assert!(error.to_string().contains("log extension"));
+
+    let logs = instance.access_shared_handler(|h| h.logs.lock().unwrap().clone());
+    assert_eq!(
+        logs,
+        [(
+            LogSeverity::Error,
+            "Plugin failed to start processing: Audio device is not ready".to_owned()
+        )]
+    );
+
+    instance.deactivate(error.into_stopped_processor());
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use clack_extensions::state::{PluginState, PluginStateImpl};
+use clack_extensions::state_context::{
+    PluginStateContext, PluginStateContextImpl, StateContextType,
+};
+use clack_host::extensions::Extension;
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use clack_plugin::stream::{InputStream, OutputStream};
+use std::io::{Read, Write};
+use std::sync::Mutex;
+
+static LOADED_CONTEXTS: Mutex<Vec<StateContextType>> = Mutex::new(Vec::new());
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.state-context";
+
+pub struct StateContextPluginAudioProcessor;
+pub struct StateContextPlugin;
+pub struct StateContextPluginMainThread {
+    seed: u8,
+}
+
+impl PluginMainThread<'_, ()> for StateContextPluginMainThread {}
+
+impl Plugin for StateContextPlugin {
+    type AudioProcessor<'a> = StateContextPluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = StateContextPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder
+            .register::<PluginState>()
+            .register::<PluginStateContext>();
+    }
+}
+
+impl DefaultPluginFactory for StateContextPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "State Context Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(StateContextPluginMainThread { seed: 42 })
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), StateContextPluginMainThread>
+    for StateContextPluginAudioProcessor
+{
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut StateContextPluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        unimplemented!()
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        unimplemented!()
+    }
+}
+
+impl PluginStateImpl for StateContextPluginMainThread {
+    fn save(&mut self, output: &mut OutputStream) -> Result<(), PluginError> {
+        PluginStateContextImpl::save(self, output, StateContextType::Project)
+    }
+
+    fn load(&mut self, input: &mut InputStream) -> Result<(), PluginError> {
+        PluginStateContextImpl::load(self, input, StateContextType::Project)
+    }
+}
+
+impl PluginStateContextImpl for StateContextPluginMainThread {
+    fn save(
+        &mut self,
+        output: &mut OutputStream,
+        context: StateContextType,
+    ) -> Result<(), PluginError> {
+        // The randomization seed is specific to this instance: don't include it in presets.
+        match context {
+            StateContextType::Preset => output.write_all(b"preset")?,
+            _ => output.write_all(&[self.seed])?,
+        }
+
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        input: &mut InputStream,
+        context: StateContextType,
+    ) -> Result<(), PluginError> {
+        let mut data = Vec::new();
+        input.read_to_end(&mut data)?;
+
+        if context != StateContextType::Preset {
+            self.seed = data[0];
+        }
+
+        LOADED_CONTEXTS.lock().unwrap().push(context);
+        Ok(())
+    }
+}
+
+pub static STATE_CONTEXT_ENTRY: EntryDescriptor =
+    clack_entry!(SinglePluginEntry<StateContextPlugin>);
+
+#[test]
+pub fn uses_final_extension_numbering() {
+    assert_eq!(
+        PluginStateContext::IDENTIFIER.to_bytes(),
+        b"clap.state-context/2"
+    );
+
+    assert_eq!(StateContextType::Preset.to_raw(), 1);
+    assert_eq!(StateContextType::Duplicate.to_raw(), 2);
+    assert_eq!(StateContextType::Project.to_raw(), 3);
+    assert_eq!(StateContextType::from_raw(4), None);
+}
+
+#[test]
+pub fn passes_state_context_type_to_plugin() {
+    let bundle = common::load_bundle(&STATE_CONTEXT_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let state_context: PluginStateContext = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the State Context extension");
+
+    let mut preset = Vec::new();
+    state_context
+        .save_with_context(
+            &mut instance.plugin_handle(),
+            &mut preset,
+            StateContextType::Preset,
+        )
+        .unwrap();
+    assert_eq!(preset, b"preset");
+
+    let mut project = Vec::new();
+    state_context
+        .save_with_context(
+            &mut instance.plugin_handle(),
+            &mut project,
+            StateContextType::Project,
+        )
+        .unwrap();
+    assert_eq!(project, [42]);
+
+    let contexts = [
+        StateContextType::Duplicate,
+        StateContextType::Preset,
+        StateContextType::Project,
+    ];
+
+    for context in contexts {
+        state_context
+            .load_with_context(&mut instance.plugin_handle(), &mut &[7u8][..], context)
+            .unwrap();
+    }
+
+    assert_eq!(*LOADED_CONTEXTS.lock().unwrap(), contexts);
+
+    // The seed was loaded from the duplicate and project states, but not from the preset.
+    let mut duplicate = Vec::new();
+    state_context
+        .save_with_context(
+            &mut instance.plugin_handle(),
+            &mut duplicate,
+            StateContextType::Duplicate,
+        )
+        .unwrap();
+    assert_eq!(duplicate, [7]);
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use clack_extensions::state::{PluginState, PluginStateImpl};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use clack_plugin::stream::{InputStream, OutputStream};
+use std::io::{Cursor, ErrorKind, Read, Write};
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.state";
+
+pub struct StatePluginAudioProcessor;
+pub struct StatePlugin;
+pub struct StatePluginMainThread {
+    data: Vec<u8>,
+}
+
+impl PluginMainThread<'_, ()> for StatePluginMainThread {}
+
+impl Plugin for StatePlugin {
+    type AudioProcessor<'a> = StatePluginAudioProcessor;
+    type Shared<'a> = ();
+    type MainThread<'a> = StatePluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginState>();
+    }
+}
+
+impl DefaultPluginFactory for StatePlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "State Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        // Large enough to require multiple reads and writes.
+        let data = (0..100_000u32).map(|i| i as u8).collect();
+        Ok(StatePluginMainThread { data })
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, (), StatePluginMainThread> for StatePluginAudioProcessor {
+    fn activate(
+        _host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut StatePluginMainThread,
+        _shared: &'a (),
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        unimplemented!()
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        unimplemented!()
+    }
+}
+
+impl PluginStateImpl for StatePluginMainThread {
+    fn save(&mut self, output: &mut OutputStream) -> Result<(), PluginError> {
+        for chunk in self.data.chunks(4096) {
+            output.write_all(chunk)?;
+        }
+
+        Ok(())
+    }
+
+    fn load(&mut self, input: &mut InputStream) -> Result<(), PluginError> {
+        let mut data = Vec::new();
+        input.read_to_end(&mut data)?;
+        self.data = data;
+
+        Ok(())
+    }
+}
+
+pub static STATE_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<StatePlugin>);
+
+/// A reader that gets interrupted before every successful read.
+struct InterruptingReader<R> {
+    inner: R,
+    interrupted: bool,
+}
+
+impl<R: Read> Read for InterruptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupted = !self.interrupted;
+
+        if self.interrupted {
+            Err(ErrorKind::Interrupted.into())
+        } else {
+            self.inner.read(buf)
+        }
+    }
+}
+
+/// A writer that fails after a given amount of bytes were written.
+struct FailingWriter {
+    remaining: usize,
+}
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(std::io::Error::new(ErrorKind::Other, "Disk full"));
+        }
+
+        let written = buf.len().min(self.remaining);
+        self.remaining -= written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+pub fn state_round_trips_through_streams() {
+    let bundle = common::load_bundle(&STATE_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+    let state: PluginState = instance.plugin_handle().get_extension().unwrap();
+
+    let mut saved = Vec::new();
+    state
+        .save(&mut instance.plugin_handle(), &mut saved)
+        .unwrap();
+    assert_eq!(saved.len(), 100_000);
+
+    // Load a different state, then read it back.
+    let other_state: Vec<u8> = saved.iter().rev().copied().collect();
+    state
+        .load(
+            &mut instance.plugin_handle(),
+            &mut Cursor::new(&other_state),
+        )
+        .unwrap();
+
+    let mut reloaded = Vec::new();
+    state
+        .save(&mut instance.plugin_handle(), &mut reloaded)
+        .unwrap();
+    assert_eq!(reloaded, other_state);
+
+    // The saved state can be loaded into a new instance.
+    let mut new_instance = common::instantiate(&bundle, PLUGIN_ID);
+    let state: PluginState = new_instance.plugin_handle().get_extension().unwrap();
+    let mut reader = InterruptingReader {
+        inner: Cursor::new(&reloaded),
+        interrupted: false,
+    };
+    state
+        .load(&mut new_instance.plugin_handle(), &mut reader)
+        .unwrap();
+
+    let mut copied = Vec::new();
+    state
+        .save(&mut new_instance.plugin_handle(), &mut copied)
+        .unwrap();
+    assert_eq!(copied, other_state);
+}
+
+#[test]
+pub fn stream_errors_are_reported_to_the_host() {
+    let bundle = common::load_bundle(&STATE_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+    let state: PluginState = instance.plugin_handle().get_extension().unwrap();
+
+    let mut writer = FailingWriter { remaining: 10_000 };
+    assert!(state
+        .save(&mut instance.plugin_handle(), &mut writer)
+        .is_err());
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use clack_extensions::surround::SurroundChannel::*;
+use clack_extensions::surround::{
+    PluginSurround, PluginSurroundImpl, SurroundChannel, SurroundChannelMapWriter,
+};
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+
+const LAYOUT_5_1: [SurroundChannel; 6] = [
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    LowFrequency,
+    BackLeft,
+    BackRight,
+];
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.surround";
+
+pub struct SurroundPlugin;
+pub struct SurroundPluginMainThread;
+
+impl PluginMainThread<'_, ()> for SurroundPluginMainThread {}
+
+impl Plugin for SurroundPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = SurroundPluginMainThread;
+
+    fn declare_extensions(builder: &mut PluginExtensions<Self>, _shared: Option<&()>) {
+        builder.register::<PluginSurround>();
+    }
+}
+
+impl DefaultPluginFactory for SurroundPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        use clack_plugin::plugin::features::*;
+
+        PluginDescriptor::new(PLUGIN_ID, "Surround Test Plugin").with_features([SURROUND])
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(SurroundPluginMainThread)
+    }
+}
+
+impl PluginSurroundImpl for SurroundPluginMainThread {
+    fn is_channel_mask_supported(&mut self, channel_mask: u64) -> bool {
+        channel_mask == SurroundChannel::mask(&LAYOUT_5_1)
+    }
+
+    fn channel_map(
+        &mut self,
+        is_input: bool,
+        port_index: u32,
+        writer: &mut SurroundChannelMapWriter,
+    ) {
+        if !is_input && port_index == 0 {
+            writer.write(&LAYOUT_5_1);
+        }
+    }
+}
+
+pub static SURROUND_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<SurroundPlugin>);
+
+#[test]
+pub fn can_read_channel_map() {
+    let bundle = common::load_bundle(&SURROUND_ENTRY);
+
+    let mut instance = common::instantiate(&bundle, PLUGIN_ID);
+
+    let surround: PluginSurround = instance
+        .plugin_shared_handle()
+        .get_extension()
+        .expect("Plugin should implement the Surround extension");
+
+    let channel_map = surround.channel_map(&mut instance.plugin_handle(), 0, false);
+    assert_eq!(channel_map, LAYOUT_5_1.map(Some));
+
+    assert!(surround
+        .channel_map(&mut instance.plugin_handle(), 0, true)
+        .is_empty());
+
+    assert!(surround.is_layout_supported(&mut instance.plugin_handle(), &LAYOUT_5_1));
+    assert!(!surround.is_layout_supported(&mut instance.plugin_handle(), &[FrontLeft, FrontRight]));
+}
+
+#[test]
+pub fn can_convert_channel_masks() {
+    assert_eq!(SurroundChannel::mask(&LAYOUT_5_1), 0b11_1111);
+    assert_eq!(TopBackRight.bit(), 1 << 17);
+
+    for raw in 0..18 {
+        assert_eq!(SurroundChannel::from_raw(raw).unwrap().to_raw(), raw);
+    }
+    assert_eq!(SurroundChannel::from_raw(18), None);
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use clack_extensions::thread_pool::{
+    HostThreadPool, HostThreadPoolImpl, PluginThreadPool, PluginThreadPoolImpl, ThreadPoolExecutor,
+};
+use clack_host::events::io::{EventBuffer, InputEvents};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+const TASK_COUNT: u32 = 10_000;
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.thread-pool";
+
+pub struct ThreadPoolPlugin;
+
+static SUM: AtomicU64 = AtomicU64::new(0);
+static EXECUTED_TASKS: AtomicU32 = AtomicU32::new(0);
+
+pub struct ThreadPoolPluginShared;
+
+impl PluginShared<'_> for ThreadPoolPluginShared {}
+
+impl PluginThreadPoolImpl for ThreadPoolPluginShared {
+    fn exec(&self, task_index: u32) {
+        SUM.fetch_add(task_index as u64, Ordering::Relaxed);
+        EXECUTED_TASKS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub struct ThreadPoolPluginMainThread;
+
+impl<'a> PluginMainThread<'a, ThreadPoolPluginShared> for ThreadPoolPluginMainThread {}
+
+pub struct ThreadPoolPluginAudioProcessor<'a> {
+    host: HostAudioProcessorHandle<'a>,
+}
+
+impl Plugin for ThreadPoolPlugin {
+    type AudioProcessor<'a> = ThreadPoolPluginAudioProcessor<'a>;
+    type Shared<'a> = ThreadPoolPluginShared;
+    type MainThread<'a> = ThreadPoolPluginMainThread;
+
+    fn declare_extensions(
+        builder: &mut PluginExtensions<Self>,
+        _shared: Option<&ThreadPoolPluginShared>,
+    ) {
+        builder.register::<PluginThreadPool>();
+    }
+}
+
+impl DefaultPluginFactory for ThreadPoolPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Thread Pool Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(ThreadPoolPluginShared)
+    }
+
+    fn new_main_thread<'a>(
+        _host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        Ok(ThreadPoolPluginMainThread)
+    }
+}
+
+impl<'a> PluginAudioProcessor<'a, ThreadPoolPluginShared, ThreadPoolPluginMainThread>
+    for ThreadPoolPluginAudioProcessor<'a>
+{
+    fn activate(
+        host: HostAudioProcessorHandle<'a>,
+        _main_thread: &mut ThreadPoolPluginMainThread,
+        _shared: &'a ThreadPoolPluginShared,
+        _audio_config: PluginAudioConfiguration,
+    ) -> Result<Self, PluginError> {
+        Ok(Self { host })
+    }
+
+    fn process(
+        &mut self,
+        _process: Process,
+        _audio: Audio,
+        _events: Events,
+    ) -> Result<ProcessStatus, PluginError> {
+        SUM.store(0, Ordering::Relaxed);
+        EXECUTED_TASKS.store(0, Ordering::Relaxed);
+
+        let thread_pool: HostThreadPool = self.host.get_extension().unwrap();
+        thread_pool.request_exec(&mut self.host, TASK_COUNT)?;
+
+        Ok(ProcessStatus::Continue)
+    }
+}
+
+pub static THREAD_POOL_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<ThreadPoolPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostMainThread<'a> {
+    plugin: Option<InitializedPluginHandle<'a>>,
+}
+
+impl<'a> MainThreadHandler<'a> for MyHostMainThread<'a> {
+    fn initialized(&mut self, instance: InitializedPluginHandle<'a>) {
+        self.plugin = Some(instance);
+    }
+}
+
+struct MyHostAudioProcessor<'a> {
+    executor: Arc<ThreadPoolExecutor>,
+    plugin: InitializedPluginHandle<'a>,
+    thread_pool: PluginThreadPool,
+}
+
+impl<'a> AudioProcessorHandler<'a> for MyHostAudioProcessor<'a> {}
+
+impl HostThreadPoolImpl for MyHostAudioProcessor<'_> {
+    fn request_exec(&mut self, task_count: u32) -> Result<(), HostError> {
+        self.plugin
+            .access(|plugin| self.executor.exec(plugin, self.thread_pool, task_count))
+            .ok_or(HostError::Message("Plugin was destroyed"))?
+            .map_err(|_| HostError::Message("Plugin does not support thread pool execution"))
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+
+    type MainThread<'a> = MyHostMainThread<'a>;
+    type AudioProcessor<'a> = MyHostAudioProcessor<'a>;
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostThreadPool>();
+    }
+}
+
+#[test]
+pub fn executor_runs_all_plugin_tasks() {
+    let bundle = common::load_bundle(&THREAD_POOL_ENTRY);
+
+    let mut instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread { plugin: None },
+    );
+
+    let executor = Arc::new(ThreadPoolExecutor::new(4));
+    assert_eq!(executor.worker_count(), 4);
+
+    let processor = instance
+        .activate(
+            |_, main_thread| {
+                let plugin = main_thread.plugin.clone().unwrap();
+                MyHostAudioProcessor {
+                    executor: executor.clone(),
+                    thread_pool: plugin.get_extension().unwrap(),
+                    plugin,
+                }
+            },
+            PluginAudioConfiguration {
+                sample_rate: 44_100.0,
+                min_frames_count: 1,
+                max_frames_count: 256,
+            },
+        )
+        .unwrap();
+
+    let mut processor = processor.start_processing().unwrap();
+    let mut output_events = EventBuffer::new();
+
+    let expected_sum = (0..TASK_COUNT as u64).sum::<u64>();
+
+    for _ in 0..100 {
+        processor
+            .process(
+                &InputAudioBuffers::empty(),
+                &mut OutputAudioBuffers::empty(),
+                &InputEvents::empty(),
+                &mut output_events.as_output(),
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(EXECUTED_TASKS.load(Ordering::Relaxed), TASK_COUNT);
+        assert_eq!(SUM.load(Ordering::Relaxed), expected_sum);
+    }
+
+    instance.deactivate(processor.stop_processing());
+}
@@ -0,0 +1,121 @@
+#![cfg(debug_assertions)]
+
+mod common;
+
+use clack_extensions::latency::{HostLatency, HostLatencyImpl};
+use clack_host::prelude::*;
+use clack_plugin::clack_entry;
+use clack_plugin::prelude::*;
+use clap_sys::ext::latency::{clap_host_latency, CLAP_EXT_LATENCY};
+use clap_sys::host::clap_host;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// The raw host pointer the plugin received, so the test can misbehave on its behalf.
+static RAW_HOST: AtomicPtr<clap_host> = AtomicPtr::new(core::ptr::null_mut());
+
+const PLUGIN_ID: &str = "org.rust-audio.clack.tests.wrong-thread";
+
+pub struct WrongThreadPlugin;
+
+impl Plugin for WrongThreadPlugin {
+    type AudioProcessor<'a> = ();
+    type Shared<'a> = ();
+    type MainThread<'a> = ();
+}
+
+impl DefaultPluginFactory for WrongThreadPlugin {
+    fn get_descriptor() -> PluginDescriptor {
+        PluginDescriptor::new(PLUGIN_ID, "Wrong Thread Test Plugin")
+    }
+
+    fn new_shared(_host: HostSharedHandle<'_>) -> Result<Self::Shared<'_>, PluginError> {
+        Ok(())
+    }
+
+    fn new_main_thread<'a>(
+        host: HostMainThreadHandle<'a>,
+        _shared: &'a Self::Shared<'a>,
+    ) -> Result<Self::MainThread<'a>, PluginError> {
+        RAW_HOST.store(host.as_raw() as *const _ as *mut _, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+pub static WRONG_THREAD_ENTRY: EntryDescriptor = clack_entry!(SinglePluginEntry<WrongThreadPlugin>);
+
+struct MyHostShared;
+impl SharedHandler<'_> for MyHostShared {
+    fn request_restart(&self) {
+        unimplemented!()
+    }
+    fn request_process(&self) {
+        unimplemented!()
+    }
+    fn request_callback(&self) {
+        unimplemented!()
+    }
+}
+
+struct MyHostMainThread {
+    latency_changed_calls: u32,
+}
+
+impl MainThreadHandler<'_> for MyHostMainThread {}
+
+impl HostLatencyImpl for MyHostMainThread {
+    fn changed(&mut self) {
+        self.latency_changed_calls += 1;
+    }
+}
+
+struct MyHost;
+impl HostHandlers for MyHost {
+    type Shared<'a> = MyHostShared;
+    type MainThread<'a> = MyHostMainThread;
+    type AudioProcessor<'a> = ();
+
+    fn declare_extensions(builder: &mut HostExtensions<Self>, _shared: &Self::Shared<'_>) {
+        builder.register::<HostLatency>();
+    }
+}
+
+/// Calls the host's latency `changed` callback, like a plugin would.
+fn call_latency_changed(host: usize) {
+    let host = host as *const clap_host;
+
+    // SAFETY: the host pointer is valid as long as the plugin instance is alive.
+    unsafe {
+        let get_extension = (*host).get_extension.unwrap();
+        let latency = get_extension(host, CLAP_EXT_LATENCY.as_ptr()) as *const clap_host_latency;
+        assert!(!latency.is_null());
+
+        (*latency).changed.unwrap()(host);
+    }
+}
+
+#[test]
+pub fn main_thread_callbacks_are_rejected_from_other_threads() {
+    let bundle = common::load_bundle(&WRONG_THREAD_ENTRY);
+
+    let instance = common::instantiate_with::<MyHost, _, _>(
+        &bundle,
+        PLUGIN_ID,
+        |_| MyHostShared,
+        |_| MyHostMainThread {
+            latency_changed_calls: 0,
+        },
+    );
+
+    let host = RAW_HOST.load(Ordering::SeqCst) as usize;
+    assert_ne!(host, 0);
+
+    // Called from the main thread: this is fine.
+    call_latency_changed(host);
+    assert_eq!(instance.access_handler(|h| h.latency_changed_calls), 1);
+
+    // Called from another thread: the callback is rejected, and the handler isn't touched.
+    std::thread::spawn(move || call_latency_changed(host))
+        .join()
+        .unwrap();
+    assert_eq!(instance.access_handler(|h| h.latency_changed_calls), 1);
+}
@@ -138,14 +138,12 @@ impl PluginAudioPortsImpl for GainPluginMainThread<'_> {
 
     fn get(&mut self, index: u32, _is_input: bool, writer: &mut AudioPortInfoWriter) {
         if index == 0 {
-            writer.set(&AudioPortInfo {
-                id: ClapId::new(0),
-                name: b"main",
-                channel_count: 2,
-                flags: AudioPortFlags::IS_MAIN,
-                port_type: Some(AudioPortType::STEREO),
-                in_place_pair: None,
-            });
+            writer.set(
+                &AudioPortInfo::new(ClapId::new(0), b"main")
+                    .with_channels(2)
+                    .with_type(AudioPortType::STEREO)
+                    .main(),
+            );
         }
     }
 }
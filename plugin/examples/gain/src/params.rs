@@ -95,7 +95,7 @@ impl PluginMainThreadParams for GainPluginMainThread<'_> {
             return;
         }
         info.set(&ParamInfo {
-            id: 1.into(),
+            id: ClapId::new(1),
             flags: ParamInfoFlags::IS_AUTOMATABLE,
             cookie: Default::default(),
             name: b"Volume",
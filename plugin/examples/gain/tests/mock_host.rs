@@ -0,0 +1,62 @@
+use clack_host::events::event_types::ParamValueEvent;
+use clack_host::prelude::*;
+use clack_host::test_util::MockPlugin;
+use clack_host::utils::Cookie;
+use std::ffi::CStr;
+
+use clack_plugin_gain::clap_entry;
+
+#[test]
+pub fn applies_gain_on_mock_host() {
+    // SAFETY: only called this once here
+    let bundle = unsafe { PluginBundle::load_from_raw(&clap_entry, "") }.unwrap();
+    let plugin_id = CStr::from_bytes_with_nul(b"org.rust-audio.clack.gain\0").unwrap();
+
+    let mut plugin = MockPlugin::new(&bundle, plugin_id).unwrap();
+    assert!(plugin.main_thread().is_initialized());
+
+    let input_events = [ParamValueEvent::new(
+        0,
+        ClapId::new(1),
+        Pckn::match_all(),
+        0.5,
+        Cookie::empty(),
+    )];
+
+    // Processing is not possible before activation
+    assert_eq!(
+        plugin.process(
+            &mut [[0f32; 32]],
+            &mut [[0f32; 32]],
+            &InputEvents::from_buffer(&input_events)
+        ),
+        Err(PluginInstanceError::DeactivatedPlugin)
+    );
+
+    plugin
+        .activate(PluginAudioConfiguration {
+            sample_rate: 44_100.0,
+            min_frames_count: 32,
+            max_frames_count: 32,
+        })
+        .unwrap();
+    assert!(plugin.is_active());
+
+    let mut inputs = [[69f32; 32], [42f32; 32]];
+    let mut outputs = [[0f32; 32], [0f32; 32]];
+
+    plugin
+        .process(
+            &mut inputs,
+            &mut outputs,
+            &InputEvents::from_buffer(&input_events),
+        )
+        .unwrap();
+
+    assert_eq!(outputs, [[69f32 * 0.5; 32], [42f32 * 0.5; 32]]);
+
+    assert_eq!(plugin.shared().restart_requests(), 0);
+
+    plugin.deactivate();
+    assert!(!plugin.is_active());
+}
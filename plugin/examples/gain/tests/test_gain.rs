@@ -1,8 +1,9 @@
-use clack_extensions::audio_ports::{AudioPortInfoBuffer, PluginAudioPorts};
-use clack_host::events::event_types::ParamValueEvent;
+use clack_extensions::audio_ports::{
+    AudioPortFlags, AudioPortInfoBuffer, AudioPortType, PluginAudioPorts,
+};
 use clack_host::factory::PluginFactory;
 use clack_host::prelude::*;
-use clack_host::utils::Cookie;
+use std::ffi::CStr;
 
 use clack_plugin_gain::clap_entry;
 
@@ -11,7 +12,7 @@ pub fn it_works() {
     // Initialize host
     //let mut host = TestHost::instantiate(&clap_entry);
     // Initialize host with basic info
-    let info = HostInfo::new("test", "", "", "").unwrap();
+    let info = HostInfo::try_new("Clack Test Host", "Clack", "", "0.1.0").unwrap();
 
     // Get plugin entry from the exported static
     // SAFETY: only called this once here
@@ -85,13 +86,7 @@ pub fn it_works() {
     let mut input_events = EventBuffer::with_capacity(10);
     let mut output_events = EventBuffer::with_capacity(10);
 
-    input_events.push(&ParamValueEvent::new(
-        0,
-        ClapId::new(1),
-        Pckn::match_all(),
-        0.5,
-        Cookie::empty(),
-    ));
+    input_events.push_param_value(0, ClapId::new(1), 0.5);
 
     let mut input_buffers = [vec![69f32; 32], vec![69f32; 32]];
     let mut output_buffers = [vec![0f32; 32], vec![0f32; 32]];
@@ -138,6 +133,44 @@ pub fn it_works() {
     plugin.deactivate(processor.stop_processing());
 }
 
+#[test]
+pub fn audio_ports_layout_can_be_read() {
+    let info = HostInfo::try_new("Clack Test Host", "Clack", "", "0.1.0").unwrap();
+    // SAFETY: only called this once here
+    let bundle = unsafe { PluginBundle::load_from_raw(&clap_entry, "") }.unwrap();
+
+    let mut plugin = PluginInstance::<TestHostHandlers>::new(
+        |_| TestHostShared,
+        |_| TestHostMainThread,
+        &bundle,
+        CStr::from_bytes_with_nul(b"org.rust-audio.clack.gain\0").unwrap(),
+        &info,
+    )
+    .unwrap();
+
+    let mut plugin_main_thread = plugin.plugin_handle();
+    let ports_ext = plugin_main_thread
+        .get_extension::<PluginAudioPorts>()
+        .unwrap();
+
+    let layout = ports_ext.layout(&mut plugin_main_thread);
+    assert_eq!(layout.inputs.len(), 1);
+    assert_eq!(layout.outputs.len(), 1);
+
+    for port in [&layout.inputs[0], &layout.outputs[0]] {
+        assert_eq!(port.id, 0);
+        assert_eq!(port.name, "main");
+        assert_eq!(port.channel_count, 2);
+        assert_eq!(port.flags, AudioPortFlags::IS_MAIN);
+        assert_eq!(port.port_type(), Some(AudioPortType::STEREO));
+        assert_eq!(port.in_place_pair, None);
+    }
+
+    assert_eq!(layout.main_input_index(), Some(0));
+    assert_eq!(layout.main_output(), Some(&layout.outputs[0]));
+    assert_eq!(layout.channel_layout(), AudioPortsLayout::stereo());
+}
+
 struct TestHostMainThread;
 struct TestHostShared;
 struct TestHostAudioProcessor;
@@ -160,6 +160,10 @@ impl PolySynthAudioProcessor<'_> {
         match event.as_core_event() {
             Some(CoreEventSpace::NoteOn(event)) => self.poly_osc.handle_note_on(event),
             Some(CoreEventSpace::NoteOff(event)) => self.poly_osc.handle_note_off(event),
+            Some(CoreEventSpace::Midi2(event)) => self.poly_osc.handle_midi2(event),
+            Some(CoreEventSpace::NoteExpression(event)) => {
+                self.poly_osc.handle_note_expression(event)
+            }
             Some(CoreEventSpace::ParamValue(event)) => {
                 // This is a global modulation event
                 if event.pckn().matches_all() {
@@ -192,14 +196,12 @@ impl PluginAudioPortsImpl for PolySynthPluginMainThread<'_> {
 
     fn get(&mut self, index: u32, is_input: bool, writer: &mut AudioPortInfoWriter) {
         if !is_input && index == 0 {
-            writer.set(&AudioPortInfo {
-                id: ClapId::new(1),
-                name: b"main",
-                channel_count: 1,
-                flags: AudioPortFlags::IS_MAIN,
-                port_type: Some(AudioPortType::MONO),
-                in_place_pair: None,
-            });
+            writer.set(
+                &AudioPortInfo::new(ClapId::new(1), b"main")
+                    .with_channels(1)
+                    .with_type(AudioPortType::MONO)
+                    .main(),
+            );
         }
     }
 }
@@ -219,7 +221,7 @@ impl PluginNotePortsImpl for PolySynthPluginMainThread<'_> {
                 id: ClapId::new(1),
                 name: b"main",
                 preferred_dialect: Some(NoteDialect::Clap),
-                supported_dialects: NoteDialects::CLAP,
+                supported_dialects: NoteDialects::CLAP | NoteDialects::MIDI2,
             })
         }
     }
@@ -40,7 +40,16 @@ impl SquareOscillator {
     /// For setting the frequency directly, see [`SquareOscillator::set_frequency`].
     #[inline]
     pub fn set_note_number(&mut self, new_note_number: u8) {
-        self.set_frequency(440.0 * 2.0f32.powf((new_note_number as f32 - 69.0) / 12.0));
+        self.set_pitch(new_note_number as f32);
+    }
+
+    /// Sets the current frequency of this oscillator using a fractional MIDI note number.
+    ///
+    /// This allows to tune the oscillator in between notes, e.g. `60.5` is a quarter tone above
+    /// Middle C.
+    #[inline]
+    pub fn set_pitch(&mut self, new_pitch: f32) {
+        self.set_frequency(440.0 * 2.0f32.powf((new_pitch - 69.0) / 12.0));
     }
 
     /// Sets the new frequency of this oscillator.
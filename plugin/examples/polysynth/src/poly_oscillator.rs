@@ -3,9 +3,10 @@
 use crate::oscillator::SquareOscillator;
 use crate::params::PARAM_VOLUME_ID;
 use clack_plugin::events::event_types::{
-    NoteOffEvent, NoteOnEvent, ParamModEvent, ParamValueEvent,
+    Midi2Event, NoteExpressionEvent, NoteExpressionType, NoteOffEvent, NoteOnEvent, ParamModEvent,
+    ParamValueEvent,
 };
-use clack_plugin::events::Match;
+use clack_plugin::events::{Match, Pckn};
 
 /// A voice in the polyphonic oscillator.
 ///
@@ -33,24 +34,28 @@ struct Voice {
     /// The voice-specific modulation amount of the volume parameter.
     /// This is None if the host didn't apply polyphonic modulation to this voice.
     volume_mod: Option<f32>,
+
+    /// The tuning of this voice relative to its key, in semitones.
+    /// This is set by the host through the Tuning note expression.
+    tuning: f32,
 }
 
 impl Voice {
-    /// Returns whether this voice matches the given matchers.
+    /// Returns the PCKN tuple of the note this voice is playing.
+    ///
+    /// All voices play on our single note port (port 0). If the host didn't assign an ID to the
+    /// note, the voice matches any note ID.
     #[inline]
-    fn matches(&self, channel: Match<u16>, note_key: Match<u16>, note_id: Match<u32>) -> bool {
-        if !channel.matches(self.channel) {
-            return false;
-        }
-
-        if !note_key.matches(self.key_number) {
-            return false;
-        }
-
-        note_id.matches(match self.note_id {
-            None => Match::All,
-            Some(id) => Match::Specific(id),
-        })
+    fn pckn(&self) -> Pckn {
+        Pckn::new(
+            0u16,
+            self.channel,
+            self.key_number,
+            match self.note_id {
+                None => Match::All,
+                Some(id) => Match::Specific(id),
+            },
+        )
     }
 }
 
@@ -81,6 +86,7 @@ impl PolyOscillator {
                     note_id: None,
                     volume: None,
                     volume_mod: None,
+                    tuning: 0.0,
                 };
                 voice_count
             ]
@@ -103,18 +109,19 @@ impl PolyOscillator {
         available_voice.channel = channel;
         available_voice.key_number = new_note_key;
         available_voice.note_id = note_id;
+        available_voice.tuning = 0.0;
 
         self.active_voice_count += 1;
     }
 
-    /// Stops all voices that match the given MIDI note key and note ID matcher.
+    /// Stops all voices that match the given PCKN tuple.
     ///
     /// If no matching voice is found, this does nothing.
-    fn stop_voices(&mut self, channel: Match<u16>, note_key: Match<u16>, note_id: Match<u32>) {
+    fn stop_voices(&mut self, pckn: Pckn) {
         while let Some(voice_index) = self
             .active_voice_buffer()
             .iter()
-            .position(|v| v.matches(channel, note_key, note_id))
+            .position(|v| pckn.matches(&v.pckn()))
         {
             // Swap the targeted voice with the last one.
             self.voice_buffer
@@ -144,19 +151,52 @@ impl PolyOscillator {
 
     /// Handles the given Note Off input event.
     pub fn handle_note_off(&mut self, event: &NoteOffEvent) {
-        if !event.port_index().matches(0u16) {
+        self.stop_voices(event.pckn())
+    }
+
+    /// Handles the given MIDI 2.0 input event.
+    ///
+    /// Only the MIDI 2.0 Note On and Note Off channel voice messages are supported, all other
+    /// messages are ignored.
+    pub fn handle_midi2(&mut self, event: &Midi2Event) {
+        // 0x4 is the message type of MIDI 2.0 channel voice messages.
+        if event.port_index() != 0 || event.message_type() != 0x4 {
             return;
         }
 
-        self.stop_voices(event.channel(), event.key(), event.note_id())
+        // For Note On and Note Off messages, the note number is in bits 14 to 8.
+        let key = ((event.data()[0] >> 8) & 0x7F) as u8;
+
+        // The opcode is the high nibble of the status byte.
+        match event.status() >> 4 {
+            0x9 => self.start_new_voice(event.channel(), key, None),
+            0x8 => self.stop_voices(Pckn::new(0u16, event.channel(), key, Match::All)),
+            _ => {}
+        }
     }
 
-    /// Handles the given polyphonic Parameter Value event.
-    pub fn handle_param_value(&mut self, event: &ParamValueEvent) {
-        if !event.port_index().matches(0u16) {
+    /// Handles the given Note Expression event.
+    ///
+    /// Only the Tuning expression is supported, all other expressions are ignored.
+    pub fn handle_note_expression(&mut self, event: &NoteExpressionEvent) {
+        if event.expression_type() != Some(NoteExpressionType::Tuning) {
             return;
         }
 
+        for voice in self
+            .active_voice_buffer_mut()
+            .iter_mut()
+            .filter(|v| event.pckn().matches(&v.pckn()))
+        {
+            voice.tuning = event.value() as f32;
+            voice
+                .oscillator
+                .set_pitch(voice.key_number as f32 + voice.tuning);
+        }
+    }
+
+    /// Handles the given polyphonic Parameter Value event.
+    pub fn handle_param_value(&mut self, event: &ParamValueEvent) {
         if event.param_id() != PARAM_VOLUME_ID {
             return;
         }
@@ -164,7 +204,7 @@ impl PolyOscillator {
         for voice in self
             .active_voice_buffer_mut()
             .iter_mut()
-            .filter(|v| v.matches(event.channel(), event.key(), event.note_id()))
+            .filter(|v| event.pckn().matches(&v.pckn()))
         {
             voice.volume = Some(event.value() as f32);
         }
@@ -172,10 +212,6 @@ impl PolyOscillator {
 
     /// Handles the given polyphonic Parameter Modulation event.
     pub fn handle_param_mod(&mut self, event: &ParamModEvent) {
-        if !event.port_index().matches(0u16) {
-            return;
-        }
-
         if event.param_id() != PARAM_VOLUME_ID {
             return;
         }
@@ -183,7 +219,7 @@ impl PolyOscillator {
         for voice in self
             .active_voice_buffer_mut()
             .iter_mut()
-            .filter(|v| v.matches(event.channel(), event.key(), event.note_id()))
+            .filter(|v| event.pckn().matches(&v.pckn()))
         {
             voice.volume_mod = Some(event.amount() as f32);
         }
@@ -227,3 +263,33 @@ impl PolyOscillator {
         &mut self.voice_buffer[..self.active_voice_count]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tuning_expression_only_applies_to_matching_voices() {
+        let mut poly_osc = PolyOscillator::new(4, 44_100.0);
+        poly_osc.start_new_voice(0, 60, Some(1));
+        poly_osc.start_new_voice(0, 64, Some(2));
+
+        let target = Pckn::new(0u16, 0u16, 64u16, 2u32);
+        poly_osc.handle_note_expression(&NoteExpressionEvent::tuning(0, target, 0.5));
+
+        // Other expressions are ignored.
+        poly_osc.handle_note_expression(&NoteExpressionEvent::pressure(0, Pckn::match_all(), 1.0));
+
+        let tunings: Vec<_> = poly_osc
+            .active_voice_buffer()
+            .iter()
+            .map(|v| v.tuning)
+            .collect();
+        assert_eq!(tunings, [0.0, 0.5]);
+
+        // Restarting a voice resets its tuning.
+        poly_osc.stop_voices(target);
+        poly_osc.start_new_voice(0, 64, Some(3));
+        assert_eq!(poly_osc.active_voice_buffer()[1].tuning, 0.0);
+    }
+}
@@ -16,7 +16,8 @@
 //!
 //! See the [`Entry`] trait documentation for information and examples on how to implement your own
 //! entry type, or see the provided [`SinglePluginEntry`] convenience type if you only need to
-//! expose a single plugin type to the host.
+//! expose a single plugin type to the host. Bundles that also ship built-in presets can use the
+//! [`PluginWithPresetsEntry`] type to expose a preset-discovery factory alongside that plugin.
 
 use crate::extensions::wrapper::handle_panic;
 use crate::factory::Factory;
@@ -30,8 +31,10 @@ use std::sync::Mutex;
 pub use clack_common::entry::*;
 
 mod single;
+mod with_presets;
 
 pub use single::{DefaultPluginFactory, SinglePluginEntry};
+pub use with_presets::PluginWithPresetsEntry;
 
 /// A prelude that's helpful for implementing custom [`Entry`] and [`PluginFactory`](crate::factory::plugin::PluginFactory) types.
 pub mod prelude {
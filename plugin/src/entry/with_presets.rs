@@ -0,0 +1,167 @@
+#![deny(unsafe_code)]
+
+use crate::entry::prelude::*;
+use crate::entry::DefaultPluginFactory;
+use std::ffi::CStr;
+
+/// An [`Entry`] that exposes a single plugin type alongside a preset-discovery factory.
+///
+/// This works just like [`SinglePluginEntry`], but also registers the given `PD` factory, allowing
+/// plugins that ship with built-in presets to expose them to the host without having to write a
+/// full custom entry.
+///
+/// Clack does not provide a safe preset-discovery factory implementation yet, so `PD` can be any
+/// [`Factory`] type, which is instantiated using its [`Default`] implementation when the entry
+/// is loaded.
+///
+/// # Example
+///
+/// ```
+/// use clack_plugin::entry::{DefaultPluginFactory, PluginWithPresetsEntry};
+/// use clack_plugin::factory::Factory;
+/// use clack_plugin::prelude::*;
+/// use clap_sys::factory::draft::preset_discovery::*;
+/// use std::ffi::CStr;
+///
+/// pub struct MyPlugin;
+///
+/// impl Plugin for MyPlugin {
+///     type AudioProcessor<'a> = ();
+///     type Shared<'a> = ();
+///     type MainThread<'a> = ();
+/// }
+///
+/// impl DefaultPluginFactory for MyPlugin {
+///     fn get_descriptor() -> PluginDescriptor {
+///         PluginDescriptor::new("my.plugin", "My Plugin")
+///     }
+///
+///     fn new_shared<'a>(
+///         _host: HostSharedHandle<'a>
+///     ) -> Result<Self::Shared<'a>, PluginError> {
+///         Ok(())
+///     }
+///
+///     fn new_main_thread<'a>(
+///         host: HostMainThreadHandle<'a>,
+///         shared: &'a Self::Shared<'a>
+///     ) -> Result<Self::MainThread<'a>, PluginError> {
+///         Ok(())
+///     }
+/// }
+///
+/// #[repr(C)]
+/// pub struct MyPresetDiscoveryFactory(clap_preset_discovery_factory);
+///
+/// unsafe impl Factory for MyPresetDiscoveryFactory {
+///     const IDENTIFIER: &'static CStr = CLAP_PRESET_DISCOVERY_FACTORY_ID;
+/// }
+///
+/// impl Default for MyPresetDiscoveryFactory {
+///     fn default() -> Self {
+///         Self(clap_preset_discovery_factory {
+///             /* Your preset discovery provider implementation */
+/// #           count: None,
+/// #           get_descriptor: None,
+/// #           create: None,
+///         })
+///     }
+/// }
+///
+/// clack_export_entry!(PluginWithPresetsEntry::<MyPlugin, MyPresetDiscoveryFactory>);
+/// ```
+pub struct PluginWithPresetsEntry<P: DefaultPluginFactory, PD> {
+    plugin_entry: SinglePluginEntry<P>,
+    preset_discovery_factory: PD,
+}
+
+impl<P, PD> Entry for PluginWithPresetsEntry<P, PD>
+where
+    P: DefaultPluginFactory,
+    PD: Factory + Default + Send + Sync + 'static,
+{
+    fn new(bundle_path: &CStr) -> Result<Self, EntryLoadError> {
+        Ok(Self {
+            plugin_entry: SinglePluginEntry::new(bundle_path)?,
+            preset_discovery_factory: PD::default(),
+        })
+    }
+
+    #[inline]
+    fn declare_factories<'a>(&'a self, builder: &mut EntryFactories<'a>) {
+        self.plugin_entry.declare_factories(builder);
+        builder.register_factory(&self.preset_discovery_factory);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use clap_sys::factory::draft::preset_discovery::*;
+    use clap_sys::factory::plugin_factory::CLAP_PLUGIN_FACTORY_ID;
+
+    struct MyPlugin;
+
+    impl Plugin for MyPlugin {
+        type AudioProcessor<'a> = ();
+        type Shared<'a> = ();
+        type MainThread<'a> = ();
+    }
+
+    impl DefaultPluginFactory for MyPlugin {
+        fn get_descriptor() -> PluginDescriptor {
+            PluginDescriptor::new("my.plugin", "My Plugin")
+        }
+
+        fn new_shared(_host: HostSharedHandle<'_>) -> Result<(), PluginError> {
+            Ok(())
+        }
+
+        fn new_main_thread<'a>(
+            _host: HostMainThreadHandle<'a>,
+            _shared: &'a (),
+        ) -> Result<(), PluginError> {
+            Ok(())
+        }
+    }
+
+    #[repr(C)]
+    struct MyPresetDiscoveryFactory(clap_preset_discovery_factory);
+
+    #[allow(unsafe_code)]
+    // SAFETY: This type is repr(C) and wraps the matching CLAP factory struct.
+    unsafe impl Factory for MyPresetDiscoveryFactory {
+        const IDENTIFIER: &'static CStr = CLAP_PRESET_DISCOVERY_FACTORY_ID;
+    }
+
+    impl Default for MyPresetDiscoveryFactory {
+        fn default() -> Self {
+            Self(clap_preset_discovery_factory {
+                count: None,
+                get_descriptor: None,
+                create: None,
+            })
+        }
+    }
+
+    fn find_factory(entry: &impl Entry, identifier: &CStr) -> bool {
+        let mut builder = EntryFactories::new(identifier);
+        entry.declare_factories(&mut builder);
+        !builder.found().is_null()
+    }
+
+    #[test]
+    fn registers_both_factories() {
+        let bundle_path = CStr::from_bytes_with_nul(b"/my/plugin.clap\0").unwrap();
+        let entry =
+            PluginWithPresetsEntry::<MyPlugin, MyPresetDiscoveryFactory>::new(bundle_path).unwrap();
+
+        assert!(find_factory(&entry, CLAP_PLUGIN_FACTORY_ID));
+        assert!(find_factory(&entry, CLAP_PRESET_DISCOVERY_FACTORY_ID));
+        assert!(!find_factory(
+            &entry,
+            CStr::from_bytes_with_nul(b"unknown.factory\0").unwrap()
+        ));
+    }
+}
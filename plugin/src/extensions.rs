@@ -83,7 +83,9 @@
 //!
 //!
 
+use crate::extensions::wrapper::PluginWrapperError;
 use crate::plugin::Plugin;
+use clap_sys::ext::log::CLAP_LOG_WARNING;
 use core::ffi::c_void;
 use std::ffi::CStr;
 use std::marker::PhantomData;
@@ -98,9 +100,14 @@ pub use clack_common::extensions::*;
 /// Plugins can declare the different extensions they support by using the
 /// [`register`](PluginExtensions::register) method on this struct, during a call to
 /// [`declare_extensions`](Plugin::declare_extensions).
+///
+/// Extensions are matched in registration order: if the same extension is registered more than
+/// once, only the first registration is used. Because extensions are only declared when the host
+/// queries them, such duplicates are only detected once the host queries that extension.
 pub struct PluginExtensions<'a, P: ?Sized> {
     found: Option<NonNull<c_void>>,
     requested: &'a CStr,
+    has_duplicate: bool,
     plugin_type: PhantomData<P>,
 }
 
@@ -110,6 +117,7 @@ impl<'a, P: Plugin> PluginExtensions<'a, P> {
         Self {
             found: None,
             requested,
+            has_duplicate: false,
             plugin_type: PhantomData,
         }
     }
@@ -121,18 +129,53 @@ impl<'a, P: Plugin> PluginExtensions<'a, P> {
             .unwrap_or(core::ptr::null_mut())
     }
 
+    /// Returns an error if the requested extension was registered more than once.
+    #[inline]
+    pub(crate) fn check_duplicates(&self) -> Result<(), PluginWrapperError> {
+        if !self.has_duplicate {
+            return Ok(());
+        }
+
+        Err(PluginWrapperError::Error(
+            CLAP_LOG_WARNING,
+            format!(
+                "Extension {:?} was registered more than once. Only the first registration is used.",
+                self.requested
+            )
+            .into(),
+        ))
+    }
+
     /// Adds a given extension implementation to the list of extensions this plugin supports.
+    ///
+    /// If an implementation of the same extension was already registered, the first one is kept
+    /// and this registration is ignored. In release builds, this is reported to the host's log.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if an implementation of the same extension was already
+    /// registered, and the host is querying that extension.
     pub fn register<E: ExtensionImplementation<P, ExtensionSide = PluginExtensionSide>>(
         &mut self,
     ) -> &mut Self {
-        if self.found.is_some() {
+        if E::IDENTIFIER != self.requested {
             return self;
         }
 
-        if E::IDENTIFIER == self.requested {
-            self.found = Some(E::IMPLEMENTATION.as_ptr())
+        if self.found.is_some() {
+            self.has_duplicate = true;
+
+            if cfg!(debug_assertions) {
+                panic!(
+                    "Extension {:?} was registered more than once. Only the first registration is used.",
+                    self.requested
+                );
+            }
+
+            return self;
         }
 
+        self.found = Some(E::IMPLEMENTATION.as_ptr());
         self
     }
 }
@@ -144,8 +187,8 @@ pub mod prelude {
     pub use crate::{
         extensions::wrapper::{PluginWrapper, PluginWrapperError},
         extensions::{
-            Extension, ExtensionImplementation, HostExtensionSide, PluginExtensionSide,
-            RawExtension, RawExtensionImplementation,
+            clack_extension, Extension, ExtensionImplementation, HostExtensionSide,
+            PluginExtensionSide, RawExtension, RawExtensionImplementation,
         },
         host::{HostAudioProcessorHandle, HostMainThreadHandle, HostSharedHandle},
         plugin::{Plugin, PluginError},
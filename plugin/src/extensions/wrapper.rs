@@ -5,6 +5,8 @@
 
 use crate::host::HostSharedHandle;
 use crate::internal_utils::UnsafeOptionCell;
+#[cfg(not(test))]
+use crate::panic::{panic_policy, PanicPolicy};
 use crate::plugin::{logging, Plugin, PluginAudioProcessor, PluginBoxInner, PluginError};
 use crate::process::PluginAudioConfiguration;
 use clap_sys::ext::log::*;
@@ -13,12 +15,24 @@ use std::cell::UnsafeCell;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::panic::AssertUnwindSafe;
+#[cfg(not(test))]
+use std::panic::UnwindSafe;
 use std::pin::Pin;
 use std::ptr::NonNull;
 
+/// Catches any panic occurring in the given closure, unless the [`PanicPolicy::Abort`] policy is
+/// set, in which case the process is aborted instead.
 #[cfg(not(test))]
 #[allow(unused)]
-pub(crate) use std::panic::catch_unwind as handle_panic;
+pub(crate) fn handle_panic<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> std::thread::Result<R> {
+    let result = std::panic::catch_unwind(f);
+
+    if result.is_err() && panic_policy() == PanicPolicy::Abort {
+        std::process::abort();
+    }
+
+    result
+}
 
 #[cfg(test)]
 #[inline]
@@ -160,7 +174,9 @@ impl<'a, P: Plugin> PluginWrapper<'a, P> {
     ///
     /// * The given `clap_plugin` pointer is null-checked, as well as some other host-provided
     ///   pointers;
-    /// * The handler is wrapped in [`std::panic::catch_unwind`];
+    /// * The handler is wrapped in [`std::panic::catch_unwind`] (unless the
+    ///   [`PanicPolicy::Abort`](crate::panic::PanicPolicy::Abort) policy is set, in which case
+    ///   panics abort the process);
     /// * Any [`PluginWrapperError`] returned by the handler is caught.
     ///
     /// If any of the above safety check fails, an error message is logged (using the standard CLAP
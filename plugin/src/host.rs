@@ -114,12 +114,32 @@ impl<'a> HostInfo<'a> {
         }
     }
 
-    /// Returns a raw, C FFI-compatible reference to the host handle.
+    /// Returns a shared reference to the raw, C-FFI compatible host struct.
+    ///
+    /// This type enforces that the reference is valid for the lifetime of the plugin instance
+    /// (`'a`).
+    ///
+    /// If you need to access the raw pointer without dereferencing it first, use
+    /// [`as_raw_ptr`](Self::as_raw_ptr) instead.
     #[inline]
     pub fn as_raw(&self) -> &'a clap_host {
         // SAFETY: this type ensures the raw pointer is valid
         unsafe { self.raw.as_ref() }
     }
+
+    /// Returns a raw pointer to the raw, C-FFI compatible host struct, without dereferencing it.
+    ///
+    /// This is an escape hatch for interoperating with C code, e.g. to implement CLAP extensions
+    /// that aren't covered by Clack yet. The pointer remains valid for the lifetime of the plugin
+    /// instance (`'a`), but what is done with it is not checked by this handle: the caller is
+    /// responsible for upholding the threading requirements of the CLAP specification.
+    ///
+    /// If you need to safely access the host struct through a shared reference,
+    /// use [`as_raw`](Self::as_raw) instead.
+    #[inline]
+    pub fn as_raw_ptr(&self) -> *const clap_host {
+        self.raw.as_ptr()
+    }
 }
 
 /// A thread-safe handle to the host.
@@ -148,13 +168,33 @@ impl<'a> HostSharedHandle<'a> {
         }
     }
 
-    /// Returns a raw, C FFI-compatible reference to the host handle.
+    /// Returns a shared reference to the raw, C-FFI compatible host struct.
+    ///
+    /// This type enforces that the reference is valid for the lifetime of the plugin instance
+    /// (`'a`).
+    ///
+    /// If you need to access the raw pointer without dereferencing it first, use
+    /// [`as_raw_ptr`](Self::as_raw_ptr) instead.
     #[inline]
     pub fn as_raw(&self) -> &'a clap_host {
         // SAFETY: this type enforces the pointer is valid for 'a
         unsafe { self.raw.as_ref() }
     }
 
+    /// Returns a raw pointer to the raw, C-FFI compatible host struct, without dereferencing it.
+    ///
+    /// This is an escape hatch for interoperating with C code, e.g. to implement CLAP extensions
+    /// that aren't covered by Clack yet. The pointer remains valid for the lifetime of the plugin
+    /// instance (`'a`), but what is done with it is not checked by this handle: the caller is
+    /// responsible for upholding the threading requirements of the CLAP specification.
+    ///
+    /// If you need to safely access the host struct through a shared reference,
+    /// use [`as_raw`](Self::as_raw) instead.
+    #[inline]
+    pub fn as_raw_ptr(&self) -> *const clap_host {
+        self.raw.as_ptr()
+    }
+
     /// Returns this handle as a reference to the host's information.
     #[inline]
     pub fn as_info(&self) -> &HostInfo<'a> {
@@ -283,12 +323,32 @@ impl<'a> HostMainThreadHandle<'a> {
         unsafe { &*(self as *const Self as *const HostSharedHandle<'a>) }
     }
 
-    /// Returns a raw, C FFI-compatible reference to the host handle.
+    /// Returns a shared reference to the raw, C-FFI compatible host struct.
+    ///
+    /// This type enforces that the reference is valid for the lifetime of the plugin instance
+    /// (`'a`).
+    ///
+    /// If you need to access the raw pointer without dereferencing it first, use
+    /// [`as_raw_ptr`](Self::as_raw_ptr) instead.
     #[inline]
     pub fn as_raw(&self) -> &'a clap_host {
         // SAFETY: this type enforces the pointer is valid for 'a
         unsafe { self.raw.as_ref() }
     }
+
+    /// Returns a raw pointer to the raw, C-FFI compatible host struct, without dereferencing it.
+    ///
+    /// This is an escape hatch for interoperating with C code, e.g. to implement CLAP extensions
+    /// that aren't covered by Clack yet. The pointer remains valid for the lifetime of the plugin
+    /// instance (`'a`), but what is done with it is not checked by this handle: the caller is
+    /// responsible for upholding the threading requirements of the CLAP specification.
+    ///
+    /// If you need to safely access the host struct through a shared reference,
+    /// use [`as_raw`](Self::as_raw) instead.
+    #[inline]
+    pub fn as_raw_ptr(&self) -> *const clap_host {
+        self.raw.as_ptr()
+    }
 }
 
 impl<'a> From<HostMainThreadHandle<'a>> for HostSharedHandle<'a> {
@@ -329,13 +389,33 @@ impl<'a> HostAudioProcessorHandle<'a> {
         }
     }
 
-    /// Returns a raw, C FFI-compatible reference to the host handle.
+    /// Returns a shared reference to the raw, C-FFI compatible host struct.
+    ///
+    /// This type enforces that the reference is valid for the lifetime of the plugin instance
+    /// (`'a`).
+    ///
+    /// If you need to access the raw pointer without dereferencing it first, use
+    /// [`as_raw_ptr`](Self::as_raw_ptr) instead.
     #[inline]
     pub fn as_raw(&self) -> &'a clap_host {
         // SAFETY: this type enforces the pointer is valid for 'a
         unsafe { self.raw.as_ref() }
     }
 
+    /// Returns a raw pointer to the raw, C-FFI compatible host struct, without dereferencing it.
+    ///
+    /// This is an escape hatch for interoperating with C code, e.g. to implement CLAP extensions
+    /// that aren't covered by Clack yet. The pointer remains valid for the lifetime of the plugin
+    /// instance (`'a`), but what is done with it is not checked by this handle: the caller is
+    /// responsible for upholding the threading requirements of the CLAP specification.
+    ///
+    /// If you need to safely access the host struct through a shared reference,
+    /// use [`as_raw`](Self::as_raw) instead.
+    #[inline]
+    pub fn as_raw_ptr(&self) -> *const clap_host {
+        self.raw.as_ptr()
+    }
+
     /// Returns this handle as a reference to a thread-safe host handle from this handle.
     #[inline]
     pub fn as_shared(&self) -> &HostSharedHandle<'a> {
@@ -10,6 +10,7 @@ pub mod entry;
 pub mod extensions;
 pub mod factory;
 pub mod host;
+pub mod panic;
 pub mod plugin;
 pub mod process;
 
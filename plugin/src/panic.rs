@@ -0,0 +1,95 @@
+//! Configuration of how panics in plugin code are handled.
+//!
+//! By default, all panics occurring in plugin code called by the host (e.g. in
+//! [`process`](crate::plugin::PluginAudioProcessor::process)) are caught before they reach the
+//! host. The panic is then reported (using the standard CLAP logging extension) and treated as an
+//! error, in order to not take the whole host process down.
+//!
+//! While this is the safest behavior for plugins running in a user's host, plugin developers
+//! debugging locally may prefer the process to abort right away, e.g. to inspect it in a debugger.
+//! This can be done by setting the [`PanicPolicy::Abort`] policy using [`set_panic_policy`].
+//!
+//! Note that in both cases, the panic hook is still called before the panic is caught, so the
+//! panic message and backtrace (if enabled) of the fault site are still printed to `stderr`.
+//!
+//! # Example
+//!
+//! ```
+//! use clack_plugin::panic::{set_panic_policy, PanicPolicy};
+//!
+//! // e.g. in the entry's or plugin factory's initialization:
+//! if cfg!(debug_assertions) {
+//!     set_panic_policy(PanicPolicy::Abort);
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The behavior to adopt when plugin code panics.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum PanicPolicy {
+    /// Catch the panic, log it, and report the failure to the host when possible.
+    ///
+    /// This is the default.
+    #[default]
+    CatchAndLog,
+    /// Abort the process as soon as the panic is caught, right after the panic hook ran.
+    Abort,
+}
+
+impl PanicPolicy {
+    #[inline]
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Abort,
+            _ => Self::CatchAndLog,
+        }
+    }
+
+    #[inline]
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::CatchAndLog => 0,
+            Self::Abort => 1,
+        }
+    }
+}
+
+static PANIC_POLICY: AtomicU8 = AtomicU8::new(PanicPolicy::CatchAndLog.to_u8());
+
+/// Sets the [`PanicPolicy`] to use for all plugins of this binary.
+///
+/// This setting is global to the whole plugin binary (i.e. it applies to all plugin instances,
+/// factories and entries it contains), and can be safely changed from any thread at any time.
+/// However, it is not synchronized with any panic that is currently being handled on another
+/// thread, which may still use the previous policy.
+#[inline]
+pub fn set_panic_policy(policy: PanicPolicy) {
+    PANIC_POLICY.store(policy.to_u8(), Ordering::Relaxed);
+}
+
+/// Returns the [`PanicPolicy`] currently in use.
+///
+/// See [`set_panic_policy`].
+#[inline]
+pub fn panic_policy() -> PanicPolicy {
+    PanicPolicy::from_u8(PANIC_POLICY.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_policy_can_be_changed() {
+        assert_eq!(panic_policy(), PanicPolicy::CatchAndLog);
+
+        set_panic_policy(PanicPolicy::Abort);
+        assert_eq!(panic_policy(), PanicPolicy::Abort);
+
+        set_panic_policy(PanicPolicy::CatchAndLog);
+        assert_eq!(panic_policy(), PanicPolicy::CatchAndLog);
+    }
+}
@@ -193,6 +193,10 @@ pub trait PluginAudioProcessor<'a, S: PluginShared<'a>, M: PluginMainThread<'a,
     /// This operation may fail for any reason, in which case `Err` is returned
     /// and the plugin is not activated.
     ///
+    /// The host may then try to activate the plugin again, possibly with a different
+    /// configuration. Implementations should therefore make sure that any changes they made to the
+    /// `main_thread` data are either rolled back or compatible with a later activation attempt.
+    ///
     /// # Realtime Safety
     ///
     /// This method is not realtime-safe: it may perform memory allocations of audio buffers, or any
@@ -224,6 +228,12 @@ pub trait PluginAudioProcessor<'a, S: PluginShared<'a>, M: PluginMainThread<'a,
     /// # Errors
     ///
     /// This method may fail for any reason, depending on the plugin's implementation.
+    ///
+    /// Any returned error is reported to the host as the `CLAP_PROCESS_ERROR` status, as
+    /// [`ProcessStatus`] intentionally has no error variant. Since that status cannot carry a
+    /// reason, the error's message is also sent to the host's `log` extension (or to `stderr`, if
+    /// the host doesn't support it). Note that hosts may deactivate the plugin upon receiving this
+    /// status.
     fn process(
         &mut self,
         process: Process,
@@ -268,6 +278,9 @@ pub trait PluginAudioProcessor<'a, S: PluginShared<'a>, M: PluginMainThread<'a,
     /// # Errors
     ///
     /// This method may fail for any reason, depending on the plugin's implementation.
+    ///
+    /// As the CLAP ABI only allows reporting that starting failed, the error's message is sent to
+    /// the host's `log` extension (or to `stderr`, if the host doesn't support it).
     #[inline]
     fn start_processing(&mut self) -> Result<(), PluginError> {
         Ok(())
@@ -11,7 +11,12 @@ use std::error::Error;
 /// if not.
 ///
 /// This error can be constructed either from any existing [`Error`] type, or from an arbitrary
-/// error message.
+/// error message. Constant messages can use the [`Message`](PluginError::Message) variant, which
+/// doesn't allocate, while dynamic messages can use the [`Owned`](PluginError::Owned) variant.
+///
+/// Already boxed errors (which can't be converted using `?`) can be wrapped using the
+/// [`Error`](PluginError::Error) or [`Source`](PluginError::Source) variants directly, e.g.
+/// `result.map_err(PluginError::Source)`.
 ///
 /// # Example
 ///
@@ -27,7 +32,12 @@ use std::error::Error;
 /// fn perform(valid: bool) -> Result<(), PluginError> {
 ///     if !valid {
 ///         return Err(PluginError::Message("Invalid value"))
-///     }    
+///     }
+///     /* ... */
+///     let path = "samples/kick.wav";
+///     if path.is_empty() {
+///         return Err(PluginError::Owned(format!("Failed to load sample at {path}")))
+///     }
 ///     /* ... */
 ///     foo()?;
 ///     /* ... */
@@ -43,6 +53,10 @@ pub enum PluginError {
     Error(Box<dyn Error + 'static>),
     /// A constant string message to be displayed.
     Message(&'static str),
+    /// A dynamic string message to be displayed.
+    Owned(String),
+    /// A generic, type-erased error, which can be sent across threads.
+    Source(Box<dyn Error + Send + Sync + 'static>),
 }
 
 impl Display for PluginError {
@@ -50,6 +64,8 @@ impl Display for PluginError {
         match self {
             PluginError::Error(e) => Display::fmt(&e, f),
             PluginError::Message(msg) => f.write_str(msg),
+            PluginError::Owned(msg) => f.write_str(msg),
+            PluginError::Source(e) => Display::fmt(&e, f),
         }
     }
 }
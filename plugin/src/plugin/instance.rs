@@ -5,6 +5,7 @@ use crate::plugin::instance::WrapperData::*;
 use crate::plugin::{Plugin, PluginAudioProcessor, PluginError, PluginMainThread};
 use crate::prelude::PluginDescriptor;
 use crate::process::{Audio, Events, PluginAudioConfiguration, Process};
+use clap_sys::ext::log::CLAP_LOG_ERROR;
 use clap_sys::plugin::{clap_plugin, clap_plugin_descriptor};
 use clap_sys::process::{clap_process, clap_process_status, CLAP_PROCESS_ERROR};
 use core::ffi::c_void;
@@ -290,7 +291,16 @@ impl<'a, P: Plugin> PluginBoxInner<'a, P> {
     #[allow(clippy::missing_safety_doc)]
     unsafe extern "C" fn start_processing(plugin: *const clap_plugin) -> bool {
         PluginWrapper::<P>::handle(plugin, |p| {
-            Ok(p.audio_processor()?.as_mut().start_processing()?)
+            // The CLAP ABI can only report a failure to the host, so the reason is logged instead.
+            p.audio_processor()?
+                .as_mut()
+                .start_processing()
+                .map_err(|e| {
+                    PluginWrapperError::Error(
+                        CLAP_LOG_ERROR,
+                        format!("Plugin failed to start processing: {e}").into(),
+                    )
+                })
         })
         .is_some()
     }
@@ -331,7 +341,7 @@ impl<'a, P: Plugin> PluginBoxInner<'a, P> {
         PluginWrapper::<P>::handle_plugin_data(plugin, |data| {
             let p = data.as_ref().wrapper_uninit()?;
             P::declare_extensions(&mut builder, p.map(|p| p.shared()));
-            Ok(())
+            builder.check_duplicates()
         });
         builder.found()
     }
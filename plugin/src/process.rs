@@ -61,6 +61,62 @@ impl<'a> Process<'a> {
             },
         }
     }
+
+    /// Returns the current tempo, in beats per minute.
+    ///
+    /// This returns [`None`] if the host did not provide any transport information, or if it
+    /// does not provide a valid tempo. See [`TransportEvent::tempo`].
+    #[inline]
+    pub fn tempo(&self) -> Option<f64> {
+        self.transport?.tempo()
+    }
+
+    /// Returns `true` if the host's transport is currently playing.
+    ///
+    /// This returns `false` if the host did not provide any transport information. See
+    /// [`TransportEvent::is_playing`].
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.transport.is_some_and(TransportEvent::is_playing)
+    }
+
+    /// Returns the current song position, in beats.
+    ///
+    /// This returns [`None`] if the host did not provide any transport information, or if it
+    /// does not provide a beats timeline. See [`TransportEvent::song_pos_beats`].
+    #[inline]
+    pub fn song_pos_beats(&self) -> Option<f64> {
+        self.transport?.song_pos_beats()
+    }
+
+    /// Returns the current time signature, as a `(numerator, denominator)` pair.
+    ///
+    /// This returns [`None`] if the host did not provide any transport information, or if it
+    /// does not provide a valid time signature. See [`TransportEvent::time_signature`].
+    #[inline]
+    pub fn time_signature(&self) -> Option<(u16, u16)> {
+        self.transport?.time_signature()
+    }
+
+    /// Converts a duration in beats to a duration in samples, using the current transport's tempo
+    /// and the given sample rate.
+    ///
+    /// This returns [`None`] if the host did not provide any transport information, or if it
+    /// does not provide a valid tempo. See [`TransportEvent::beats_to_samples`].
+    #[inline]
+    pub fn beats_to_samples(&self, beats: f64, sample_rate: f64) -> Option<f64> {
+        self.transport?.beats_to_samples(beats, sample_rate)
+    }
+
+    /// Converts a duration in samples to a duration in beats, using the current transport's tempo
+    /// and the given sample rate.
+    ///
+    /// This returns [`None`] if the host did not provide any transport information, or if it
+    /// does not provide a valid tempo. See [`TransportEvent::samples_to_beats`].
+    #[inline]
+    pub fn samples_to_beats(&self, samples: f64, sample_rate: f64) -> Option<f64> {
+        self.transport?.samples_to_beats(samples, sample_rate)
+    }
 }
 
 /// Input and output events that occurred during this processing block.
@@ -421,6 +477,39 @@ impl<'a> Audio<'a> {
         PortPairsIter::new(self)
     }
 
+    /// Returns the left and right [`ChannelPair`]s of the first port pair, if it is a stereo pair
+    /// of [`f32`] samples.
+    ///
+    /// This is a shortcut for the common case of plugins processing a single stereo port pair.
+    ///
+    /// This returns [`None`] if there is no port pair, if its ports do not hold [`f32`] samples,
+    /// or if it does not have exactly two channel pairs. Note that if only one of the ports is
+    /// stereo, the pairs are still returned, using [`ChannelPair::InputOnly`] or
+    /// [`ChannelPair::OutputOnly`] for the unmatched channels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_plugin::prelude::*;
+    ///
+    /// # fn foo(mut audio: Audio) -> Result<(), PluginError> {
+    /// let (left, right) = audio
+    ///     .stereo_pairs()
+    ///     .ok_or(PluginError::Message("Expected a stereo f32 port pair"))?;
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn stereo_pairs(&mut self) -> Option<(ChannelPair<'_, f32>, ChannelPair<'_, f32>)> {
+        let mut port_pair = self.port_pair(0)?;
+
+        if port_pair.channel_pair_count() != 2 {
+            return None;
+        }
+
+        let mut channel_pairs = port_pair.channel_pairs_f32().ok()?;
+        Some((channel_pairs.next()?, channel_pairs.next()?))
+    }
+
     /// Returns a sub-range of ports as a new [`Audio`] struct, similar to a subslice of items.
     #[inline]
     pub fn port_sub_range<R: RangeBounds<usize> + Clone>(&mut self, range: R) -> Audio {
@@ -441,6 +530,43 @@ impl<'a> Audio<'a> {
         }
     }
 
+    /// Returns a selection of the ports at the given indices, which do not have to be contiguous.
+    ///
+    /// This is useful for plugins that need to handle a logical group of ports that are not
+    /// adjacent to each other, e.g. a main stereo port and a control-voltage port. See also
+    /// [`port_sub_range`](Audio::port_sub_range) for contiguous ranges of ports.
+    ///
+    /// The ports of the returned [`AudioSelection`] are accessed through their index in `indices`,
+    /// and are yielded in that order when iterated on.
+    ///
+    /// The selection mutably borrows this [`Audio`] struct, which cannot be used until the
+    /// selection is dropped.
+    ///
+    /// # Panics
+    ///
+    /// This panics if any of the `indices` is out of bounds (i.e. greater than or equal to
+    /// [`port_pair_count`](Audio::port_pair_count)), or if an index is present more than once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_plugin::prelude::*;
+    ///
+    /// # fn foo(mut audio: Audio) -> Result<(), PluginError> {
+    /// // Process the main port (0) and the control-voltage port (2) together.
+    /// let mut selection = audio.select_ports(&[0, 2]);
+    ///
+    /// for mut port_pair in &mut selection {
+    ///     // Both port pairs are yielded here, in the order they were selected.
+    ///     # let _ = port_pair.channels()?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn select_ports<'s>(&'s mut self, indices: &'s [usize]) -> AudioSelection<'s> {
+        AudioSelection::new(self, indices)
+    }
+
     /// Returns the number of frames to process in this block.
     ///
     /// This will always match the number of samples of every audio buffer in this [`Audio`] struct.
@@ -2,15 +2,20 @@
 
 mod error;
 mod input;
+mod metering;
+mod mixing;
 mod output;
 mod pair;
 mod sample_type;
+mod selection;
 
 pub use error::BufferError;
 pub use input::*;
+pub use mixing::*;
 pub use output::*;
 pub use pair::*;
 pub use sample_type::SampleType;
+pub use selection::*;
 
 #[cfg(test)]
 #[allow(missing_docs)]
@@ -119,6 +124,114 @@ pub mod tests {
         assert_eq!(ins, outs);
     }
 
+    #[test]
+    fn can_iterate_on_typed_channel_pairs() {
+        let mut ins = [[1f32; 4]; 2];
+        let mut outs = [[0f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        let mut port = audio.port_pair(0).unwrap();
+
+        assert!(matches!(
+            port.channel_pairs_f64(),
+            Err(BufferError::UnexpectedSampleType)
+        ));
+
+        let channel_pairs = port.channel_pairs_f32().unwrap();
+        assert_eq!(channel_pairs.len(), 2);
+
+        for channel_pair in channel_pairs {
+            let ChannelPair::InputOutput(i, o) = channel_pair else {
+                panic!("Expected I/O channel")
+            };
+            o.copy_from_slice(i);
+        }
+
+        assert_eq!(ins, outs);
+    }
+
+    #[test]
+    fn can_access_stereo_pairs() {
+        let mut ins = [[1f32; 4]; 2];
+        let mut outs = [[0f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        let (left, right) = audio.stereo_pairs().unwrap();
+
+        for channel_pair in [left, right] {
+            let ChannelPair::InputOutput(i, o) = channel_pair else {
+                panic!("Expected I/O channel")
+            };
+            o.copy_from_slice(i);
+        }
+
+        assert_eq!(ins, outs);
+    }
+
+    #[test]
+    fn stereo_pairs_surface_mismatched_channels() {
+        let mut ins = [[1f32; 4]; 1];
+        let mut outs = [[0f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(1, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        let (left, right) = audio.stereo_pairs().unwrap();
+
+        assert!(matches!(left, ChannelPair::InputOutput(_, _)));
+        assert!(matches!(right, ChannelPair::OutputOnly(_)));
+    }
+
+    #[test]
+    fn stereo_pairs_require_two_channels() {
+        let mut ins = [[1f32; 4]; 1];
+        let mut outs = [[0f32; 4]; 1];
+
+        let mut input_ports = AudioPorts::with_capacity(1, 1);
+        let mut output_ports = AudioPorts::with_capacity(1, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        assert!(audio.stereo_pairs().is_none());
+    }
+
+    #[test]
+    fn can_mix_channel_pairs() {
+        let mut ins = [[0.5f32; 4]; 2];
+        let mut outs = [[1f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        let (left, right) = audio.stereo_pairs().unwrap();
+
+        let ChannelPair::InputOutput(i, o) = left else {
+            panic!("Expected I/O channel")
+        };
+        add_from_slice(o, i);
+
+        let ChannelPair::InputOutput(i, o) = right else {
+            panic!("Expected I/O channel")
+        };
+        add_scaled_from_slice(o, i, 2.0);
+        mul_from_slice(o, i);
+
+        assert_eq!(outs, [[1.5; 4], [1.0; 4]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match destination buffer length")]
+    fn mixing_mismatched_lengths_panics() {
+        add_from_slice(&mut [0f32; 4], &[0f32; 3]);
+    }
+
     #[test]
     fn can_iterate_on_io() {
         let mut ins = [[1f32; 4]; 2];
@@ -168,6 +281,30 @@ pub mod tests {
         assert_eq!(ins, outs);
     }
 
+    #[test]
+    fn can_compute_peak_and_rms() {
+        let mut ins = [[0.5f32, -0.5, 0.5, -0.5], [1.0, -1.0, 1.0, -1.0]];
+        let mut outs = [[0f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+
+        let port = audio.input_port(0).unwrap();
+        let channels = port.channels().unwrap().into_f32().unwrap();
+        assert_eq!(channels.peak(), 1.0);
+        assert_eq!(channels.rms(), (0.625f32).sqrt());
+
+        let mut port = audio.output_port(0).unwrap();
+        let mut channels = port.channels().unwrap().into_f32().unwrap();
+        assert_eq!(channels.peak(), 0.0);
+        assert_eq!(channels.rms(), 0.0);
+
+        channels.channel_mut(1).unwrap().fill(-0.25);
+        assert_eq!(channels.peak(), 0.25);
+    }
+
     #[test]
     fn can_access_io_with_indexes() {
         let mut ins = [[1f32; 4]; 2];
@@ -207,4 +344,150 @@ pub mod tests {
 
         assert_eq!(ins, outs);
     }
+
+    #[test]
+    fn process_as_f32_uses_f32_buffers_directly() {
+        let mut ins = [[2f32; 4]; 2];
+        let mut outs = [[0f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        let mut port = audio.port_pair(0).unwrap();
+
+        let mut total = 0;
+        port.process_as_f32(|pair| {
+            let ChannelPair::InputOutput(i, o) = pair else {
+                panic!("Expected I/O channel")
+            };
+
+            for (i, o) in i.iter().zip(o) {
+                *o = *i * 0.5;
+            }
+            total += 1;
+        })
+        .unwrap();
+
+        assert_eq!(total, 2);
+        assert_eq!(outs, [[1f32; 4]; 2]);
+    }
+
+    #[test]
+    fn process_as_f32_converts_f64_buffers() {
+        let mut ins = [[2f64; 4]; 2];
+        let mut outs = [[0f64; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let input_buffers = input_ports.with_input_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f64_input_only(
+                ins.iter_mut().map(InputChannel::variable),
+            ),
+        }]);
+
+        let output_buffers = output_ports.with_output_buffers([AudioPortBuffer {
+            latency: 0,
+            channels: AudioPortBufferType::f64_output_only(
+                outs.iter_mut().map(|b| b.as_mut_slice()),
+            ),
+        }]);
+
+        let mut audio = Audio {
+            inputs: input_buffers.as_raw_buffers(),
+            frames_count: 4,
+            outputs: output_buffers.into_raw_buffers(),
+        };
+        let mut port = audio.port_pair(0).unwrap();
+
+        let process = |pair: ChannelPair<f32>| {
+            let ChannelPair::InputOutput(i, o) = pair else {
+                panic!("Expected I/O channel")
+            };
+
+            for (i, o) in i.iter().zip(o) {
+                *o = *i * 0.5;
+            }
+        };
+
+        let mut scratch = [0f32; 7];
+        assert_eq!(
+            port.process_as_f32_with_scratch(&mut scratch, process),
+            Err(BufferError::ScratchBufferTooSmall)
+        );
+
+        let mut scratch = [0f32; 8];
+        port.process_as_f32_with_scratch(&mut scratch, process)
+            .unwrap();
+
+        assert_eq!(outs, [[1f64; 4]; 2]);
+    }
+
+    #[test]
+    fn can_select_non_contiguous_ports() {
+        let mut ins = [[0f32; 4], [1f32; 4], [2f32; 4], [3f32; 4]];
+        let mut outs = [[0f32; 4]; 4];
+
+        let mut input_ports = AudioPorts::with_capacity(4, 4);
+        let mut output_ports = AudioPorts::with_capacity(4, 4);
+
+        let input_buffers =
+            input_ports.with_input_buffers(ins.iter_mut().map(|b| AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_input_only([InputChannel::variable(b)]),
+            }));
+
+        let output_buffers =
+            output_ports.with_output_buffers(outs.iter_mut().map(|b| AudioPortBuffer {
+                latency: 0,
+                channels: AudioPortBufferType::f32_output_only([b.as_mut_slice()]),
+            }));
+
+        let mut audio = Audio {
+            inputs: input_buffers.as_raw_buffers(),
+            frames_count: 4,
+            outputs: output_buffers.into_raw_buffers(),
+        };
+
+        let mut selection = audio.select_ports(&[0, 3]);
+        assert_eq!(selection.len(), 2);
+        assert_eq!(selection.indices(), &[0, 3]);
+
+        let input = selection.input_port(1).unwrap();
+        let input = input.channels().unwrap().into_f32().unwrap();
+        assert_eq!(input.channel(0).unwrap(), &[3f32; 4]);
+        assert!(selection.input_port(2).is_none());
+
+        let mut pairs = selection.port_pairs();
+        assert_eq!(pairs.len(), 2);
+
+        for (i, mut port) in (&mut pairs).enumerate() {
+            let mut channels = port.channels().unwrap().into_f32().unwrap();
+            let ChannelPair::InputOutput(input, output) = channels.channel_pair(0).unwrap() else {
+                panic!("Expected I/O channel")
+            };
+
+            for (i_sample, o_sample) in input.iter().zip(output) {
+                *o_sample = *i_sample + 10.0 * (i + 1) as f32;
+            }
+        }
+
+        assert!(pairs.next().is_none());
+        assert_eq!(outs, [[10f32; 4], [0f32; 4], [0f32; 4], [23f32; 4]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn selecting_the_same_port_twice_panics() {
+        let mut ins = [[1f32; 4]; 2];
+        let mut outs = [[0f32; 4]; 2];
+
+        let mut input_ports = AudioPorts::with_capacity(2, 1);
+        let mut output_ports = AudioPorts::with_capacity(2, 1);
+
+        let mut audio = get_audio(&mut ins, &mut outs, &mut input_ports, &mut output_ports);
+        audio.select_ports(&[0, 0]);
+    }
 }
@@ -20,6 +20,16 @@ pub enum BufferError {
     ///
     /// This error is also used by the [`SampleType::try_match_with`](super::SampleType::try_match_with) method.
     MismatchedBufferPair,
+    /// A port's channel buffers do not hold the requested sample type.
+    ///
+    /// This error is returned by the [`PortPair::channel_pairs_f32`](super::PortPair::channel_pairs_f32)
+    /// and [`PortPair::channel_pairs_f64`](super::PortPair::channel_pairs_f64) methods if the
+    /// ports hold samples of the other type.
+    UnexpectedSampleType,
+    /// A scratch buffer given to
+    /// [`PortPair::process_as_f32_with_scratch`](super::PortPair::process_as_f32_with_scratch)
+    /// is too small to hold the samples of a channel pair.
+    ScratchBufferTooSmall,
 }
 
 impl Display for BufferError {
@@ -28,7 +38,9 @@ impl Display for BufferError {
             BufferError::InvalidChannelBuffer => {
                 f.write_str("Invalid port channels buffers: both the data32 and data64 pointers were null")
             },
-            BufferError::MismatchedBufferPair => f.write_str("Invalid channel buffer pairing: attempted to read/write a 32-bit buffer and a 64-bit buffer together")
+            BufferError::MismatchedBufferPair => f.write_str("Invalid channel buffer pairing: attempted to read/write a 32-bit buffer and a 64-bit buffer together"),
+            BufferError::UnexpectedSampleType => f.write_str("Unexpected channel buffer sample type: the port does not hold samples of the requested type"),
+            BufferError::ScratchBufferTooSmall => f.write_str("Scratch buffer too small: it must be able to hold two channels' worth of samples")
         }
     }
 }
@@ -0,0 +1,66 @@
+use super::{InputChannels, OutputChannels};
+
+/// Computes the peak absolute sample value of all the given channels.
+#[inline]
+fn peak<'a, S: Copy + Into<f64> + 'a>(channels: impl Iterator<Item = &'a [S]>) -> f64 {
+    channels
+        .flat_map(|channel| channel.iter())
+        .fold(0.0, |peak, &sample| f64::max(peak, sample.into().abs()))
+}
+
+/// Computes the root mean square value of all the samples of the given channels.
+///
+/// The accumulation is always performed in 64-bit precision, to avoid precision loss on large
+/// blocks of 32-bit samples.
+#[inline]
+fn rms<'a, S: Copy + Into<f64> + 'a>(channels: impl Iterator<Item = &'a [S]>) -> f64 {
+    let mut sum = 0.0;
+    let mut count = 0usize;
+
+    for channel in channels {
+        sum += channel
+            .iter()
+            .map(|&sample| {
+                let sample: f64 = sample.into();
+                sample * sample
+            })
+            .sum::<f64>();
+
+        count += channel.len();
+    }
+
+    if count == 0 {
+        return 0.0;
+    }
+
+    (sum / count as f64).sqrt()
+}
+
+macro_rules! impl_metering {
+    ($channels:ident, $sample:ty) => {
+        impl $channels<'_, $sample> {
+            /// Returns the peak level of this block of audio, i.e. the highest absolute sample
+            /// value across all channels.
+            ///
+            /// This returns `0.0` if there are no channels or no frames to process.
+            #[inline]
+            pub fn peak(&self) -> $sample {
+                peak(self.iter()) as $sample
+            }
+
+            /// Returns the RMS (root mean square) level of this block of audio, computed over the
+            /// samples of all channels.
+            ///
+            /// This returns `0.0` if there are no channels or no frames to process.
+            #[inline]
+            pub fn rms(&self) -> $sample {
+                rms(self.iter()) as $sample
+            }
+        }
+    };
+}
+
+impl_metering!(InputChannels, f32);
+impl_metering!(InputChannels, f64);
+impl_metering!(OutputChannels, f32);
+impl_metering!(OutputChannels, f64);
@@ -0,0 +1,107 @@
+use core::ops::{Add, Mul};
+
+/// Adds the samples of the `input` buffer to the samples of the `output` buffer.
+///
+/// This is the summing counterpart of [`slice::copy_from_slice`], e.g. to accumulate multiple
+/// voices or signals into a single output channel.
+///
+/// # Panics
+///
+/// This function panics if the two buffers have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use clack_plugin::process::audio::add_from_slice;
+///
+/// let mut output = [1.0f32, 2.0, 3.0];
+/// add_from_slice(&mut output, &[0.5, 0.5, 0.5]);
+///
+/// assert_eq!(output, [1.5, 2.5, 3.5]);
+/// ```
+#[inline]
+pub fn add_from_slice<S: Copy + Add<Output = S>>(output: &mut [S], input: &[S]) {
+    assert_eq!(
+        output.len(),
+        input.len(),
+        "Source buffer length ({}) does not match destination buffer length ({})",
+        input.len(),
+        output.len()
+    );
+
+    for (output, &input) in output.iter_mut().zip(input) {
+        *output = *output + input;
+    }
+}
+
+/// Multiplies the samples of the `output` buffer by the samples of the `input` buffer.
+///
+/// This is useful to apply a modulation signal (e.g. an envelope or a LFO) to an output channel.
+///
+/// # Panics
+///
+/// This function panics if the two buffers have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use clack_plugin::process::audio::mul_from_slice;
+///
+/// let mut output = [1.0f32, 2.0, 3.0];
+/// mul_from_slice(&mut output, &[0.0, 0.5, 1.0]);
+///
+/// assert_eq!(output, [0.0, 1.0, 3.0]);
+/// ```
+#[inline]
+pub fn mul_from_slice<S: Copy + Mul<Output = S>>(output: &mut [S], input: &[S]) {
+    assert_eq!(
+        output.len(),
+        input.len(),
+        "Source buffer length ({}) does not match destination buffer length ({})",
+        input.len(),
+        output.len()
+    );
+
+    for (output, &input) in output.iter_mut().zip(input) {
+        *output = *output * input;
+    }
+}
+
+/// Adds the samples of the `input` buffer, multiplied by the given `gain`, to the samples of the
+/// `output` buffer.
+///
+/// This covers the most common mixing operation, where each signal is summed into the output
+/// at its own level.
+///
+/// # Panics
+///
+/// This function panics if the two buffers have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use clack_plugin::process::audio::add_scaled_from_slice;
+///
+/// let mut output = [1.0f32, 2.0, 3.0];
+/// add_scaled_from_slice(&mut output, &[1.0, 2.0, 4.0], 0.5);
+///
+/// assert_eq!(output, [1.5, 3.0, 5.0]);
+/// ```
+#[inline]
+pub fn add_scaled_from_slice<S: Copy + Add<Output = S> + Mul<Output = S>>(
+    output: &mut [S],
+    input: &[S],
+    gain: S,
+) {
+    assert_eq!(
+        output.len(),
+        input.len(),
+        "Source buffer length ({}) does not match destination buffer length ({})",
+        input.len(),
+        output.len()
+    );
+
+    for (output, &input) in output.iter_mut().zip(input) {
+        *output = *output + input * gain;
+    }
+}
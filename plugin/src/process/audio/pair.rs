@@ -146,6 +146,201 @@ impl<'a> PortPair<'a> {
         ))
     }
 
+    /// Returns an iterator over this port pair's [`ChannelPair`]s of [`f32`] samples.
+    ///
+    /// This is a shortcut for calling [`channels`](Self::channels), checking the ports hold
+    /// [`f32`] samples, and iterating over the resulting [`PairedChannels`].
+    ///
+    /// If the two ports have a different number of channels, the extra channels are returned as
+    /// [`ChannelPair::InputOnly`] or [`ChannelPair::OutputOnly`].
+    ///
+    /// # Errors
+    ///
+    /// On top of the errors [`channels`](Self::channels) may return, this method returns a
+    /// [`BufferError::UnexpectedSampleType`] if the ports only hold [`f64`] samples.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_plugin::process::audio::{BufferError, ChannelPair, PortPair};
+    ///
+    /// # fn foo(mut port: PortPair) -> Result<(), BufferError> {
+    /// for channel_pair in port.channel_pairs_f32()? {
+    ///     match channel_pair {
+    ///         ChannelPair::InputOnly(_) => {}
+    ///         ChannelPair::OutputOnly(output) => output.fill(0.0),
+    ///         ChannelPair::InputOutput(input, output) => output.copy_from_slice(input),
+    ///         ChannelPair::InPlace(_) => {}
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn channel_pairs_f32(&mut self) -> Result<PairedChannelsIter<'a, f32>, BufferError> {
+        self.channels()?
+            .into_f32()
+            .map(PairedChannels::into_iter)
+            .ok_or(BufferError::UnexpectedSampleType)
+    }
+
+    /// Returns an iterator over this port pair's [`ChannelPair`]s of [`f64`] samples.
+    ///
+    /// This is the [`f64`] counterpart to [`channel_pairs_f32`](Self::channel_pairs_f32).
+    ///
+    /// # Errors
+    ///
+    /// On top of the errors [`channels`](Self::channels) may return, this method returns a
+    /// [`BufferError::UnexpectedSampleType`] if the ports only hold [`f32`] samples.
+    #[inline]
+    pub fn channel_pairs_f64(&mut self) -> Result<PairedChannelsIter<'a, f64>, BufferError> {
+        self.channels()?
+            .into_f64()
+            .map(PairedChannels::into_iter)
+            .ok_or(BufferError::UnexpectedSampleType)
+    }
+
+    /// Calls the given closure with each of this port pair's [`ChannelPair`]s, as [`f32`] samples,
+    /// regardless of the sample type the host provided.
+    ///
+    /// If the ports hold [`f32`] samples, the closure is given the host's buffers directly.
+    /// Otherwise, each channel's [`f64`] samples are converted into a temporary [`f32`] scratch
+    /// buffer before calling the closure, and the output channel's samples are then converted
+    /// back into the host's [`f64`] buffer. This allows plugins to support both sample types
+    /// with a single processing code path, at the cost of the conversion and of [`f64`] precision.
+    ///
+    /// Note that channels are converted one pair at a time, so the closure cannot keep any
+    /// references to the previous channels' buffers.
+    ///
+    /// # Realtime Safety
+    ///
+    /// If the ports hold [`f64`] samples, this method allocates a scratch buffer of
+    /// `2 * frames_count` [`f32`] samples on every call. See
+    /// [`process_as_f32_with_scratch`](Self::process_as_f32_with_scratch) for a variant that uses
+    /// a caller-provided buffer instead.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`channels`](Self::channels).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clack_plugin::process::audio::{BufferError, ChannelPair, PortPair};
+    ///
+    /// # fn foo(mut port: PortPair) -> Result<(), BufferError> {
+    /// port.process_as_f32(|channel_pair| match channel_pair {
+    ///     ChannelPair::InputOnly(_) => {}
+    ///     ChannelPair::OutputOnly(output) => output.fill(0.0),
+    ///     ChannelPair::InputOutput(input, output) => output.copy_from_slice(input),
+    ///     ChannelPair::InPlace(_) => {}
+    /// })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn process_as_f32<F>(&mut self, f: F) -> Result<(), BufferError>
+    where
+        F: FnMut(ChannelPair<'_, f32>),
+    {
+        self.process_as_f32_inner(None, f)
+    }
+
+    /// Calls the given closure with each of this port pair's [`ChannelPair`]s, as [`f32`] samples,
+    /// using the given buffer as scratch space for [`f64`] conversions.
+    ///
+    /// This behaves exactly like [`process_as_f32`](Self::process_as_f32), except that it never
+    /// allocates. The given `scratch` buffer must hold at least `2 * frames_count` samples. It is
+    /// typically allocated once when the plugin is activated, using the maximum frame count.
+    ///
+    /// # Errors
+    ///
+    /// On top of the errors [`channels`](Self::channels) may return, this method returns a
+    /// [`BufferError::ScratchBufferTooSmall`] if the scratch buffer is too small, before calling
+    /// the closure. This is checked even if the ports hold [`f32`] samples, so that this error
+    /// doesn't depend on the host's choice of sample type.
+    pub fn process_as_f32_with_scratch<F>(
+        &mut self,
+        scratch: &mut [f32],
+        f: F,
+    ) -> Result<(), BufferError>
+    where
+        F: FnMut(ChannelPair<'_, f32>),
+    {
+        let required_len = self.frames_count as usize * 2;
+        let scratch = scratch
+            .get_mut(..required_len)
+            .ok_or(BufferError::ScratchBufferTooSmall)?;
+
+        self.process_as_f32_inner(Some(scratch), f)
+    }
+
+    fn process_as_f32_inner<F>(
+        &mut self,
+        scratch: Option<&mut [f32]>,
+        mut f: F,
+    ) -> Result<(), BufferError>
+    where
+        F: FnMut(ChannelPair<'_, f32>),
+    {
+        let channels = match self.channels()? {
+            SampleType::F32(channels) | SampleType::Both(channels, _) => {
+                channels.into_iter().for_each(f);
+                return Ok(());
+            }
+            SampleType::F64(channels) => channels,
+        };
+
+        let frames_count = self.frames_count as usize;
+
+        let mut allocated = Vec::new();
+        let scratch = match scratch {
+            Some(scratch) => scratch,
+            None => {
+                allocated.resize(frames_count * 2, 0.0);
+                &mut allocated
+            }
+        };
+
+        let (input_scratch, output_scratch) = scratch.split_at_mut(frames_count);
+
+        fn convert(from: &[f64], to: &mut [f32]) {
+            for (from, to) in from.iter().zip(to) {
+                *to = *from as f32;
+            }
+        }
+
+        fn write_back(from: &[f32], to: &mut [f64]) {
+            for (from, to) in from.iter().zip(to) {
+                *to = *from as f64;
+            }
+        }
+
+        for pair in channels {
+            match pair {
+                InputOnly(input) => {
+                    convert(input, input_scratch);
+                    f(InputOnly(input_scratch));
+                }
+                OutputOnly(output) => {
+                    convert(output, output_scratch);
+                    f(OutputOnly(output_scratch));
+                    write_back(output_scratch, output);
+                }
+                InputOutput(input, output) => {
+                    convert(input, input_scratch);
+                    convert(output, output_scratch);
+                    f(InputOutput(input_scratch, output_scratch));
+                    write_back(output_scratch, output);
+                }
+                InPlace(io) => {
+                    convert(io, output_scratch);
+                    f(InPlace(output_scratch));
+                    write_back(output_scratch, io);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// The number of channels in this port pair.
     ///
     /// Since there may be more channels in one port than in the other, this method also counts
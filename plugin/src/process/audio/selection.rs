@@ -0,0 +1,179 @@
+use crate::process::audio::{InputPort, OutputPort, PortPair};
+use crate::process::Audio;
+use clap_sys::audio_buffer::clap_audio_buffer;
+use std::slice::Iter;
+
+/// A selection of (possibly non-contiguous) ports of an [`Audio`] struct, created by
+/// [`Audio::select_ports`].
+///
+/// All ports are accessed through their index *in the selection*, not their index in the
+/// original [`Audio`] struct. For instance, if ports `0` and `3` were selected, then port `3` is
+/// accessed with the index `1`.
+///
+/// This selection mutably borrows the [`Audio`] struct it was created from, which therefore
+/// cannot be used until the selection is dropped.
+pub struct AudioSelection<'a> {
+    inputs: &'a [clap_audio_buffer],
+    outputs: &'a mut [clap_audio_buffer],
+    indices: &'a [usize],
+    frames_count: u32,
+}
+
+impl<'a> AudioSelection<'a> {
+    /// # Panics
+    ///
+    /// This panics if any index is out of bounds on both the input and output side, or if any
+    /// index is present more than once.
+    pub(crate) fn new(audio: &'a mut Audio<'_>, indices: &'a [usize]) -> Self {
+        let port_pair_count = audio.port_pair_count();
+
+        for (i, index) in indices.iter().enumerate() {
+            assert!(
+                *index < port_pair_count,
+                "Port index out of bounds: the index is {index} but the port count is {port_pair_count}"
+            );
+            assert!(
+                !indices[..i].contains(index),
+                "Port index {index} was selected more than once"
+            );
+        }
+
+        Self {
+            inputs: audio.inputs,
+            outputs: audio.outputs,
+            indices,
+            frames_count: audio.frames_count,
+        }
+    }
+
+    /// Returns the indices of the selected ports in the original [`Audio`] struct, in the order
+    /// of the selection.
+    #[inline]
+    pub fn indices(&self) -> &'a [usize] {
+        self.indices
+    }
+
+    /// Returns the number of selected ports.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no ports were selected.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Retrieves the [`InputPort`] at a given index in the selection.
+    ///
+    /// This returns [`None`] if there is no selected port at the given index, or if the selected
+    /// port has no input side.
+    #[inline]
+    pub fn input_port(&self, index: usize) -> Option<InputPort<'_>> {
+        let buffer = self.inputs.get(*self.indices.get(index)?)?;
+
+        // SAFETY: the Audio type this was created from ensures the provided buffer is valid and
+        // frames_count is correct
+        Some(unsafe { InputPort::from_raw(buffer, self.frames_count) })
+    }
+
+    /// Retrieves the [`OutputPort`] at a given index in the selection.
+    ///
+    /// This returns [`None`] if there is no selected port at the given index, or if the selected
+    /// port has no output side.
+    #[inline]
+    pub fn output_port(&mut self, index: usize) -> Option<OutputPort<'_>> {
+        let buffer = self.outputs.get_mut(*self.indices.get(index)?)?;
+
+        // SAFETY: the Audio type this was created from ensures the provided buffer is valid and
+        // frames_count is correct. Also, &mut ensures there is no input being read concurrently
+        Some(unsafe { OutputPort::from_raw(buffer, self.frames_count) })
+    }
+
+    /// Retrieves the [`PortPair`] at a given index in the selection.
+    ///
+    /// This returns [`None`] if there is no selected port at the given index.
+    #[inline]
+    pub fn port_pair(&mut self, index: usize) -> Option<PortPair<'_>> {
+        let index = *self.indices.get(index)?;
+
+        // SAFETY: the Audio type this was created from ensures the provided buffers are valid and
+        // frames_count is correct
+        unsafe {
+            PortPair::from_raw(
+                self.inputs.get(index),
+                self.outputs.get_mut(index),
+                self.frames_count,
+            )
+        }
+    }
+
+    /// Returns an iterator of all the selected [`PortPair`]s, in the order of the selection.
+    #[inline]
+    pub fn port_pairs(&mut self) -> SelectedPortPairsIter<'_> {
+        SelectedPortPairsIter {
+            inputs: self.inputs,
+            outputs: self.outputs.as_mut_ptr(),
+            outputs_len: self.outputs.len(),
+            indices: self.indices.iter(),
+            frames_count: self.frames_count,
+            _outputs: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'buf: 'a, 'a> IntoIterator for &'a mut AudioSelection<'buf> {
+    type Item = PortPair<'a>;
+    type IntoIter = SelectedPortPairsIter<'a>;
+
+    /// Returns an iterator of all the selected [`PortPair`]s. This is equivalent to using
+    /// [`port_pairs`](AudioSelection::port_pairs).
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.port_pairs()
+    }
+}
+
+/// An iterator of all the [`PortPair`]s of an [`AudioSelection`].
+pub struct SelectedPortPairsIter<'a> {
+    inputs: &'a [clap_audio_buffer],
+    outputs: *mut clap_audio_buffer,
+    outputs_len: usize,
+    indices: Iter<'a, usize>,
+    frames_count: u32,
+    _outputs: core::marker::PhantomData<&'a mut [clap_audio_buffer]>,
+}
+
+impl<'a> Iterator for SelectedPortPairsIter<'a> {
+    type Item = PortPair<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = *self.indices.next()?;
+
+        let output = if index < self.outputs_len {
+            // SAFETY: the index is in bounds, and AudioSelection ensures all indices are unique,
+            // so each output buffer is only ever borrowed once.
+            Some(unsafe { &mut *self.outputs.add(index) })
+        } else {
+            None
+        };
+
+        // SAFETY: the Audio type the selection was created from ensures the provided buffers are
+        // valid and frames_count is correct
+        unsafe { PortPair::from_raw(self.inputs.get(index), output, self.frames_count) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl ExactSizeIterator for SelectedPortPairsIter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+}